@@ -39,6 +39,7 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
     mapping(address => address) private _assetAccountsToNodes; // maps asset account address to node address
     mapping(address => address) private _nodesToAssetAccounts; // maps node address to asset account address
     mapping(address => mapping(bytes32 => bool)) private _assetAccountSaltIsSpent; // maps asset account address to salt
+    bool private _dkgKeyProofRequired; // whether a dkg public key must be followed by its proof of possession
 
     // *Events*
     event NodeRegistered(address indexed nodeAddress, bytes dkgPublicKey, uint256 groupIndex);
@@ -48,6 +49,7 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
     event NodeRewarded(address indexed nodeAddress, uint256 ethAmount, uint256 arpaAmount);
     event NodeSlashed(address indexed nodeIdAddress, uint256 stakingRewardPenalty, uint256 pendingBlock);
     event AssetAccountSet(address indexed assetAccountAddress, address indexed nodeAddress);
+    event DkgKeyProofRequired();
 
     // *Errors*
     error NodeNotRegistered();
@@ -62,6 +64,7 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
     error EIP1271SignatureExpired();
     error EIP1271SignatureSaltAlreadySpent();
     error InvalidArrayLength();
+    error DkgKeyProofMissing();
 
     /// @custom:oz-upgrades-unsafe-allow constructor
     constructor() {
@@ -99,6 +102,12 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
         _nodeQuitHelper(nodeIdAddress, pendingBlock);
     }
 
+    function requireDkgKeyProof() external override(INodeRegistryOwner) onlyOwner {
+        _dkgKeyProofRequired = true;
+
+        emit DkgKeyProofRequired();
+    }
+
     function setAssetAccount(address[] calldata assetAccountAddresses, address[] calldata nodeAddresses)
         external
         override(INodeRegistryOwner)
@@ -226,10 +235,7 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
             revert NodeAlreadyActive();
         }
 
-        uint256[4] memory publicKey = BLS.fromBytesPublicKey(dkgPublicKey);
-        if (!BLS.isValidPublicKey(publicKey)) {
-            revert BLS.InvalidPublicKey();
-        }
+        _checkDkgPublicKey(dkgPublicKey);
 
         node.dkgPublicKey = dkgPublicKey;
 
@@ -301,6 +307,10 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
         return _nodes[nodeAddress].dkgPublicKey;
     }
 
+    function isDkgKeyProofRequired() public view override(INodeRegistry) returns (bool) {
+        return _dkgKeyProofRequired;
+    }
+
     function getNode(address nodeAddress) public view override(INodeRegistry) returns (Node memory) {
         return _nodes[nodeAddress];
     }
@@ -401,10 +411,7 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
             revert NodeAlreadyRegistered();
         }
 
-        uint256[4] memory publicKey = BLS.fromBytesPublicKey(dkgPublicKey);
-        if (!BLS.isValidPublicKey(publicKey)) {
-            revert BLS.InvalidPublicKey();
-        }
+        _checkDkgPublicKey(dkgPublicKey);
 
         // Populate Node struct and insert into nodes
         Node storage n = _nodes[msg.sender];
@@ -422,6 +429,23 @@ contract NodeRegistry is UUPSUpgradeable, INodeRegistry, INodeRegistryOwner, Own
         emit NodeRegistered(msg.sender, dkgPublicKey, groupIndex);
     }
 
+    function _checkDkgPublicKey(bytes calldata dkgPublicKey) internal view {
+        // the key is followed by the proof of possession of its private key, which is verified by the nodes
+        if (dkgPublicKey.length < 128) {
+            revert BLS.InvalidPublicKeyEncoding();
+        }
+
+        // a bare key is only accepted before the cutover, after which the nodes reject it as well
+        if (_dkgKeyProofRequired && dkgPublicKey.length == 128) {
+            revert DkgKeyProofMissing();
+        }
+
+        uint256[4] memory publicKey = BLS.fromBytesPublicKey(dkgPublicKey[:128]);
+        if (!BLS.isValidPublicKey(publicKey)) {
+            revert BLS.InvalidPublicKey();
+        }
+    }
+
     function _freezeNode(address nodeIdAddress, uint256 pendingBlock) internal {
         // set node state to false for frozen node
         _nodes[nodeIdAddress].state = false;
@@ -50,6 +50,8 @@ interface INodeRegistry {
 
     function getNode(address nodeAddress) external view returns (Node memory);
 
+    function isDkgKeyProofRequired() external view returns (bool);
+
     function getNodeWithdrawableTokens(address nodeAddress) external view returns (uint256, uint256);
 
     function getNodeRegistryConfig()
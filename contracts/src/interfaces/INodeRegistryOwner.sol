@@ -35,4 +35,10 @@ interface INodeRegistryOwner {
      * @param nodeAddresses The addresses of the nodes
      */
     function setAssetAccount(address[] calldata assetAccountAddresses, address[] calldata nodeAddresses) external;
+
+    /**
+     * @notice Requires every dkg public key to be followed by its proof of possession from now on.
+     * This can't be undone, and the nodes reject the bare keys registered before in the DKGs assigned after it.
+     */
+    function requireDkgKeyProof() external;
 }
@@ -77,6 +77,55 @@ contract NodeRegistryTest is RandcastTestHelper {
         INodeRegistry(address(_nodeRegistry)).nodeRegister(_dkgPubkey4, false, _node4, _emptyOperatorSignature);
     }
 
+    function testNodeRegisterWithProofOfPossession() public {
+        // Fail on dkg public key shorter than a G2 point
+        vm.expectRevert(abi.encodeWithSelector(BLS.InvalidPublicKeyEncoding.selector));
+        vm.prank(_node1);
+        INodeRegistry(address(_nodeRegistry)).nodeRegister(
+            hex"0397247610", false, _node1, _emptyOperatorSignature
+        );
+
+        // The proof of possession following the key is stored as is, and verified by the nodes
+        bytes memory dkgPubkeyWithProof = bytes.concat(_dkgPubkey1, new bytes(64));
+        vm.prank(_node1);
+        INodeRegistry(address(_nodeRegistry)).nodeRegister(
+            dkgPubkeyWithProof, false, _node1, _emptyOperatorSignature
+        );
+
+        INodeRegistry.Node memory n = INodeRegistry(address(_nodeRegistry)).getNode(_node1);
+        assertEq(n.dkgPublicKey, dkgPubkeyWithProof);
+    }
+
+    function testRequireDkgKeyProof() public {
+        assertFalse(INodeRegistry(address(_nodeRegistry)).isDkgKeyProofRequired());
+
+        // Only the owner can require the proof
+        vm.prank(_node1);
+        vm.expectRevert("Ownable: caller is not the owner");
+        INodeRegistryOwner(address(_nodeRegistry)).requireDkgKeyProof();
+
+        vm.prank(_admin);
+        INodeRegistryOwner(address(_nodeRegistry)).requireDkgKeyProof();
+        assertTrue(INodeRegistry(address(_nodeRegistry)).isDkgKeyProofRequired());
+
+        // Fail on a bare dkg public key after the cutover
+        vm.expectRevert(abi.encodeWithSelector(NodeRegistry.DkgKeyProofMissing.selector));
+        vm.prank(_node1);
+        INodeRegistry(address(_nodeRegistry)).nodeRegister(_dkgPubkey1, false, _node1, _emptyOperatorSignature);
+
+        vm.prank(_node1);
+        INodeRegistry(address(_nodeRegistry)).nodeRegister(
+            bytes.concat(_dkgPubkey1, new bytes(64)), false, _node1, _emptyOperatorSignature
+        );
+
+        // Nor can a bare dkg public key be changed to
+        vm.prank(_node1);
+        INodeRegistry(address(_nodeRegistry)).nodeQuit();
+        vm.expectRevert(abi.encodeWithSelector(NodeRegistry.DkgKeyProofMissing.selector));
+        vm.prank(_node1);
+        INodeRegistry(address(_nodeRegistry)).changeDkgPublicKey(_dkgPubkey2);
+    }
+
     function testNodeActivate() public {
         // When call nodeActivate,
         // If not registered (_nodes doesn't contain record), error and revert
@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use std::marker::PhantomData;
+use threshold_bls::{
+    group::{Curve, CurveFrom, Element, PairingCurve},
+    poly::{Eval, Poly},
+    sig::{BlindScheme, BlindThresholdScheme, G1Scheme, Share, ThresholdScheme},
+};
+
+/// Signs the blinded messages of the requesters with the share of the group key, which is
+/// injected into the main chain along with the concrete blind signature scheme.
+pub trait BlindSignatureCore<C: Curve>: std::fmt::Debug + Sync + Send {
+    /// Partially signs a blinded message with a share of the private key
+    fn sign_blind_partial(&self, private: &Share<C::Scalar>, blinded_msg: &[u8])
+        -> Result<Vec<u8>>;
+
+    /// Verifies a blind partial signature against the partial public key of its signer
+    fn verify_blind_partial(
+        &self,
+        partial_public_key: &C::Point,
+        blinded_msg: &[u8],
+        blind_partial: &[u8],
+    ) -> Result<()>;
+
+    /// Aggregates the blind partial signatures into the blind signature of the group, which
+    /// can only be unblinded by the requester, along with the public key of the group which
+    /// verifies the unblinded signature. Note that this method does not verify if the blind
+    /// partial signatures are correct or not.
+    fn aggregate(&self, threshold: usize, blind_partials: &[Vec<u8>])
+        -> Result<(Vec<u8>, Vec<u8>)>;
+}
+
+/// Signs the blinded messages in G2 with the scheme whose public keys are in G1, while the
+/// randomness is signed in G1 by the group key in G2. Any point can be blinded, so signing
+/// blinded messages in the same group as the randomness would hand out the signature of a
+/// randomness task before it is fulfilled. Each blind partial signature carries the partial
+/// public key of its signer in G1, which is checked against the one in G2 by a pairing.
+pub struct SimpleBlindSignatureCore<P> {
+    p: PhantomData<P>,
+}
+
+impl<P> SimpleBlindSignatureCore<P> {
+    pub fn new() -> Self {
+        SimpleBlindSignatureCore { p: PhantomData }
+    }
+}
+
+impl<P> Default for SimpleBlindSignatureCore<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> std::fmt::Debug for SimpleBlindSignatureCore<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleBlindSignatureCore").finish()
+    }
+}
+
+impl<P: PairingCurve + Sync + Send + 'static> BlindSignatureCore<CurveFrom<P::Scalar, P::G2>>
+    for SimpleBlindSignatureCore<P>
+where
+    <G1Scheme<P> as ThresholdScheme>::Error: Sync + Send,
+    <G1Scheme<P> as BlindScheme>::Error: Sync + Send,
+    <G1Scheme<P> as BlindThresholdScheme>::Error: Sync + Send,
+{
+    fn sign_blind_partial(
+        &self,
+        private: &Share<P::Scalar>,
+        blinded_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let blind_partial = G1Scheme::<P>::sign_blind_partial(private, blinded_msg)?;
+
+        let mut partial_public_key = P::G1::one();
+        partial_public_key.mul(&private.private);
+
+        Ok(bincode::serialize(&(blind_partial, partial_public_key))?)
+    }
+
+    fn verify_blind_partial(
+        &self,
+        partial_public_key: &P::G2,
+        blinded_msg: &[u8],
+        blind_partial: &[u8],
+    ) -> Result<()> {
+        let (blind_partial, g1_partial_public_key): (Vec<u8>, P::G1) =
+            bincode::deserialize(blind_partial)?;
+
+        // e(x * g1, g2) == e(g1, x * g2)
+        if P::pair(&g1_partial_public_key, &P::G2::one())
+            != P::pair(&P::G1::one(), partial_public_key)
+        {
+            return Err(anyhow!(
+                "the partial public key in G1 doesn't match the one of the signer"
+            ));
+        }
+
+        let blind_partial: Eval<Vec<u8>> = bincode::deserialize(&blind_partial)?;
+        G1Scheme::<P>::blind_verify(&g1_partial_public_key, blinded_msg, &blind_partial.value)?;
+        Ok(())
+    }
+
+    fn aggregate(
+        &self,
+        threshold: usize,
+        blind_partials: &[Vec<u8>],
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut partials = Vec::with_capacity(blind_partials.len());
+        let mut g1_partial_public_keys = Vec::with_capacity(blind_partials.len());
+        for blind_partial in blind_partials {
+            let (partial, g1_partial_public_key): (Vec<u8>, P::G1) =
+                bincode::deserialize(blind_partial)?;
+            let index = bincode::deserialize::<Eval<Vec<u8>>>(&partial)?.index;
+            partials.push(partial);
+            g1_partial_public_keys.push(Eval {
+                value: g1_partial_public_key,
+                index,
+            });
+        }
+
+        let blind_sig = G1Scheme::<P>::aggregate(threshold, &partials)?;
+        let public_key = Poly::<P::G1>::recover(threshold, g1_partial_public_keys)?;
+
+        Ok((blind_sig, bincode::serialize(&public_key)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlindSignatureCore, SimpleBlindSignatureCore};
+    use threshold_bls::{
+        curve::bn254::{G2Curve, PairingCurve},
+        group::{Curve, Element},
+        poly::{Idx, Poly},
+        schemes::bn254::{G1Scheme, G2Scheme},
+        sig::{BlindScheme, Share, SignatureScheme},
+    };
+
+    type Scalar = <G2Curve as Curve>::Scalar;
+    type Point = <G2Curve as Curve>::Point;
+    type G1 = <G1Scheme as threshold_bls::sig::Scheme>::Public;
+
+    #[test]
+    fn test_blind_signature() {
+        let core: Box<dyn BlindSignatureCore<G2Curve>> =
+            Box::new(SimpleBlindSignatureCore::<PairingCurve>::new());
+
+        let private = Poly::<Scalar>::new(2);
+        let public = private.commit::<Point>();
+        let shares = (0..4)
+            .map(|i| private.eval(i as Idx))
+            .map(|e| Share {
+                index: e.index,
+                private: e.value,
+            })
+            .collect::<Vec<_>>();
+
+        let msg = b"anonymous token";
+        let (token, blinded_msg) = G1Scheme::blind_msg(msg, &mut rand::thread_rng());
+
+        let blind_partials = shares
+            .iter()
+            .map(|share| core.sign_blind_partial(share, &blinded_msg).unwrap())
+            .collect::<Vec<_>>();
+
+        for (share, blind_partial) in shares.iter().zip(&blind_partials) {
+            let partial_public_key = public.eval(share.index).value;
+            core.verify_blind_partial(&partial_public_key, &blinded_msg, blind_partial)
+                .unwrap();
+
+            let other_partial_public_key = public.eval(share.index + 1).value;
+            assert!(core
+                .verify_blind_partial(&other_partial_public_key, &blinded_msg, blind_partial)
+                .is_err());
+        }
+
+        let (blind_sig, public_key) = core.aggregate(3, &blind_partials).unwrap();
+        let public_key: G1 = bincode::deserialize(&public_key).unwrap();
+        let mut expected_public_key = G1::one();
+        expected_public_key.mul(&private.get(0));
+        assert_eq!(public_key, expected_public_key);
+
+        let sig = G1Scheme::unblind_sig(&token, &blind_sig).unwrap();
+        G1Scheme::verify(&public_key, msg, &sig).unwrap();
+    }
+
+    #[test]
+    fn test_blind_signature_is_not_a_randomness_signature() {
+        let core = SimpleBlindSignatureCore::<PairingCurve>::new();
+
+        let private = Poly::<Scalar>::new(0);
+        let public = private.commit::<Point>();
+        let share = Share {
+            index: 0,
+            private: private.get(0),
+        };
+
+        // the randomness signatures are in G1, so a point of G1 can't be signed blindly
+        let (_, blinded_msg) = G2Scheme::blind_msg(b"randomness task", &mut rand::thread_rng());
+        assert!(core.sign_blind_partial(&share, &blinded_msg).is_err());
+
+        // nor does a partial public key in G1 which doesn't match the share pass
+        let (_, blinded_msg) = G1Scheme::blind_msg(b"anonymous token", &mut rand::thread_rng());
+        let (blind_partial, _): (Vec<u8>, G1) =
+            bincode::deserialize(&core.sign_blind_partial(&share, &blinded_msg).unwrap()).unwrap();
+        let forged = bincode::serialize(&(blind_partial, G1::one())).unwrap();
+        assert!(core
+            .verify_blind_partial(public.public_key(), &blinded_msg, &forged)
+            .is_err());
+    }
+}
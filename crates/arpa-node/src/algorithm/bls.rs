@@ -2,7 +2,7 @@ use anyhow::Result;
 use std::marker::PhantomData;
 use threshold_bls::{
     group::Curve,
-    poly::Eval,
+    poly::{Eval, LagrangeCache},
     sig::{Share, SignatureScheme, ThresholdScheme},
 };
 
@@ -26,6 +26,14 @@ pub(crate) trait BLSCore<C: Curve> {
     /// aggregates them.
     fn aggregate(threshold: usize, partials: &[Vec<u8>]) -> Result<Vec<u8>>;
 
+    /// Aggregates the partial signatures as `aggregate` does, reusing the Lagrange
+    /// coefficients of the cache when the same signers were aggregated before.
+    fn aggregate_with_cache(
+        threshold: usize,
+        partials: &[Vec<u8>],
+        cache: &mut LagrangeCache<C::Scalar>,
+    ) -> Result<Vec<u8>>;
+
     /// Verifies that the signature on the provided message was produced by the public key
     fn verify(public: &C::Point, msg: &[u8], sig: &[u8]) -> Result<()>;
 
@@ -56,6 +64,15 @@ where
         Ok(signature)
     }
 
+    fn aggregate_with_cache(
+        threshold: usize,
+        partials: &[Vec<u8>],
+        cache: &mut LagrangeCache<C::Scalar>,
+    ) -> Result<Vec<u8>> {
+        let signature = S::aggregate_with_cache(threshold, partials, cache)?;
+        Ok(signature)
+    }
+
     fn verify(public: &C::Point, msg: &[u8], sig: &[u8]) -> Result<()> {
         S::verify(public, msg, sig)?;
         Ok(())
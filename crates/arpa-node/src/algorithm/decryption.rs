@@ -0,0 +1,340 @@
+use crate::error::NodeError;
+use anyhow::{anyhow, Result};
+use std::marker::PhantomData;
+use threshold_bls::{
+    group::{Curve, CurveFrom, Element, PairingCurve},
+    poly::Eval,
+    sig::Share,
+    tenc::{self, DecryptionShare, ThresholdCipher},
+};
+
+/// Prefix of the label of a ciphertext which may be decrypted from the block height of the main
+/// chain which follows it on, e.g. `block:100`. Ciphertexts with other labels are not decrypted.
+pub(crate) const REVEAL_BLOCK_LABEL_PREFIX: &str = "block:";
+
+/// Returns the block height of the main chain from which a ciphertext with the label may be
+/// decrypted.
+pub(crate) fn reveal_block_height(label: &[u8]) -> Result<usize, NodeError> {
+    std::str::from_utf8(label)
+        .ok()
+        .and_then(|label| label.strip_prefix(REVEAL_BLOCK_LABEL_PREFIX))
+        .and_then(|height| height.parse().ok())
+        .ok_or_else(|| NodeError::UnsupportedDecryptionLabel(String::from_utf8_lossy(label).into()))
+}
+
+/// Decrypts the ciphertexts of the requesters with the share of the group key, which is injected
+/// into the main chain along with the concrete group of the ciphertexts.
+pub trait DecryptionCore<C: Curve>: std::fmt::Debug + Sync + Send {
+    /// Computes the decryption share of a serialized ciphertext with a share of the private key,
+    /// once the condition of its label is met at the current block height of the main chain
+    fn partial_decrypt(
+        &self,
+        private: &Share<C::Scalar>,
+        ciphertext: &[u8],
+        current_block_height: usize,
+    ) -> Result<Vec<u8>>;
+
+    /// Verifies a decryption share of a ciphertext against the partial public key of its holder
+    fn verify_decryption_share(
+        &self,
+        partial_public_key: &C::Point,
+        ciphertext: &[u8],
+        decryption_share: &[u8],
+    ) -> Result<()>;
+
+    /// Recovers the plaintext from the decryption shares. Note that this method does not
+    /// verify if the decryption shares are correct or not.
+    fn combine(
+        &self,
+        threshold: usize,
+        ciphertext: &[u8],
+        decryption_shares: &[Vec<u8>],
+    ) -> Result<Vec<u8>>;
+
+    /// Returns the partial public key of a share in the group of the ciphertexts, from which
+    /// the requesters recover the public key of the group to encrypt to.
+    fn partial_encryption_key(&self, private: &Share<C::Scalar>) -> Result<Vec<u8>>;
+}
+
+/// The curve of the ciphertexts, i.e. G1 of the pairing
+type CipherCurve<P> = CurveFrom<<P as PairingCurve>::Scalar, <P as PairingCurve>::G1>;
+
+/// Decrypts the ciphertexts in G1, which are encrypted to the public key of the group in G1,
+/// while the group key is in G2. The blinded messages are signed in G2 with the same share, so
+/// a ciphertext in G2 could be decrypted by having its ephemeral public key signed blindly,
+/// regardless of its label. Each decryption share carries the partial public key of its holder
+/// in G1, which is checked against the one in G2 by a pairing.
+pub struct SimpleDecryptionCore<P> {
+    p: PhantomData<P>,
+}
+
+impl<P> SimpleDecryptionCore<P> {
+    pub fn new() -> Self {
+        SimpleDecryptionCore { p: PhantomData }
+    }
+}
+
+impl<P> Default for SimpleDecryptionCore<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> std::fmt::Debug for SimpleDecryptionCore<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleDecryptionCore").finish()
+    }
+}
+
+impl<P: PairingCurve + Sync + Send + 'static> DecryptionCore<CurveFrom<P::Scalar, P::G2>>
+    for SimpleDecryptionCore<P>
+{
+    fn partial_decrypt(
+        &self,
+        private: &Share<P::Scalar>,
+        ciphertext: &[u8],
+        current_block_height: usize,
+    ) -> Result<Vec<u8>> {
+        let cipher: ThresholdCipher<CipherCurve<P>> = bincode::deserialize(ciphertext)?;
+        let reveal_block_height = reveal_block_height(cipher.label())?;
+        if current_block_height < reveal_block_height {
+            return Err(NodeError::DecryptionNotRevealed(reveal_block_height).into());
+        }
+        let decryption_share = tenc::partial_decrypt(private, &cipher, &mut rand::thread_rng())?;
+
+        let mut partial_public_key = P::G1::one();
+        partial_public_key.mul(&private.private);
+
+        Ok(bincode::serialize(&(decryption_share, partial_public_key))?)
+    }
+
+    fn verify_decryption_share(
+        &self,
+        partial_public_key: &P::G2,
+        ciphertext: &[u8],
+        decryption_share: &[u8],
+    ) -> Result<()> {
+        let cipher: ThresholdCipher<CipherCurve<P>> = bincode::deserialize(ciphertext)?;
+        let (decryption_share, g1_partial_public_key): (DecryptionShare<CipherCurve<P>>, P::G1) =
+            bincode::deserialize(decryption_share)?;
+
+        // e(x * g1, g2) == e(g1, x * g2)
+        if P::pair(&g1_partial_public_key, &P::G2::one())
+            != P::pair(&P::G1::one(), partial_public_key)
+        {
+            return Err(anyhow!(
+                "the partial public key in G1 doesn't match the one of the holder"
+            ));
+        }
+
+        tenc::verify_share(&g1_partial_public_key, &cipher, &decryption_share)?;
+        Ok(())
+    }
+
+    fn combine(
+        &self,
+        threshold: usize,
+        ciphertext: &[u8],
+        decryption_shares: &[Vec<u8>],
+    ) -> Result<Vec<u8>> {
+        let cipher: ThresholdCipher<CipherCurve<P>> = bincode::deserialize(ciphertext)?;
+        let decryption_shares = decryption_shares
+            .iter()
+            .map(|share| {
+                bincode::deserialize::<(DecryptionShare<CipherCurve<P>>, P::G1)>(share)
+                    .map(|(share, _)| share)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tenc::combine(threshold, &cipher, decryption_shares)?)
+    }
+
+    fn partial_encryption_key(&self, private: &Share<P::Scalar>) -> Result<Vec<u8>> {
+        let mut partial_public_key = P::G1::one();
+        partial_public_key.mul(&private.private);
+
+        Ok(bincode::serialize(&Eval {
+            value: partial_public_key,
+            index: private.index,
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reveal_block_height, DecryptionCore, SimpleDecryptionCore};
+    use crate::algorithm::blind::{BlindSignatureCore, SimpleBlindSignatureCore};
+    use threshold_bls::{
+        curve::bn254::{G1Curve, G2Curve, PairingCurve},
+        group::{Curve, Element},
+        poly::{Eval, Idx, Poly},
+        sig::Share,
+        tenc::encrypt,
+    };
+
+    type Scalar = <G2Curve as Curve>::Scalar;
+    type G1 = <G1Curve as Curve>::Point;
+    type G2 = <G2Curve as Curve>::Point;
+
+    fn shares(n: usize, t: usize) -> (Vec<Share<Scalar>>, Poly<G2>, G1) {
+        let private = Poly::<Scalar>::new(t - 1);
+        let shares = (0..n)
+            .map(|i| private.eval(i as Idx))
+            .map(|e| Share {
+                index: e.index,
+                private: e.value,
+            })
+            .collect();
+        let mut encryption_key = G1::one();
+        encryption_key.mul(&private.get(0));
+        (shares, private.commit(), encryption_key)
+    }
+
+    #[test]
+    fn test_reveal_block_height() {
+        assert_eq!(reveal_block_height(b"block:100").unwrap(), 100);
+        assert!(reveal_block_height(b"block:").is_err());
+        assert!(reveal_block_height(b"block:-1").is_err());
+        assert!(reveal_block_height(b"reveal at block 100").is_err());
+        assert!(reveal_block_height(&[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_partial_decrypt_after_reveal_block_height() {
+        let core: Box<dyn DecryptionCore<G2Curve>> =
+            Box::new(SimpleDecryptionCore::<PairingCurve>::new());
+        let (shares, public, encryption_key) = shares(1, 1);
+        let share = &shares[0];
+
+        let cipher = encrypt::<G1Curve, _>(
+            &encryption_key,
+            b"block:100",
+            b"bid",
+            &mut rand::thread_rng(),
+        );
+        let ciphertext = bincode::serialize(&cipher).unwrap();
+
+        assert!(core.partial_decrypt(share, &ciphertext, 99).is_err());
+        let decryption_share = core.partial_decrypt(share, &ciphertext, 100).unwrap();
+        core.verify_decryption_share(
+            &public.eval(share.index).value,
+            &ciphertext,
+            &decryption_share,
+        )
+        .unwrap();
+
+        let unlabeled = encrypt::<G1Curve, _>(
+            &encryption_key,
+            b"reveal now",
+            b"bid",
+            &mut rand::thread_rng(),
+        );
+        assert!(core
+            .partial_decrypt(share, &bincode::serialize(&unlabeled).unwrap(), 100)
+            .is_err());
+    }
+
+    #[test]
+    fn test_threshold_decryption() {
+        let core = SimpleDecryptionCore::<PairingCurve>::new();
+        let (shares, public, _) = shares(5, 3);
+
+        // the requester recovers the public key of the group in G1 from the partial ones
+        let partial_encryption_keys = shares
+            .iter()
+            .take(3)
+            .map(|share| {
+                bincode::deserialize::<Eval<G1>>(&core.partial_encryption_key(share).unwrap())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let encryption_key = Poly::<G1>::recover(3, partial_encryption_keys).unwrap();
+
+        let cipher = encrypt::<G1Curve, _>(
+            &encryption_key,
+            b"block:100",
+            b"sealed bid",
+            &mut rand::thread_rng(),
+        );
+        let ciphertext = bincode::serialize(&cipher).unwrap();
+
+        let decryption_shares = shares
+            .iter()
+            .map(|share| core.partial_decrypt(share, &ciphertext, 100).unwrap())
+            .collect::<Vec<_>>();
+
+        for (share, decryption_share) in shares.iter().zip(&decryption_shares) {
+            core.verify_decryption_share(
+                &public.eval(share.index).value,
+                &ciphertext,
+                decryption_share,
+            )
+            .unwrap();
+
+            let other_partial_public_key = public.eval(share.index + 1).value;
+            assert!(core
+                .verify_decryption_share(&other_partial_public_key, &ciphertext, decryption_share)
+                .is_err());
+        }
+
+        assert_eq!(
+            core.combine(3, &ciphertext, &decryption_shares[2..])
+                .unwrap(),
+            b"sealed bid"
+        );
+    }
+
+    #[test]
+    fn test_blind_signing_does_not_decrypt() {
+        let decryption_core = SimpleDecryptionCore::<PairingCurve>::new();
+        let blind_signature_core = SimpleBlindSignatureCore::<PairingCurve>::new();
+        let (shares, _, encryption_key) = shares(3, 2);
+
+        let cipher = encrypt::<G1Curve, _>(
+            &encryption_key,
+            b"block:100",
+            b"sealed bid",
+            &mut rand::thread_rng(),
+        );
+        let ciphertext = bincode::serialize(&cipher).unwrap();
+
+        // the ephemeral public key follows the label in the ciphertext, which is in G1 while the
+        // blinded messages are signed in G2, so it can't be submitted as a blinded message
+        let (_, ephemeral): (Vec<u8>, G1) = bincode::deserialize(&ciphertext).unwrap();
+        let ephemeral = bincode::serialize(&ephemeral).unwrap();
+        for share in shares.iter() {
+            assert!(blind_signature_core
+                .sign_blind_partial(share, &ephemeral)
+                .is_err());
+        }
+
+        // nor do the blind partial signatures of any point in G2 combine into the plaintext
+        let blinded_msg = bincode::serialize(&G2::rand(&mut rand::thread_rng())).unwrap();
+        let blind_partials = shares
+            .iter()
+            .map(|share| {
+                blind_signature_core
+                    .sign_blind_partial(share, &blinded_msg)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(decryption_core
+            .combine(2, &ciphertext, &blind_partials)
+            .is_err());
+
+        // which is only decrypted once the label is met
+        let decryption_shares = shares
+            .iter()
+            .map(|share| {
+                decryption_core
+                    .partial_decrypt(share, &ciphertext, 100)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decryption_core
+                .combine(2, &ciphertext, &decryption_shares)
+                .unwrap(),
+            b"sealed bid"
+        );
+    }
+}
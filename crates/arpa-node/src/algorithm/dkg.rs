@@ -1,16 +1,22 @@
+use super::off_chain_board::DKGBundleStore;
 use crate::error::{NodeError, NodeResult};
 use arpa_contract_client::coordinator::{CoordinatorTransactions, CoordinatorViews};
+use arpa_core::metrics::observe_dkg_phase_duration;
+use arpa_core::{DKGProtocol, DKGTask};
+use arpa_dal::{DKGCheckpoint, GroupInfoHandler};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use dkg_core::{
-    primitives::{joint_feldman::*, *},
-    BoardPublisher, DKGNodeError, DKGPhase, Phase2Result,
+    primitives::{joint_feldman::*, pvss::PVSSDKG, *},
+    read_registered_dkg_public_key, BoardPublisher,
 };
-use log::info;
+use log::{info, warn};
 use rand::RngCore;
 use rustc_hex::ToHex;
-use std::marker::PhantomData;
-use threshold_bls::{group::Curve, poly::Idx};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{marker::PhantomData, sync::Arc, time::Instant};
+use threshold_bls::{group::Curve, poly::Idx, sig::SignatureScheme};
+use tokio::sync::RwLock;
 
 #[async_trait]
 pub(crate) trait DKGCore<F, R, C> {
@@ -29,43 +35,162 @@ pub(crate) trait DKGCore<F, R, C> {
 pub(crate) struct AllPhasesDKGCore<
     P: CoordinatorTransactions + CoordinatorViews + BoardPublisher<C>,
     C: Curve,
+    S: SignatureScheme<Public = C::Point, Private = C::Scalar>,
 > {
     coordinator_client: P,
+    group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<C>>>>,
+    task: DKGTask,
     c: PhantomData<C>,
+    s: PhantomData<S>,
     dkg_wait_for_phase_interval_millis: u64,
+    dkg_protocol: DKGProtocol,
+    is_dkg_key_proof_required: bool,
+    dkg_bundle_store: Option<Arc<RwLock<DKGBundleStore>>>,
 }
 
-impl<P: CoordinatorTransactions + CoordinatorViews + BoardPublisher<C>, C: Curve>
-    AllPhasesDKGCore<P, C>
+/// The state machine to run the next phase with, which is checkpointed after every phase.
+#[allow(clippy::enum_variant_names)]
+enum DKGPhaseState<S1, S2, S3> {
+    WaitingShare(S1),
+    WaitingResponse(S2),
+    WaitingJustification(S3),
+}
+
+type Phase1Of<D, C> = <D as Phase0<C>>::Next;
+type Phase2Of<D, C> = <Phase1Of<D, C> as Phase1<C>>::Next;
+type Phase3Of<D, C> = <Phase2Of<D, C> as Phase2<C>>::Next;
+type DKGPhaseStateOf<D, C> = DKGPhaseState<Phase1Of<D, C>, Phase2Of<D, C>, Phase3Of<D, C>>;
+
+impl<
+        P: CoordinatorTransactions + CoordinatorViews + BoardPublisher<C>,
+        C: Curve,
+        S: SignatureScheme<Public = C::Point, Private = C::Scalar>,
+    > AllPhasesDKGCore<P, C, S>
 {
-    pub fn new(coordinator_client: P, dkg_wait_for_phase_interval_millis: u64) -> Self {
+    /// The bundle store is the one the board serves the bundles from, if the bundles
+    /// are exchanged off chain.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        coordinator_client: P,
+        group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<C>>>>,
+        task: DKGTask,
+        dkg_wait_for_phase_interval_millis: u64,
+        dkg_protocol: DKGProtocol,
+        is_dkg_key_proof_required: bool,
+        dkg_bundle_store: Option<Arc<RwLock<DKGBundleStore>>>,
+    ) -> Self {
         AllPhasesDKGCore {
             coordinator_client,
+            group_cache,
+            task,
             c: PhantomData,
+            s: PhantomData,
             dkg_wait_for_phase_interval_millis,
+            dkg_protocol,
+            is_dkg_key_proof_required,
+            dkg_bundle_store,
         }
     }
-}
 
-#[async_trait]
-impl<F, R, P, C> DKGCore<F, R, C> for AllPhasesDKGCore<P, C>
-where
-    R: RngCore,
-    F: Fn() -> R,
-    P: CoordinatorTransactions + CoordinatorViews + BoardPublisher<C> + Sync + Send,
-    C: Curve,
-{
-    async fn run_dkg(
+    /// Saves the state of the next phase along with the bundle of the last phase,
+    /// then publishes the bundle, so that a restart in between never produces a different bundle.
+    ///
+    /// The state is serialized by the caller, as the phases are not required to be `Sync`.
+    async fn checkpoint_and_publish<B: Serialize + Send>(
         &mut self,
-        dkg_private_key: C::Scalar,
-        node_rpc_endpoint: String,
-        rng: F,
-    ) -> NodeResult<DKGOutput<C>>
+        completed_phases: usize,
+        state: Vec<u8>,
+        bundle: Option<B>,
+    ) -> NodeResult<()> {
+        let bundle = bundle
+            .map(|bundle| bincode::serialize(&bundle))
+            .transpose()?;
+
+        let checkpoint = DKGCheckpoint {
+            task: self.task.clone(),
+            completed_phases,
+            state,
+            bundle: bundle.clone(),
+        };
+
+        self.group_cache
+            .write()
+            .await
+            .save_dkg_checkpoint(self.task.group_index, self.task.epoch, checkpoint)
+            .await?;
+
+        if let Some(bundle) = bundle {
+            self.coordinator_client.publish(bundle).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Restores the state machine from the checkpoint, and publishes the bundle of the last phase
+    /// only if it didn't make it to the board before the restart.
+    async fn resume<D>(&mut self, checkpoint: DKGCheckpoint) -> NodeResult<DKGPhaseStateOf<D, C>>
     where
-        F: Send + Debug + 'async_trait,
+        D: Phase0<C>,
+        Phase3Of<D, C>: DeserializeOwned,
     {
-        // TODO error handling and retry
+        let completed_phases = checkpoint.completed_phases;
+
+        let state = match completed_phases {
+            1 => DKGPhaseState::WaitingShare(bincode::deserialize(&checkpoint.state)?),
+            2 => DKGPhaseState::WaitingResponse(bincode::deserialize(&checkpoint.state)?),
+            3 => DKGPhaseState::WaitingJustification(bincode::deserialize(&checkpoint.state)?),
+            _ => return Err(NodeError::InvalidDKGCheckpoint(completed_phases)),
+        };
+
+        info!(
+            "Resuming DKG of group {} epoch {} after Phase {}.",
+            self.task.group_index,
+            self.task.epoch,
+            completed_phases - 1
+        );
+
+        if let Some(bundle) = checkpoint.bundle {
+            // the bundle of shares has to be served to the other members again,
+            // whether or not its commitment made it to the board
+            if completed_phases == 1 {
+                if let Some(dkg_bundle_store) = &self.dkg_bundle_store {
+                    dkg_bundle_store.write().await.insert(
+                        self.task.group_index,
+                        self.task.epoch,
+                        bundle.clone(),
+                    );
+                }
+            }
+
+            let posted = match completed_phases {
+                1 => self.coordinator_client.get_shares().await?,
+                2 => self.coordinator_client.get_responses().await?,
+                _ => self.coordinator_client.get_justifications().await?,
+            };
+
+            if posted.contains(&bundle) {
+                info!(
+                    "Bundle of Phase {} is already on the board.",
+                    completed_phases - 1
+                );
+            } else if self.coordinator_client.in_phase().await? == completed_phases as i8 {
+                info!("Publishing the bundle of Phase {}.", completed_phases - 1);
+                self.coordinator_client.publish(bundle).await?;
+            } else {
+                warn!(
+                    "Phase {} is over before the bundle could be published.",
+                    completed_phases - 1
+                );
+            }
+        }
+
+        Ok(state)
+    }
 
+    /// Builds the group from the DKG keys of the participants, along with the indices of
+    /// the participants whose keys come without a valid proof of possession, or as bare keys
+    /// once the NodeRegistry requires the proofs.
+    async fn get_group(&self) -> NodeResult<(Group<C>, Vec<Idx>)> {
         // Wait for Phase 0
         wait_for_phase(
             &self.coordinator_client,
@@ -88,111 +213,262 @@ where
             info!("{:?} -> {}", address, key)
         }
 
-        let nodes = group
+        let mut nodes = vec![];
+        let mut invalid_key_indices = vec![];
+
+        for (i, pubkey) in group
             .1
             .into_iter()
             .filter(|pubkey| !pubkey.is_empty()) // skip users that did not register
             .enumerate()
-            .map(|(i, pubkey)| {
-                let pubkey = bincode::deserialize(&pubkey)?;
-                Ok(Node::<C>::new(i as Idx, pubkey))
-            })
-            .collect::<NodeResult<_>>()?;
+        {
+            match read_registered_dkg_public_key::<S>(&pubkey, self.is_dkg_key_proof_required) {
+                Ok((pubkey, is_legacy)) => {
+                    if is_legacy {
+                        warn!(
+                            "DKG key of node {} carries no proof of possession, it should be replaced by change-dkg-public-key",
+                            i
+                        );
+                    }
+                    nodes.push(Node::<C>::new(i as Idx, pubkey))
+                }
+                Err(e) => {
+                    warn!("Invalid DKG key of node {}: {}", i, e);
+                    invalid_key_indices.push(i as Idx);
+                }
+            }
+        }
 
         let group = Group {
             threshold: group.0,
             nodes,
         };
 
+        Ok((group, invalid_key_indices))
+    }
+
+    async fn run_phase0<D, R, F>(
+        &mut self,
+        new_dkg: fn(C::Scalar, String, Group<C>) -> DKGResult<D>,
+        group: Group<C>,
+        dkg_private_key: C::Scalar,
+        node_rpc_endpoint: String,
+        rng: F,
+    ) -> NodeResult<DKGPhaseStateOf<D, C>>
+    where
+        D: Phase0<C>,
+        Phase1Of<D, C>: Send,
+        <Phase1Of<D, C> as Phase1<C>>::Shares: Send,
+        R: RngCore,
+        F: Fn() -> R,
+    {
         // Instantiate the DKG with the group info
         info!("Calculating and broadcasting our shares... Running Phase 0.");
-        let phase0 = DKG::new(dkg_private_key, node_rpc_endpoint, group)?;
+        let mut phase0 = new_dkg(dkg_private_key, node_rpc_endpoint, group)?;
 
         // Run Phase 0 and publish to the chain
-        let phase1 = phase0.run(&mut self.coordinator_client, rng).await?;
+        phase0.set_rpc_endpoint();
+        let (phase1, shares) = phase0.encrypt_shares(rng)?;
+        self.checkpoint_and_publish(1, bincode::serialize(&phase1)?, shares)
+            .await?;
 
-        // Wait for Phase 1
-        wait_for_phase(
-            &self.coordinator_client,
-            1,
-            self.dkg_wait_for_phase_interval_millis,
-        )
-        .await?;
-
-        // Get the shares
-        let shares = self.coordinator_client.get_shares().await?;
-        info!("Got {} shares...", shares.len());
-        let shares = parse_bundle(&shares)?;
-        info!("Parsed {} shares. Running Phase 1.", shares.len());
-
-        // Run Phase 1
-        match phase1.run(&mut self.coordinator_client, &shares).await {
-            Ok(phase2) => {
-                // Wait for Phase 2
-                wait_for_phase(
-                    &self.coordinator_client,
-                    2,
-                    self.dkg_wait_for_phase_interval_millis,
-                )
-                .await?;
-
-                // Get the responses
-                let responses = self.coordinator_client.get_responses().await?;
-                info!("Got {} responses...", responses.len());
-                let responses = parse_bundle(&responses)?;
-                info!("Parsed {} responses. Running Phase 2.", responses.len());
-
-                // Run Phase 2
-                let result = match phase2.run(&mut self.coordinator_client, &responses).await? {
-                    Phase2Result::Output(out) => Ok(out),
-                    // Run Phase 3 if Phase 2 errored
-                    Phase2Result::GoToPhase3(phase3) => {
-                        info!("There were complaints. Running Phase 3.");
-                        // Wait for Phase 3
-                        wait_for_phase(
-                            &self.coordinator_client,
-                            3,
-                            self.dkg_wait_for_phase_interval_millis,
-                        )
-                        .await?;
-
-                        let justifications = self.coordinator_client.get_justifications().await?;
-                        let justifications = parse_bundle(&justifications)?;
-
-                        // Run Phase 3
-                        phase3
-                            .run(&mut self.coordinator_client, &justifications)
-                            .await
-                    }
-                };
+        Ok(DKGPhaseState::WaitingShare(phase1))
+    }
 
-                match result {
-                    Ok(output) => {
-                        info!("Success. Your share and threshold pubkey are ready.");
+    /// Runs every phase of the DKG with the given protocol, from Phase 0 or from
+    /// where the last checkpoint of the task left off.
+    async fn run_protocol<D, R, F>(
+        &mut self,
+        new_dkg: fn(C::Scalar, String, Group<C>) -> DKGResult<D>,
+        dkg_private_key: C::Scalar,
+        node_rpc_endpoint: String,
+        rng: F,
+    ) -> NodeResult<DKGOutput<C>>
+    where
+        D: Phase0<C>,
+        Phase1Of<D, C>: Send,
+        <Phase1Of<D, C> as Phase1<C>>::Shares: Send,
+        Phase2Of<D, C>: Send,
+        Phase3Of<D, C>: Serialize + DeserializeOwned + Send,
+        R: RngCore,
+        F: Fn() -> R,
+    {
+        // TODO error handling and retry
 
-                        write_output(&output)?;
+        let checkpoint = self
+            .group_cache
+            .read()
+            .await
+            .get_dkg_checkpoint()?
+            .filter(|checkpoint| {
+                checkpoint.task.group_index == self.task.group_index
+                    && checkpoint.task.epoch == self.task.epoch
+            })
+            .cloned();
 
-                        // info!("{:#?}", output.qual.nodes);
+        let mut phase_started_at = Instant::now();
 
-                        // info!("public key: {}", output.public.public_key());
+        let mut state = match checkpoint {
+            Some(checkpoint) => self.resume::<D>(checkpoint).await?,
+            None => {
+                let (group, invalid_key_indices) = self.get_group().await?;
 
-                        Ok(output)
-                    }
-                    Err(err) => Err(err.into()),
+                // the members who can't prove the possession of their keys are disqualified
+                // before any share is dealt, so that the group is formed again without them
+                if !invalid_key_indices.is_empty() {
+                    return Ok(DKGOutput::<C>::fail(invalid_key_indices));
                 }
+
+                self.run_phase0(new_dkg, group, dkg_private_key, node_rpc_endpoint, rng)
+                    .await?
             }
-            Err(err) => match err {
-                DKGNodeError::DKGError(e) => match e {
-                    DKGError::NotEnoughValidShares(_, _, disqualified_node_indices) => {
-                        Ok(DKGOutput::<C>::fail(disqualified_node_indices))
+        };
+
+        let result = loop {
+            state = match state {
+                DKGPhaseState::WaitingShare(phase1) => {
+                    // Wait for Phase 1
+                    wait_for_phase(
+                        &self.coordinator_client,
+                        1,
+                        self.dkg_wait_for_phase_interval_millis,
+                    )
+                    .await?;
+
+                    observe_dkg_phase_duration(0, phase_started_at.elapsed());
+                    phase_started_at = Instant::now();
+
+                    // Get the shares
+                    let shares = self.coordinator_client.get_shares().await?;
+                    info!("Got {} shares...", shares.len());
+                    let shares = parse_bundle::<<Phase1Of<D, C> as Phase1<C>>::Shares>(&shares)?;
+                    info!("Parsed {} shares. Running Phase 1.", shares.len());
+
+                    // Run Phase 1
+                    match phase1.process_shares(&shares, true) {
+                        Ok((phase2, responses)) => {
+                            self.checkpoint_and_publish(2, bincode::serialize(&phase2)?, responses)
+                                .await?;
+
+                            DKGPhaseState::WaitingResponse(phase2)
+                        }
+                        Err(
+                            DKGError::NotEnoughValidShares(_, _, disqualified_node_indices)
+                            | DKGError::NotEnoughJustifications(_, _, disqualified_node_indices),
+                        ) => {
+                            return Ok(DKGOutput::<C>::fail(disqualified_node_indices));
+                        }
+                        Err(e) => return Err(e.into()),
                     }
-                    DKGError::NotEnoughJustifications(_, _, disqualified_node_indices) => {
-                        Ok(DKGOutput::<C>::fail(disqualified_node_indices))
+                }
+                DKGPhaseState::WaitingResponse(phase2) => {
+                    // Wait for Phase 2
+                    wait_for_phase(
+                        &self.coordinator_client,
+                        2,
+                        self.dkg_wait_for_phase_interval_millis,
+                    )
+                    .await?;
+
+                    observe_dkg_phase_duration(1, phase_started_at.elapsed());
+                    phase_started_at = Instant::now();
+
+                    // Get the responses
+                    let responses = self.coordinator_client.get_responses().await?;
+                    info!("Got {} responses...", responses.len());
+                    let responses = parse_bundle(&responses)?;
+                    info!("Parsed {} responses. Running Phase 2.", responses.len());
+
+                    // Run Phase 2
+                    match phase2.process_responses(&responses) {
+                        Ok(output) => {
+                            observe_dkg_phase_duration(2, phase_started_at.elapsed());
+
+                            break Ok(output);
+                        }
+                        // Run Phase 3 if Phase 2 errored
+                        Err(Ok((phase3, justifications))) => {
+                            info!("There were complaints. Running Phase 3.");
+                            // Nodes may just see that justifications are needed but they
+                            // don't have to create any, since no complaint have been filed
+                            // against their deal.
+                            self.checkpoint_and_publish(
+                                3,
+                                bincode::serialize(&phase3)?,
+                                justifications,
+                            )
+                            .await?;
+
+                            DKGPhaseState::WaitingJustification(phase3)
+                        }
+                        Err(Err(e)) => return Err(e.into()),
                     }
-                    _ => Err(e.into()),
-                },
-                _ => Err(err.into()),
-            },
+                }
+                DKGPhaseState::WaitingJustification(phase3) => {
+                    // Wait for Phase 3
+                    wait_for_phase(
+                        &self.coordinator_client,
+                        3,
+                        self.dkg_wait_for_phase_interval_millis,
+                    )
+                    .await?;
+
+                    observe_dkg_phase_duration(2, phase_started_at.elapsed());
+                    phase_started_at = Instant::now();
+
+                    let justifications = self.coordinator_client.get_justifications().await?;
+                    let justifications = parse_bundle(&justifications)?;
+
+                    // Run Phase 3
+                    let result = phase3.process_justifications(&justifications);
+
+                    observe_dkg_phase_duration(3, phase_started_at.elapsed());
+
+                    break result;
+                }
+            };
+        };
+
+        match result {
+            Ok(output) => {
+                info!("Success. Your share and threshold pubkey are ready.");
+
+                write_output(&output)?;
+
+                Ok(output)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl<F, R, P, C, S> DKGCore<F, R, C> for AllPhasesDKGCore<P, C, S>
+where
+    R: RngCore,
+    F: Fn() -> R,
+    P: CoordinatorTransactions + CoordinatorViews + BoardPublisher<C> + Sync + Send,
+    C: Curve,
+    S: SignatureScheme<Public = C::Point, Private = C::Scalar> + Sync + Send,
+{
+    async fn run_dkg(
+        &mut self,
+        dkg_private_key: C::Scalar,
+        node_rpc_endpoint: String,
+        rng: F,
+    ) -> NodeResult<DKGOutput<C>>
+    where
+        F: Send + Debug + 'async_trait,
+    {
+        match self.dkg_protocol {
+            DKGProtocol::JointFeldman => {
+                self.run_protocol(DKG::new, dkg_private_key, node_rpc_endpoint, rng)
+                    .await
+            }
+            DKGProtocol::Pvss => {
+                self.run_protocol(PVSSDKG::new, dkg_private_key, node_rpc_endpoint, rng)
+                    .await
+            }
         }
     }
 }
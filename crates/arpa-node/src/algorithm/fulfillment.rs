@@ -0,0 +1,200 @@
+use arpa_contract_client::adapter::FulfillmentGasEstimate;
+use arpa_core::{FulfillmentPolicyDescriptor, RandomnessTask};
+use ethers::types::U256;
+use std::cmp::min;
+
+const WEI_PER_GWEI: u64 = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FulfillmentEstimate {
+    pub gas_limit: U256,
+    pub wei_per_gas: U256,
+    pub gas_cost: U256,
+    pub expected_reward: U256,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FulfillmentDecision {
+    Fulfill(FulfillmentEstimate),
+    Defer(FulfillmentEstimate),
+}
+
+pub trait FulfillmentPolicy: std::fmt::Debug + Sync + Send {
+    /// Estimates the cost and the expected reward of fulfilling the task at the given gas price,
+    /// from the same simulation that the gas limit of the fulfillment transaction comes from
+    fn estimate(
+        &self,
+        randomness_task: &RandomnessTask,
+        gas_estimate: &FulfillmentGasEstimate,
+        wei_per_gas: U256,
+    ) -> FulfillmentEstimate;
+
+    /// Decides whether the task should be fulfilled now or deferred until the gas price drops
+    fn evaluate(
+        &self,
+        randomness_task: &RandomnessTask,
+        gas_estimate: &FulfillmentGasEstimate,
+        wei_per_gas: U256,
+    ) -> FulfillmentDecision;
+}
+
+/// Fulfills a task only when the gas reimbursement, which is capped by the `callback_max_gas_price`
+/// of the request, plus the extra reward covers the gas cost and the L1 data fee within the
+/// tolerated loss.
+#[derive(Debug, Clone, Copy)]
+pub struct ProfitabilityFulfillmentPolicy {
+    extra_reward: U256,
+    max_loss: U256,
+}
+
+impl ProfitabilityFulfillmentPolicy {
+    pub fn new(extra_reward: U256, max_loss: U256) -> Self {
+        ProfitabilityFulfillmentPolicy {
+            extra_reward,
+            max_loss,
+        }
+    }
+}
+
+impl From<FulfillmentPolicyDescriptor> for ProfitabilityFulfillmentPolicy {
+    fn from(descriptor: FulfillmentPolicyDescriptor) -> Self {
+        ProfitabilityFulfillmentPolicy::new(
+            U256::from(descriptor.extra_reward_gwei) * U256::from(WEI_PER_GWEI),
+            U256::from(descriptor.max_loss_gwei) * U256::from(WEI_PER_GWEI),
+        )
+    }
+}
+
+impl FulfillmentPolicy for ProfitabilityFulfillmentPolicy {
+    fn estimate(
+        &self,
+        randomness_task: &RandomnessTask,
+        gas_estimate: &FulfillmentGasEstimate,
+        wei_per_gas: U256,
+    ) -> FulfillmentEstimate {
+        // the adapter reimburses the gas used rather than the gas limit, and never the L1 data fee
+        let gas_cost = gas_estimate.estimated_gas * wei_per_gas + gas_estimate.l1_data_fee;
+
+        let expected_reward = gas_estimate.estimated_gas
+            * min(wei_per_gas, randomness_task.callback_max_gas_price)
+            + self.extra_reward;
+
+        FulfillmentEstimate {
+            gas_limit: gas_estimate.gas_limit,
+            wei_per_gas,
+            gas_cost,
+            expected_reward,
+        }
+    }
+
+    fn evaluate(
+        &self,
+        randomness_task: &RandomnessTask,
+        gas_estimate: &FulfillmentGasEstimate,
+        wei_per_gas: U256,
+    ) -> FulfillmentDecision {
+        let estimate = self.estimate(randomness_task, gas_estimate, wei_per_gas);
+
+        if estimate.expected_reward + self.max_loss >= estimate.gas_cost {
+            FulfillmentDecision::Fulfill(estimate)
+        } else {
+            FulfillmentDecision::Defer(estimate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FulfillmentDecision, FulfillmentPolicy, ProfitabilityFulfillmentPolicy};
+    use arpa_contract_client::adapter::FulfillmentGasEstimate;
+    use arpa_core::{
+        buffer_fulfill_randomness_gas, FulfillmentPolicyDescriptor, RandomnessRequestType,
+        RandomnessTask,
+    };
+    use ethers::types::{Address, U256};
+
+    fn build_randomness_task(callback_max_gas_price: U256) -> RandomnessTask {
+        RandomnessTask {
+            request_id: vec![1],
+            subscription_id: 1,
+            group_index: 0,
+            request_type: RandomnessRequestType::Randomness,
+            params: vec![],
+            requester: Address::zero(),
+            seed: U256::from(42),
+            request_confirmations: 6,
+            callback_gas_limit: 100000,
+            callback_max_gas_price,
+            assignment_block_height: 100,
+        }
+    }
+
+    fn build_gas_estimate(estimated_gas: u64, l1_data_fee: u64) -> FulfillmentGasEstimate {
+        FulfillmentGasEstimate {
+            estimated_gas: U256::from(estimated_gas),
+            gas_limit: buffer_fulfill_randomness_gas(U256::from(estimated_gas)),
+            l1_data_fee: U256::from(l1_data_fee),
+        }
+    }
+
+    #[test]
+    fn test_default_policy_defers_when_gas_price_exceeds_max_gas_price() {
+        let policy = ProfitabilityFulfillmentPolicy::from(FulfillmentPolicyDescriptor::default());
+        let task = build_randomness_task(U256::from(1_000_000_000u64));
+        let gas_estimate = build_gas_estimate(800000, 0);
+
+        assert!(matches!(
+            policy.evaluate(&task, &gas_estimate, U256::from(1_000_000_000u64)),
+            FulfillmentDecision::Fulfill(_)
+        ));
+        assert!(matches!(
+            policy.evaluate(&task, &gas_estimate, U256::from(1_000_000_001u64)),
+            FulfillmentDecision::Defer(_)
+        ));
+    }
+
+    #[test]
+    fn test_policy_accounts_for_l1_data_fee() {
+        let task = build_randomness_task(U256::from(1_000_000_000u64));
+        let wei_per_gas = U256::from(1_000_000_000u64);
+        let policy = ProfitabilityFulfillmentPolicy::new(U256::zero(), U256::zero());
+
+        let estimate = policy.estimate(&task, &build_gas_estimate(800000, 0), wei_per_gas);
+        assert_eq!(estimate.gas_cost, estimate.expected_reward);
+        assert_eq!(
+            estimate.gas_limit,
+            buffer_fulfill_randomness_gas(U256::from(800000))
+        );
+
+        assert!(matches!(
+            policy.evaluate(&task, &build_gas_estimate(800000, 1), wei_per_gas),
+            FulfillmentDecision::Defer(_)
+        ));
+
+        // just enough to cover the L1 data fee
+        let policy = ProfitabilityFulfillmentPolicy::new(U256::zero(), U256::from(1));
+
+        assert!(matches!(
+            policy.evaluate(&task, &build_gas_estimate(800000, 1), wei_per_gas),
+            FulfillmentDecision::Fulfill(_)
+        ));
+    }
+
+    #[test]
+    fn test_extra_reward_makes_fulfillment_profitable() {
+        let task = build_randomness_task(U256::from(1_000_000_000u64));
+        let policy = ProfitabilityFulfillmentPolicy::from(FulfillmentPolicyDescriptor {
+            extra_reward_gwei: 1_000_000,
+            max_loss_gwei: 0,
+        });
+
+        assert!(matches!(
+            policy.evaluate(
+                &task,
+                &build_gas_estimate(800000, 0),
+                U256::from(1_100_000_000u64)
+            ),
+            FulfillmentDecision::Fulfill(_)
+        ));
+    }
+}
@@ -1,2 +1,6 @@
+pub mod blind;
 pub mod bls;
+pub mod decryption;
 pub mod dkg;
+pub mod fulfillment;
+pub mod off_chain_board;
@@ -0,0 +1,1028 @@
+use crate::error::{NodeError, NodeResult};
+use arpa_contract_client::{
+    coordinator::{CoordinatorTransactions, CoordinatorViews, DKGContractError},
+    error::{ContractClientError, ContractClientResult},
+};
+use arpa_core::DKGProtocol;
+use async_trait::async_trait;
+use dkg_core::{
+    deserialize_dkg_public_key,
+    primitives::{BundledJustification, BundledResponses, BundledShares},
+    BoardPublisher,
+};
+use ethers::{
+    types::{Address, TransactionReceipt},
+    utils::keccak256,
+};
+use log::{info, warn};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap},
+    marker::PhantomData,
+    sync::Arc,
+};
+use threshold_bls::{
+    ecies::{self, EciesCipher},
+    group::{Curve, Element},
+    sig::SignatureScheme,
+};
+use tokio::sync::RwLock;
+
+/// What is published to the coordinator in place of a bundle of shares when the bundles
+/// are exchanged off chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct DKGBundleCommitment<C: Curve> {
+    /// The keccak256 digest of the bundle
+    pub digest: [u8; 32],
+    /// The Joint-Feldman bundle of shares without the encrypted shares, i.e. the index of the
+    /// dealer and its public polynomial, so that every member judges the dealer with the same
+    /// polynomial, even if it can't get the encrypted shares. Empty for the other protocols.
+    pub public_bundle: Vec<u8>,
+    /// The rpc endpoint of the publisher encrypted to every participant, in the order of
+    /// the participants, so that the members can reach each other before any bundle is exchanged.
+    pub rpc_endpoint_secrets: Vec<Option<EciesCipher<C>>>,
+}
+
+/// What a member publishes to the coordinator in Phase 2 of the PVSS DKG, where no response
+/// is published, to attest which dealings it holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DKGBundleAttestation {
+    /// The participants whose committed dealing the member couldn't get
+    pub unavailable: Vec<usize>,
+}
+
+/// The DKG bundles published or fetched by the node, which are served to the other members
+/// by their digests. Only the bundles of the latest DKG task are kept.
+#[derive(Debug, Default)]
+pub struct DKGBundleStore {
+    task: Option<(usize, usize)>,
+    bundles: HashMap<[u8; 32], Vec<u8>>,
+}
+
+impl DKGBundleStore {
+    pub fn new() -> Self {
+        DKGBundleStore::default()
+    }
+
+    /// Keeps the bundle under its digest, dropping the bundles of any previous DKG task.
+    pub fn insert(&mut self, group_index: usize, epoch: usize, bundle: Vec<u8>) -> [u8; 32] {
+        if self.task != Some((group_index, epoch)) {
+            self.bundles.clear();
+            self.task = Some((group_index, epoch));
+        }
+
+        let digest = keccak256(&bundle);
+
+        self.bundles.insert(digest, bundle);
+
+        digest
+    }
+
+    pub fn get(&self, digest: &[u8]) -> Option<&Vec<u8>> {
+        <[u8; 32]>::try_from(digest)
+            .ok()
+            .and_then(|digest| self.bundles.get(&digest))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bundles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bundles.is_empty()
+    }
+}
+
+#[async_trait]
+pub trait DKGBundleFetcher {
+    /// Fetches the DKG bundle with the digest from the member serving at the endpoint.
+    async fn fetch_dkg_bundle(&self, endpoint: &str, digest: &[u8; 32]) -> NodeResult<Vec<u8>>;
+}
+
+/// A DKG board which only puts the commitments of the bundles of shares on chain, while the
+/// bundles themselves are served by the members and fetched from each other over rpc.
+/// The responses and the justifications are small enough to be posted on chain as they are.
+///
+/// Every member has to judge the dealers from the same data, so a bundle of shares which some
+/// member can't get is settled on chain as well. In Joint-Feldman the member complains about
+/// the dealer as if its share was missing, and the dealer reveals the share in its
+/// justification, which every member checks against the public polynomial of the commitment.
+/// In PVSS, which has neither complaints nor justifications, every member attests in Phase 2
+/// which dealings it couldn't get, and the dealers attested by anyone are left out by everyone.
+pub(crate) struct OffChainBoard<P, F, C: Curve, S> {
+    coordinator_client: P,
+    fetcher: F,
+    bundle_store: Arc<RwLock<DKGBundleStore>>,
+    group_index: usize,
+    epoch: usize,
+    dkg_private_key: C::Scalar,
+    node_rpc_endpoint: String,
+    dkg_protocol: DKGProtocol,
+    dkg_wait_for_phase_interval_millis: u64,
+    s: PhantomData<S>,
+}
+
+impl<P, F, C, S> OffChainBoard<P, F, C, S>
+where
+    P: CoordinatorTransactions + CoordinatorViews + Sync + Send,
+    F: DKGBundleFetcher + Sync + Send,
+    C: Curve,
+    S: SignatureScheme<Public = C::Point>,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        coordinator_client: P,
+        fetcher: F,
+        bundle_store: Arc<RwLock<DKGBundleStore>>,
+        group_index: usize,
+        epoch: usize,
+        dkg_private_key: C::Scalar,
+        node_rpc_endpoint: String,
+        dkg_protocol: DKGProtocol,
+        dkg_wait_for_phase_interval_millis: u64,
+    ) -> Self {
+        OffChainBoard {
+            coordinator_client,
+            fetcher,
+            bundle_store,
+            group_index,
+            epoch,
+            dkg_private_key,
+            node_rpc_endpoint,
+            dkg_protocol,
+            dkg_wait_for_phase_interval_millis,
+            s: PhantomData,
+        }
+    }
+
+    fn dkg_public_key(&self) -> C::Point {
+        let mut public_key = C::Point::one();
+        public_key.mul(&self.dkg_private_key);
+        public_key
+    }
+
+    /// Encrypts the rpc endpoint of the node to every participant who registered a DKG key.
+    async fn encrypt_rpc_endpoint(&self) -> ContractClientResult<Vec<Option<EciesCipher<C>>>> {
+        let (_, dkg_keys) = self.coordinator_client.get_dkg_keys().await?;
+
+        let mut rng = rand::thread_rng();
+
+        dkg_keys
+            .iter()
+            .map(|key| -> ContractClientResult<Option<EciesCipher<C>>> {
+                if key.is_empty() {
+                    return Ok(None);
+                }
+                // a member without a valid proof of possession is disqualified by everyone
+                let Ok(key) = deserialize_dkg_public_key::<S>(key) else {
+                    return Ok(None);
+                };
+                Ok(Some(ecies::encrypt::<C, _>(
+                    &key,
+                    self.node_rpc_endpoint.as_bytes(),
+                    &mut rng,
+                )))
+            })
+            .collect()
+    }
+
+    /// The position of the node among the participants, found by its DKG key.
+    async fn self_position(&self) -> ContractClientResult<Option<usize>> {
+        let (_, dkg_keys) = self.coordinator_client.get_dkg_keys().await?;
+
+        let dkg_public_key = self.dkg_public_key();
+
+        Ok(dkg_keys.iter().position(|key| {
+            deserialize_dkg_public_key::<S>(key).is_ok_and(|key| key == dkg_public_key)
+        }))
+    }
+
+    /// Decrypts the rpc endpoints of the members from the commitments of the shares,
+    /// in the order of the participants.
+    async fn member_rpc_endpoints(
+        &self,
+        commitments: &[Vec<u8>],
+    ) -> ContractClientResult<Vec<Option<String>>> {
+        let Some(self_position) = self.self_position().await? else {
+            warn!("DKG key of the node is not found among the participants.");
+            return Ok(vec![]);
+        };
+
+        Ok(commitments
+            .iter()
+            .map(|commitment| {
+                let commitment = bincode::deserialize::<DKGBundleCommitment<C>>(commitment).ok()?;
+                let secret = commitment
+                    .rpc_endpoint_secrets
+                    .get(self_position)?
+                    .as_ref()?;
+                let endpoint = ecies::decrypt::<C>(&self.dkg_private_key, secret).ok()?;
+                String::from_utf8(endpoint).ok()
+            })
+            .collect())
+    }
+
+    /// Looks up the bundle in the store, otherwise fetches it from the publisher first
+    /// and then from the other members who may have fetched it already.
+    async fn resolve_bundle(
+        &self,
+        publisher: usize,
+        digest: &[u8; 32],
+        endpoints: &[Option<String>],
+    ) -> Option<Vec<u8>> {
+        if let Some(bundle) = self.bundle_store.read().await.get(digest) {
+            return Some(bundle.clone());
+        }
+
+        let mut candidates: Vec<&String> = vec![];
+        for endpoint in endpoints
+            .get(publisher)
+            .into_iter()
+            .chain(endpoints.iter())
+            .flatten()
+        {
+            if *endpoint != self.node_rpc_endpoint && !candidates.contains(&endpoint) {
+                candidates.push(endpoint);
+            }
+        }
+
+        for endpoint in candidates {
+            match self.fetcher.fetch_dkg_bundle(endpoint, digest).await {
+                Ok(bundle) if keccak256(&bundle) == *digest => {
+                    self.bundle_store.write().await.insert(
+                        self.group_index,
+                        self.epoch,
+                        bundle.clone(),
+                    );
+
+                    return Some(bundle);
+                }
+                Ok(_) => warn!(
+                    "Fetching DKG bundle 0x{} from {} failed: {}",
+                    hex::encode(digest),
+                    endpoint,
+                    NodeError::DKGBundleDigestMismatch
+                ),
+                Err(e) => warn!(
+                    "Fetching DKG bundle 0x{} from {} failed: {:?}",
+                    hex::encode(digest),
+                    endpoint,
+                    e
+                ),
+            }
+        }
+
+        None
+    }
+
+    /// Resolves the commitments of the shares, in the order of the participants.
+    /// An invalid commitment is regarded as not published, as every member reads the same one,
+    /// while a valid one is paired with its bundle if the node can get it.
+    async fn resolve_commitments(
+        &self,
+        commitments: &[Vec<u8>],
+    ) -> ContractClientResult<Vec<Option<(DKGBundleCommitment<C>, Option<Vec<u8>>)>>> {
+        let endpoints = self.member_rpc_endpoints(commitments).await?;
+
+        let mut resolved = Vec::with_capacity(commitments.len());
+
+        for (publisher, commitment) in commitments.iter().enumerate() {
+            if commitment.is_empty() {
+                resolved.push(None);
+                continue;
+            }
+
+            let commitment = match bincode::deserialize::<DKGBundleCommitment<C>>(commitment) {
+                Ok(commitment) => commitment,
+                Err(e) => {
+                    warn!(
+                        "Invalid DKG bundle commitment of participant {}: {:?}",
+                        publisher, e
+                    );
+                    resolved.push(None);
+                    continue;
+                }
+            };
+
+            let bundle = self
+                .resolve_bundle(publisher, &commitment.digest, &endpoints)
+                .await;
+
+            if bundle.is_none() {
+                warn!(
+                    "DKG bundle 0x{} of participant {} is not available.",
+                    hex::encode(commitment.digest),
+                    publisher
+                );
+            }
+
+            resolved.push(Some((commitment, bundle)));
+        }
+
+        Ok(resolved)
+    }
+
+    /// Replaces the commitments of the Joint-Feldman shares with the bundles they commit to,
+    /// taking the dealer index and the public polynomial from the commitment. A bundle which
+    /// can't be resolved comes without any encrypted share, so the node complains about the
+    /// dealer, who has to reveal the share on chain in its justification.
+    async fn resolve_shares(
+        &self,
+        commitments: Vec<Vec<u8>>,
+    ) -> ContractClientResult<Vec<Vec<u8>>> {
+        if commitments.iter().all(|commitment| commitment.is_empty()) {
+            return Ok(commitments);
+        }
+
+        let mut bundles = Vec::with_capacity(commitments.len());
+
+        for (publisher, resolved) in self
+            .resolve_commitments(&commitments)
+            .await?
+            .into_iter()
+            .enumerate()
+        {
+            let Some((commitment, bundle)) = resolved else {
+                bundles.push(vec![]);
+                continue;
+            };
+
+            let public_bundle =
+                match bincode::deserialize::<BundledShares<C>>(&commitment.public_bundle) {
+                    Ok(public_bundle) => public_bundle,
+                    Err(e) => {
+                        warn!(
+                            "Invalid public bundle of participant {}: {:?}",
+                            publisher, e
+                        );
+                        bundles.push(vec![]);
+                        continue;
+                    }
+                };
+
+            let shares = bundle
+                .and_then(|bundle| bincode::deserialize::<BundledShares<C>>(&bundle).ok())
+                .map(|bundle| bundle.shares)
+                .unwrap_or_default();
+
+            bundles.push(bincode::serialize(&BundledShares {
+                dealer_idx: public_bundle.dealer_idx,
+                shares,
+                public: public_bundle.public,
+            })?);
+        }
+
+        Ok(bundles)
+    }
+
+    /// Replaces the commitments of the PVSS dealings with the dealings they commit to, leaving
+    /// out the ones which any member attested as unavailable.
+    ///
+    /// The node attests which dealings it couldn't get in Phase 2, and waits for the others to
+    /// do the same until Phase 3. A dealing attested by no one is held by every member who
+    /// attested, so a node which didn't attest in time fetches it from them, and fails the read
+    /// only if it still can't get it.
+    async fn resolve_attested_dealings(
+        &self,
+        commitments: Vec<Vec<u8>>,
+    ) -> ContractClientResult<Vec<Vec<u8>>> {
+        if commitments.iter().all(|commitment| commitment.is_empty()) {
+            return Ok(commitments);
+        }
+
+        let resolved = self.resolve_commitments(&commitments).await?;
+
+        // before Phase 2 only the dealing of the node itself is looked for, which is always
+        // in the store
+        if self.coordinator_client.in_phase().await? == 1 {
+            return Ok(resolved
+                .into_iter()
+                .map(|resolved| resolved.and_then(|(_, bundle)| bundle).unwrap_or_default())
+                .collect());
+        }
+
+        let attestations = self.coordinator_client.get_responses().await?;
+
+        let self_position = self.self_position().await?;
+
+        let attested = self_position.is_some_and(|position| {
+            attestations
+                .get(position)
+                .is_some_and(|attestation| !attestation.is_empty())
+        });
+
+        if !attested && self.coordinator_client.in_phase().await? == 2 {
+            let attestation = DKGBundleAttestation {
+                unavailable: resolved
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, resolved)| matches!(resolved, Some((_, None))))
+                    .map(|(publisher, _)| publisher)
+                    .collect(),
+            };
+
+            info!(
+                "Attesting the unavailable DKG dealings {:?}",
+                attestation.unavailable
+            );
+
+            self.coordinator_client
+                .publish(bincode::serialize(&attestation)?)
+                .await?;
+        }
+
+        // every member reads the attestations once Phase 2 is over
+        while (1..=2).contains(&self.coordinator_client.in_phase().await?) {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                self.dkg_wait_for_phase_interval_millis,
+            ))
+            .await;
+        }
+
+        let unavailable = self
+            .coordinator_client
+            .get_responses()
+            .await?
+            .iter()
+            .filter_map(|attestation| {
+                bincode::deserialize::<DKGBundleAttestation>(attestation).ok()
+            })
+            .flat_map(|attestation| attestation.unavailable)
+            .collect::<BTreeSet<_>>();
+
+        if !unavailable.is_empty() {
+            warn!(
+                "DKG dealings {:?} are attested as unavailable, leaving them out.",
+                unavailable
+            );
+        }
+
+        let endpoints = self.member_rpc_endpoints(&commitments).await?;
+
+        let mut bundles = Vec::with_capacity(commitments.len());
+
+        for (publisher, resolved) in resolved.into_iter().enumerate() {
+            match resolved {
+                None => bundles.push(vec![]),
+                Some(_) if unavailable.contains(&publisher) => bundles.push(vec![]),
+                Some((_, Some(bundle))) => bundles.push(bundle),
+                Some((commitment, None)) => match self
+                    .resolve_bundle(publisher, &commitment.digest, &endpoints)
+                    .await
+                {
+                    Some(bundle) => bundles.push(bundle),
+                    None => return Err(ContractClientError::DKGBundleUnavailable(publisher)),
+                },
+            }
+        }
+
+        Ok(bundles)
+    }
+}
+
+#[async_trait]
+impl<P, F, C, S> CoordinatorTransactions for OffChainBoard<P, F, C, S>
+where
+    P: CoordinatorTransactions + CoordinatorViews + Sync + Send,
+    F: DKGBundleFetcher + Sync + Send,
+    C: Curve,
+    S: SignatureScheme<Public = C::Point> + Sync + Send,
+{
+    async fn publish(&self, value: Vec<u8>) -> ContractClientResult<TransactionReceipt> {
+        if self.coordinator_client.in_phase().await? != 1 {
+            return self.coordinator_client.publish(value).await;
+        }
+
+        let rpc_endpoint_secrets = self.encrypt_rpc_endpoint().await?;
+
+        let public_bundle = match self.dkg_protocol {
+            DKGProtocol::JointFeldman => {
+                let shares = bincode::deserialize::<BundledShares<C>>(&value)?;
+                bincode::serialize(&BundledShares::<C> {
+                    dealer_idx: shares.dealer_idx,
+                    shares: vec![],
+                    public: shares.public,
+                })?
+            }
+            DKGProtocol::Pvss => vec![],
+        };
+
+        let digest = self
+            .bundle_store
+            .write()
+            .await
+            .insert(self.group_index, self.epoch, value);
+
+        info!(
+            "Publishing the commitment of DKG bundle 0x{}",
+            hex::encode(digest)
+        );
+
+        let commitment = DKGBundleCommitment::<C> {
+            digest,
+            public_bundle,
+            rpc_endpoint_secrets,
+        };
+
+        self.coordinator_client
+            .publish(bincode::serialize(&commitment)?)
+            .await
+    }
+}
+
+#[async_trait]
+impl<P, F, C, S> CoordinatorViews for OffChainBoard<P, F, C, S>
+where
+    P: CoordinatorTransactions + CoordinatorViews + Sync + Send,
+    F: DKGBundleFetcher + Sync + Send,
+    C: Curve,
+    S: SignatureScheme<Public = C::Point> + Sync + Send,
+{
+    async fn get_shares(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+        let commitments = self.coordinator_client.get_shares().await?;
+        match self.dkg_protocol {
+            DKGProtocol::JointFeldman => self.resolve_shares(commitments).await,
+            DKGProtocol::Pvss => self.resolve_attested_dealings(commitments).await,
+        }
+    }
+
+    async fn get_responses(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+        let responses = self.coordinator_client.get_responses().await?;
+        match self.dkg_protocol {
+            DKGProtocol::JointFeldman => Ok(responses),
+            // the attestations take the place of the responses, which PVSS has none of
+            DKGProtocol::Pvss => Ok(vec![vec![]; responses.len()]),
+        }
+    }
+
+    async fn get_justifications(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+        self.coordinator_client.get_justifications().await
+    }
+
+    async fn get_participants(&self) -> ContractClientResult<Vec<Address>> {
+        self.coordinator_client.get_participants().await
+    }
+
+    async fn get_dkg_keys(&self) -> ContractClientResult<(usize, Vec<Vec<u8>>)> {
+        self.coordinator_client.get_dkg_keys().await
+    }
+
+    async fn in_phase(&self) -> ContractClientResult<i8> {
+        self.coordinator_client.in_phase().await
+    }
+}
+
+#[async_trait]
+impl<P, F, C, S> BoardPublisher<C> for OffChainBoard<P, F, C, S>
+where
+    P: CoordinatorTransactions + CoordinatorViews + Sync + Send,
+    F: DKGBundleFetcher + Sync + Send,
+    C: Curve + 'static,
+    S: SignatureScheme<Public = C::Point> + Sync + Send,
+{
+    type Error = DKGContractError;
+
+    async fn publish_shares(&mut self, shares: BundledShares<C>) -> Result<(), Self::Error> {
+        let serialized = bincode::serialize(&shares)?;
+        self.publish(serialized).await?;
+        Ok(())
+    }
+
+    async fn publish_responses(&mut self, responses: BundledResponses) -> Result<(), Self::Error> {
+        let serialized = bincode::serialize(&responses)?;
+        self.publish(serialized).await?;
+        Ok(())
+    }
+
+    async fn publish_justifications(
+        &mut self,
+        justifications: BundledJustification<C>,
+    ) -> Result<(), Self::Error> {
+        let serialized = bincode::serialize(&justifications)?;
+        self.publish(serialized).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod off_chain_board_tests {
+    use super::{
+        DKGBundleAttestation, DKGBundleCommitment, DKGBundleFetcher, DKGBundleStore, OffChainBoard,
+    };
+    use crate::{
+        algorithm::dkg::{AllPhasesDKGCore, DKGCore},
+        error::{NodeError, NodeResult},
+    };
+    use arpa_contract_client::{
+        coordinator::{CoordinatorTransactions, CoordinatorViews},
+        error::ContractClientResult,
+    };
+    use arpa_core::{DKGProtocol, DKGTask};
+    use arpa_dal::{cache::InMemoryGroupInfoCache, GroupInfoHandler, GroupInfoUpdater};
+    use async_trait::async_trait;
+    use dkg_core::{primitives::DKGOutput, serialize_dkg_public_key};
+    use ethers::{
+        types::{Address, TransactionReceipt},
+        utils::keccak256,
+    };
+    use rand::prelude::ThreadRng;
+    use std::{
+        collections::HashMap,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+    use threshold_bls::{
+        curve::bn254::{G2Curve, PairingCurve},
+        group::{Curve, Element},
+        sig::G2Scheme,
+    };
+    use tokio::sync::RwLock;
+
+    type Scalar = <G2Curve as Curve>::Scalar;
+
+    /// A coordinator which moves to the next phase every `phase_duration`, starting from Phase 1.
+    struct MockCoordinatorState {
+        started_at: Instant,
+        phase_duration: Duration,
+        threshold: usize,
+        participants: Vec<Address>,
+        dkg_keys: Vec<Vec<u8>>,
+        bundles: Vec<Vec<Vec<u8>>>,
+    }
+
+    #[derive(Clone)]
+    struct MockCoordinator {
+        state: Arc<std::sync::RwLock<MockCoordinatorState>>,
+        position: usize,
+    }
+
+    #[async_trait]
+    impl CoordinatorTransactions for MockCoordinator {
+        async fn publish(&self, value: Vec<u8>) -> ContractClientResult<TransactionReceipt> {
+            let phase = self.in_phase().await? as usize;
+            self.state.write().unwrap().bundles[phase - 1][self.position] = value;
+            Ok(TransactionReceipt::default())
+        }
+    }
+
+    #[async_trait]
+    impl CoordinatorViews for MockCoordinator {
+        async fn get_shares(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+            Ok(self.state.read().unwrap().bundles[0].clone())
+        }
+
+        async fn get_responses(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+            Ok(self.state.read().unwrap().bundles[1].clone())
+        }
+
+        async fn get_justifications(&self) -> ContractClientResult<Vec<Vec<u8>>> {
+            Ok(self.state.read().unwrap().bundles[2].clone())
+        }
+
+        async fn get_participants(&self) -> ContractClientResult<Vec<Address>> {
+            Ok(self.state.read().unwrap().participants.clone())
+        }
+
+        async fn get_dkg_keys(&self) -> ContractClientResult<(usize, Vec<Vec<u8>>)> {
+            let state = self.state.read().unwrap();
+            Ok((state.threshold, state.dkg_keys.clone()))
+        }
+
+        async fn in_phase(&self) -> ContractClientResult<i8> {
+            let state = self.state.read().unwrap();
+            let elapsed = state.started_at.elapsed().as_millis() / state.phase_duration.as_millis();
+            Ok((1 + elapsed).min(4) as i8)
+        }
+    }
+
+    /// Serves the bundles straight from the stores of the members by their endpoints.
+    #[derive(Clone, Default)]
+    struct InMemoryFetcher {
+        stores: HashMap<String, Arc<RwLock<DKGBundleStore>>>,
+        /// The participant whose bundle of shares can't be fetched by the member, from anyone
+        withheld: Option<(MockCoordinator, usize)>,
+    }
+
+    #[async_trait]
+    impl DKGBundleFetcher for InMemoryFetcher {
+        async fn fetch_dkg_bundle(&self, endpoint: &str, digest: &[u8; 32]) -> NodeResult<Vec<u8>> {
+            if let Some((coordinator, publisher)) = &self.withheld {
+                let commitment = coordinator.get_shares().await?[*publisher].clone();
+                let commitment: DKGBundleCommitment<G2Curve> = bincode::deserialize(&commitment)?;
+                if commitment.digest == *digest {
+                    return Err(NodeError::RpcResponseError(tonic::Status::unavailable(
+                        endpoint,
+                    )));
+                }
+            }
+
+            let store = self
+                .stores
+                .get(endpoint)
+                .ok_or_else(|| NodeError::RpcResponseError(tonic::Status::unavailable(endpoint)))?;
+            let bundle = store.read().await.get(digest).cloned();
+            bundle.ok_or_else(|| NodeError::RpcResponseError(tonic::Status::not_found("")))
+        }
+    }
+
+    /// Answers every request with the same bytes, whatever the digest is.
+    struct TamperingFetcher(Vec<u8>);
+
+    #[async_trait]
+    impl DKGBundleFetcher for TamperingFetcher {
+        async fn fetch_dkg_bundle(&self, _: &str, _: &[u8; 32]) -> NodeResult<Vec<u8>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn mock_coordinators(
+        size: usize,
+        threshold: usize,
+        phase_duration: Duration,
+    ) -> (Vec<MockCoordinator>, Vec<Scalar>) {
+        let rng = &mut rand::thread_rng();
+
+        let private_keys = (0..size).map(|_| Scalar::rand(rng)).collect::<Vec<_>>();
+
+        let dkg_keys = private_keys
+            .iter()
+            .map(|private_key| {
+                serialize_dkg_public_key::<G2Scheme<PairingCurve>>(private_key).unwrap()
+            })
+            .collect();
+
+        let state = Arc::new(std::sync::RwLock::new(MockCoordinatorState {
+            started_at: Instant::now(),
+            phase_duration,
+            threshold,
+            participants: (0..size).map(|_| Address::random()).collect(),
+            dkg_keys,
+            bundles: vec![vec![vec![]; size]; 3],
+        }));
+
+        let coordinators = (0..size)
+            .map(|position| MockCoordinator {
+                state: state.clone(),
+                position,
+            })
+            .collect();
+
+        (coordinators, private_keys)
+    }
+
+    #[test]
+    fn test_bundle_store_keeps_latest_task() {
+        let mut store = DKGBundleStore::new();
+
+        let digest = store.insert(1, 1, vec![1, 2, 3]);
+        assert_eq!(digest, keccak256([1, 2, 3]));
+        store.insert(1, 1, vec![4, 5, 6]);
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(&digest), Some(&vec![1, 2, 3]));
+        assert_eq!(store.get(&digest[1..]), None);
+
+        store.insert(1, 2, vec![7, 8, 9]);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get(&digest), None);
+    }
+
+    #[tokio::test]
+    async fn test_bundle_with_mismatched_digest_is_rejected() {
+        let (mut coordinators, mut private_keys) = mock_coordinators(2, 2, Duration::from_secs(60));
+
+        let store = Arc::new(RwLock::new(DKGBundleStore::new()));
+
+        let board = OffChainBoard::<_, _, G2Curve, G2Scheme<PairingCurve>>::new(
+            coordinators.remove(0),
+            TamperingFetcher(vec![4, 5, 6]),
+            store.clone(),
+            1,
+            1,
+            private_keys.remove(0),
+            "127.0.0.1:50061".to_string(),
+            DKGProtocol::JointFeldman,
+            50,
+        );
+
+        let digest = keccak256([1, 2, 3]);
+        let endpoints = vec![None, Some("127.0.0.1:50062".to_string())];
+
+        assert_eq!(board.resolve_bundle(1, &digest, &endpoints).await, None);
+        assert!(store.read().await.is_empty());
+    }
+
+    /// Runs the DKG on the off-chain board with every member, where the member at `withheld_from`
+    /// can't fetch the bundle of shares of the participant at `withheld_dealer`.
+    async fn run_dkg_with_off_chain_board(
+        withheld: Option<(usize, usize)>,
+    ) -> (
+        Vec<MockCoordinator>,
+        Vec<Arc<RwLock<DKGBundleStore>>>,
+        Vec<NodeResult<DKGOutput<G2Curve>>>,
+    ) {
+        let size = 3;
+        let threshold = 2;
+
+        // the phases are long enough for a debug build to get through every one of them
+        let (coordinators, private_keys) =
+            mock_coordinators(size, threshold, Duration::from_secs(2));
+
+        let participants = coordinators[0].get_participants().await.unwrap();
+
+        let endpoints = (0..size)
+            .map(|i| format!("127.0.0.1:{}", 50061 + i))
+            .collect::<Vec<_>>();
+
+        let stores = (0..size)
+            .map(|_| Arc::new(RwLock::new(DKGBundleStore::new())))
+            .collect::<Vec<_>>();
+
+        let fetcher = InMemoryFetcher {
+            stores: endpoints
+                .iter()
+                .cloned()
+                .zip(stores.iter().cloned())
+                .collect(),
+            withheld: None,
+        };
+
+        let task = DKGTask {
+            group_index: 1,
+            epoch: 1,
+            size,
+            threshold,
+            members: participants,
+            assignment_block_height: 100,
+            coordinator_address: Address::random(),
+        };
+
+        let mut dkg_cores = vec![];
+
+        for (i, coordinator) in coordinators.iter().enumerate() {
+            let mut group_cache = InMemoryGroupInfoCache::<G2Curve>::new();
+            group_cache.save_task_info(i, task.clone()).await.unwrap();
+            let group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<G2Curve>>>> =
+                Arc::new(RwLock::new(Box::new(group_cache)));
+
+            let mut fetcher = fetcher.clone();
+            if let Some((withheld_from, withheld_dealer)) = withheld {
+                if withheld_from == i {
+                    fetcher.withheld = Some((coordinator.clone(), withheld_dealer));
+                }
+            }
+
+            let board = OffChainBoard::<_, _, _, G2Scheme<PairingCurve>>::new(
+                coordinator.clone(),
+                fetcher,
+                stores[i].clone(),
+                task.group_index,
+                task.epoch,
+                private_keys[i],
+                endpoints[i].clone(),
+                DKGProtocol::JointFeldman,
+                50,
+            );
+
+            dkg_cores.push(AllPhasesDKGCore::<_, _, G2Scheme<PairingCurve>>::new(
+                board,
+                group_cache,
+                task.clone(),
+                50,
+                DKGProtocol::JointFeldman,
+                true,
+                Some(stores[i].clone()),
+            ));
+        }
+
+        let outputs = futures::future::join_all(
+            dkg_cores
+                .iter_mut()
+                .zip(private_keys.iter().zip(endpoints.iter()))
+                .map(|(dkg_core, (private_key, endpoint))| {
+                    dkg_core.run_dkg(
+                        *private_key,
+                        endpoint.clone(),
+                        rand::thread_rng as fn() -> ThreadRng,
+                    )
+                }),
+        )
+        .await;
+
+        (coordinators, stores, outputs)
+    }
+
+    #[tokio::test]
+    async fn test_dkg_with_off_chain_board() {
+        let (coordinators, stores, outputs) = run_dkg_with_off_chain_board(None).await;
+
+        let outputs = outputs.into_iter().collect::<NodeResult<Vec<_>>>().unwrap();
+
+        for output in outputs.iter() {
+            assert!(output.disqualified_node_indices.is_empty());
+            assert_eq!(output.public, outputs[0].public);
+        }
+
+        // only the commitments are on chain, while every member holds every bundle of shares
+        let commitments = coordinators[0].get_shares().await.unwrap();
+        for commitment in commitments.iter() {
+            let commitment: DKGBundleCommitment<G2Curve> =
+                bincode::deserialize(commitment).unwrap();
+            assert_eq!(commitment.rpc_endpoint_secrets.len(), stores.len());
+            for store in stores.iter() {
+                assert!(store.read().await.get(&commitment.digest).is_some());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_shares_are_justified_on_chain() {
+        let (coordinators, _, outputs) = run_dkg_with_off_chain_board(Some((1, 2))).await;
+
+        // the member complains about the dealer, who reveals the share on chain,
+        // so every member agrees on the dealers, including the one with the withheld bundle
+        let outputs = outputs.into_iter().collect::<NodeResult<Vec<_>>>().unwrap();
+        for output in outputs.iter() {
+            assert!(output.disqualified_node_indices.is_empty());
+            assert!(output.qual.nodes.iter().any(|node| node.id() == 2));
+            assert_eq!(output.public, outputs[0].public);
+        }
+
+        let justifications = coordinators[0].get_justifications().await.unwrap();
+        assert!(!justifications[2].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_attested_unavailable_dealing_is_left_out() {
+        let size = 3;
+
+        // the dealings are not verified by the board, so any bytes will do
+        let (coordinators, private_keys) = mock_coordinators(size, 2, Duration::from_secs(1));
+
+        let endpoints = (0..size)
+            .map(|i| format!("127.0.0.1:{}", 50061 + i))
+            .collect::<Vec<_>>();
+
+        let stores = (0..size)
+            .map(|_| Arc::new(RwLock::new(DKGBundleStore::new())))
+            .collect::<Vec<_>>();
+
+        let mut boards = vec![];
+
+        for (i, coordinator) in coordinators.iter().enumerate() {
+            let mut fetcher = InMemoryFetcher {
+                stores: endpoints
+                    .iter()
+                    .cloned()
+                    .zip(stores.iter().cloned())
+                    .collect(),
+                withheld: None,
+            };
+            if i == 1 {
+                fetcher.withheld = Some((coordinator.clone(), 2));
+            }
+
+            let board = OffChainBoard::<_, _, G2Curve, G2Scheme<PairingCurve>>::new(
+                coordinator.clone(),
+                fetcher,
+                stores[i].clone(),
+                1,
+                1,
+                private_keys[i],
+                endpoints[i].clone(),
+                DKGProtocol::Pvss,
+                50,
+            );
+
+            board.publish(vec![i as u8; 8]).await.unwrap();
+
+            boards.push(board);
+        }
+
+        while coordinators[0].in_phase().await.unwrap() == 1 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let dealings = futures::future::join_all(boards.iter().map(|board| board.get_shares()))
+            .await
+            .into_iter()
+            .collect::<ContractClientResult<Vec<_>>>()
+            .unwrap();
+
+        // every member leaves out the dealing which only one of them couldn't get
+        for dealings in dealings.iter() {
+            assert_eq!(dealings, &vec![vec![0; 8], vec![1; 8], vec![]]);
+        }
+
+        let attestations = coordinators[0].get_responses().await.unwrap();
+        for (i, attestation) in attestations.iter().enumerate() {
+            let attestation: DKGBundleAttestation = bincode::deserialize(attestation).unwrap();
+            let expected = if i == 1 { vec![2] } else { vec![] };
+            assert_eq!(attestation.unavailable, expected);
+        }
+
+        // and the attestations are not taken for responses
+        let responses = boards[0].get_responses().await.unwrap();
+        assert!(responses.iter().all(|response| response.is_empty()));
+    }
+}
@@ -1,8 +1,11 @@
 use super::{CommitterClient, CommitterService, ServiceClient};
+use crate::algorithm::off_chain_board::DKGBundleFetcher;
 use crate::error::{NodeError, NodeResult};
 use crate::rpc_stub::committer::committer_service_client::CommitterServiceClient;
-use crate::rpc_stub::committer::CommitPartialSignatureRequest;
+use crate::rpc_stub::committer::{CommitPartialSignatureRequest, GetDkgBundleRequest};
+use crate::telemetry::inject_trace_context;
 use arpa_core::{address_to_string, jitter, BLSTaskType, ExponentialBackoffRetryDescriptor};
+use async_trait::async_trait;
 use ethers::types::Address;
 use log::error;
 use tokio_retry::{strategy::ExponentialBackoff, RetryIf};
@@ -100,7 +103,7 @@ impl CommitterService for GeneralCommitterClient {
                 let message = message.clone();
                 let partial_signature = partial_signature.clone();
 
-                let request = Request::new(CommitPartialSignatureRequest {
+                let mut request = Request::new(CommitPartialSignatureRequest {
                     id_address: address_to_string(self.id_address),
                     chain_id: chain_id as u32,
                     task_type: task_type.to_i32(),
@@ -109,6 +112,8 @@ impl CommitterService for GeneralCommitterClient {
                     partial_signature,
                 });
 
+                inject_trace_context(&mut request);
+
                 let mut committer_client = self.prepare_service_client().await?;
 
                 committer_client
@@ -129,3 +134,27 @@ impl CommitterService for GeneralCommitterClient {
         .await
     }
 }
+
+/// Fetches the DKG bundles from the committer servers of the other members.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GeneralDKGBundleFetcher;
+
+#[async_trait]
+impl DKGBundleFetcher for GeneralDKGBundleFetcher {
+    async fn fetch_dkg_bundle(&self, endpoint: &str, digest: &[u8; 32]) -> NodeResult<Vec<u8>> {
+        let mut committer_client =
+            CommitterServiceClient::connect(format!("{}{}", "http://", endpoint)).await?;
+
+        let mut request = Request::new(GetDkgBundleRequest {
+            digest: digest.to_vec(),
+        });
+
+        inject_trace_context(&mut request);
+
+        Ok(committer_client
+            .get_dkg_bundle(request)
+            .await?
+            .into_inner()
+            .bundle)
+    }
+}
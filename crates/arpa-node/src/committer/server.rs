@@ -1,13 +1,18 @@
 use crate::{
-    algorithm::bls::{BLSCore, SimpleBLSCore},
+    algorithm::{
+        bls::{BLSCore, SimpleBLSCore},
+        off_chain_board::DKGBundleStore,
+    },
     context::{types::GeneralContext, Context},
     error::NodeError,
+    telemetry::set_remote_parent,
 };
 use crate::{
-    context::chain::Chain,
+    context::chain::{Chain, MainChain},
     rpc_stub::committer::{
         committer_service_server::{CommitterService, CommitterServiceServer},
-        CommitPartialSignatureReply, CommitPartialSignatureRequest,
+        CommitPartialSignatureReply, CommitPartialSignatureRequest, GetDkgBundleReply,
+        GetDkgBundleRequest,
     },
 };
 use arpa_core::{BLSTaskError, BLSTaskType, SchedulerError};
@@ -21,6 +26,7 @@ use threshold_bls::{
 };
 use tokio::sync::RwLock;
 use tonic::{transport::Server, Request, Response, Status};
+use tracing::{info_span, Instrument};
 
 type NodeContext<PC, S> = Arc<RwLock<GeneralContext<PC, S>>>;
 
@@ -30,6 +36,7 @@ pub(crate) struct BLSCommitterServiceServer<
 > {
     id_address: Address,
     group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<PC>>>>,
+    dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
     context: NodeContext<PC, S>,
     c: PhantomData<PC>,
     s: PhantomData<S>,
@@ -41,11 +48,13 @@ impl<PC: Curve, S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private
     pub fn new(
         id_address: Address,
         group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<PC>>>>,
+        dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
         context: NodeContext<PC, S>,
     ) -> Self {
         BLSCommitterServiceServer {
             id_address,
             group_cache,
+            dkg_bundle_store,
             context,
             c: PhantomData,
             s: PhantomData,
@@ -72,123 +81,296 @@ where
         &self,
         request: Request<CommitPartialSignatureRequest>,
     ) -> Result<Response<CommitPartialSignatureReply>, Status> {
-        let req = request.into_inner();
+        // the span lasts until the request is handled, as a child of the span of the sender
+        let span = info_span!("receive_partial_signature");
+        set_remote_parent(&span, &request);
 
-        if let Err(_) | Ok(false) = self.group_cache.read().await.get_state() {
-            return Err(Status::not_found(NodeError::GroupNotReady.to_string()));
-        }
+        async move {
+            let req = request.into_inner();
 
-        if let Err(_) | Ok(false) = self.group_cache.read().await.is_committer(self.id_address) {
-            return Err(Status::not_found(NodeError::NotCommitter.to_string()));
-        }
+            if let Err(_) | Ok(false) = self.group_cache.read().await.get_state() {
+                return Err(Status::not_found(NodeError::GroupNotReady.to_string()));
+            }
 
-        let chain_id = req.chain_id as usize;
-
-        let req_id_address: Address = req
-            .id_address
-            .parse()
-            .map_err(|_| Status::invalid_argument(NodeError::AddressFormatError.to_string()))?;
-
-        if let Ok(member) = self.group_cache.read().await.get_member(req_id_address) {
-            let partial_public_key = member.partial_public_key.clone().unwrap();
-
-            SimpleBLSCore::<PC, S>::partial_verify(
-                &partial_public_key,
-                &req.message,
-                &req.partial_signature,
-            )
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-            match BLSTaskType::from(req.task_type) {
-                BLSTaskType::Randomness => {
-                    let main_chain_id = self
-                        .context
-                        .read()
-                        .await
-                        .get_main_chain()
-                        .get_chain_identity()
-                        .read()
-                        .await
-                        .get_chain_id();
-
-                    let randomness_result_cache = if chain_id == main_chain_id {
-                        self.context
+            if let Err(_) | Ok(false) = self.group_cache.read().await.is_committer(self.id_address)
+            {
+                return Err(Status::not_found(NodeError::NotCommitter.to_string()));
+            }
+
+            let chain_id = req.chain_id as usize;
+
+            let req_id_address: Address = req
+                .id_address
+                .parse()
+                .map_err(|_| Status::invalid_argument(NodeError::AddressFormatError.to_string()))?;
+
+            if let Ok(member) = self.group_cache.read().await.get_member(req_id_address) {
+                let partial_public_key = member.partial_public_key.clone().unwrap();
+
+                match BLSTaskType::from(req.task_type) {
+                    BLSTaskType::Randomness => {
+                        SimpleBLSCore::<PC, S>::partial_verify(
+                            &partial_public_key,
+                            &req.message,
+                            &req.partial_signature,
+                        )
+                        .map_err(|e| Status::internal(e.to_string()))?;
+
+                        let main_chain_id = self
+                            .context
                             .read()
                             .await
                             .get_main_chain()
-                            .get_randomness_result_cache()
-                    } else {
-                        if !self.context.read().await.contains_relayed_chain(chain_id) {
+                            .get_chain_identity()
+                            .read()
+                            .await
+                            .get_chain_id();
+
+                        let randomness_result_cache = if chain_id == main_chain_id {
+                            self.context
+                                .read()
+                                .await
+                                .get_main_chain()
+                                .get_randomness_result_cache()
+                        } else {
+                            if !self.context.read().await.contains_relayed_chain(chain_id) {
+                                return Err(Status::invalid_argument(
+                                    SchedulerError::InvalidChainId(chain_id).to_string(),
+                                ));
+                            }
+                            self.context
+                                .read()
+                                .await
+                                .get_relayed_chain(req.chain_id as usize)
+                                .unwrap()
+                                .get_randomness_result_cache()
+                        };
+
+                        if !randomness_result_cache
+                            .read()
+                            .await
+                            .contains(&req.request_id)
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Err(Status::invalid_argument(
+                                BLSTaskError::CommitterCacheNotExisted.to_string(),
+                            ));
+                            // because we can't assure reliability of requested partial signature to original message,
+                            // we refuse to accept other node's request if the committer has not build this committer cache first.
+                        }
+
+                        let committer_cache_message = randomness_result_cache
+                            .read()
+                            .await
+                            .get(&req.request_id)
+                            .await
+                            .unwrap()
+                            .result_cache
+                            .message
+                            .clone();
+
+                        if req.message != committer_cache_message {
+                            return Err(Status::invalid_argument(
+                                NodeError::InvalidTaskMessage.to_string(),
+                            ));
+                        }
+
+                        if !randomness_result_cache
+                            .write()
+                            .await
+                            .add_partial_signature(
+                                req.request_id,
+                                req_id_address,
+                                req.partial_signature,
+                            )
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Err(Status::invalid_argument(
+                                BLSTaskError::AlreadyCommittedPartialSignature.to_string(),
+                            ));
+                        }
+                    }
+
+                    BLSTaskType::Decryption => {
+                        let main_chain_id = self
+                            .context
+                            .read()
+                            .await
+                            .get_main_chain()
+                            .get_chain_identity()
+                            .read()
+                            .await
+                            .get_chain_id();
+
+                        // the ciphertexts are encrypted to the group key, which is only served
+                        // on the main chain
+                        if chain_id != main_chain_id {
                             return Err(Status::invalid_argument(
                                 SchedulerError::InvalidChainId(chain_id).to_string(),
                             ));
                         }
+
+                        let decryption_result_cache = self
+                            .context
+                            .read()
+                            .await
+                            .get_main_chain()
+                            .get_decryption_result_cache();
+
+                        // the ciphertext is checked against the one of the task as with randomness,
+                        // so the committer cache has to be built first
+                        let committer_cache_message = decryption_result_cache
+                            .read()
+                            .await
+                            .get(&req.request_id)
+                            .await
+                            .map_err(|_| {
+                                Status::invalid_argument(
+                                    BLSTaskError::CommitterCacheNotExisted.to_string(),
+                                )
+                            })?
+                            .result_cache
+                            .message;
+
+                        if req.message != committer_cache_message {
+                            return Err(Status::invalid_argument(
+                                NodeError::InvalidTaskMessage.to_string(),
+                            ));
+                        }
+
                         self.context
                             .read()
                             .await
-                            .get_relayed_chain(req.chain_id as usize)
-                            .unwrap()
-                            .get_randomness_result_cache()
-                    };
-
-                    if !randomness_result_cache
-                        .read()
-                        .await
-                        .contains(&req.request_id)
-                        .await
-                        .map_err(|e| Status::internal(e.to_string()))?
-                    {
-                        return Err(Status::invalid_argument(
-                            BLSTaskError::CommitterCacheNotExisted.to_string(),
-                        ));
-                        // because we can't assure reliability of requested partial signature to original message,
-                        // we refuse to accept other node's request if the committer has not build this committer cache first.
+                            .get_main_chain()
+                            .get_decryption_core()
+                            .verify_decryption_share(
+                                &partial_public_key,
+                                &req.message,
+                                &req.partial_signature,
+                            )
+                            .map_err(|e| Status::internal(e.to_string()))?;
+
+                        if !decryption_result_cache
+                            .write()
+                            .await
+                            .add_partial_signature(
+                                req.request_id,
+                                req_id_address,
+                                req.partial_signature,
+                            )
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Err(Status::invalid_argument(
+                                BLSTaskError::AlreadyCommittedPartialSignature.to_string(),
+                            ));
+                        }
                     }
 
-                    let committer_cache_message = randomness_result_cache
-                        .read()
-                        .await
-                        .get(&req.request_id)
-                        .await
-                        .unwrap()
-                        .result_cache
-                        .message
-                        .clone();
-
-                    if req.message != committer_cache_message {
-                        return Err(Status::invalid_argument(
-                            NodeError::InvalidTaskMessage.to_string(),
-                        ));
+                    BLSTaskType::BlindSignature => {
+                        let main_chain_id = self
+                            .context
+                            .read()
+                            .await
+                            .get_main_chain()
+                            .get_chain_identity()
+                            .read()
+                            .await
+                            .get_chain_id();
+
+                        // blind signatures are signed by the group key on the main chain only
+                        if chain_id != main_chain_id {
+                            return Err(Status::invalid_argument(
+                                SchedulerError::InvalidChainId(chain_id).to_string(),
+                            ));
+                        }
+
+                        let blind_signature_result_cache = self
+                            .context
+                            .read()
+                            .await
+                            .get_main_chain()
+                            .get_blind_signature_result_cache();
+
+                        let committer_cache_message = blind_signature_result_cache
+                            .read()
+                            .await
+                            .get(&req.request_id)
+                            .await
+                            .map_err(|_| {
+                                Status::invalid_argument(
+                                    BLSTaskError::CommitterCacheNotExisted.to_string(),
+                                )
+                            })?
+                            .result_cache
+                            .message;
+
+                        if req.message != committer_cache_message {
+                            return Err(Status::invalid_argument(
+                                NodeError::InvalidTaskMessage.to_string(),
+                            ));
+                        }
+
+                        self.context
+                            .read()
+                            .await
+                            .get_main_chain()
+                            .get_blind_signature_core()
+                            .verify_blind_partial(
+                                &partial_public_key,
+                                &req.message,
+                                &req.partial_signature,
+                            )
+                            .map_err(|e| Status::internal(e.to_string()))?;
+
+                        if !blind_signature_result_cache
+                            .write()
+                            .await
+                            .add_partial_signature(
+                                req.request_id,
+                                req_id_address,
+                                req.partial_signature,
+                            )
+                            .await
+                            .map_err(|e| Status::internal(e.to_string()))?
+                        {
+                            return Err(Status::invalid_argument(
+                                BLSTaskError::AlreadyCommittedPartialSignature.to_string(),
+                            ));
+                        }
                     }
 
-                    if !randomness_result_cache
-                        .write()
-                        .await
-                        .add_partial_signature(
-                            req.request_id,
-                            req_id_address,
-                            req.partial_signature,
-                        )
-                        .await
-                        .map_err(|e| Status::internal(e.to_string()))?
-                    {
+                    _ => {
                         return Err(Status::invalid_argument(
-                            BLSTaskError::AlreadyCommittedPartialSignature.to_string(),
+                            NodeError::InvalidTaskType.to_string(),
                         ));
                     }
                 }
 
-                _ => {
-                    return Err(Status::invalid_argument(
-                        NodeError::InvalidTaskType.to_string(),
-                    ));
-                }
+                return Ok(Response::new(CommitPartialSignatureReply { result: true }));
             }
 
-            return Ok(Response::new(CommitPartialSignatureReply { result: true }));
+            Err(Status::not_found(NodeError::MemberNotExisted.to_string()))
         }
+        .instrument(span)
+        .await
+    }
+
+    async fn get_dkg_bundle(
+        &self,
+        request: Request<GetDkgBundleRequest>,
+    ) -> Result<Response<GetDkgBundleReply>, Status> {
+        // the bundles are addressed by the digests committed on chain, so anyone may read them
+        let req = request.into_inner();
 
-        Err(Status::not_found(NodeError::MemberNotExisted.to_string()))
+        match self.dkg_bundle_store.read().await.get(&req.digest) {
+            Some(bundle) => Ok(Response::new(GetDkgBundleReply {
+                bundle: bundle.clone(),
+            })),
+            None => Err(Status::not_found("DKG bundle not found")),
+        }
     }
 }
 
@@ -223,9 +405,11 @@ where
 
     let group_cache = context.read().await.get_main_chain().get_group_cache();
 
+    let dkg_bundle_store = context.read().await.get_main_chain().get_dkg_bundle_store();
+
     Server::builder()
         .add_service(CommitterServiceServer::with_interceptor(
-            BLSCommitterServiceServer::new(id_address, group_cache, context),
+            BLSCommitterServiceServer::new(id_address, group_cache, dkg_bundle_store, context),
             intercept,
         ))
         .serve_with_shutdown(addr, shutdown_signal)
@@ -263,9 +447,11 @@ where
 
     let group_cache = context.read().await.get_main_chain().get_group_cache();
 
+    let dkg_bundle_store = context.read().await.get_main_chain().get_dkg_bundle_store();
+
     Server::builder()
         .add_service(CommitterServiceServer::with_interceptor(
-            BLSCommitterServiceServer::new(id_address, group_cache, context),
+            BLSCommitterServiceServer::new(id_address, group_cache, dkg_bundle_store, context),
             intercept,
         ))
         .serve(addr)
@@ -1,7 +1,17 @@
 pub mod types;
-use crate::{queue::event_queue::EventQueue, scheduler::fixed::SimpleFixedTaskScheduler};
+use crate::{
+    algorithm::{
+        blind::BlindSignatureCore, decryption::DecryptionCore, off_chain_board::DKGBundleStore,
+    },
+    queue::{deferred_fulfillment_queue::DeferredFulfillmentQueue, event_queue::EventQueue},
+    scheduler::fixed::SimpleFixedTaskScheduler,
+};
 
-use arpa_core::{ListenerDescriptor, SchedulerResult};
+use arpa_core::{BlindSignatureTask, DecryptionTask, ListenerDescriptor, SchedulerResult};
+use arpa_dal::{
+    cache::{BlindSignatureResultCache, DecryptionResultCache},
+    BLSTasksHandler, SignatureResultCacheHandler,
+};
 use async_trait::async_trait;
 use std::sync::Arc;
 use threshold_bls::{
@@ -41,6 +51,8 @@ pub trait Chain<
 
     fn get_randomness_result_cache(&self) -> Arc<RwLock<Self::RandomnessResultCaches>>;
 
+    fn get_deferred_fulfillment_queue(&self) -> Arc<RwLock<DeferredFulfillmentQueue>>;
+
     async fn init_components(
         &self,
         context: &(dyn ContextFetcher + Sync + Send),
@@ -67,6 +79,26 @@ pub trait MainChain<
     S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>,
 >: Chain<PC, S>
 {
+    fn get_dkg_bundle_store(&self) -> Arc<RwLock<DKGBundleStore>>;
+
+    fn get_decryption_tasks_cache(&self) -> Arc<RwLock<Box<dyn BLSTasksHandler<DecryptionTask>>>>;
+
+    fn get_decryption_result_cache(
+        &self,
+    ) -> Arc<RwLock<Box<dyn SignatureResultCacheHandler<DecryptionResultCache>>>>;
+
+    fn get_decryption_core(&self) -> Arc<dyn DecryptionCore<PC>>;
+
+    fn get_blind_signature_tasks_cache(
+        &self,
+    ) -> Arc<RwLock<Box<dyn BLSTasksHandler<BlindSignatureTask>>>>;
+
+    fn get_blind_signature_result_cache(
+        &self,
+    ) -> Arc<RwLock<Box<dyn SignatureResultCacheHandler<BlindSignatureResultCache>>>>;
+
+    fn get_blind_signature_core(&self) -> Arc<dyn BlindSignatureCore<PC>>;
+
     async fn init_schedule_listeners(
         &self,
         context: &(dyn ContextFetcher + Sync + Send),
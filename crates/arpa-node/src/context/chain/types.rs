@@ -1,5 +1,9 @@
 use super::{Chain, MainChain, RelayedChain};
 use crate::{
+    algorithm::{
+        blind::BlindSignatureCore, decryption::DecryptionCore, fulfillment::FulfillmentPolicy,
+        off_chain_board::DKGBundleStore,
+    },
     context::{ChainIdentityHandlerType, ContextFetcher},
     listener::{
         block::BlockListener, new_randomness_task::NewRandomnessTaskListener,
@@ -9,11 +13,11 @@ use crate::{
         ready_to_handle_randomness_task::ReadyToHandleRandomnessTaskListener,
         schedule_node_activation::NodeActivationListener, Listener,
     },
-    queue::event_queue::EventQueue,
+    queue::{deferred_fulfillment_queue::DeferredFulfillmentQueue, event_queue::EventQueue},
     scheduler::{fixed::SimpleFixedTaskScheduler, TaskScheduler},
     subscriber::{
-        block::BlockSubscriber, in_grouping::InGroupingSubscriber,
-        post_grouping::PostGroupingSubscriber,
+        block::BlockSubscriber, deferred_fulfillment::DeferredFulfillmentSubscriber,
+        in_grouping::InGroupingSubscriber, post_grouping::PostGroupingSubscriber,
         post_success_grouping::PostSuccessGroupingSubscriber, pre_grouping::PreGroupingSubscriber,
         randomness_signature_aggregation::RandomnessSignatureAggregationSubscriber,
         ready_to_handle_randomness_task::ReadyToHandleRandomnessTaskSubscriber,
@@ -21,11 +25,14 @@ use crate::{
     },
 };
 use arpa_core::{
-    ChainIdentity, ComponentTaskType, GeneralMainChainIdentity, GeneralRelayedChainIdentity,
-    ListenerDescriptor, ListenerType, RandomnessTask, SchedulerError, SchedulerResult,
-    TimeLimitDescriptor, DEFAULT_NODE_ACTIVATION_INTERVAL_MILLIS,
+    BlindSignatureTask, ChainIdentity, ComponentTaskType, DKGBoard, DKGProtocol, DecryptionTask,
+    GeneralMainChainIdentity, GeneralRelayedChainIdentity, ListenerDescriptor, ListenerType,
+    RandomnessTask, SchedulerError, SchedulerResult, TimeLimitDescriptor,
+    DEFAULT_NODE_ACTIVATION_INTERVAL_MILLIS,
+};
+use arpa_dal::cache::{
+    BlindSignatureResultCache, DecryptionResultCache, InMemoryBlockInfoCache, RandomnessResultCache,
 };
-use arpa_dal::cache::{InMemoryBlockInfoCache, RandomnessResultCache};
 use arpa_dal::{
     BLSTasksHandler, BlockInfoHandler, GroupInfoHandler, NodeInfoHandler,
     SignatureResultCacheHandler,
@@ -51,14 +58,7 @@ async fn add_listener_task(
     fs.write().await.add_task(
         ComponentTaskType::Listener(chain_id, listener_descriptor.l_type),
         async move {
-            if let Err(e) = listener
-                .start(
-                    listener_descriptor.interval_millis,
-                    listener_descriptor.use_jitter,
-                    listener_descriptor.reset_descriptor,
-                )
-                .await
-            {
+            if let Err(e) = listener.start(listener_descriptor).await {
                 error!("{:?}", e);
             };
         },
@@ -80,10 +80,23 @@ pub struct GeneralMainChain<
     randomness_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<RandomnessTask>>>>,
     committer_randomness_result_cache:
         Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>,
+    decryption_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<DecryptionTask>>>>,
+    committer_decryption_result_cache:
+        Arc<RwLock<Box<dyn SignatureResultCacheHandler<DecryptionResultCache>>>>,
+    decryption_core: Arc<dyn DecryptionCore<PC>>,
+    blind_signature_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<BlindSignatureTask>>>>,
+    committer_blind_signature_result_cache:
+        Arc<RwLock<Box<dyn SignatureResultCacheHandler<BlindSignatureResultCache>>>>,
+    blind_signature_core: Arc<dyn BlindSignatureCore<PC>>,
+    fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+    deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
     c: PhantomData<PC>,
     s: PhantomData<S>,
     time_limits: TimeLimitDescriptor,
     listener_descriptors: Vec<ListenerDescriptor>,
+    dkg_protocol: DKGProtocol,
+    dkg_board: DKGBoard,
+    dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
 }
 
 impl<
@@ -107,8 +120,21 @@ impl<
         committer_randomness_result_cache: Arc<
             RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>,
         >,
+        decryption_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<DecryptionTask>>>>,
+        committer_decryption_result_cache: Arc<
+            RwLock<Box<dyn SignatureResultCacheHandler<DecryptionResultCache>>>,
+        >,
+        decryption_core: Arc<dyn DecryptionCore<PC>>,
+        blind_signature_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<BlindSignatureTask>>>>,
+        committer_blind_signature_result_cache: Arc<
+            RwLock<Box<dyn SignatureResultCacheHandler<BlindSignatureResultCache>>>,
+        >,
+        blind_signature_core: Arc<dyn BlindSignatureCore<PC>>,
+        fulfillment_policy: Arc<dyn FulfillmentPolicy>,
         time_limits: TimeLimitDescriptor,
         listener_descriptors: Vec<ListenerDescriptor>,
+        dkg_protocol: DKGProtocol,
+        dkg_board: DKGBoard,
     ) -> Self {
         let chain_id = chain_identity.get_chain_id();
         GeneralMainChain {
@@ -122,12 +148,23 @@ impl<
             )))),
             randomness_tasks_cache,
             committer_randomness_result_cache,
+            decryption_tasks_cache,
+            committer_decryption_result_cache,
+            decryption_core,
+            blind_signature_tasks_cache,
+            committer_blind_signature_result_cache,
+            blind_signature_core,
+            fulfillment_policy,
+            deferred_fulfillment_queue: Arc::new(RwLock::new(DeferredFulfillmentQueue::new())),
             node_cache,
             group_cache,
             c: PhantomData,
             s: PhantomData,
             time_limits,
             listener_descriptors,
+            dkg_protocol,
+            dkg_board,
+            dkg_bundle_store: Arc::new(RwLock::new(DKGBundleStore::new())),
         }
     }
 }
@@ -192,6 +229,10 @@ where
         self.committer_randomness_result_cache.clone()
     }
 
+    fn get_deferred_fulfillment_queue(&self) -> Arc<RwLock<DeferredFulfillmentQueue>> {
+        self.deferred_fulfillment_queue.clone()
+    }
+
     async fn init_listener(
         &self,
         eq: Arc<RwLock<EventQueue>>,
@@ -337,6 +378,40 @@ where
     <S as ThresholdScheme>::Error: Sync + Send,
     <S as SignatureScheme>::Error: Sync + Send,
 {
+    fn get_dkg_bundle_store(&self) -> Arc<RwLock<DKGBundleStore>> {
+        self.dkg_bundle_store.clone()
+    }
+
+    fn get_decryption_tasks_cache(&self) -> Arc<RwLock<Box<dyn BLSTasksHandler<DecryptionTask>>>> {
+        self.decryption_tasks_cache.clone()
+    }
+
+    fn get_decryption_result_cache(
+        &self,
+    ) -> Arc<RwLock<Box<dyn SignatureResultCacheHandler<DecryptionResultCache>>>> {
+        self.committer_decryption_result_cache.clone()
+    }
+
+    fn get_decryption_core(&self) -> Arc<dyn DecryptionCore<PC>> {
+        self.decryption_core.clone()
+    }
+
+    fn get_blind_signature_tasks_cache(
+        &self,
+    ) -> Arc<RwLock<Box<dyn BLSTasksHandler<BlindSignatureTask>>>> {
+        self.blind_signature_tasks_cache.clone()
+    }
+
+    fn get_blind_signature_result_cache(
+        &self,
+    ) -> Arc<RwLock<Box<dyn SignatureResultCacheHandler<BlindSignatureResultCache>>>> {
+        self.committer_blind_signature_result_cache.clone()
+    }
+
+    fn get_blind_signature_core(&self) -> Arc<dyn BlindSignatureCore<PC>> {
+        self.blind_signature_core.clone()
+    }
+
     async fn init_schedule_listeners(
         &self,
         context: &(dyn ContextFetcher + Sync + Send),
@@ -440,13 +515,16 @@ where
 
         s_pre_grouping.subscribe().await;
 
-        let s_in_grouping = InGroupingSubscriber::new(
+        let s_in_grouping = InGroupingSubscriber::<PC, S>::new(
             self.get_chain_identity(),
             self.get_node_cache(),
             self.get_group_cache(),
             context.get_event_queue(),
             context.get_dynamic_task_handler(),
             self.time_limits.dkg_wait_for_phase_interval_millis,
+            self.dkg_protocol,
+            self.dkg_board,
+            self.get_dkg_bundle_store(),
         );
 
         s_in_grouping.subscribe().await;
@@ -490,11 +568,27 @@ where
                 self.get_chain_identity(),
                 self.get_block_cache(),
                 self.get_randomness_result_cache(),
+                self.fulfillment_policy.clone(),
+                self.get_deferred_fulfillment_queue(),
                 context.get_event_queue(),
                 context.get_dynamic_task_handler(),
             );
 
         s_randomness_signature_aggregation.subscribe().await;
+
+        let s_deferred_fulfillment = DeferredFulfillmentSubscriber::<PC>::new(
+            self.id(),
+            id_address,
+            self.get_chain_identity(),
+            self.get_block_cache(),
+            self.get_randomness_result_cache(),
+            self.fulfillment_policy.clone(),
+            self.get_deferred_fulfillment_queue(),
+            context.get_event_queue(),
+            context.get_dynamic_task_handler(),
+        );
+
+        s_deferred_fulfillment.subscribe().await;
     }
 }
 
@@ -512,6 +606,8 @@ pub struct GeneralRelayedChain<
     randomness_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<RandomnessTask>>>>,
     committer_randomness_result_cache:
         Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>,
+    fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+    deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
     c: PhantomData<PC>,
     s: PhantomData<S>,
     time_limits: TimeLimitDescriptor,
@@ -538,6 +634,7 @@ impl<
         committer_randomness_result_cache: Arc<
             RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>,
         >,
+        fulfillment_policy: Arc<dyn FulfillmentPolicy>,
         time_limits: TimeLimitDescriptor,
         listener_descriptors: Vec<ListenerDescriptor>,
     ) -> Self {
@@ -553,6 +650,8 @@ impl<
             )))),
             randomness_tasks_cache,
             committer_randomness_result_cache,
+            fulfillment_policy,
+            deferred_fulfillment_queue: Arc::new(RwLock::new(DeferredFulfillmentQueue::new())),
             node_cache,
             group_cache,
             c: PhantomData,
@@ -622,6 +721,10 @@ where
         self.committer_randomness_result_cache.clone()
     }
 
+    fn get_deferred_fulfillment_queue(&self) -> Arc<RwLock<DeferredFulfillmentQueue>> {
+        self.deferred_fulfillment_queue.clone()
+    }
+
     async fn init_listener(
         &self,
         eq: Arc<RwLock<EventQueue>>,
@@ -799,10 +902,26 @@ where
                 self.get_chain_identity(),
                 self.get_block_cache(),
                 self.get_randomness_result_cache(),
+                self.fulfillment_policy.clone(),
+                self.get_deferred_fulfillment_queue(),
                 context.get_event_queue(),
                 context.get_dynamic_task_handler(),
             );
 
         s_randomness_signature_aggregation.subscribe().await;
+
+        let s_deferred_fulfillment = DeferredFulfillmentSubscriber::<PC>::new(
+            self.id(),
+            id_address,
+            self.get_chain_identity(),
+            self.get_block_cache(),
+            self.get_randomness_result_cache(),
+            self.fulfillment_policy.clone(),
+            self.get_deferred_fulfillment_queue(),
+            context.get_event_queue(),
+            context.get_dynamic_task_handler(),
+        );
+
+        s_deferred_fulfillment.subscribe().await;
     }
 }
@@ -27,13 +27,14 @@ use arpa_dal::{
     BLSTasksHandler, BlockInfoHandler, GroupInfoHandler, NodeInfoHandler,
     SignatureResultCacheHandler,
 };
+use arpa_sqlite_db::SqliteDB;
 use async_trait::async_trait;
 use std::sync::Arc;
 use threshold_bls::{
     group::Curve,
     sig::{SignatureScheme, ThresholdScheme},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 
 pub trait ChainIdentityHandler<PC: Curve>:
     ChainIdentity
@@ -91,9 +92,23 @@ pub trait Context<
 
     fn add_relayed_chain(&mut self, relayed_chain: RelayedChainType<PC, S>) -> NodeResult<()>;
 
+    fn remove_relayed_chain(&mut self, index: usize) -> NodeResult<RelayedChainType<PC, S>>;
+
     async fn deploy(self) -> SchedulerResult<ContextHandle>;
 }
 
+/// Progress of a graceful shutdown of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShutdownState {
+    Running,
+    /// The listeners are stopped, and the dynamic tasks are being drained.
+    Draining,
+    /// The servers are stopping after their in-flight requests.
+    Closing,
+    /// The database is flushed, the node can exit.
+    Stopped,
+}
+
 #[async_trait]
 pub trait TaskWaiter {
     async fn wait_task(&self);
@@ -109,6 +124,14 @@ pub trait ContextFetcher {
     fn get_event_queue(&self) -> Arc<RwLock<EventQueue>>;
 
     fn get_config(&self) -> &Config;
+
+    fn get_db(&self) -> Option<&SqliteDB>;
+
+    fn get_shutdown_state(&self) -> ShutdownState;
+
+    fn set_shutdown_state(&self, state: ShutdownState);
+
+    fn subscribe_shutdown_state(&self) -> watch::Receiver<ShutdownState>;
 }
 
 pub(crate) trait CommitterServerStarter<
@@ -2,8 +2,8 @@ use super::{
     chain::{types::GeneralMainChain, Chain, RelayedChain},
     BLSTasksHandler, BlockInfoHandler, ChainIdentityHandler, ChainIdentityHandlerType,
     CommitterServerStarter, Context, ContextFetcher, GroupInfoHandler, ManagementServerStarter,
-    NodeInfoHandler, RelayedChainType, SignatureResultCacheHandler, StatisticsServerStarter,
-    TaskWaiter,
+    NodeInfoHandler, RelayedChainType, ShutdownState, SignatureResultCacheHandler,
+    StatisticsServerStarter, TaskWaiter,
 };
 use crate::{
     committer::server as committer_server,
@@ -17,17 +17,18 @@ use crate::{
 };
 use arpa_core::{
     ComponentTaskType, Config, GeneralMainChainIdentity, GeneralRelayedChainIdentity,
-    HttpServerType, RandomnessTask, RpcServerType, SchedulerResult,
+    HttpServerType, RandomnessTask, RpcServerType, SchedulerError, SchedulerResult,
     DEFAULT_DYNAMIC_TASK_CLEANER_INTERVAL_MILLIS,
 };
 use arpa_dal::cache::RandomnessResultCache;
+use arpa_sqlite_db::SqliteDB;
 use async_trait::async_trait;
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
 use threshold_bls::{
     group::Curve,
     sig::{SignatureScheme, ThresholdScheme},
 };
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 
 #[derive(Debug)]
 pub struct GeneralContext<
@@ -40,6 +41,8 @@ pub struct GeneralContext<
     ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
     f_ts: Arc<RwLock<SimpleFixedTaskScheduler>>,
     config: Config,
+    db: Option<SqliteDB>,
+    shutdown_state: watch::Sender<ShutdownState>,
 }
 
 impl<
@@ -60,8 +63,25 @@ impl<
             ts: Arc::new(RwLock::new(SimpleDynamicTaskScheduler::new())),
             f_ts: Arc::new(RwLock::new(SimpleFixedTaskScheduler::new())),
             config,
+            db: None,
+            shutdown_state: watch::channel(ShutdownState::Running).0,
         }
     }
+
+    /// Sets the database the caches are persisted to, so that its integrity can be checked
+    pub fn set_db(&mut self, db: SqliteDB) {
+        self.db = Some(db);
+    }
+
+    /// Replaces the config after its changes have been applied to the components
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
+    /// Returns the config to record the components changed at runtime
+    pub fn get_config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
 }
 
 impl<
@@ -139,6 +159,12 @@ where
         Ok(())
     }
 
+    fn remove_relayed_chain(&mut self, index: usize) -> NodeResult<RelayedChainType<PC, S>> {
+        self.relayed_chains
+            .remove(&index)
+            .ok_or_else(|| SchedulerError::InvalidChainId(index).into())
+    }
+
     async fn deploy(self) -> SchedulerResult<ContextHandle> {
         self.get_main_chain().init_components(&self).await?;
         for relayed_chain in self.relayed_chains.values() {
@@ -169,9 +195,16 @@ where
             .await
             .start_statistics_server(node_statistics_http_endpoint, context.clone())?;
 
+        #[cfg(unix)]
+        tokio::spawn(crate::management::reload_config_on_hangup(context.clone()));
+
+        tokio::spawn(crate::management::shutdown_on_signal(context.clone()));
+
         let ts = context.read().await.get_dynamic_task_handler();
 
-        Ok(ContextHandle { ts })
+        let shutdown_state = context.read().await.subscribe_shutdown_state();
+
+        Ok(ContextHandle { ts, shutdown_state })
     }
 }
 
@@ -204,33 +237,71 @@ impl<
     fn get_config(&self) -> &Config {
         &self.config
     }
+
+    fn get_db(&self) -> Option<&SqliteDB> {
+        self.db.as_ref()
+    }
+
+    fn get_shutdown_state(&self) -> ShutdownState {
+        *self.shutdown_state.borrow()
+    }
+
+    fn set_shutdown_state(&self, state: ShutdownState) {
+        self.shutdown_state.send_replace(state);
+    }
+
+    fn subscribe_shutdown_state(&self) -> watch::Receiver<ShutdownState> {
+        self.shutdown_state.subscribe()
+    }
 }
 pub struct ContextHandle {
     ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
+    shutdown_state: watch::Receiver<ShutdownState>,
 }
 
 #[async_trait]
 impl TaskWaiter for ContextHandle {
+    /// Cleans up the finished dynamic tasks until the node is stopped.
     async fn wait_task(&self) {
+        let mut shutdown_state = self.shutdown_state.clone();
+
         loop {
             while !self.ts.read().await.dynamic_tasks.is_empty() {
                 let (task_recv, task_monitor) = self.ts.write().await.dynamic_tasks.pop().unwrap();
 
-                let _ = task_recv.await;
+                tokio::select! {
+                    _ = task_recv => {}
+                    _ = wait_for_stopped(&mut shutdown_state) => return,
+                }
 
                 if let Some(monitor) = task_monitor {
                     monitor.abort();
                 }
             }
 
-            tokio::time::sleep(std::time::Duration::from_millis(
-                DEFAULT_DYNAMIC_TASK_CLEANER_INTERVAL_MILLIS,
-            ))
-            .await;
+            tokio::select! {
+                _ = tokio::time::sleep(std::time::Duration::from_millis(
+                    DEFAULT_DYNAMIC_TASK_CLEANER_INTERVAL_MILLIS,
+                )) => {}
+                _ = wait_for_stopped(&mut shutdown_state) => return,
+            }
         }
     }
 }
 
+/// Resolves when the shutdown of the node reaches the given state.
+pub async fn wait_for_shutdown_state(
+    shutdown_state: &mut watch::Receiver<ShutdownState>,
+    state: ShutdownState,
+) {
+    // the sender lives as long as the context, a closed channel means the node is stopped
+    let _ = shutdown_state.wait_for(|s| *s >= state).await;
+}
+
+async fn wait_for_stopped(shutdown_state: &mut watch::Receiver<ShutdownState>) {
+    wait_for_shutdown_state(shutdown_state, ShutdownState::Stopped).await
+}
+
 impl<
         PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
         S: SignatureScheme
@@ -249,9 +320,20 @@ where
         rpc_endpoint: String,
         context: Arc<RwLock<GeneralContext<PC, S>>>,
     ) -> SchedulerResult<()> {
+        let context_for_shutdown = context.clone();
+
+        // the committer server keeps serving the other members until the tasks are drained
         self.add_task(
             ComponentTaskType::RpcServer(RpcServerType::Committer),
-            committer_server::start_committer_server(rpc_endpoint, context),
+            committer_server::start_committer_server_with_shutdown(
+                rpc_endpoint,
+                context,
+                async move {
+                    let mut shutdown_state =
+                        context_for_shutdown.read().await.subscribe_shutdown_state();
+                    wait_for_shutdown_state(&mut shutdown_state, ShutdownState::Closing).await;
+                },
+            ),
         )
     }
 }
@@ -1,5 +1,5 @@
 use arpa_contract_client::error::ContractClientError;
-use arpa_core::SchedulerError;
+use arpa_core::{ConfigError, SchedulerError};
 use arpa_dal::error::DataAccessError;
 use arpa_sqlite_db::DBError;
 use dkg_core::{primitives::DKGError, DKGNodeError};
@@ -42,6 +42,44 @@ pub enum NodeError {
     #[error("There is already this chain id in the context. Please check config.yml")]
     RepeatedChainId,
 
+    #[error("the relayed chain {0} still has {1} unfulfilled tasks")]
+    RelayedChainNotDrained(usize, usize),
+
+    #[error("the database is not available in the context")]
+    DBNotAvailable,
+
+    #[error("the node is in a DKG phase, shut it down by force if it is intended")]
+    ShutdownDuringDKG,
+
+    #[error("the node is already shutting down")]
+    AlreadyShuttingDown,
+
+    #[error("the DKG checkpoint after phase {0} is invalid")]
+    InvalidDKGCheckpoint(usize),
+
+    #[error("the DKG bundle doesn't match the digest committed on chain")]
+    DKGBundleDigestMismatch,
+
+    #[error("the label {0} of the ciphertext is not supported")]
+    UnsupportedDecryptionLabel(String),
+
+    #[error("the ciphertext can't be decrypted before block {0}")]
+    DecryptionNotRevealed(usize),
+
+    #[error("the decryption shares of the task are not enough to combine yet")]
+    DecryptionSharesNotEnough,
+
+    #[error("the blind partial signatures of the task are not enough to aggregate yet")]
+    BlindPartialSignaturesNotEnough,
+
+    #[error("the caller {0} is not allowed to request blind signatures")]
+    BlindSignatureCallerNotAllowed(String),
+
+    #[error(
+        "too many blind signatures are issued to the caller or the requester, please retry later"
+    )]
+    BlindSignatureIssuanceRateLimited,
+
     #[error("can't connect to the rpc server, please check the endpoint. Original error: {0}")]
     RpcNotAvailableError(tonic::transport::Error),
 
@@ -51,6 +89,9 @@ pub enum NodeError {
     #[error(transparent)]
     SchedulerError(#[from] SchedulerError),
 
+    #[error(transparent)]
+    ConfigError(#[from] ConfigError),
+
     #[error(transparent)]
     ContractClientError(#[from] ContractClientError),
 
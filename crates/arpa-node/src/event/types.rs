@@ -19,3 +19,45 @@ pub enum Topic {
     ReadyToFulfillGroupRelayConfirmationTask(usize),
     NodeActivation,
 }
+
+impl Topic {
+    /// Returns the name of the topic regardless of the chain.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topic::NewBlock(_) => "NewBlock",
+            Topic::NewDKGTask => "NewDKGTask",
+            Topic::RunDKG => "RunDKG",
+            Topic::DKGPhase => "DKGPhase",
+            Topic::DKGSuccess => "DKGSuccess",
+            Topic::DKGPostProcess => "DKGPostProcess",
+            Topic::NewRandomnessTask(_) => "NewRandomnessTask",
+            Topic::NewGroupRelayTask => "NewGroupRelayTask",
+            Topic::NewGroupRelayConfirmationTask(_) => "NewGroupRelayConfirmationTask",
+            Topic::ReadyToHandleRandomnessTask(_) => "ReadyToHandleRandomnessTask",
+            Topic::ReadyToHandleGroupRelayTask => "ReadyToHandleGroupRelayTask",
+            Topic::ReadyToHandleGroupRelayConfirmationTask(_) => {
+                "ReadyToHandleGroupRelayConfirmationTask"
+            }
+            Topic::ReadyToFulfillRandomnessTask(_) => "ReadyToFulfillRandomnessTask",
+            Topic::ReadyToFulfillGroupRelayTask => "ReadyToFulfillGroupRelayTask",
+            Topic::ReadyToFulfillGroupRelayConfirmationTask(_) => {
+                "ReadyToFulfillGroupRelayConfirmationTask"
+            }
+            Topic::NodeActivation => "NodeActivation",
+        }
+    }
+
+    /// Returns the chain id of the topic, or None if the topic is on the main chain only.
+    pub fn chain_id(&self) -> Option<usize> {
+        match self {
+            Topic::NewBlock(chain_id)
+            | Topic::NewRandomnessTask(chain_id)
+            | Topic::NewGroupRelayConfirmationTask(chain_id)
+            | Topic::ReadyToHandleRandomnessTask(chain_id)
+            | Topic::ReadyToHandleGroupRelayConfirmationTask(chain_id)
+            | Topic::ReadyToFulfillRandomnessTask(chain_id)
+            | Topic::ReadyToFulfillGroupRelayConfirmationTask(chain_id) => Some(*chain_id),
+            _ => None,
+        }
+    }
+}
@@ -6,9 +6,11 @@ pub mod context;
 pub mod error;
 pub mod event;
 pub mod listener;
+pub mod logger;
 pub mod management;
 pub mod queue;
 pub mod rpc_stub;
 pub mod scheduler;
 pub mod stats;
 pub mod subscriber;
+pub mod telemetry;
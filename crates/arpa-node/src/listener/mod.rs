@@ -13,7 +13,8 @@ use crate::error::NodeResult;
 use arpa_core::{
     jitter,
     log::{build_general_payload, LogType},
-    FixedIntervalRetryDescriptor,
+    metrics::{inc_listener_interruptions, record_payload, set_listener_last_success},
+    ListenerDescriptor,
 };
 use async_trait::async_trait;
 use log::error;
@@ -22,28 +23,32 @@ use tokio_retry::{strategy::FixedInterval, Retry};
 
 #[async_trait]
 pub trait Listener {
-    async fn start(
-        &self,
-        interval_millis: u64,
-        use_jitter: bool,
-        reset_descriptor: FixedIntervalRetryDescriptor,
-    ) -> NodeResult<()>
+    async fn start(&self, listener_descriptor: ListenerDescriptor) -> NodeResult<()>
     where
         Self: Display,
     {
+        let ListenerDescriptor {
+            l_type,
+            interval_millis,
+            use_jitter,
+            reset_descriptor,
+        } = listener_descriptor;
+
         let mut next_polling_strategy =
             FixedInterval::from_millis(interval_millis)
                 .map(|e| if use_jitter { jitter(e) } else { e });
 
         loop {
             if let Err(err) = self.listen().await {
+                inc_listener_interruptions(self.chain_id().await, &self.to_string());
+
                 error!(
                     "{}",
-                    build_general_payload(
+                    record_payload(build_general_payload(
                         LogType::ListenerInterrupted,
                         &format!("{} is interrupted. Retry... Error: {:?}.", self, err),
                         Some(self.chain_id().await)
-                    )
+                    ))
                 );
 
                 let reset_strategy = FixedInterval::from_millis(reset_descriptor.interval_millis)
@@ -60,6 +65,8 @@ pub trait Listener {
                     self.handle_interruption().await
                 })
                 .await?;
+            } else {
+                set_listener_last_success(self.chain_id().await, &format!("{:?}", l_type));
             }
             sleep(next_polling_strategy.next().unwrap()).await;
         }
@@ -4,10 +4,12 @@ use crate::{
     error::NodeResult,
     event::new_randomness_task::NewRandomnessTask,
     queue::{event_queue::EventQueue, EventPublisher},
+    telemetry::start_randomness_task_span,
 };
 use arpa_contract_client::adapter::AdapterLogs;
 use arpa_core::{
     log::{build_task_related_payload, LogType},
+    metrics::record_payload,
     BLSTaskType, RandomnessTask, TaskType,
 };
 use arpa_dal::BLSTasksHandler;
@@ -84,7 +86,7 @@ impl<PC: Curve + Sync + Send> Listener for NewRandomnessTaskListener<PC> {
                     if let Ok(false) = contained_res {
                         info!(
                             "{}",
-                            build_task_related_payload(
+                            record_payload(build_task_related_payload(
                                 LogType::TaskReceived,
                                 "New randomness task received.",
                                 self.chain_id,
@@ -92,9 +94,11 @@ impl<PC: Curve + Sync + Send> Listener for NewRandomnessTaskListener<PC> {
                                 TaskType::BLS(BLSTaskType::Randomness),
                                 json!(randomness_task),
                                 None
-                            )
+                            ))
                         );
 
+                        start_randomness_task_span(chain_id, &randomness_task.request_id);
+
                         randomness_tasks_cache
                             .write()
                             .await
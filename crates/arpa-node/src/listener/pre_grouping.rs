@@ -8,6 +8,7 @@ use crate::{
 use arpa_contract_client::controller::ControllerLogs;
 use arpa_core::{
     log::{build_task_related_payload, LogType},
+    metrics::record_payload,
     TaskType,
 };
 use arpa_dal::GroupInfoHandler;
@@ -81,7 +82,7 @@ impl<PC: Curve + Sync + Send> Listener for PreGroupingListener<PC> {
                         if cache_index != dkg_task.group_index || cache_epoch != dkg_task.epoch {
                             info!(
                                 "{}",
-                                build_task_related_payload(
+                                record_payload(build_task_related_payload(
                                     LogType::TaskReceived,
                                     "DKG grouping task received.",
                                     chain_id,
@@ -89,7 +90,7 @@ impl<PC: Curve + Sync + Send> Listener for PreGroupingListener<PC> {
                                     TaskType::DKG,
                                     json!(dkg_task),
                                     None
-                                )
+                                ))
                             );
 
                             let self_index = node_index;
@@ -10,6 +10,7 @@ use arpa_core::RandomnessTask;
 use arpa_dal::{BLSTasksHandler, BlockInfoHandler, GroupInfoHandler};
 use async_trait::async_trait;
 use ethers::{providers::Middleware, types::Address};
+use log::error;
 use std::{marker::PhantomData, sync::Arc};
 use threshold_bls::group::Curve;
 use tokio::sync::RwLock;
@@ -92,19 +93,42 @@ impl<PC: Curve + Sync + Send> Listener for ReadyToHandleRandomnessTaskListener<P
                 return Ok(());
             }
 
-            let mut tasks_to_process: Vec<RandomnessTask> = vec![];
-
             let client = self
                 .chain_identity
                 .read()
                 .await
                 .build_adapter_client(self.id_address);
 
-            for task in available_tasks {
-                if let Ok(true) = client.is_task_pending(&task.request_id).await {
-                    tasks_to_process.push(task);
+            let request_ids = available_tasks
+                .iter()
+                .map(|task| task.request_id.clone())
+                .collect::<Vec<_>>();
+
+            let tasks_to_process: Vec<RandomnessTask> = match client
+                .are_tasks_pending(&request_ids)
+                .await
+            {
+                Ok(pending_statuses) => available_tasks
+                    .into_iter()
+                    .zip(pending_statuses)
+                    .filter_map(|(task, is_pending)| is_pending.then_some(task))
+                    .collect(),
+                Err(e) => {
+                    // a failing batch shouldn't hold back the tasks which can be checked
+                    error!(
+                            "failed to check the pending tasks in batch, checking them one by one: {:?}",
+                            e
+                        );
+
+                    let mut tasks_to_process = vec![];
+                    for task in available_tasks {
+                        if let Ok(true) = client.is_task_pending(&task.request_id).await {
+                            tasks_to_process.push(task);
+                        }
+                    }
+                    tasks_to_process
                 }
-            }
+            };
 
             if !tasks_to_process.is_empty() {
                 self.publish(ReadyToHandleRandomnessTask {
@@ -0,0 +1,173 @@
+use crate::management::access::MANAGEMENT_AUDIT_LOG_TARGET;
+use arpa_core::{log::encoder::JsonEncoder, LoggerDescriptor};
+use lazy_static::lazy_static;
+use log::LevelFilter;
+use log4rs::{
+    append::{
+        console::ConsoleAppender,
+        rolling_file::{
+            policy::compound::{
+                roll::delete::DeleteRoller, trigger::size::SizeTrigger, CompoundPolicy,
+            },
+            RollingFileAppender,
+        },
+    },
+    config::{Appender, Logger, Root},
+    filter::threshold::ThresholdFilter,
+    Config as LogConfig, Handle,
+};
+use parking_lot::Mutex;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Crates whose logs are emitted at the configured level
+const NODE_LOG_TARGETS: [&str; 8] = [
+    "node_client",
+    "arpa_node",
+    "arpa_core",
+    "arpa_contract_client",
+    "arpa_sqlite_db",
+    "arpa_dal",
+    "dkg_core",
+    "threshold_bls",
+];
+
+struct LoggerState {
+    handle: Handle,
+    node_id: String,
+    l1_chain_id: usize,
+    logger_descriptor: LoggerDescriptor,
+}
+
+lazy_static! {
+    /// The installed logger, kept to change the log level at runtime
+    static ref LOGGER_STATE: Mutex<Option<LoggerState>> = Mutex::new(None);
+}
+
+/// Installs the console and rolling file appenders as the global logger.
+pub fn init_logger(node_id: &str, l1_chain_id: usize, logger_descriptor: &LoggerDescriptor) {
+    let log_config = build_log_config(
+        node_id,
+        l1_chain_id,
+        logger_descriptor,
+        logger_descriptor.get_log_level(),
+    );
+
+    let handle = log4rs::init_config(log_config).unwrap();
+
+    *LOGGER_STATE.lock() = Some(LoggerState {
+        handle,
+        node_id: node_id.to_string(),
+        l1_chain_id,
+        logger_descriptor: logger_descriptor.clone(),
+    });
+}
+
+/// Changes the level of the logs emitted by the node crates, without restarting the node.
+/// Does nothing if the logger has not been installed by `init_logger`.
+pub fn set_log_level(level: LevelFilter) {
+    if let Some(state) = LOGGER_STATE.lock().as_ref() {
+        state.handle.set_config(build_log_config(
+            &state.node_id,
+            state.l1_chain_id,
+            &state.logger_descriptor,
+            level,
+        ));
+    }
+}
+
+fn build_log_config(
+    node_id: &str,
+    l1_chain_id: usize,
+    logger_descriptor: &LoggerDescriptor,
+    level: LevelFilter,
+) -> LogConfig {
+    let context_logging = logger_descriptor.get_context_logging();
+    let log_file_path = logger_descriptor.get_log_file_path();
+    let rolling_file_size = logger_descriptor.get_rolling_file_size();
+
+    let stdout = ConsoleAppender::builder()
+        .encoder(Box::new(
+            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
+                .context_logging(context_logging),
+        ))
+        .build();
+
+    let rolling_file = RollingFileAppender::builder()
+        .encoder(Box::new(
+            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
+                .context_logging(context_logging),
+        ))
+        .build(
+            format!(
+                "{}/node.log",
+                if let Some(path_without_slash) = log_file_path.strip_suffix('/') {
+                    path_without_slash
+                } else {
+                    log_file_path
+                }
+            ),
+            Box::new(CompoundPolicy::new(
+                Box::new(SizeTrigger::new(rolling_file_size)),
+                Box::new(DeleteRoller::new()),
+            )),
+        )
+        .unwrap();
+
+    let rolling_err_file = RollingFileAppender::builder()
+        .encoder(Box::new(
+            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
+                .context_logging(context_logging),
+        ))
+        .build(
+            format!("{}/node_err.log", log_file_path),
+            Box::new(CompoundPolicy::new(
+                Box::new(SizeTrigger::new(rolling_file_size)),
+                Box::new(DeleteRoller::new()),
+            )),
+        )
+        .unwrap();
+
+    let rolling_audit_file = RollingFileAppender::builder()
+        .encoder(Box::new(
+            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
+                .context_logging(context_logging),
+        ))
+        .build(
+            format!("{}/management_audit.log", log_file_path),
+            Box::new(CompoundPolicy::new(
+                Box::new(SizeTrigger::new(rolling_file_size)),
+                Box::new(DeleteRoller::new()),
+            )),
+        )
+        .unwrap();
+
+    LogConfig::builder()
+        .appender(Appender::builder().build("stdout", Box::new(stdout)))
+        .appender(Appender::builder().build("file", Box::new(rolling_file)))
+        .appender(Appender::builder().build("audit_file", Box::new(rolling_audit_file)))
+        .appender(
+            Appender::builder()
+                .filter(Box::new(ThresholdFilter::new(LevelFilter::Error)))
+                .build("err_file", Box::new(rolling_err_file)),
+        )
+        .loggers(
+            NODE_LOG_TARGETS
+                .iter()
+                .map(|target| Logger::builder().build(*target, level)),
+        )
+        .logger(
+            Logger::builder()
+                .appender("audit_file")
+                .additive(false)
+                .build(MANAGEMENT_AUDIT_LOG_TARGET, LevelFilter::Info),
+        )
+        .build(
+            Root::builder()
+                .appender("err_file")
+                .appender("stdout")
+                .appender("file")
+                .build(LevelFilter::Error),
+        )
+        .unwrap()
+}
@@ -0,0 +1,127 @@
+use arpa_core::ManagementScope;
+use serde::Serialize;
+use std::fmt::Debug;
+use tonic::Request;
+
+/// Target of the audit records, which are written to a separate log file.
+pub const MANAGEMENT_AUDIT_LOG_TARGET: &str = "management_audit";
+
+/// Header of the confirmation token required by destructive RPCs if configured.
+pub const CONFIRMATION_TOKEN_HEADER: &str = "x-confirmation-token";
+
+/// Caller of a management RPC, attached to the request once it is authorized.
+#[derive(Debug, Clone)]
+pub struct ManagementCaller {
+    pub name: String,
+    pub rpc: String,
+}
+
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    caller: &'a str,
+    rpc: &'a str,
+    result: &'a str,
+    arguments: Option<String>,
+}
+
+/// Returns the scope required by the management RPC, or None if the RPC is unknown.
+pub fn rpc_scope(rpc: &str) -> Option<ManagementScope> {
+    match rpc {
+        "ListFixedTasks"
+        | "AggregatePartialSigs"
+        | "VerifySig"
+        | "VerifyPartialSigs"
+        | "ListRandomnessTasks"
+        | "GetRandomnessTask"
+        | "ListSignatureResults"
+        | "GetSignatureResult"
+        | "SubscribeEvents" => Some(ManagementScope::Inspect),
+        "StartListener" | "ShutdownListener" => Some(ManagementScope::Listener),
+        "NodeRegister" | "NodeActivate" | "GetNodeInfo" | "GetGroupInfo" | "PostProcessDkg"
+        | "PartialSign" | "SendPartialSig" | "FulfillRandomness" => Some(ManagementScope::Key),
+        "GetPartialEncryptionKey"
+        | "PartialDecrypt"
+        | "SendDecryptionShare"
+        | "CombineDecryptionShares"
+        | "GetDecryptionResult"
+        | "BlindPartialSign"
+        | "SendBlindPartialSig"
+        | "AggregateBlindPartialSigs"
+        | "GetBlindSignatureResult" => Some(ManagementScope::Request),
+        "NodeQuit" | "ShutdownNode" | "ReloadConfig" | "AddRelayedChain" | "RemoveRelayedChain" => {
+            Some(ManagementScope::Destructive)
+        }
+        _ => None,
+    }
+}
+
+/// Records a management call which is rejected before reaching the service.
+pub fn audit_rejected(caller: Option<&str>, rpc: &str, reason: &str) {
+    log::warn!(
+        target: MANAGEMENT_AUDIT_LOG_TARGET,
+        "{}",
+        serde_json::to_string(&AuditRecord {
+            caller: caller.unwrap_or("unknown"),
+            rpc,
+            result: reason,
+            arguments: None,
+        })
+        .unwrap()
+    );
+}
+
+/// Records an authorized management call along with its arguments.
+pub fn audit<T: Debug>(request: &Request<T>) {
+    if let Some(caller) = request.extensions().get::<ManagementCaller>() {
+        log::info!(
+            target: MANAGEMENT_AUDIT_LOG_TARGET,
+            "{}",
+            serde_json::to_string(&AuditRecord {
+                caller: &caller.name,
+                rpc: &caller.rpc,
+                result: "authorized",
+                arguments: Some(format!("{:?}", request.get_ref())),
+            })
+            .unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rpc_scope;
+    use arpa_core::ManagementScope;
+
+    #[test]
+    fn test_rpc_scope() {
+        assert_eq!(
+            rpc_scope("GetRandomnessTask"),
+            Some(ManagementScope::Inspect)
+        );
+        assert_eq!(rpc_scope("StartListener"), Some(ManagementScope::Listener));
+        assert_eq!(rpc_scope("PartialSign"), Some(ManagementScope::Key));
+        assert_eq!(rpc_scope("PartialDecrypt"), Some(ManagementScope::Request));
+        assert_eq!(
+            rpc_scope("GetDecryptionResult"),
+            Some(ManagementScope::Request)
+        );
+        assert_eq!(
+            rpc_scope("BlindPartialSign"),
+            Some(ManagementScope::Request)
+        );
+        assert_eq!(rpc_scope("GetNodeInfo"), Some(ManagementScope::Key));
+        assert_eq!(
+            rpc_scope("ShutdownNode"),
+            Some(ManagementScope::Destructive)
+        );
+        assert_eq!(
+            rpc_scope("ReloadConfig"),
+            Some(ManagementScope::Destructive)
+        );
+        assert_eq!(
+            rpc_scope("RemoveRelayedChain"),
+            Some(ManagementScope::Destructive)
+        );
+        assert_eq!(rpc_scope("Unknown"), None);
+    }
+}
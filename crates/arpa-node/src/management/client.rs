@@ -1,15 +1,23 @@
+use super::access::CONFIRMATION_TOKEN_HEADER;
 use crate::error::{NodeError, NodeResult};
 use crate::rpc_stub::management::management_service_client::ManagementServiceClient;
-use crate::rpc_stub::management::ListFixedTasksRequest;
+use crate::rpc_stub::management::{
+    AddRelayedChainReply, AddRelayedChainRequest, BlsRandomnessTask, BlsResultCacheState,
+    GetRandomnessTaskRequest, GetSignatureResultRequest, ListFixedTasksRequest,
+    ListRandomnessTasksRequest, ListSignatureResultsRequest, NodeEvent, ReloadConfigReply,
+    ReloadConfigRequest, RemoveRelayedChainReply, RemoveRelayedChainRequest, ShutdownNodeReply,
+    ShutdownNodeRequest, SignatureResult, SubscribeEventsRequest,
+};
 use tonic::codegen::InterceptedService;
 use tonic::service::Interceptor;
 use tonic::transport::Channel;
-use tonic::Request;
+use tonic::{Request, Streaming};
 
 #[derive(Clone, Debug)]
 pub struct GeneralManagementClient {
     management_endpoint: String,
     authorization_token: String,
+    confirmation_token: Option<String>,
 }
 
 impl GeneralManagementClient {
@@ -17,9 +25,16 @@ impl GeneralManagementClient {
         GeneralManagementClient {
             management_endpoint,
             authorization_token,
+            confirmation_token: None,
         }
     }
 
+    /// Sets the confirmation token required by destructive RPCs.
+    pub fn with_confirmation_token(mut self, confirmation_token: String) -> Self {
+        self.confirmation_token = Some(confirmation_token);
+        self
+    }
+
     async fn prepare_service_client(
         &self,
     ) -> NodeResult<ManagementServiceClient<InterceptedService<Channel, impl Interceptor + '_>>>
@@ -39,6 +54,12 @@ impl GeneralManagementClient {
                     "authorization",
                     self.authorization_token.clone().parse().unwrap(),
                 );
+                if let Some(confirmation_token) = &self.confirmation_token {
+                    req.metadata_mut().insert(
+                        CONFIRMATION_TOKEN_HEADER,
+                        confirmation_token.parse().unwrap(),
+                    );
+                }
                 Ok(req)
             });
 
@@ -55,4 +76,190 @@ impl GeneralManagementClient {
             .map(|r| r.into_inner().fixed_tasks)
             .map_err(|status| status.into())
     }
+
+    pub async fn list_randomness_tasks(
+        &self,
+        chain_id: usize,
+        handled: Option<bool>,
+        from_block_height: Option<usize>,
+        to_block_height: Option<usize>,
+        limit: Option<u32>,
+        cursor: Option<Vec<u8>>,
+    ) -> NodeResult<(Vec<BlsRandomnessTask>, Option<Vec<u8>>)> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(ListRandomnessTasksRequest {
+            chain_id: chain_id as u32,
+            handled,
+            from_block_height: from_block_height.map(|h| h as u64),
+            to_block_height: to_block_height.map(|h| h as u64),
+            limit,
+            cursor,
+        });
+        management_client
+            .list_randomness_tasks(request)
+            .await
+            .map(|r| {
+                let reply = r.into_inner();
+                (reply.tasks, reply.next_cursor)
+            })
+            .map_err(|status| status.into())
+    }
+
+    pub async fn get_randomness_task(
+        &self,
+        chain_id: usize,
+        request_id: Vec<u8>,
+    ) -> NodeResult<Option<BlsRandomnessTask>> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(GetRandomnessTaskRequest {
+            chain_id: chain_id as u32,
+            request_id,
+        });
+        management_client
+            .get_randomness_task(request)
+            .await
+            .map(|r| r.into_inner().task)
+            .map_err(|status| status.into())
+    }
+
+    pub async fn list_signature_results(
+        &self,
+        chain_id: usize,
+        state: Option<BlsResultCacheState>,
+        from_block_height: Option<usize>,
+        to_block_height: Option<usize>,
+        limit: Option<u32>,
+        cursor: Option<Vec<u8>>,
+    ) -> NodeResult<(Vec<SignatureResult>, Option<Vec<u8>>)> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(ListSignatureResultsRequest {
+            chain_id: chain_id as u32,
+            state: state.map(|s| s as i32),
+            from_block_height: from_block_height.map(|h| h as u64),
+            to_block_height: to_block_height.map(|h| h as u64),
+            limit,
+            cursor,
+        });
+        management_client
+            .list_signature_results(request)
+            .await
+            .map(|r| {
+                let reply = r.into_inner();
+                (reply.results, reply.next_cursor)
+            })
+            .map_err(|status| status.into())
+    }
+
+    pub async fn get_signature_result(
+        &self,
+        chain_id: usize,
+        request_id: Vec<u8>,
+    ) -> NodeResult<Option<SignatureResult>> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(GetSignatureResultRequest {
+            chain_id: chain_id as u32,
+            request_id,
+        });
+        management_client
+            .get_signature_result(request)
+            .await
+            .map(|r| r.into_inner().result)
+            .map_err(|status| status.into())
+    }
+
+    pub async fn subscribe_events(
+        &self,
+        topics: Vec<String>,
+        chain_ids: Vec<usize>,
+        with_logs: bool,
+    ) -> NodeResult<Streaming<NodeEvent>> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(SubscribeEventsRequest {
+            topics,
+            chain_ids: chain_ids.into_iter().map(|c| c as u32).collect(),
+            with_logs,
+        });
+        management_client
+            .subscribe_events(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
+
+    pub async fn reload_config(
+        &self,
+        config_path: Option<String>,
+        dry_run: bool,
+    ) -> NodeResult<ReloadConfigReply> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(ReloadConfigRequest {
+            config_path,
+            dry_run,
+        });
+        management_client
+            .reload_config(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
+
+    pub async fn add_relayed_chain(
+        &self,
+        chain_id: usize,
+        config_path: Option<String>,
+    ) -> NodeResult<AddRelayedChainReply> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(AddRelayedChainRequest {
+            chain_id: chain_id as u32,
+            config_path,
+        });
+        management_client
+            .add_relayed_chain(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
+
+    pub async fn remove_relayed_chain(
+        &self,
+        chain_id: usize,
+        drain_timeout_secs: u64,
+    ) -> NodeResult<RemoveRelayedChainReply> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(RemoveRelayedChainRequest {
+            chain_id: chain_id as u32,
+            drain_timeout_secs,
+        });
+        management_client
+            .remove_relayed_chain(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
+
+    pub async fn shutdown_node(
+        &self,
+        force: bool,
+        drain_timeout_secs: u64,
+    ) -> NodeResult<ShutdownNodeReply> {
+        let mut management_client = self.prepare_service_client().await?;
+
+        let request = Request::new(ShutdownNodeRequest {
+            force,
+            drain_timeout_secs,
+        });
+        management_client
+            .shutdown_node(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|status| status.into())
+    }
 }
@@ -0,0 +1,152 @@
+use crate::error::{NodeError, NodeResult};
+use arpa_core::BlindSignatureIssuanceDescriptor;
+use ethers::types::Address;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+pub trait BlindSignatureIssuancePolicy: std::fmt::Debug + Sync + Send {
+    /// Decides whether a blind partial signature is issued to the caller for the requester,
+    /// and counts the issuance if so
+    fn authorize(&self, caller: &str, requester: Address) -> NodeResult<()>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum IssuanceKey {
+    Caller(String),
+    Requester(Address),
+}
+
+/// Issues blind partial signatures to the allowed callers only, and at most
+/// `max_issuances_per_window` of them to the same caller, and for the same requester,
+/// within a sliding window.
+#[derive(Debug)]
+pub struct RateLimitedIssuancePolicy {
+    allowed_callers: Option<Vec<String>>,
+    max_issuances_per_window: usize,
+    window: Duration,
+    issuances: Mutex<HashMap<IssuanceKey, VecDeque<Instant>>>,
+}
+
+impl RateLimitedIssuancePolicy {
+    pub fn new(
+        allowed_callers: Option<Vec<String>>,
+        max_issuances_per_window: usize,
+        window: Duration,
+    ) -> Self {
+        RateLimitedIssuancePolicy {
+            allowed_callers,
+            max_issuances_per_window,
+            window,
+            issuances: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn authorize_at(&self, caller: &str, requester: Address, now: Instant) -> NodeResult<()> {
+        if let Some(allowed_callers) = &self.allowed_callers {
+            if !allowed_callers.iter().any(|c| c == caller) {
+                return Err(NodeError::BlindSignatureCallerNotAllowed(
+                    caller.to_string(),
+                ));
+            }
+        }
+
+        let keys = [
+            IssuanceKey::Caller(caller.to_string()),
+            IssuanceKey::Requester(requester),
+        ];
+
+        let mut issuances = self.issuances.lock().unwrap();
+
+        for key in keys.iter() {
+            let times = issuances.entry(key.clone()).or_default();
+            while times
+                .front()
+                .is_some_and(|t| now.duration_since(*t) >= self.window)
+            {
+                times.pop_front();
+            }
+            if times.len() >= self.max_issuances_per_window {
+                return Err(NodeError::BlindSignatureIssuanceRateLimited);
+            }
+        }
+
+        // only an issuance within both limits is counted
+        for key in keys {
+            issuances.entry(key).or_default().push_back(now);
+        }
+
+        Ok(())
+    }
+}
+
+impl From<BlindSignatureIssuanceDescriptor> for RateLimitedIssuancePolicy {
+    fn from(descriptor: BlindSignatureIssuanceDescriptor) -> Self {
+        RateLimitedIssuancePolicy::new(
+            descriptor.allowed_callers,
+            descriptor.max_issuances_per_window as usize,
+            Duration::from_secs(descriptor.window_secs),
+        )
+    }
+}
+
+impl BlindSignatureIssuancePolicy for RateLimitedIssuancePolicy {
+    fn authorize(&self, caller: &str, requester: Address) -> NodeResult<()> {
+        self.authorize_at(caller, requester, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RateLimitedIssuancePolicy;
+    use crate::error::NodeError;
+    use ethers::types::Address;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_only_allowed_callers_are_issued() {
+        let policy = RateLimitedIssuancePolicy::new(
+            Some(vec!["wallet".to_string()]),
+            10,
+            Duration::from_secs(60),
+        );
+        let now = Instant::now();
+
+        assert!(policy
+            .authorize_at("wallet", Address::random(), now)
+            .is_ok());
+        assert!(matches!(
+            policy.authorize_at("monitor", Address::random(), now),
+            Err(NodeError::BlindSignatureCallerNotAllowed(caller)) if caller == "monitor"
+        ));
+    }
+
+    #[test]
+    fn test_issuances_are_limited_per_caller_and_requester() {
+        let policy = RateLimitedIssuancePolicy::new(None, 2, Duration::from_secs(60));
+        let now = Instant::now();
+        let requester = Address::random();
+
+        assert!(policy.authorize_at("wallet", requester, now).is_ok());
+        assert!(policy.authorize_at("wallet", requester, now).is_ok());
+        assert!(matches!(
+            policy.authorize_at("wallet", Address::random(), now),
+            Err(NodeError::BlindSignatureIssuanceRateLimited)
+        ));
+        // another caller can't get around the limit of the requester
+        assert!(matches!(
+            policy.authorize_at("gateway", requester, now),
+            Err(NodeError::BlindSignatureIssuanceRateLimited)
+        ));
+        // while the rejected issuance is not counted against the caller
+        assert!(policy
+            .authorize_at("gateway", Address::random(), now)
+            .is_ok());
+
+        // the window slides
+        let later = now + Duration::from_secs(60);
+        assert!(policy.authorize_at("wallet", requester, later).is_ok());
+    }
+}
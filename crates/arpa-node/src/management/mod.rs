@@ -1,28 +1,78 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use super::{
-    algorithm::bls::{BLSCore, SimpleBLSCore},
+    algorithm::{
+        bls::{BLSCore, SimpleBLSCore},
+        fulfillment::ProfitabilityFulfillmentPolicy,
+    },
     committer::{client::GeneralCommitterClient, CommitterClient, CommitterService},
-    context::{chain::Chain, types::GeneralContext, Context, ContextFetcher},
+    context::{
+        chain::{types::GeneralRelayedChain, Chain, MainChain},
+        types::GeneralContext,
+        Context, ContextFetcher, ShutdownState,
+    },
     error::{NodeError, NodeResult},
-    scheduler::FixedTaskScheduler,
+    logger::set_log_level,
+    queue::event_queue::EventQueue,
+    scheduler::{fixed::SimpleFixedTaskScheduler, FixedTaskScheduler},
 };
 use anyhow::Result;
-use arpa_contract_client::{adapter::AdapterTransactions, controller::ControllerTransactions};
+use arpa_contract_client::{
+    adapter::{AdapterTransactions, AdapterViews},
+    controller::ControllerTransactions,
+};
 use arpa_core::{
-    BLSTaskType, ComponentTaskType, DKGStatus, ExponentialBackoffRetryDescriptor, Group,
-    ListenerDescriptor, ListenerType, PartialSignature, SchedulerError, SchedulerResult,
-    DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_BASE, DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_FACTOR,
+    get_chain_profile,
+    log::{build_task_related_payload, LogType},
+    metrics::record_payload,
+    register_chain_profiles, BLSTask, BLSTaskType, BlindSignatureTask, ChainProfileRegistry,
+    ComponentTaskType, Config, ConfigDiff, ConfigError, DKGStatus, DecryptionTask,
+    ExponentialBackoffRetryDescriptor, GeneralRelayedChainIdentity, Group, ListenerDescriptor,
+    ListenerType, PartialSignature, RandomnessTask, RpcServerType, SchedulerError, SchedulerResult,
+    TaskType, DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_BASE,
+    DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_FACTOR,
     DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_MAX_ATTEMPTS,
-    DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER,
+    DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER, DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
+    RELAYED_CHAIN_DRAIN_CHECK_INTERVAL_MILLIS, SHUTDOWN_DRAIN_CHECK_INTERVAL_MILLIS,
 };
+use arpa_dal::cache::{BLSResultCache, RandomnessResultCache};
 use arpa_dal::error::DataAccessResult;
+use arpa_dal::{
+    BLSResultCacheState, BLSTasksHandler, BlockHeightRange, ListPage, SignatureResultCacheHandler,
+};
+use ethers::providers::{Middleware, Provider, Ws};
 use ethers::types::Address;
+use log::{error, info, warn};
+use serde_json::json;
 use threshold_bls::{
     group::Curve,
     poly::Eval,
     sig::{Share, SignatureScheme, ThresholdScheme},
 };
+use tokio::sync::RwLock;
+
+// Listeners run by the relayed chains
+const RELAYED_CHAIN_LISTENER_TYPES: [ListenerType; 4] = [
+    ListenerType::Block,
+    ListenerType::NewRandomnessTask,
+    ListenerType::ReadyToHandleRandomnessTask,
+    ListenerType::RandomnessSignatureAggregation,
+];
+
+// Listeners which bring new tasks to the node, instead of finishing the taken ones
+const TASK_TAKING_LISTENER_TYPES: [ListenerType; 2] = [
+    ListenerType::NewRandomnessTask,
+    ListenerType::ReadyToHandleRandomnessTask,
+];
+
+pub mod access;
+
+pub mod issuance;
 
 pub mod server;
 
@@ -54,7 +104,8 @@ pub trait NodeService {
 
     async fn node_quit(&self) -> NodeResult<()>;
 
-    async fn shutdown_node(&self) -> NodeResult<()>;
+    /// Stops the listeners and marks the node as shutting down. It is refused during a DKG phase unless forced.
+    async fn shutdown_node(&self, force: bool) -> NodeResult<()>;
 }
 
 pub trait ComponentService {
@@ -75,6 +126,66 @@ pub trait DBService<PC: Curve> {
     async fn get_group_info(&self) -> DataAccessResult<GroupInfo<PC>>;
 }
 
+pub trait InspectService {
+    async fn list_randomness_tasks(
+        &self,
+        chain_id: usize,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> NodeResult<Vec<BLSTask<RandomnessTask>>>;
+
+    async fn get_randomness_task(
+        &self,
+        chain_id: usize,
+        randomness_task_request_id: &[u8],
+    ) -> NodeResult<BLSTask<RandomnessTask>>;
+
+    async fn list_signature_results(
+        &self,
+        chain_id: usize,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> NodeResult<Vec<BLSResultCache<RandomnessResultCache>>>;
+
+    async fn get_signature_result(
+        &self,
+        chain_id: usize,
+        randomness_task_request_id: &[u8],
+    ) -> NodeResult<BLSResultCache<RandomnessResultCache>>;
+}
+
+pub trait ConfigService {
+    /// Reloads the config from the file, and applies the changes which are safe to apply at runtime.
+    /// Nothing is applied if any of the changes requires a restart of the node.
+    async fn reload_config(
+        &mut self,
+        config_path: Option<PathBuf>,
+        dry_run: bool,
+    ) -> NodeResult<ConfigDiff>;
+}
+
+pub trait RelayedChainService {
+    /// Builds the relayed chain configured in the file, and starts its listeners and subscribers.
+    async fn load_relayed_chain(
+        &mut self,
+        chain_id: usize,
+        config_path: Option<PathBuf>,
+    ) -> NodeResult<()>;
+
+    /// Stops the listeners which take new tasks of the relayed chain, the ones fulfilling
+    /// the in-flight tasks keep running.
+    async fn stop_taking_tasks(&self, chain_id: usize) -> NodeResult<()>;
+
+    async fn resume_taking_tasks(&self, chain_id: usize) -> NodeResult<()>;
+
+    async fn get_unfulfilled_task_count(&self, chain_id: usize) -> NodeResult<usize>;
+
+    /// Stops all the listeners and subscribers of the relayed chain, and removes it from the node.
+    async fn unload_relayed_chain(&mut self, chain_id: usize) -> NodeResult<()>;
+}
+
 pub trait DKGService {
     async fn post_process_dkg(&self) -> NodeResult<()>;
 }
@@ -118,6 +229,71 @@ pub trait BLSRandomnessService<PC: Curve> {
     ) -> Result<()>;
 }
 
+pub trait BLSDecryptionService<PC: Curve> {
+    /// Returns the partial public key of the node in the group of the ciphertexts.
+    async fn get_partial_encryption_key(&self) -> Result<Vec<u8>>;
+
+    /// Computes the decryption share of the node, once the label of the ciphertext is met
+    /// at the current block height of the main chain.
+    async fn partial_decrypt(
+        &self,
+        decryption_task_request_id: Vec<u8>,
+        requester: Address,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>>;
+
+    async fn send_decryption_share(
+        &self,
+        member_id_address: Address,
+        ciphertext: Vec<u8>,
+        decryption_task_request_id: Vec<u8>,
+        decryption_share: Vec<u8>,
+    ) -> Result<()>;
+
+    fn combine_decryption_shares(
+        &self,
+        threshold: usize,
+        ciphertext: &[u8],
+        decryption_shares: &[Vec<u8>],
+    ) -> Result<Vec<u8>>;
+
+    /// Combines the decryption shares collected by the committer into the plaintext which is
+    /// returned to the requester.
+    async fn get_decryption_result(&self, decryption_task_request_id: &[u8]) -> Result<Vec<u8>>;
+}
+
+pub trait BLSBlindSignatureService<PC: Curve> {
+    async fn blind_partial_sign(
+        &self,
+        blind_signature_task_request_id: Vec<u8>,
+        requester: Address,
+        blinded_msg: &[u8],
+    ) -> Result<Vec<u8>>;
+
+    async fn send_blind_partial_sig(
+        &self,
+        member_id_address: Address,
+        blinded_msg: Vec<u8>,
+        blind_signature_task_request_id: Vec<u8>,
+        blind_partial: Vec<u8>,
+    ) -> Result<()>;
+
+    /// Aggregates the blind partial signatures with the threshold of the group, and returns
+    /// the blind signature along with the public key of the group which verifies it once
+    /// unblinded.
+    async fn aggregate_blind_partial_sigs(
+        &self,
+        blind_partials: &[Vec<u8>],
+    ) -> Result<(Vec<u8>, Vec<u8>)>;
+
+    /// Aggregates the blind partial signatures collected by the committer into the blind
+    /// signature returned to the requester, along with the public key of the group.
+    async fn get_blind_signature_result(
+        &self,
+        blind_signature_task_request_id: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>)>;
+}
+
 impl<
         PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
         S: SignatureScheme
@@ -163,9 +339,54 @@ where
         todo!()
     }
 
-    async fn shutdown_node(&self) -> NodeResult<()> {
-        // TODO shutdown gracefully
-        std::process::exit(1);
+    async fn shutdown_node(&self, force: bool) -> NodeResult<()> {
+        if self.get_shutdown_state() != ShutdownState::Running {
+            return Err(NodeError::AlreadyShuttingDown);
+        }
+
+        let dkg_status = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_dkg_status();
+
+        if matches!(dkg_status, Ok(DKGStatus::InPhase)) {
+            if !force {
+                return Err(NodeError::ShutdownDuringDKG);
+            }
+            warn!("shutting down by force during a DKG phase");
+        }
+
+        self.set_shutdown_state(ShutdownState::Draining);
+
+        // no new tasks are taken from now on
+        let listeners = self
+            .get_fixed_task_handler()
+            .read()
+            .await
+            .get_tasks()
+            .into_iter()
+            .filter(|t| matches!(t, ComponentTaskType::Listener(..)))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for listener in listeners.iter() {
+            match self
+                .get_fixed_task_handler()
+                .write()
+                .await
+                .abort(listener)
+                .await
+            {
+                Ok(()) | Err(SchedulerError::TaskNotFound) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        info!("listeners stopped, draining the in-flight tasks");
+
+        Ok(())
     }
 }
 
@@ -206,23 +427,40 @@ where
             .get_chain_id();
 
         if chain_id == main_chain_id {
+            let listener = self
+                .get_config()
+                .get_listeners()
+                .iter()
+                .find(|l| l.l_type == task_type)
+                .copied()
+                .unwrap_or_else(|| ListenerDescriptor::default(task_type));
+
             self.get_main_chain()
                 .init_listener(
                     self.get_event_queue(),
                     self.get_fixed_task_handler(),
-                    ListenerDescriptor::default(task_type),
+                    listener,
                 )
                 .await
         } else {
             if !self.contains_relayed_chain(chain_id) {
                 return Err(SchedulerError::InvalidChainId(chain_id));
             }
+            let listener = self
+                .get_config()
+                .get_relayed_chains()
+                .iter()
+                .find(|c| c.get_chain_id() == chain_id)
+                .and_then(|c| c.get_listeners().iter().find(|l| l.l_type == task_type))
+                .copied()
+                .unwrap_or_else(|| ListenerDescriptor::default(task_type));
+
             self.get_relayed_chain(chain_id)
                 .unwrap()
                 .init_listener(
                     self.get_event_queue(),
                     self.get_fixed_task_handler(),
-                    ListenerDescriptor::default(task_type),
+                    listener,
                 )
                 .await
         }
@@ -337,6 +575,11 @@ where
     }
 }
 
+type RandomnessTasksCache = Arc<RwLock<Box<dyn BLSTasksHandler<RandomnessTask>>>>;
+
+type RandomnessResultCaches =
+    Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>;
+
 impl<
         PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
         S: SignatureScheme
@@ -345,36 +588,36 @@ impl<
             + Send
             + Sync
             + 'static,
-    > DKGService for GeneralContext<PC, S>
+    > GeneralContext<PC, S>
 where
     <S as ThresholdScheme>::Error: Sync + Send,
     <S as SignatureScheme>::Error: Sync + Send,
 {
-    async fn post_process_dkg(&self) -> NodeResult<()> {
-        let client = self
+    async fn get_randomness_caches(
+        &self,
+        chain_id: usize,
+    ) -> NodeResult<(RandomnessTasksCache, RandomnessResultCaches)> {
+        let main_chain_id = self
             .get_main_chain()
             .get_chain_identity()
             .read()
             .await
-            .build_controller_client();
-
-        let group_index = self
-            .get_main_chain()
-            .get_group_cache()
-            .read()
-            .await
-            .get_index()?;
-
-        let group_epoch = self
-            .get_main_chain()
-            .get_group_cache()
-            .read()
-            .await
-            .get_epoch()?;
-
-        client.post_process_dkg(group_index, group_epoch).await?;
+            .get_chain_id();
 
-        Ok(())
+        if chain_id == main_chain_id {
+            Ok((
+                self.get_main_chain().get_randomness_tasks_cache(),
+                self.get_main_chain().get_randomness_result_cache(),
+            ))
+        } else {
+            let relayed_chain = self
+                .get_relayed_chain(chain_id)
+                .ok_or(SchedulerError::InvalidChainId(chain_id))?;
+            Ok((
+                relayed_chain.get_randomness_tasks_cache(),
+                relayed_chain.get_randomness_result_cache(),
+            ))
+        }
     }
 }
 
@@ -386,160 +629,1173 @@ impl<
             + Send
             + Sync
             + 'static,
-    > BLSRandomnessService<PC> for GeneralContext<PC, S>
+    > InspectService for GeneralContext<PC, S>
 where
     <S as ThresholdScheme>::Error: Sync + Send,
     <S as SignatureScheme>::Error: Sync + Send,
 {
-    async fn partial_sign(
+    async fn list_randomness_tasks(
         &self,
-        randomness_task_request_id: Vec<u8>,
-        threshold: usize,
-        msg: &[u8],
-    ) -> Result<Vec<u8>> {
-        let id_address = self
-            .get_main_chain()
-            .get_node_cache()
-            .read()
-            .await
-            .get_id_address()?;
-
-        let partial_signature = SimpleBLSCore::<PC, S>::partial_sign(
-            self.get_main_chain()
-                .get_group_cache()
-                .read()
-                .await
-                .get_secret_share()?,
-            msg,
-        )?;
+        chain_id: usize,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> NodeResult<Vec<BLSTask<RandomnessTask>>> {
+        let (randomness_tasks_cache, _) = self.get_randomness_caches(chain_id).await?;
 
-        if self
-            .get_main_chain()
-            .get_group_cache()
+        let tasks = randomness_tasks_cache
             .read()
             .await
-            .is_committer(id_address)?
-        {
-            let contained_res = self
-                .get_main_chain()
-                .get_randomness_result_cache()
-                .read()
-                .await
-                .contains(&randomness_task_request_id)
-                .await?;
-
-            if !contained_res {
-                let group_index = self
-                    .get_main_chain()
-                    .get_group_cache()
-                    .read()
-                    .await
-                    .get_index()?;
-
-                let task = self
-                    .get_main_chain()
-                    .get_randomness_tasks_cache()
-                    .read()
-                    .await
-                    .get(&randomness_task_request_id)
-                    .await?;
-
-                self.get_main_chain()
-                    .get_randomness_result_cache()
-                    .write()
-                    .await
-                    .add(group_index, task, msg.to_vec(), threshold)
-                    .await?;
-            }
-
-            self.get_main_chain()
-                .get_randomness_result_cache()
-                .write()
-                .await
-                .add_partial_signature(
-                    randomness_task_request_id.clone(),
-                    id_address,
-                    partial_signature.clone(),
-                )
-                .await?;
-        }
+            .list(is_handled, block_height_range, page)
+            .await?;
 
-        Ok(partial_signature)
+        Ok(tasks)
     }
 
-    fn aggregate_partial_sigs(
+    async fn get_randomness_task(
         &self,
-        threshold: usize,
-        partial_sigs: &[Vec<u8>],
-    ) -> Result<Vec<u8>> {
-        SimpleBLSCore::<PC, S>::aggregate(threshold, partial_sigs)
-    }
+        chain_id: usize,
+        randomness_task_request_id: &[u8],
+    ) -> NodeResult<BLSTask<RandomnessTask>> {
+        let (randomness_tasks_cache, _) = self.get_randomness_caches(chain_id).await?;
 
-    fn verify_sig(&self, public: &PC::Point, msg: &[u8], sig: &[u8]) -> Result<()> {
-        SimpleBLSCore::<PC, S>::verify(public, msg, sig)
-    }
+        let randomness_tasks_cache = randomness_tasks_cache.read().await;
+        let task = randomness_tasks_cache
+            .get(randomness_task_request_id)
+            .await?;
+        let state = randomness_tasks_cache
+            .is_handled(randomness_task_request_id)
+            .await?;
 
-    fn verify_partial_sigs(
-        &self,
-        publics: &[PC::Point],
-        msg: &[u8],
-        partial_sigs: &[&[u8]],
-    ) -> Result<()> {
-        SimpleBLSCore::<PC, S>::verify_partial_sigs(publics, msg, partial_sigs)
+        Ok(BLSTask { task, state })
     }
 
-    async fn send_partial_sig(
+    async fn list_signature_results(
         &self,
         chain_id: usize,
-        member_id_address: Address,
-        msg: Vec<u8>,
-        randomness_task_request_id: Vec<u8>,
-        partial: Vec<u8>,
-    ) -> Result<()> {
-        let id_address = self
-            .get_main_chain()
-            .get_node_cache()
-            .read()
-            .await
-            .get_id_address()?;
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> NodeResult<Vec<BLSResultCache<RandomnessResultCache>>> {
+        let (_, randomness_result_caches) = self.get_randomness_caches(chain_id).await?;
 
-        let committer_id_address = self
-            .get_main_chain()
-            .get_group_cache()
+        let results = randomness_result_caches
             .read()
             .await
-            .get_member(member_id_address)?
-            .id_address;
+            .list(state, block_height_range, page)
+            .await?;
 
-        let endpoint = self
-            .get_main_chain()
-            .get_group_cache()
+        Ok(results)
+    }
+
+    async fn get_signature_result(
+        &self,
+        chain_id: usize,
+        randomness_task_request_id: &[u8],
+    ) -> NodeResult<BLSResultCache<RandomnessResultCache>> {
+        let (_, randomness_result_caches) = self.get_randomness_caches(chain_id).await?;
+
+        let result = randomness_result_caches
             .read()
             .await
-            .get_member(member_id_address)?
-            .rpc_endpoint
-            .as_ref()
-            .unwrap()
-            .to_string();
-
-        let commit_partial_signature_retry_descriptor = ExponentialBackoffRetryDescriptor {
-            base: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_BASE,
-            factor: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_FACTOR,
-            max_attempts: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_MAX_ATTEMPTS,
-            use_jitter: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER,
-        };
+            .get(randomness_task_request_id)
+            .await?;
 
-        let committer_client = GeneralCommitterClient::build(
-            id_address,
-            committer_id_address,
-            endpoint,
-            commit_partial_signature_retry_descriptor,
-        );
+        Ok(result)
+    }
+}
 
-        committer_client
-            .commit_partial_signature(
-                chain_id,
-                BLSTaskType::Randomness,
+impl<
+        PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+        S: SignatureScheme
+            + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    > ConfigService for GeneralContext<PC, S>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    async fn reload_config(
+        &mut self,
+        config_path: Option<PathBuf>,
+        dry_run: bool,
+    ) -> NodeResult<ConfigDiff> {
+        let config_path = config_path
+            .or_else(|| self.get_config().get_source_path().cloned())
+            .ok_or_else(|| {
+                ConfigError::ReadingFileError("the node is not started from a file".to_string())
+            })?;
+
+        let new_config = Config::try_load(config_path)?;
+
+        let diff = self.get_config().diff(&new_config);
+
+        if dry_run || diff.is_empty() || !diff.is_applicable() {
+            return Ok(diff);
+        }
+
+        // connect to the changed provider endpoints before applying anything,
+        // so that an unreachable endpoint leaves the running config untouched
+        let mut providers = vec![];
+        for relayed_chain_config in new_config.get_relayed_chains() {
+            let chain_id = relayed_chain_config.get_chain_id();
+            let chain_identity = self
+                .get_relayed_chain(chain_id)
+                .ok_or(SchedulerError::InvalidChainId(chain_id))?
+                .get_chain_identity();
+            let chain_identity = chain_identity.read().await;
+
+            let provider_endpoint = relayed_chain_config.get_provider_endpoint();
+            if chain_identity.get_provider_endpoint() != provider_endpoint {
+                let provider = chain_identity.connect_provider(provider_endpoint).await?;
+                providers.push((chain_id, provider_endpoint.to_string(), provider));
+            }
+        }
+
+        for (chain_id, provider_endpoint, provider) in providers {
+            self.get_relayed_chain(chain_id)
+                .ok_or(SchedulerError::InvalidChainId(chain_id))?
+                .get_chain_identity()
+                .write()
+                .await
+                .set_provider(provider_endpoint, provider);
+        }
+
+        for relayed_chain_config in new_config.get_relayed_chains() {
+            let chain_id = relayed_chain_config.get_chain_id();
+            let chain_identity = self
+                .get_relayed_chain(chain_id)
+                .ok_or(SchedulerError::InvalidChainId(chain_id))?
+                .get_chain_identity();
+            let mut chain_identity = chain_identity.write().await;
+
+            let time_limits = relayed_chain_config.get_time_limits();
+            chain_identity.set_contract_transaction_retry_descriptor(
+                time_limits.contract_transaction_retry_descriptor,
+            );
+            chain_identity
+                .set_contract_view_retry_descriptor(time_limits.contract_view_retry_descriptor);
+        }
+
+        {
+            let time_limits = new_config.get_time_limits();
+            let chain_identity = self.get_main_chain().get_chain_identity();
+            let mut chain_identity = chain_identity.write().await;
+            chain_identity.set_contract_transaction_retry_descriptor(
+                time_limits.contract_transaction_retry_descriptor,
+            );
+            chain_identity
+                .set_contract_view_retry_descriptor(time_limits.contract_view_retry_descriptor);
+        }
+
+        restart_changed_listeners::<PC, S, _>(
+            self.get_main_chain(),
+            self.get_config().get_listeners(),
+            new_config.get_listeners(),
+            self.get_event_queue(),
+            self.get_fixed_task_handler(),
+        )
+        .await?;
+
+        for relayed_chain_config in new_config.get_relayed_chains() {
+            let chain_id = relayed_chain_config.get_chain_id();
+            let old_listeners = self
+                .get_config()
+                .get_relayed_chains()
+                .iter()
+                .find(|c| c.get_chain_id() == chain_id)
+                .map(|c| c.get_listeners().as_slice())
+                .unwrap_or_default();
+
+            restart_changed_listeners::<PC, S, _>(
+                self.get_relayed_chain(chain_id)
+                    .ok_or(SchedulerError::InvalidChainId(chain_id))?
+                    .as_ref(),
+                old_listeners,
+                relayed_chain_config.get_listeners(),
+                self.get_event_queue(),
+                self.get_fixed_task_handler(),
+            )
+            .await?;
+        }
+
+        let log_level = new_config.get_logger_descriptor().get_log_level();
+        if self.get_config().get_logger_descriptor().get_log_level() != log_level {
+            set_log_level(log_level);
+        }
+
+        for change in diff.applicable.iter() {
+            info!("config reloaded, {}", change);
+        }
+
+        self.set_config(new_config);
+
+        Ok(diff)
+    }
+}
+
+impl<
+        PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+        S: SignatureScheme
+            + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    > RelayedChainService for GeneralContext<PC, S>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    async fn load_relayed_chain(
+        &mut self,
+        chain_id: usize,
+        config_path: Option<PathBuf>,
+    ) -> NodeResult<()> {
+        if self.contains_relayed_chain(chain_id) {
+            return Err(NodeError::RepeatedChainId);
+        }
+
+        let config_path = config_path
+            .or_else(|| self.get_config().get_source_path().cloned())
+            .ok_or_else(|| {
+                ConfigError::ReadingFileError("the node is not started from a file".to_string())
+            })?;
+
+        let new_config = Config::try_load(config_path)?;
+
+        let relayed_chain_config = new_config
+            .get_relayed_chains()
+            .iter()
+            .find(|c| c.get_chain_id() == chain_id)
+            .cloned()
+            .ok_or(ConfigError::InvalidChainId(chain_id))?;
+
+        // relayed chains sharing the same tables would overwrite each other's tasks,
+        // unless the tables are keyed by the chain id
+        let chain_profile = new_config
+            .get_chain_profiles()
+            .iter()
+            .find(|p| p.chain_id == chain_id)
+            .cloned();
+        let storage_namespace = chain_profile
+            .as_ref()
+            .or(ChainProfileRegistry::builtin().get(chain_id))
+            .map(|p| p.storage_namespace)
+            .ok_or_else(|| {
+                ConfigError::InvalidConfig(format!(
+                    "relayed chain {} has no chain profile",
+                    chain_id
+                ))
+            })?;
+        for running_chain_id in self.get_supported_relayed_chains() {
+            if !storage_namespace.is_shared()
+                && get_chain_profile(running_chain_id).map(|p| p.storage_namespace)
+                    == Some(storage_namespace)
+            {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "storage namespace {:?} is already in use by relayed chain {}",
+                    storage_namespace, running_chain_id
+                ))
+                .into());
+            }
+        }
+        if let Some(chain_profile) = chain_profile.as_ref() {
+            register_chain_profiles(std::slice::from_ref(chain_profile));
+        }
+
+        let db = self.get_db().ok_or(NodeError::DBNotAvailable)?;
+
+        let randomness_tasks_cache =
+            Arc::new(RwLock::new(db.build_randomness_tasks_cache(chain_id)?));
+
+        let randomness_result_cache = Arc::new(RwLock::new(
+            db.build_randomness_result_cache(chain_id).await?,
+        ));
+
+        let wallet = self
+            .get_main_chain()
+            .get_chain_identity()
+            .read()
+            .await
+            .get_client()
+            .inner()
+            .signer()
+            .clone();
+
+        let provider = Arc::new(
+            Provider::<Ws>::connect_with_reconnects(
+                relayed_chain_config.get_provider_endpoint(),
+                DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
+            )
+            .await?
+            .interval(Duration::from_millis(
+                relayed_chain_config
+                    .get_time_limits()
+                    .provider_polling_interval_millis,
+            )),
+        );
+
+        let relayed_chain_identity = GeneralRelayedChainIdentity::new(
+            chain_id,
+            wallet,
+            provider,
+            relayed_chain_config.get_provider_endpoint().to_string(),
+            relayed_chain_config
+                .get_controller_oracle_address()
+                .parse()
+                .map_err(|_| NodeError::AddressFormatError)?,
+            relayed_chain_config
+                .get_adapter_address()
+                .parse()
+                .map_err(|_| NodeError::AddressFormatError)?,
+            relayed_chain_config
+                .get_time_limits()
+                .contract_transaction_retry_descriptor,
+            relayed_chain_config
+                .get_time_limits()
+                .contract_view_retry_descriptor,
+            relayed_chain_config.get_private_submission().cloned(),
+        );
+
+        let relayed_chain = GeneralRelayedChain::<PC, S>::new(
+            relayed_chain_config.get_description().to_string(),
+            relayed_chain_identity,
+            self.get_main_chain().get_node_cache(),
+            self.get_main_chain().get_group_cache(),
+            randomness_tasks_cache,
+            randomness_result_cache,
+            Arc::new(ProfitabilityFulfillmentPolicy::from(
+                *relayed_chain_config.get_fulfillment_policy(),
+            )),
+            *relayed_chain_config.get_time_limits(),
+            relayed_chain_config.get_listeners().to_vec(),
+        );
+
+        if let Err(e) = relayed_chain.init_components(self).await {
+            // the listeners started before the failure are left without the chain
+            abort_relayed_chain_components(
+                chain_id,
+                self.get_event_queue(),
+                self.get_fixed_task_handler(),
+            )
+            .await?;
+            return Err(e.into());
+        }
+
+        self.add_relayed_chain(Box::new(relayed_chain))?;
+
+        self.get_config_mut()
+            .add_relayed_chain(relayed_chain_config, chain_profile);
+
+        info!("relayed chain {} added", chain_id);
+
+        Ok(())
+    }
+
+    async fn stop_taking_tasks(&self, chain_id: usize) -> NodeResult<()> {
+        if !self.contains_relayed_chain(chain_id) {
+            return Err(SchedulerError::InvalidChainId(chain_id).into());
+        }
+
+        for l_type in TASK_TAKING_LISTENER_TYPES {
+            match self
+                .get_fixed_task_handler()
+                .write()
+                .await
+                .abort(&ComponentTaskType::Listener(chain_id, l_type))
+                .await
+            {
+                Ok(()) | Err(SchedulerError::TaskNotFound) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn resume_taking_tasks(&self, chain_id: usize) -> NodeResult<()> {
+        let relayed_chain = self
+            .get_relayed_chain(chain_id)
+            .ok_or(SchedulerError::InvalidChainId(chain_id))?;
+
+        let listeners = self
+            .get_config()
+            .get_relayed_chains()
+            .iter()
+            .find(|c| c.get_chain_id() == chain_id)
+            .map(|c| c.get_listeners().as_slice())
+            .unwrap_or_default();
+
+        for listener in listeners
+            .iter()
+            .filter(|l| TASK_TAKING_LISTENER_TYPES.contains(&l.l_type))
+        {
+            match relayed_chain
+                .init_listener(
+                    self.get_event_queue(),
+                    self.get_fixed_task_handler(),
+                    *listener,
+                )
+                .await
+            {
+                Ok(()) | Err(SchedulerError::TaskAlreadyExisted) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_unfulfilled_task_count(&self, chain_id: usize) -> NodeResult<usize> {
+        let relayed_chain = self
+            .get_relayed_chain(chain_id)
+            .ok_or(SchedulerError::InvalidChainId(chain_id))?;
+
+        let unfulfilled_count = relayed_chain
+            .get_randomness_result_cache()
+            .read()
+            .await
+            .get_unfulfilled_count()
+            .await?;
+
+        Ok(unfulfilled_count)
+    }
+
+    async fn unload_relayed_chain(&mut self, chain_id: usize) -> NodeResult<()> {
+        if !self.contains_relayed_chain(chain_id) {
+            return Err(SchedulerError::InvalidChainId(chain_id).into());
+        }
+
+        abort_relayed_chain_components(
+            chain_id,
+            self.get_event_queue(),
+            self.get_fixed_task_handler(),
+        )
+        .await?;
+
+        self.remove_relayed_chain(chain_id)?;
+
+        self.get_config_mut().remove_relayed_chain(chain_id);
+
+        info!("relayed chain {} removed", chain_id);
+
+        Ok(())
+    }
+}
+
+/// Removes the relayed chain from the running node. The listeners taking new tasks are stopped first,
+/// and the chain is removed after its in-flight tasks are fulfilled. If they are not drained in time,
+/// the listeners are restarted and the chain is kept.
+pub async fn drain_and_remove_relayed_chain<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    S: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: Arc<RwLock<GeneralContext<PC, S>>>,
+    chain_id: usize,
+    drain_timeout: Duration,
+) -> NodeResult<()>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    context.read().await.stop_taking_tasks(chain_id).await?;
+
+    let deadline = Instant::now() + drain_timeout;
+
+    // the context is not locked while waiting, the committer server still needs it
+    loop {
+        let unfulfilled_count = context
+            .read()
+            .await
+            .get_unfulfilled_task_count(chain_id)
+            .await?;
+
+        if unfulfilled_count == 0 {
+            break;
+        }
+
+        if Instant::now() >= deadline {
+            context.read().await.resume_taking_tasks(chain_id).await?;
+            return Err(NodeError::RelayedChainNotDrained(
+                chain_id,
+                unfulfilled_count,
+            ));
+        }
+
+        info!(
+            "waiting for {} unfulfilled tasks of relayed chain {} to be drained",
+            unfulfilled_count, chain_id
+        );
+
+        tokio::time::sleep(Duration::from_millis(
+            RELAYED_CHAIN_DRAIN_CHECK_INTERVAL_MILLIS,
+        ))
+        .await;
+    }
+
+    context.write().await.unload_relayed_chain(chain_id).await
+}
+
+/// Drains the tasks of the node which has stopped taking new ones, and stops it.
+/// The dynamic tasks are given the drain timeout to finish, and the fulfillments which are cut off
+/// or deferred are recorded in the database to be resumed after restart. Then the committer server
+/// stops after its in-flight requests, and the database is flushed.
+pub async fn drain_and_stop<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    S: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: Arc<RwLock<GeneralContext<PC, S>>>,
+    drain_timeout: Duration,
+) where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    let ts = context.read().await.get_dynamic_task_handler();
+
+    let deadline = Instant::now() + drain_timeout;
+
+    // neither the context nor the scheduler is locked while waiting,
+    // the committer server and the running tasks still need them
+    loop {
+        let running_task_count = ts.read().await.get_running_task_count();
+
+        if running_task_count == 0 {
+            info!("all the in-flight tasks are drained");
+            break;
+        }
+
+        if Instant::now() >= deadline {
+            warn!(
+                "{} in-flight tasks are not drained in time and will be cut off",
+                running_task_count
+            );
+            break;
+        }
+
+        info!(
+            "waiting for {} in-flight tasks to be drained",
+            running_task_count
+        );
+
+        tokio::time::sleep(Duration::from_millis(SHUTDOWN_DRAIN_CHECK_INTERVAL_MILLIS)).await;
+    }
+
+    record_unfulfilled_fulfillments(&context).await;
+
+    context
+        .read()
+        .await
+        .set_shutdown_state(ShutdownState::Closing);
+
+    let committer_server = context
+        .read()
+        .await
+        .get_fixed_task_handler()
+        .write()
+        .await
+        .take(&ComponentTaskType::RpcServer(RpcServerType::Committer));
+
+    if let Ok(committer_server) = committer_server {
+        if tokio::time::timeout(drain_timeout, committer_server)
+            .await
+            .is_err()
+        {
+            warn!("the committer server is not stopped in time");
+        }
+    }
+
+    if let Some(db) = context.read().await.get_db() {
+        match db.close().await {
+            Ok(()) => info!("database flushed"),
+            Err(e) => error!("failed to flush the database: {}", e),
+        }
+    }
+
+    context
+        .read()
+        .await
+        .set_shutdown_state(ShutdownState::Stopped);
+
+    info!("node stopped");
+}
+
+/// Records the fulfillments which are left in Committing state, either cut off before their
+/// transactions are confirmed or deferred by the fulfillment policy, as the deferred queue lives
+/// in memory. They are checked against the adapter: the ones no longer pending are marked as
+/// committed, and the others are set back to NotCommitted to be aggregated and fulfilled again
+/// after restart.
+async fn record_unfulfilled_fulfillments<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    S: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: &RwLock<GeneralContext<PC, S>>,
+) where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    // the context is released before the calls to the adapters
+    let chains = {
+        let context = context.read().await;
+
+        let main_chain = context.get_main_chain();
+
+        let id_address = match main_chain.get_node_cache().read().await.get_id_address() {
+            Ok(id_address) => id_address,
+            Err(e) => {
+                error!(
+                    "failed to record the unfulfilled results without the id address: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        let mut chains = vec![(
+            main_chain.id(),
+            main_chain
+                .get_chain_identity()
+                .read()
+                .await
+                .build_adapter_client(id_address),
+            main_chain.get_randomness_result_cache(),
+            main_chain.get_deferred_fulfillment_queue(),
+        )];
+
+        for chain_id in context.get_supported_relayed_chains() {
+            if let Some(relayed_chain) = context.get_relayed_chain(chain_id) {
+                chains.push((
+                    chain_id,
+                    relayed_chain
+                        .get_chain_identity()
+                        .read()
+                        .await
+                        .build_adapter_client(id_address),
+                    relayed_chain.get_randomness_result_cache(),
+                    relayed_chain.get_deferred_fulfillment_queue(),
+                ));
+            }
+        }
+
+        chains
+    };
+
+    for (chain_id, client, randomness_result_cache, deferred_fulfillment_queue) in chains {
+        let committing_results = match randomness_result_cache
+            .read()
+            .await
+            .list(
+                Some(BLSResultCacheState::Committing),
+                BlockHeightRange::default(),
+                ListPage::default(),
+            )
+            .await
+        {
+            Ok(committing_results) => committing_results,
+            Err(e) => {
+                error!(
+                    "failed to get the unfulfilled results of chain {}: {}",
+                    chain_id, e
+                );
+                continue;
+            }
+        };
+
+        if committing_results.is_empty() {
+            continue;
+        }
+
+        let request_ids = committing_results
+            .iter()
+            .map(|r| r.result_cache.randomness_task.request_id.clone())
+            .collect::<Vec<_>>();
+
+        let pendings = match client.are_tasks_pending(&request_ids).await {
+            Ok(pendings) => pendings,
+            Err(e) => {
+                // they are still reset to NotCommitted when the result cache is built on startup
+                error!(
+                    "failed to check the {} unfulfilled results of chain {}, they will be resumed after restart: {}",
+                    request_ids.len(),
+                    chain_id,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let mut deferred_fulfillment_queue = deferred_fulfillment_queue.write().await;
+
+        for (result, is_pending) in committing_results.into_iter().zip(pendings) {
+            let randomness_task = result.result_cache.randomness_task;
+
+            let was_deferred = deferred_fulfillment_queue
+                .remove(&randomness_task.request_id)
+                .is_some();
+
+            // a deferred fulfillment has never been sent, so the task is fulfilled by others
+            let state = match (is_pending, was_deferred) {
+                (true, _) => BLSResultCacheState::NotCommitted,
+                (false, false) => BLSResultCacheState::Committed,
+                (false, true) => BLSResultCacheState::CommittedByOthers,
+            };
+
+            if let Err(e) = randomness_result_cache
+                .write()
+                .await
+                .update_commit_result(&randomness_task.request_id, state)
+                .await
+            {
+                error!(
+                    "failed to record the unfulfilled result {} of chain {}: {}",
+                    format!("0x{}", hex::encode(&randomness_task.request_id)),
+                    chain_id,
+                    e
+                );
+                continue;
+            }
+
+            let message = if is_pending {
+                format!(
+                    "Randomness fulfillment recorded to be resumed after restart. was_deferred: {}",
+                    was_deferred
+                )
+            } else {
+                format!(
+                    "Randomness task fulfilled before the node stopped. was_deferred: {}",
+                    was_deferred
+                )
+            };
+
+            warn!(
+                "{}",
+                record_payload(build_task_related_payload(
+                    LogType::FulfillmentRecorded,
+                    &message,
+                    chain_id,
+                    &randomness_task.request_id,
+                    TaskType::BLS(BLSTaskType::Randomness),
+                    json!(randomness_task),
+                    None,
+                ))
+            );
+        }
+    }
+}
+
+/// Shuts down the node gracefully on SIGTERM or ctrl-c. If the node is in a DKG phase,
+/// the shutdown is refused, and a second signal shuts it down by force.
+pub async fn shutdown_on_signal<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    S: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: Arc<RwLock<GeneralContext<PC, S>>>,
+) where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    #[cfg(unix)]
+    let mut terminate =
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(terminate) => Some(terminate),
+            Err(e) => {
+                error!("failed to listen to SIGTERM: {}", e);
+                None
+            }
+        };
+
+    let mut force = false;
+
+    loop {
+        #[cfg(unix)]
+        let terminated = async {
+            match terminate.as_mut() {
+                Some(terminate) => {
+                    terminate.recv().await;
+                }
+                None => futures::future::pending::<()>().await,
+            }
+        };
+        #[cfg(not(unix))]
+        let terminated = futures::future::pending::<()>();
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminated => {}
+        }
+
+        let drain_timeout = Duration::from_millis(
+            context
+                .read()
+                .await
+                .get_config()
+                .get_time_limits()
+                .shutdown_drain_timeout_millis,
+        );
+
+        match context.read().await.shutdown_node(force).await {
+            Ok(()) => {
+                tokio::spawn(drain_and_stop(context.clone(), drain_timeout));
+            }
+            Err(NodeError::ShutdownDuringDKG) => {
+                error!(
+                    "the node is in a DKG phase, send the signal again to shut it down by force"
+                );
+                force = true;
+            }
+            Err(NodeError::AlreadyShuttingDown) => info!("the node is already shutting down"),
+            Err(e) => error!("failed to shut down the node: {}", e),
+        }
+    }
+}
+
+/// Aborts the listeners of the relayed chain and drops its subscribers.
+async fn abort_relayed_chain_components(
+    chain_id: usize,
+    eq: Arc<RwLock<EventQueue>>,
+    fs: Arc<RwLock<SimpleFixedTaskScheduler>>,
+) -> SchedulerResult<()> {
+    for l_type in RELAYED_CHAIN_LISTENER_TYPES {
+        match fs
+            .write()
+            .await
+            .abort(&ComponentTaskType::Listener(chain_id, l_type))
+            .await
+        {
+            Ok(()) | Err(SchedulerError::TaskNotFound) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    eq.write().await.unsubscribe_chain(chain_id);
+
+    Ok(())
+}
+
+/// Reloads the config from the file the node is started with, whenever the node receives SIGHUP.
+#[cfg(unix)]
+pub async fn reload_config_on_hangup<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    S: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: Arc<RwLock<GeneralContext<PC, S>>>,
+) where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            error!(
+                "failed to listen to SIGHUP, config reloading is disabled: {}",
+                e
+            );
+            return;
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        match context.write().await.reload_config(None, false).await {
+            Ok(diff) if !diff.is_applicable() => {
+                for change in diff.rejected.iter() {
+                    error!(
+                        "config reload rejected, restart the node to apply {}",
+                        change
+                    );
+                }
+            }
+            Ok(diff) if diff.is_empty() => info!("config reloaded without changes"),
+            Ok(_) => {}
+            Err(e) => error!("config reload failed: {}", e),
+        }
+    }
+}
+
+/// Restarts the listeners of the chain whose descriptors have changed,
+/// starts the added ones and stops the removed ones.
+async fn restart_changed_listeners<PC, S, C>(
+    chain: &C,
+    old_listeners: &[ListenerDescriptor],
+    new_listeners: &[ListenerDescriptor],
+    eq: Arc<RwLock<EventQueue>>,
+    fs: Arc<RwLock<SimpleFixedTaskScheduler>>,
+) -> SchedulerResult<()>
+where
+    PC: Curve,
+    S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>,
+    C: Chain<PC, S> + Sync + ?Sized,
+{
+    let listener_types = old_listeners
+        .iter()
+        .chain(new_listeners)
+        .map(|l| l.l_type)
+        .collect::<HashSet<_>>();
+
+    for l_type in listener_types {
+        // the node activation listener is not configured by the listener descriptors
+        if l_type == ListenerType::ScheduleNodeActivation {
+            continue;
+        }
+
+        let old_listener = old_listeners.iter().find(|l| l.l_type == l_type);
+        let new_listener = new_listeners.iter().find(|l| l.l_type == l_type);
+        if old_listener == new_listener {
+            continue;
+        }
+
+        match fs
+            .write()
+            .await
+            .abort(&ComponentTaskType::Listener(chain.id(), l_type))
+            .await
+        {
+            Ok(()) | Err(SchedulerError::TaskNotFound) => {}
+            Err(e) => return Err(e),
+        }
+
+        if let Some(listener) = new_listener {
+            match chain.init_listener(eq.clone(), fs.clone(), *listener).await {
+                // the relayed chains only run a part of the listeners
+                Ok(()) | Err(SchedulerError::UnsupportedListenerType(..)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl<
+        PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+        S: SignatureScheme
+            + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    > DKGService for GeneralContext<PC, S>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    async fn post_process_dkg(&self) -> NodeResult<()> {
+        let client = self
+            .get_main_chain()
+            .get_chain_identity()
+            .read()
+            .await
+            .build_controller_client();
+
+        let group_index = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_index()?;
+
+        let group_epoch = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_epoch()?;
+
+        client.post_process_dkg(group_index, group_epoch).await?;
+
+        Ok(())
+    }
+}
+
+impl<
+        PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+        S: SignatureScheme
+            + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    > BLSRandomnessService<PC> for GeneralContext<PC, S>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    async fn partial_sign(
+        &self,
+        randomness_task_request_id: Vec<u8>,
+        threshold: usize,
+        msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let partial_signature = SimpleBLSCore::<PC, S>::partial_sign(
+            self.get_main_chain()
+                .get_group_cache()
+                .read()
+                .await
+                .get_secret_share()?,
+            msg,
+        )?;
+
+        if self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .is_committer(id_address)?
+        {
+            let contained_res = self
+                .get_main_chain()
+                .get_randomness_result_cache()
+                .read()
+                .await
+                .contains(&randomness_task_request_id)
+                .await?;
+
+            if !contained_res {
+                let group_index = self
+                    .get_main_chain()
+                    .get_group_cache()
+                    .read()
+                    .await
+                    .get_index()?;
+
+                let task = self
+                    .get_main_chain()
+                    .get_randomness_tasks_cache()
+                    .read()
+                    .await
+                    .get(&randomness_task_request_id)
+                    .await?;
+
+                self.get_main_chain()
+                    .get_randomness_result_cache()
+                    .write()
+                    .await
+                    .add(group_index, task, msg.to_vec(), threshold)
+                    .await?;
+            }
+
+            self.get_main_chain()
+                .get_randomness_result_cache()
+                .write()
+                .await
+                .add_partial_signature(
+                    randomness_task_request_id.clone(),
+                    id_address,
+                    partial_signature.clone(),
+                )
+                .await?;
+        }
+
+        Ok(partial_signature)
+    }
+
+    fn aggregate_partial_sigs(
+        &self,
+        threshold: usize,
+        partial_sigs: &[Vec<u8>],
+    ) -> Result<Vec<u8>> {
+        SimpleBLSCore::<PC, S>::aggregate(threshold, partial_sigs)
+    }
+
+    fn verify_sig(&self, public: &PC::Point, msg: &[u8], sig: &[u8]) -> Result<()> {
+        SimpleBLSCore::<PC, S>::verify(public, msg, sig)
+    }
+
+    fn verify_partial_sigs(
+        &self,
+        publics: &[PC::Point],
+        msg: &[u8],
+        partial_sigs: &[&[u8]],
+    ) -> Result<()> {
+        SimpleBLSCore::<PC, S>::verify_partial_sigs(publics, msg, partial_sigs)
+    }
+
+    async fn send_partial_sig(
+        &self,
+        chain_id: usize,
+        member_id_address: Address,
+        msg: Vec<u8>,
+        randomness_task_request_id: Vec<u8>,
+        partial: Vec<u8>,
+    ) -> Result<()> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let committer_id_address = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_member(member_id_address)?
+            .id_address;
+
+        let endpoint = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_member(member_id_address)?
+            .rpc_endpoint
+            .as_ref()
+            .unwrap()
+            .to_string();
+
+        let commit_partial_signature_retry_descriptor = ExponentialBackoffRetryDescriptor {
+            base: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_BASE,
+            factor: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_FACTOR,
+            max_attempts: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_MAX_ATTEMPTS,
+            use_jitter: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER,
+        };
+
+        let committer_client = GeneralCommitterClient::build(
+            id_address,
+            committer_id_address,
+            endpoint,
+            commit_partial_signature_retry_descriptor,
+        );
+
+        committer_client
+            .commit_partial_signature(
+                chain_id,
+                BLSTaskType::Randomness,
                 randomness_task_request_id,
                 msg,
                 partial,
@@ -549,14 +1805,308 @@ where
         Ok(())
     }
 
-    async fn fulfill_randomness(
+    async fn fulfill_randomness(
+        &self,
+        chain_id: usize,
+        group_index: usize,
+        randomness_task_request_id: Vec<u8>,
+        sig: Vec<u8>,
+        partial_sigs: HashMap<Address, Vec<u8>>,
+    ) -> Result<()> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let main_chain_id = self
+            .get_main_chain()
+            .get_chain_identity()
+            .read()
+            .await
+            .get_chain_id();
+
+        let partial_signatures = partial_sigs
+            .iter()
+            .map(|(addr, partial)| {
+                let eval: Eval<Vec<u8>> = bincode::deserialize(partial)?;
+                let partial = PartialSignature {
+                    index: eval.index as usize,
+                    signature: eval.value,
+                };
+                Ok((*addr, partial))
+            })
+            .collect::<Result<_, NodeError>>()?;
+
+        let (client, randomness_task) = if chain_id == main_chain_id {
+            (
+                self.get_main_chain()
+                    .get_chain_identity()
+                    .read()
+                    .await
+                    .build_adapter_client(id_address),
+                self.get_main_chain()
+                    .get_randomness_tasks_cache()
+                    .read()
+                    .await
+                    .get(&randomness_task_request_id)
+                    .await?,
+            )
+        } else {
+            if !self.contains_relayed_chain(chain_id) {
+                return Err(SchedulerError::InvalidChainId(chain_id).into());
+            }
+            (
+                self.get_relayed_chain(chain_id)
+                    .unwrap()
+                    .get_chain_identity()
+                    .read()
+                    .await
+                    .build_adapter_client(id_address),
+                self.get_relayed_chain(chain_id)
+                    .unwrap()
+                    .get_randomness_tasks_cache()
+                    .read()
+                    .await
+                    .get(&randomness_task_request_id)
+                    .await?,
+            )
+        };
+
+        let gas_estimate = client
+            .estimate_fulfill_randomness(group_index, &randomness_task, &sig, &partial_signatures)
+            .await?;
+
+        client
+            .fulfill_randomness(
+                group_index,
+                randomness_task,
+                sig,
+                partial_signatures,
+                gas_estimate.gas_limit,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<
+        PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+        S: SignatureScheme
+            + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    > BLSDecryptionService<PC> for GeneralContext<PC, S>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    async fn get_partial_encryption_key(&self) -> Result<Vec<u8>> {
+        self.get_main_chain()
+            .get_decryption_core()
+            .partial_encryption_key(
+                self.get_main_chain()
+                    .get_group_cache()
+                    .read()
+                    .await
+                    .get_secret_share()?,
+            )
+    }
+
+    async fn partial_decrypt(
+        &self,
+        decryption_task_request_id: Vec<u8>,
+        requester: Address,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let current_block_height = self
+            .get_main_chain()
+            .get_block_cache()
+            .read()
+            .await
+            .get_block_height();
+
+        let decryption_share = self
+            .get_main_chain()
+            .get_decryption_core()
+            .partial_decrypt(
+                self.get_main_chain()
+                    .get_group_cache()
+                    .read()
+                    .await
+                    .get_secret_share()?,
+                ciphertext,
+                current_block_height,
+            )?;
+
+        // the request is recorded by every member, as there is no request event on chain yet
+        let contained_task = self
+            .get_main_chain()
+            .get_decryption_tasks_cache()
+            .read()
+            .await
+            .contains(&decryption_task_request_id)
+            .await?;
+
+        if !contained_task {
+            let group_index = self
+                .get_main_chain()
+                .get_group_cache()
+                .read()
+                .await
+                .get_index()?;
+
+            let task = DecryptionTask {
+                request_id: decryption_task_request_id.clone(),
+                group_index: group_index as u32,
+                requester,
+                ciphertext: ciphertext.to_vec(),
+                request_confirmations: 0,
+                assignment_block_height: current_block_height,
+            };
+
+            self.get_main_chain()
+                .get_decryption_tasks_cache()
+                .write()
+                .await
+                .add(task)
+                .await?;
+        }
+
+        if self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .is_committer(id_address)?
+        {
+            let contained_res = self
+                .get_main_chain()
+                .get_decryption_result_cache()
+                .read()
+                .await
+                .contains(&decryption_task_request_id)
+                .await?;
+
+            if !contained_res {
+                let (group_index, threshold) = {
+                    let group_cache = self.get_main_chain().get_group_cache();
+                    let group_cache = group_cache.read().await;
+                    (group_cache.get_index()?, group_cache.get_threshold()?)
+                };
+
+                let task = self
+                    .get_main_chain()
+                    .get_decryption_tasks_cache()
+                    .read()
+                    .await
+                    .get(&decryption_task_request_id)
+                    .await?;
+
+                self.get_main_chain()
+                    .get_decryption_result_cache()
+                    .write()
+                    .await
+                    .add(group_index, task, ciphertext.to_vec(), threshold)
+                    .await?;
+            }
+
+            self.get_main_chain()
+                .get_decryption_result_cache()
+                .write()
+                .await
+                .add_partial_signature(
+                    decryption_task_request_id.clone(),
+                    id_address,
+                    decryption_share.clone(),
+                )
+                .await?;
+        }
+
+        Ok(decryption_share)
+    }
+
+    async fn send_decryption_share(
+        &self,
+        member_id_address: Address,
+        ciphertext: Vec<u8>,
+        decryption_task_request_id: Vec<u8>,
+        decryption_share: Vec<u8>,
+    ) -> Result<()> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let main_chain_id = self
+            .get_main_chain()
+            .get_chain_identity()
+            .read()
+            .await
+            .get_chain_id();
+
+        let member = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_member(member_id_address)?
+            .clone();
+
+        let commit_partial_signature_retry_descriptor = ExponentialBackoffRetryDescriptor {
+            base: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_BASE,
+            factor: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_FACTOR,
+            max_attempts: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_MAX_ATTEMPTS,
+            use_jitter: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER,
+        };
+
+        let committer_client = GeneralCommitterClient::build(
+            id_address,
+            member.id_address,
+            member.rpc_endpoint.as_ref().unwrap().to_string(),
+            commit_partial_signature_retry_descriptor,
+        );
+
+        committer_client
+            .commit_partial_signature(
+                main_chain_id,
+                BLSTaskType::Decryption,
+                decryption_task_request_id,
+                ciphertext,
+                decryption_share,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn combine_decryption_shares(
         &self,
-        chain_id: usize,
-        group_index: usize,
-        randomness_task_request_id: Vec<u8>,
-        sig: Vec<u8>,
-        partial_sigs: HashMap<Address, Vec<u8>>,
-    ) -> Result<()> {
+        threshold: usize,
+        ciphertext: &[u8],
+        decryption_shares: &[Vec<u8>],
+    ) -> Result<Vec<u8>> {
+        self.get_main_chain().get_decryption_core().combine(
+            threshold,
+            ciphertext,
+            decryption_shares,
+        )
+    }
+
+    async fn get_decryption_result(&self, decryption_task_request_id: &[u8]) -> Result<Vec<u8>> {
         let id_address = self
             .get_main_chain()
             .get_node_cache()
@@ -564,64 +2114,313 @@ where
             .await
             .get_id_address()?;
 
-        let main_chain_id = self
+        if !self
             .get_main_chain()
-            .get_chain_identity()
+            .get_group_cache()
             .read()
             .await
-            .get_chain_id();
+            .is_committer(id_address)?
+        {
+            return Err(NodeError::NotCommitter.into());
+        }
 
-        let partial_signatures = partial_sigs
-            .iter()
-            .map(|(addr, partial)| {
-                let eval: Eval<Vec<u8>> = bincode::deserialize(partial)?;
-                let partial = PartialSignature {
-                    index: eval.index as usize,
-                    signature: eval.value,
-                };
-                Ok((*addr, partial))
-            })
-            .collect::<Result<_, NodeError>>()?;
+        let decryption_result_cache = self.get_main_chain().get_decryption_result_cache();
 
-        let (client, randomness_task) = if chain_id == main_chain_id {
-            (
-                self.get_main_chain()
-                    .get_chain_identity()
-                    .read()
-                    .await
-                    .build_adapter_client(id_address),
+        let result_cache = decryption_result_cache
+            .read()
+            .await
+            .get(decryption_task_request_id)
+            .await?
+            .result_cache;
+
+        // the shares are verified by the committer before they are cached
+        if result_cache.decryption_shares.len() < result_cache.threshold {
+            return Err(NodeError::DecryptionSharesNotEnough.into());
+        }
+
+        let decryption_shares = result_cache
+            .decryption_shares
+            .into_values()
+            .collect::<Vec<_>>();
+
+        let plaintext = self.get_main_chain().get_decryption_core().combine(
+            result_cache.threshold,
+            &result_cache.message,
+            &decryption_shares,
+        )?;
+
+        let mut decryption_result_cache = decryption_result_cache.write().await;
+        decryption_result_cache
+            .update_commit_result(decryption_task_request_id, BLSResultCacheState::Committed)
+            .await?;
+        decryption_result_cache
+            .incr_committed_times(decryption_task_request_id)
+            .await?;
+
+        Ok(plaintext)
+    }
+}
+
+impl<
+        PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+        S: SignatureScheme
+            + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    > BLSBlindSignatureService<PC> for GeneralContext<PC, S>
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    async fn blind_partial_sign(
+        &self,
+        blind_signature_task_request_id: Vec<u8>,
+        requester: Address,
+        blinded_msg: &[u8],
+    ) -> Result<Vec<u8>> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let blind_partial = self
+            .get_main_chain()
+            .get_blind_signature_core()
+            .sign_blind_partial(
                 self.get_main_chain()
-                    .get_randomness_tasks_cache()
+                    .get_group_cache()
                     .read()
                     .await
-                    .get(&randomness_task_request_id)
-                    .await?,
-            )
-        } else {
-            if !self.contains_relayed_chain(chain_id) {
-                return Err(SchedulerError::InvalidChainId(chain_id).into());
-            }
-            (
-                self.get_relayed_chain(chain_id)
-                    .unwrap()
-                    .get_chain_identity()
+                    .get_secret_share()?,
+                blinded_msg,
+            )?;
+
+        // the request is recorded by every member, as there is no request event on chain yet
+        let contained_task = self
+            .get_main_chain()
+            .get_blind_signature_tasks_cache()
+            .read()
+            .await
+            .contains(&blind_signature_task_request_id)
+            .await?;
+
+        if !contained_task {
+            let group_index = self
+                .get_main_chain()
+                .get_group_cache()
+                .read()
+                .await
+                .get_index()?;
+
+            let assignment_block_height = self
+                .get_main_chain()
+                .get_block_cache()
+                .read()
+                .await
+                .get_block_height();
+
+            let task = BlindSignatureTask {
+                request_id: blind_signature_task_request_id.clone(),
+                group_index: group_index as u32,
+                requester,
+                blinded_message: blinded_msg.to_vec(),
+                request_confirmations: 0,
+                assignment_block_height,
+            };
+
+            self.get_main_chain()
+                .get_blind_signature_tasks_cache()
+                .write()
+                .await
+                .add(task)
+                .await?;
+        }
+
+        if self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .is_committer(id_address)?
+        {
+            let contained_res = self
+                .get_main_chain()
+                .get_blind_signature_result_cache()
+                .read()
+                .await
+                .contains(&blind_signature_task_request_id)
+                .await?;
+
+            if !contained_res {
+                let (group_index, threshold) = {
+                    let group_cache = self.get_main_chain().get_group_cache();
+                    let group_cache = group_cache.read().await;
+                    (group_cache.get_index()?, group_cache.get_threshold()?)
+                };
+
+                let task = self
+                    .get_main_chain()
+                    .get_blind_signature_tasks_cache()
                     .read()
                     .await
-                    .build_adapter_client(id_address),
-                self.get_relayed_chain(chain_id)
-                    .unwrap()
-                    .get_randomness_tasks_cache()
-                    .read()
+                    .get(&blind_signature_task_request_id)
+                    .await?;
+
+                self.get_main_chain()
+                    .get_blind_signature_result_cache()
+                    .write()
                     .await
-                    .get(&randomness_task_request_id)
-                    .await?,
-            )
+                    .add(group_index, task, blinded_msg.to_vec(), threshold)
+                    .await?;
+            }
+
+            self.get_main_chain()
+                .get_blind_signature_result_cache()
+                .write()
+                .await
+                .add_partial_signature(
+                    blind_signature_task_request_id.clone(),
+                    id_address,
+                    blind_partial.clone(),
+                )
+                .await?;
+        }
+
+        Ok(blind_partial)
+    }
+
+    async fn send_blind_partial_sig(
+        &self,
+        member_id_address: Address,
+        blinded_msg: Vec<u8>,
+        blind_signature_task_request_id: Vec<u8>,
+        blind_partial: Vec<u8>,
+    ) -> Result<()> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        let main_chain_id = self
+            .get_main_chain()
+            .get_chain_identity()
+            .read()
+            .await
+            .get_chain_id();
+
+        let member = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_member(member_id_address)?
+            .clone();
+
+        let commit_partial_signature_retry_descriptor = ExponentialBackoffRetryDescriptor {
+            base: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_BASE,
+            factor: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_FACTOR,
+            max_attempts: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_MAX_ATTEMPTS,
+            use_jitter: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER,
         };
 
-        client
-            .fulfill_randomness(group_index, randomness_task, sig, partial_signatures)
+        let committer_client = GeneralCommitterClient::build(
+            id_address,
+            member.id_address,
+            member.rpc_endpoint.as_ref().unwrap().to_string(),
+            commit_partial_signature_retry_descriptor,
+        );
+
+        committer_client
+            .commit_partial_signature(
+                main_chain_id,
+                BLSTaskType::BlindSignature,
+                blind_signature_task_request_id,
+                blinded_msg,
+                blind_partial,
+            )
             .await?;
 
         Ok(())
     }
+
+    async fn aggregate_blind_partial_sigs(
+        &self,
+        blind_partials: &[Vec<u8>],
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let threshold = self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .get_threshold()?;
+
+        self.get_main_chain()
+            .get_blind_signature_core()
+            .aggregate(threshold, blind_partials)
+    }
+
+    async fn get_blind_signature_result(
+        &self,
+        blind_signature_task_request_id: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let id_address = self
+            .get_main_chain()
+            .get_node_cache()
+            .read()
+            .await
+            .get_id_address()?;
+
+        if !self
+            .get_main_chain()
+            .get_group_cache()
+            .read()
+            .await
+            .is_committer(id_address)?
+        {
+            return Err(NodeError::NotCommitter.into());
+        }
+
+        let blind_signature_result_cache = self.get_main_chain().get_blind_signature_result_cache();
+
+        let result_cache = blind_signature_result_cache
+            .read()
+            .await
+            .get(blind_signature_task_request_id)
+            .await?
+            .result_cache;
+
+        // the partials are verified by the committer before they are cached
+        if result_cache.partial_signatures.len() < result_cache.threshold {
+            return Err(NodeError::BlindPartialSignaturesNotEnough.into());
+        }
+
+        let blind_partials = result_cache
+            .partial_signatures
+            .into_values()
+            .collect::<Vec<_>>();
+
+        let blind_signature = self
+            .get_main_chain()
+            .get_blind_signature_core()
+            .aggregate(result_cache.threshold, &blind_partials)?;
+
+        let mut blind_signature_result_cache = blind_signature_result_cache.write().await;
+        blind_signature_result_cache
+            .update_commit_result(
+                blind_signature_task_request_id,
+                BLSResultCacheState::Committed,
+            )
+            .await?;
+        blind_signature_result_cache
+            .incr_committed_times(blind_signature_task_request_id)
+            .await?;
+
+        Ok(blind_signature)
+    }
 }
@@ -1,28 +1,52 @@
 use crate::context::types::GeneralContext;
 use crate::context::ContextFetcher;
 use crate::error::NodeError;
-use crate::management::ComponentService;
+use crate::management::{
+    drain_and_remove_relayed_chain, drain_and_stop, ComponentService, ConfigService,
+    RelayedChainService,
+};
+use crate::queue::event_queue::EventRecord;
 use crate::rpc_stub::management::management_service_server::{
     ManagementService, ManagementServiceServer,
 };
 use crate::rpc_stub::management::{
-    AggregatePartialSigsReply, AggregatePartialSigsRequest, FulfillRandomnessReply,
-    FulfillRandomnessRequest, GetGroupInfoReply, GetGroupInfoRequest, GetNodeInfoReply,
-    GetNodeInfoRequest, Group, ListFixedTasksReply, ListFixedTasksRequest, Member,
-    NodeActivateReply, NodeActivateRequest, NodeQuitReply, NodeQuitRequest, NodeRegisterReply,
-    NodeRegisterRequest, PartialSignReply, PartialSignRequest, PostProcessDkgReply,
-    PostProcessDkgRequest, SendPartialSigReply, SendPartialSigRequest, ShutdownListenerReply,
-    ShutdownListenerRequest, ShutdownNodeReply, ShutdownNodeRequest, StartListenerReply,
-    StartListenerRequest, VerifyPartialSigsReply, VerifyPartialSigsRequest, VerifySigReply,
-    VerifySigRequest,
+    AddRelayedChainReply, AddRelayedChainRequest, AggregateBlindPartialSigsReply,
+    AggregateBlindPartialSigsRequest, AggregatePartialSigsReply, AggregatePartialSigsRequest,
+    BlindPartialSignReply, BlindPartialSignRequest, BlsRandomnessTask,
+    BlsResultCacheState as RpcBLSResultCacheState, CombineDecryptionSharesReply,
+    CombineDecryptionSharesRequest, ConfigChange as RpcConfigChange, FulfillRandomnessReply,
+    FulfillRandomnessRequest, GetBlindSignatureResultReply, GetBlindSignatureResultRequest,
+    GetDecryptionResultReply, GetDecryptionResultRequest, GetGroupInfoReply, GetGroupInfoRequest,
+    GetNodeInfoReply, GetNodeInfoRequest, GetPartialEncryptionKeyReply,
+    GetPartialEncryptionKeyRequest, GetRandomnessTaskReply, GetRandomnessTaskRequest,
+    GetSignatureResultReply, GetSignatureResultRequest, Group, ListFixedTasksReply,
+    ListFixedTasksRequest, ListRandomnessTasksReply, ListRandomnessTasksRequest,
+    ListSignatureResultsReply, ListSignatureResultsRequest, Member, NodeActivateReply,
+    NodeActivateRequest, NodeEvent, NodeEventSource, NodeQuitReply, NodeQuitRequest,
+    NodeRegisterReply, NodeRegisterRequest, PartialDecryptReply, PartialDecryptRequest,
+    PartialSignReply, PartialSignRequest, PostProcessDkgReply, PostProcessDkgRequest,
+    RandomnessTask as RpcRandomnessTask, ReloadConfigReply, ReloadConfigRequest,
+    RemoveRelayedChainReply, RemoveRelayedChainRequest, SendBlindPartialSigReply,
+    SendBlindPartialSigRequest, SendDecryptionShareReply, SendDecryptionShareRequest,
+    SendPartialSigReply, SendPartialSigRequest, ShutdownListenerReply, ShutdownListenerRequest,
+    ShutdownNodeReply, ShutdownNodeRequest, SignatureResult, StartListenerReply,
+    StartListenerRequest, SubscribeEventsRequest, VerifyPartialSigsReply, VerifyPartialSigsRequest,
+    VerifySigReply, VerifySigRequest,
 };
+use arpa_core::log::feed::{subscribe_payloads, PayloadRecord};
 use arpa_core::{
-    address_to_string, Group as ModelGroup, ListenerType, Member as ModelMember, SchedulerError,
+    address_to_string, BLSTask, ConfigChange as ModelConfigChange, Group as ModelGroup,
+    ListenerType, ManagementScope, Member as ModelMember, RandomnessTask, SchedulerError,
+    DEFAULT_MANAGEMENT_LIST_LIMIT, DEFAULT_RELAYED_CHAIN_DRAIN_TIMEOUT_SECS,
+    MAX_MANAGEMENT_LIST_LIMIT,
 };
+use arpa_dal::cache::{BLSResultCache, RandomnessResultCache};
 use arpa_dal::error::DataAccessError;
+use arpa_dal::{BLSResultCacheState, BlockHeightRange, ListCursor, ListPage};
 use arpa_log::debug;
-use hyper::http::HeaderValue;
 use rustc_hex::FromHexError;
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::{
     task::{Context, Poll},
@@ -31,27 +55,45 @@ use std::{
 use threshold_bls::group::Curve;
 use threshold_bls::sig::{SignatureScheme, ThresholdScheme};
 use tokio::sync::RwLock;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 use tonic::transport::Body;
 use tonic::{body::BoxBody, transport::Server, Request, Response, Status};
 use tower::{Layer, Service};
 use uuid::Uuid;
 
-use super::{BLSRandomnessService, DBService, DKGService, GroupInfo, NodeInfo, NodeService};
+use super::access::{
+    audit, audit_rejected, rpc_scope, ManagementCaller, CONFIRMATION_TOKEN_HEADER,
+};
+use super::issuance::{BlindSignatureIssuancePolicy, RateLimitedIssuancePolicy};
+use super::{
+    BLSBlindSignatureService, BLSDecryptionService, BLSRandomnessService, DBService, DKGService,
+    GroupInfo, InspectService, NodeInfo, NodeService,
+};
 
 type NodeContext<PC, S> = Arc<RwLock<GeneralContext<PC, S>>>;
 
+type NodeEventStream = Pin<Box<dyn Stream<Item = NodeEvent> + Send>>;
+
 pub(crate) struct NodeManagementServiceServer<
     PC: Curve,
     S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>,
 > {
     context: NodeContext<PC, S>,
+    blind_signature_issuance_policy: Arc<dyn BlindSignatureIssuancePolicy>,
 }
 
 impl<PC: Curve, S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>>
     NodeManagementServiceServer<PC, S>
 {
-    pub fn new(context: NodeContext<PC, S>) -> Self {
-        NodeManagementServiceServer { context }
+    pub fn new(
+        context: NodeContext<PC, S>,
+        blind_signature_issuance_policy: Arc<dyn BlindSignatureIssuancePolicy>,
+    ) -> Self {
+        NodeManagementServiceServer {
+            context,
+            blind_signature_issuance_policy,
+        }
     }
 }
 
@@ -69,10 +111,13 @@ where
     <S as ThresholdScheme>::Error: Sync + Send,
     <S as SignatureScheme>::Error: Sync + Send,
 {
+    type SubscribeEventsStream = Pin<Box<dyn Stream<Item = Result<NodeEvent, Status>> + Send>>;
+
     async fn list_fixed_tasks(
         &self,
         request: Request<ListFixedTasksRequest>,
     ) -> Result<Response<ListFixedTasksReply>, Status> {
+        audit(&request);
         let _req = request.into_inner();
 
         let fixed_tasks = self
@@ -91,6 +136,7 @@ where
         &self,
         request: Request<StartListenerRequest>,
     ) -> Result<Response<StartListenerReply>, Status> {
+        audit(&request);
         let req = request.into_inner();
 
         let task_type: ListenerType = (req.task_type() as i32)
@@ -111,6 +157,7 @@ where
         &self,
         request: Request<ShutdownListenerRequest>,
     ) -> Result<Response<ShutdownListenerReply>, Status> {
+        audit(&request);
         let req = request.into_inner();
 
         let task_type: ListenerType = (req.task_type() as i32)
@@ -131,6 +178,7 @@ where
         &self,
         request: Request<NodeRegisterRequest>,
     ) -> Result<tonic::Response<NodeRegisterReply>, tonic::Status> {
+        audit(&request);
         let _req = request.into_inner();
         self.context
             .read()
@@ -145,6 +193,7 @@ where
         &self,
         request: Request<NodeActivateRequest>,
     ) -> Result<tonic::Response<NodeActivateReply>, tonic::Status> {
+        audit(&request);
         let _req = request.into_inner();
         //TODO
         // return Ok(Response::new(NodeActivateReply { res: true }));
@@ -155,6 +204,7 @@ where
         &self,
         request: Request<NodeQuitRequest>,
     ) -> Result<tonic::Response<NodeQuitReply>, tonic::Status> {
+        audit(&request);
         let _req = request.into_inner();
         //TODO
         // return Ok(Response::new(NodeQuitReply { res: true }));
@@ -165,13 +215,37 @@ where
         &self,
         request: Request<ShutdownNodeRequest>,
     ) -> Result<tonic::Response<ShutdownNodeReply>, tonic::Status> {
-        let _req = request.into_inner();
+        audit(&request);
+        let req = request.into_inner();
+
+        let drain_timeout = if req.drain_timeout_secs == 0 {
+            Duration::from_millis(
+                self.context
+                    .read()
+                    .await
+                    .get_config()
+                    .get_time_limits()
+                    .shutdown_drain_timeout_millis,
+            )
+        } else {
+            Duration::from_secs(req.drain_timeout_secs)
+        };
+
         self.context
             .read()
             .await
-            .shutdown_node()
+            .shutdown_node(req.force)
             .await
-            .map_err(|e: NodeError| Status::internal(e.to_string()))?;
+            .map_err(|e: NodeError| match e {
+                NodeError::ShutdownDuringDKG | NodeError::AlreadyShuttingDown => {
+                    Status::failed_precondition(e.to_string())
+                }
+                _ => Status::internal(e.to_string()),
+            })?;
+
+        // the reply is sent before the node stops
+        tokio::spawn(drain_and_stop(self.context.clone(), drain_timeout));
+
         return Ok(Response::new(ShutdownNodeReply { res: true }));
     }
 
@@ -179,6 +253,7 @@ where
         &self,
         request: Request<GetNodeInfoRequest>,
     ) -> Result<tonic::Response<GetNodeInfoReply>, tonic::Status> {
+        audit(&request);
         let _req = request.into_inner();
         let node_info = self
             .context
@@ -194,6 +269,7 @@ where
         &self,
         request: Request<GetGroupInfoRequest>,
     ) -> Result<tonic::Response<GetGroupInfoReply>, tonic::Status> {
+        audit(&request);
         let _req = request.into_inner();
         let group_info = self
             .context
@@ -209,6 +285,7 @@ where
         &self,
         request: Request<PostProcessDkgRequest>,
     ) -> Result<tonic::Response<PostProcessDkgReply>, tonic::Status> {
+        audit(&request);
         let _req = request.into_inner();
         self.context
             .write()
@@ -223,6 +300,7 @@ where
         &self,
         request: Request<PartialSignRequest>,
     ) -> Result<tonic::Response<PartialSignReply>, tonic::Status> {
+        audit(&request);
         let req = request.into_inner();
         let request_id = req.request_id;
         let threshold = req.threshold as usize;
@@ -241,6 +319,7 @@ where
         &self,
         request: Request<AggregatePartialSigsRequest>,
     ) -> Result<tonic::Response<AggregatePartialSigsReply>, tonic::Status> {
+        audit(&request);
         let req = request.into_inner();
         let threshold = req.threshold as usize;
         let partial_sigs = req.partial_sigs;
@@ -257,6 +336,7 @@ where
         &self,
         request: Request<VerifySigRequest>,
     ) -> Result<tonic::Response<VerifySigReply>, tonic::Status> {
+        audit(&request);
         let req = request.into_inner();
         let public = bincode::deserialize(&req.public)
             .map_err(|e: bincode::Error| Status::invalid_argument(e.to_string()))?;
@@ -274,6 +354,7 @@ where
         &self,
         request: Request<VerifyPartialSigsRequest>,
     ) -> Result<tonic::Response<VerifyPartialSigsReply>, tonic::Status> {
+        audit(&request);
         let req = request.into_inner();
         let publics = req
             .publics
@@ -298,6 +379,7 @@ where
         &self,
         request: Request<SendPartialSigRequest>,
     ) -> Result<tonic::Response<SendPartialSigReply>, tonic::Status> {
+        audit(&request);
         let req = request.into_inner();
         let member_id_address = req
             .member_id_address
@@ -326,6 +408,7 @@ where
         &self,
         request: Request<FulfillRandomnessRequest>,
     ) -> Result<tonic::Response<FulfillRandomnessReply>, tonic::Status> {
+        audit(&request);
         let req = request.into_inner();
         let group_index = req.group_index as usize;
         let request_id = req.request_id;
@@ -349,6 +432,457 @@ where
             .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
         return Ok(Response::new(FulfillRandomnessReply { res: true }));
     }
+
+    async fn get_partial_encryption_key(
+        &self,
+        request: Request<GetPartialEncryptionKeyRequest>,
+    ) -> Result<tonic::Response<GetPartialEncryptionKeyReply>, tonic::Status> {
+        audit(&request);
+        let _req = request.into_inner();
+        let partial_encryption_key = self
+            .context
+            .read()
+            .await
+            .get_partial_encryption_key()
+            .await
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(GetPartialEncryptionKeyReply {
+            partial_encryption_key,
+        }));
+    }
+
+    async fn partial_decrypt(
+        &self,
+        request: Request<PartialDecryptRequest>,
+    ) -> Result<tonic::Response<PartialDecryptReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let request_id = req.request_id;
+        let requester = req
+            .requester
+            .parse()
+            .map_err(|e: FromHexError| Status::invalid_argument(e.to_string()))?;
+        let ciphertext = req.ciphertext;
+        let decryption_share = self
+            .context
+            .write()
+            .await
+            .partial_decrypt(request_id, requester, &ciphertext)
+            .await
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(PartialDecryptReply { decryption_share }));
+    }
+
+    async fn send_decryption_share(
+        &self,
+        request: Request<SendDecryptionShareRequest>,
+    ) -> Result<tonic::Response<SendDecryptionShareReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let member_id_address = req
+            .member_id_address
+            .parse()
+            .map_err(|e: FromHexError| Status::invalid_argument(e.to_string()))?;
+
+        self.context
+            .write()
+            .await
+            .send_decryption_share(
+                member_id_address,
+                req.ciphertext,
+                req.request_id,
+                req.decryption_share,
+            )
+            .await
+            .map_err(|e: anyhow::Error| Status::unavailable(e.to_string()))?;
+        return Ok(Response::new(SendDecryptionShareReply { res: true }));
+    }
+
+    async fn combine_decryption_shares(
+        &self,
+        request: Request<CombineDecryptionSharesRequest>,
+    ) -> Result<tonic::Response<CombineDecryptionSharesReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let threshold = req.threshold as usize;
+        let plaintext = self
+            .context
+            .read()
+            .await
+            .combine_decryption_shares(threshold, &req.ciphertext, &req.decryption_shares)
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(CombineDecryptionSharesReply { plaintext }));
+    }
+
+    async fn get_decryption_result(
+        &self,
+        request: Request<GetDecryptionResultRequest>,
+    ) -> Result<tonic::Response<GetDecryptionResultReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let plaintext = self
+            .context
+            .read()
+            .await
+            .get_decryption_result(&req.request_id)
+            .await
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(GetDecryptionResultReply { plaintext }));
+    }
+
+    async fn blind_partial_sign(
+        &self,
+        request: Request<BlindPartialSignRequest>,
+    ) -> Result<tonic::Response<BlindPartialSignReply>, tonic::Status> {
+        audit(&request);
+        let caller = request
+            .extensions()
+            .get::<ManagementCaller>()
+            .map(|caller| caller.name.clone())
+            .ok_or_else(|| Status::unauthenticated("No valid auth token"))?;
+        let req = request.into_inner();
+        let request_id = req.request_id;
+        let requester = req
+            .requester
+            .parse()
+            .map_err(|e: FromHexError| Status::invalid_argument(e.to_string()))?;
+        self.blind_signature_issuance_policy
+            .authorize(&caller, requester)
+            .map_err(|e| match e {
+                NodeError::BlindSignatureIssuanceRateLimited => {
+                    Status::resource_exhausted(e.to_string())
+                }
+                _ => Status::permission_denied(e.to_string()),
+            })?;
+        let blinded_msg = req.blinded_msg;
+        let blind_partial_sig = self
+            .context
+            .write()
+            .await
+            .blind_partial_sign(request_id, requester, &blinded_msg)
+            .await
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(BlindPartialSignReply { blind_partial_sig }));
+    }
+
+    async fn send_blind_partial_sig(
+        &self,
+        request: Request<SendBlindPartialSigRequest>,
+    ) -> Result<tonic::Response<SendBlindPartialSigReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let member_id_address = req
+            .member_id_address
+            .parse()
+            .map_err(|e: FromHexError| Status::invalid_argument(e.to_string()))?;
+
+        self.context
+            .write()
+            .await
+            .send_blind_partial_sig(
+                member_id_address,
+                req.blinded_msg,
+                req.request_id,
+                req.blind_partial_sig,
+            )
+            .await
+            .map_err(|e: anyhow::Error| Status::unavailable(e.to_string()))?;
+        return Ok(Response::new(SendBlindPartialSigReply { res: true }));
+    }
+
+    async fn aggregate_blind_partial_sigs(
+        &self,
+        request: Request<AggregateBlindPartialSigsRequest>,
+    ) -> Result<tonic::Response<AggregateBlindPartialSigsReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let (blind_sig, public_key) = self
+            .context
+            .read()
+            .await
+            .aggregate_blind_partial_sigs(&req.blind_partial_sigs)
+            .await
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(AggregateBlindPartialSigsReply {
+            blind_sig,
+            public_key,
+        }));
+    }
+
+    async fn get_blind_signature_result(
+        &self,
+        request: Request<GetBlindSignatureResultRequest>,
+    ) -> Result<tonic::Response<GetBlindSignatureResultReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let (blind_sig, public_key) = self
+            .context
+            .read()
+            .await
+            .get_blind_signature_result(&req.request_id)
+            .await
+            .map_err(|e: anyhow::Error| Status::failed_precondition(e.to_string()))?;
+        return Ok(Response::new(GetBlindSignatureResultReply {
+            blind_sig,
+            public_key,
+        }));
+    }
+
+    async fn list_randomness_tasks(
+        &self,
+        request: Request<ListRandomnessTasksRequest>,
+    ) -> Result<tonic::Response<ListRandomnessTasksReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let block_height_range = BlockHeightRange {
+            from: req.from_block_height.map(|h| h as usize),
+            to: req.to_block_height.map(|h| h as usize),
+        };
+        let (limit, page) = build_list_page(req.limit, req.cursor.as_deref())?;
+        let mut tasks = self
+            .context
+            .read()
+            .await
+            .list_randomness_tasks(req.chain_id as usize, req.handled, block_height_range, page)
+            .await
+            .map_err(|e: NodeError| Status::unavailable(e.to_string()))?;
+        let next_cursor = (tasks.len() > limit).then(|| {
+            tasks.truncate(limit);
+            ListCursor::of(&tasks[limit - 1].task).to_bytes()
+        });
+        return Ok(Response::new(ListRandomnessTasksReply {
+            tasks: tasks.into_iter().map(|t| t.into()).collect(),
+            next_cursor,
+        }));
+    }
+
+    async fn get_randomness_task(
+        &self,
+        request: Request<GetRandomnessTaskRequest>,
+    ) -> Result<tonic::Response<GetRandomnessTaskReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let task = self
+            .context
+            .read()
+            .await
+            .get_randomness_task(req.chain_id as usize, &req.request_id)
+            .await
+            .map_err(|e: NodeError| Status::not_found(e.to_string()))?;
+        return Ok(Response::new(GetRandomnessTaskReply {
+            task: Some(task.into()),
+        }));
+    }
+
+    async fn list_signature_results(
+        &self,
+        request: Request<ListSignatureResultsRequest>,
+    ) -> Result<tonic::Response<ListSignatureResultsReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let state = req.state.map(|_| req.state().into());
+        let block_height_range = BlockHeightRange {
+            from: req.from_block_height.map(|h| h as usize),
+            to: req.to_block_height.map(|h| h as usize),
+        };
+        let (limit, page) = build_list_page(req.limit, req.cursor.as_deref())?;
+        let mut results = self
+            .context
+            .read()
+            .await
+            .list_signature_results(req.chain_id as usize, state, block_height_range, page)
+            .await
+            .map_err(|e: NodeError| Status::unavailable(e.to_string()))?;
+        let next_cursor = (results.len() > limit).then(|| {
+            results.truncate(limit);
+            ListCursor::of(&results[limit - 1].result_cache.randomness_task).to_bytes()
+        });
+        return Ok(Response::new(ListSignatureResultsReply {
+            results: results.into_iter().map(|r| r.into()).collect(),
+            next_cursor,
+        }));
+    }
+
+    async fn get_signature_result(
+        &self,
+        request: Request<GetSignatureResultRequest>,
+    ) -> Result<tonic::Response<GetSignatureResultReply>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+        let result = self
+            .context
+            .read()
+            .await
+            .get_signature_result(req.chain_id as usize, &req.request_id)
+            .await
+            .map_err(|e: NodeError| Status::not_found(e.to_string()))?;
+        return Ok(Response::new(GetSignatureResultReply {
+            result: Some(result.into()),
+        }));
+    }
+
+    async fn subscribe_events(
+        &self,
+        request: Request<SubscribeEventsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeEventsStream>, tonic::Status> {
+        audit(&request);
+        let req = request.into_inner();
+
+        let (main_chain_id, event_feed) = {
+            let context = self.context.read().await;
+            let main_chain_id = context.get_config().get_main_chain_id();
+            let event_feed = context.get_event_queue().read().await.subscribe_feed();
+            (main_chain_id, event_feed)
+        };
+
+        // lagged records are skipped since the client is only watching
+        let events: NodeEventStream = Box::pin(
+            BroadcastStream::new(event_feed)
+                .filter_map(|record| record.ok())
+                .map(move |record| build_event(record, main_chain_id)),
+        );
+
+        let logs: NodeEventStream = if req.with_logs {
+            Box::pin(
+                BroadcastStream::new(subscribe_payloads())
+                    .filter_map(|record| record.ok())
+                    .map(move |record| build_log(record, main_chain_id)),
+            )
+        } else {
+            Box::pin(tokio_stream::empty())
+        };
+
+        let topics = req.topics;
+        let chain_ids = req.chain_ids;
+
+        let stream = events
+            .merge(logs)
+            .filter(move |event| {
+                (topics.is_empty() || topics.contains(&event.topic))
+                    && (chain_ids.is_empty() || chain_ids.contains(&event.chain_id))
+            })
+            .map(Ok);
+
+        return Ok(Response::new(Box::pin(stream)));
+    }
+
+    async fn reload_config(
+        &self,
+        request: Request<ReloadConfigRequest>,
+    ) -> Result<Response<ReloadConfigReply>, Status> {
+        audit(&request);
+        let req = request.into_inner();
+
+        let diff = self
+            .context
+            .write()
+            .await
+            .reload_config(req.config_path.map(PathBuf::from), req.dry_run)
+            .await
+            .map_err(|e: NodeError| match e {
+                NodeError::ConfigError(_) => Status::invalid_argument(e.to_string()),
+                _ => Status::internal(e.to_string()),
+            })?;
+
+        return Ok(Response::new(ReloadConfigReply {
+            applied: !req.dry_run && diff.is_applicable(),
+            applicable: diff.applicable.into_iter().map(|c| c.into()).collect(),
+            rejected: diff.rejected.into_iter().map(|c| c.into()).collect(),
+        }));
+    }
+
+    async fn add_relayed_chain(
+        &self,
+        request: Request<AddRelayedChainRequest>,
+    ) -> Result<Response<AddRelayedChainReply>, Status> {
+        audit(&request);
+        let req = request.into_inner();
+
+        self.context
+            .write()
+            .await
+            .load_relayed_chain(req.chain_id as usize, req.config_path.map(PathBuf::from))
+            .await
+            .map_err(|e: NodeError| match e {
+                NodeError::ConfigError(_) | NodeError::RepeatedChainId => {
+                    Status::invalid_argument(e.to_string())
+                }
+                _ => Status::internal(e.to_string()),
+            })?;
+
+        return Ok(Response::new(AddRelayedChainReply {}));
+    }
+
+    async fn remove_relayed_chain(
+        &self,
+        request: Request<RemoveRelayedChainRequest>,
+    ) -> Result<Response<RemoveRelayedChainReply>, Status> {
+        audit(&request);
+        let req = request.into_inner();
+
+        let drain_timeout_secs = if req.drain_timeout_secs == 0 {
+            DEFAULT_RELAYED_CHAIN_DRAIN_TIMEOUT_SECS
+        } else {
+            req.drain_timeout_secs
+        };
+
+        drain_and_remove_relayed_chain(
+            self.context.clone(),
+            req.chain_id as usize,
+            Duration::from_secs(drain_timeout_secs),
+        )
+        .await
+        .map_err(|e: NodeError| match e {
+            NodeError::SchedulerError(SchedulerError::InvalidChainId(_)) => {
+                Status::invalid_argument(e.to_string())
+            }
+            NodeError::RelayedChainNotDrained(..) => Status::deadline_exceeded(e.to_string()),
+            _ => Status::internal(e.to_string()),
+        })?;
+
+        return Ok(Response::new(RemoveRelayedChainReply {}));
+    }
+}
+
+/// Returns the limit of the page and the page to fetch, which holds one more item than the limit
+/// so that the next cursor is only returned when there are more items
+fn build_list_page(limit: Option<u32>, cursor: Option<&[u8]>) -> Result<(usize, ListPage), Status> {
+    let limit = limit
+        .map(|l| l as usize)
+        .unwrap_or(DEFAULT_MANAGEMENT_LIST_LIMIT)
+        .clamp(1, MAX_MANAGEMENT_LIST_LIMIT);
+    let after = cursor
+        .map(|c| {
+            ListCursor::from_bytes(c).ok_or_else(|| Status::invalid_argument("invalid cursor"))
+        })
+        .transpose()?;
+    Ok((
+        limit,
+        ListPage {
+            limit: Some(limit + 1),
+            after,
+        },
+    ))
+}
+
+fn build_event(record: EventRecord, main_chain_id: usize) -> NodeEvent {
+    NodeEvent {
+        source: NodeEventSource::Event as i32,
+        topic: record.topic.name().to_owned(),
+        chain_id: record.topic.chain_id().unwrap_or(main_chain_id) as u32,
+        content: record.content,
+        timestamp: record.timestamp,
+    }
+}
+
+fn build_log(record: PayloadRecord, main_chain_id: usize) -> NodeEvent {
+    NodeEvent {
+        source: NodeEventSource::Log as i32,
+        topic: record.log_type,
+        chain_id: record.chain_id.unwrap_or(main_chain_id) as u32,
+        content: record.content,
+        timestamp: record.timestamp,
+    }
 }
 
 impl<PC: Curve> From<NodeInfo<PC>> for GetNodeInfoReply {
@@ -425,6 +959,90 @@ impl<PC: Curve> From<ModelMember<PC>> for Member {
     }
 }
 
+impl From<RandomnessTask> for RpcRandomnessTask {
+    fn from(t: RandomnessTask) -> Self {
+        RpcRandomnessTask {
+            request_id: t.request_id,
+            subscription_id: t.subscription_id,
+            group_index: t.group_index,
+            request_type: t.request_type.to_u8() as u32,
+            params: t.params,
+            requester: address_to_string(t.requester),
+            seed: t.seed.to_string(),
+            request_confirmations: t.request_confirmations as u32,
+            callback_gas_limit: t.callback_gas_limit,
+            callback_max_gas_price: t.callback_max_gas_price.to_string(),
+            assignment_block_height: t.assignment_block_height as u64,
+        }
+    }
+}
+
+impl From<BLSTask<RandomnessTask>> for BlsRandomnessTask {
+    fn from(t: BLSTask<RandomnessTask>) -> Self {
+        BlsRandomnessTask {
+            task: Some(t.task.into()),
+            handled: t.state,
+        }
+    }
+}
+
+impl From<BLSResultCacheState> for RpcBLSResultCacheState {
+    fn from(s: BLSResultCacheState) -> Self {
+        match s {
+            BLSResultCacheState::NotCommitted => RpcBLSResultCacheState::NotCommitted,
+            BLSResultCacheState::Committing => RpcBLSResultCacheState::Committing,
+            BLSResultCacheState::Committed => RpcBLSResultCacheState::Committed,
+            BLSResultCacheState::CommittedByOthers => RpcBLSResultCacheState::CommittedByOthers,
+            BLSResultCacheState::Expired => RpcBLSResultCacheState::Expired,
+            BLSResultCacheState::FAULTY => RpcBLSResultCacheState::Faulty,
+        }
+    }
+}
+
+impl From<RpcBLSResultCacheState> for BLSResultCacheState {
+    fn from(s: RpcBLSResultCacheState) -> Self {
+        match s {
+            RpcBLSResultCacheState::NotCommitted => BLSResultCacheState::NotCommitted,
+            RpcBLSResultCacheState::Committing => BLSResultCacheState::Committing,
+            RpcBLSResultCacheState::Committed => BLSResultCacheState::Committed,
+            RpcBLSResultCacheState::CommittedByOthers => BLSResultCacheState::CommittedByOthers,
+            RpcBLSResultCacheState::Expired => BLSResultCacheState::Expired,
+            RpcBLSResultCacheState::Faulty => BLSResultCacheState::FAULTY,
+        }
+    }
+}
+
+impl From<BLSResultCache<RandomnessResultCache>> for SignatureResult {
+    fn from(r: BLSResultCache<RandomnessResultCache>) -> Self {
+        let state: RpcBLSResultCacheState = r.state.into();
+        let result_cache = r.result_cache;
+
+        SignatureResult {
+            task: Some(result_cache.randomness_task.into()),
+            group_index: result_cache.group_index as u32,
+            message: result_cache.message,
+            threshold: result_cache.threshold as u32,
+            partial_signers: result_cache
+                .partial_signatures
+                .into_keys()
+                .map(address_to_string)
+                .collect(),
+            committed_times: result_cache.committed_times as u32,
+            state: state as i32,
+        }
+    }
+}
+
+impl From<ModelConfigChange> for RpcConfigChange {
+    fn from(c: ModelConfigChange) -> Self {
+        RpcConfigChange {
+            item: c.item,
+            old: c.old,
+            new: c.new,
+        }
+    }
+}
+
 pub async fn start_management_server<
     PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
     SS: SignatureScheme
@@ -443,6 +1061,15 @@ where
 {
     let addr = endpoint.parse()?;
 
+    let blind_signature_issuance_policy = Arc::new(RateLimitedIssuancePolicy::from(
+        context
+            .read()
+            .await
+            .get_config()
+            .get_blind_signature_issuance()
+            .clone(),
+    ));
+
     // The stack of middleware that our service will be wrapped in
     let layer = tower::ServiceBuilder::new()
         // Apply middleware from tower
@@ -455,7 +1082,7 @@ where
     Server::builder()
         .layer(layer)
         .add_service(ManagementServiceServer::new(
-            NodeManagementServiceServer::new(context),
+            NodeManagementServiceServer::new(context, blind_signature_issuance_policy),
         ))
         .serve(addr)
         .await?;
@@ -541,20 +1168,63 @@ where
 
             debug!("Intercepting management request: {:?}", req);
 
-            let token = HeaderValue::from_str(
-                context
-                    .read()
-                    .await
-                    .get_config()
-                    .get_node_management_rpc_token(),
-            )
-            .unwrap();
+            let rpc = req
+                .uri()
+                .path()
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
 
-            match req.headers().get("authorization") {
-                Some(t) if token == t => {}
-                _ => return Ok(Status::unauthenticated("No valid auth token").to_http()),
+            let management_access = context
+                .read()
+                .await
+                .get_config()
+                .get_management_access()
+                .clone();
+
+            let token = match req
+                .headers()
+                .get("authorization")
+                .and_then(|t| t.to_str().ok())
+                .and_then(|t| management_access.authenticate(t))
+            {
+                Some(token) => token,
+                None => {
+                    audit_rejected(None, &rpc, "unauthenticated");
+                    return Ok(Status::unauthenticated("No valid auth token").to_http());
+                }
             };
 
+            // unknown RPCs are treated as the most dangerous ones
+            let scope = rpc_scope(&rpc).unwrap_or(ManagementScope::Destructive);
+
+            if !token.has_scope(scope) {
+                audit_rejected(Some(&token.name), &rpc, "permission denied");
+                return Ok(Status::permission_denied(format!(
+                    "Token {} is not granted the {:?} scope",
+                    token.name, scope
+                ))
+                .to_http());
+            }
+
+            if management_access.requires_confirmation(scope)
+                && !req
+                    .headers()
+                    .get(CONFIRMATION_TOKEN_HEADER)
+                    .and_then(|t| t.to_str().ok())
+                    .map_or(false, |t| management_access.confirm(t))
+            {
+                audit_rejected(Some(&token.name), &rpc, "confirmation required");
+                return Ok(Status::permission_denied("No valid confirmation token").to_http());
+            }
+
+            let mut req = req;
+            req.extensions_mut().insert(ManagementCaller {
+                name: token.name.clone(),
+                rpc,
+            });
+
             let response = inner.call(req).await?;
 
             log_mdc::remove("management_request_id");
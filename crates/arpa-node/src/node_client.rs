@@ -7,8 +7,9 @@ use arpa_core::address_to_string;
 use arpa_core::build_wallet_from_config;
 use arpa_core::log::build_general_payload;
 use arpa_core::log::build_transaction_receipt_payload;
-use arpa_core::log::encoder::JsonEncoder;
 use arpa_core::log::LogType;
+use arpa_core::metrics::record_payload;
+use arpa_core::BLSCurve;
 use arpa_core::Config;
 use arpa_core::GeneralMainChainIdentity;
 use arpa_core::GeneralRelayedChainIdentity;
@@ -16,40 +17,38 @@ use arpa_core::DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES;
 use arpa_dal::GroupInfoHandler;
 use arpa_dal::NodeInfoHandler;
 use arpa_dal::{NodeInfoFetcher, NodeInfoUpdater};
+use arpa_node::algorithm::blind::SimpleBlindSignatureCore;
+use arpa_node::algorithm::decryption::SimpleDecryptionCore;
+use arpa_node::algorithm::fulfillment::ProfitabilityFulfillmentPolicy;
 use arpa_node::context::chain::types::GeneralMainChain;
 use arpa_node::context::chain::types::GeneralRelayedChain;
 use arpa_node::context::types::GeneralContext;
 use arpa_node::context::{Context, TaskWaiter};
+use arpa_node::logger::init_logger;
+use arpa_node::telemetry::{init_tracer, shutdown_tracer};
 use arpa_sqlite_db::SqliteDB;
 use check_latest::check_max_async;
+use dkg_core::serialize_dkg_public_key;
 use ethers::core::k256::ecdsa::SigningKey;
 use ethers::providers::Provider;
 use ethers::providers::Ws;
 use ethers::signers::Signer;
 use ethers::signers::Wallet;
 use ethers::types::U256;
-use log::{error, info, LevelFilter};
-use log4rs::append::console::ConsoleAppender;
-use log4rs::append::rolling_file::policy::compound::roll::delete::DeleteRoller;
-use log4rs::append::rolling_file::policy::compound::trigger::size::SizeTrigger;
-use log4rs::append::rolling_file::policy::compound::CompoundPolicy;
-use log4rs::append::rolling_file::RollingFileAppender;
-use log4rs::config::{Appender, Root};
-use log4rs::filter::threshold::ThresholdFilter;
-use log4rs::Config as LogConfig;
+use log::{error, info};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use structopt::StructOpt;
-use threshold_bls::schemes::bn254::G2Curve;
-use threshold_bls::schemes::bn254::G2Scheme;
+use threshold_bls::curve::bls12381::BLS12381Curve;
+use threshold_bls::curve::bn254::BN254Curve;
+use threshold_bls::curve::CurveType;
+use threshold_bls::group::{CurveFrom, PairingCurve};
 use threshold_bls::serialize::point_to_hex;
-use threshold_bls::sig::Scheme;
+use threshold_bls::sig::{G2Scheme, Scheme};
 use tokio::sync::RwLock;
 
-const VERSION: &str = env!("CARGO_PKG_VERSION");
-
 #[derive(StructOpt, Debug)]
 #[structopt(name = "Arpa Node")]
 pub struct Opt {
@@ -63,89 +62,12 @@ pub struct Opt {
     config_path: PathBuf,
 }
 
-fn init_logger(
-    node_id: &str,
-    l1_chain_id: usize,
-    context_logging: bool,
-    log_file_path: &str,
-    rolling_file_size: u64,
-) {
-    let stdout = ConsoleAppender::builder()
-        .encoder(Box::new(
-            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
-                .context_logging(context_logging),
-        ))
-        .build();
-
-    let rolling_file = RollingFileAppender::builder()
-        .encoder(Box::new(
-            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
-                .context_logging(context_logging),
-        ))
-        .build(
-            format!(
-                "{}/node.log",
-                if let Some(path_without_slash) = log_file_path.strip_suffix('/') {
-                    path_without_slash
-                } else {
-                    log_file_path
-                }
-            ),
-            Box::new(CompoundPolicy::new(
-                Box::new(SizeTrigger::new(rolling_file_size)),
-                Box::new(DeleteRoller::new()),
-            )),
-        )
-        .unwrap();
-
-    let rolling_err_file = RollingFileAppender::builder()
-        .encoder(Box::new(
-            JsonEncoder::new(node_id.to_string(), l1_chain_id, VERSION.to_string())
-                .context_logging(context_logging),
-        ))
-        .build(
-            format!("{}/node_err.log", log_file_path),
-            Box::new(CompoundPolicy::new(
-                Box::new(SizeTrigger::new(rolling_file_size)),
-                Box::new(DeleteRoller::new()),
-            )),
-        )
-        .unwrap();
-
-    let log_config = LogConfig::builder()
-        .appender(Appender::builder().build("stdout", Box::new(stdout)))
-        .appender(Appender::builder().build("file", Box::new(rolling_file)))
-        .appender(
-            Appender::builder()
-                .filter(Box::new(ThresholdFilter::new(LevelFilter::Error)))
-                .build("err_file", Box::new(rolling_err_file)),
-        )
-        .logger(log4rs::config::Logger::builder().build("node_client", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("arpa_node", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("arpa_core", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("arpa_contract_client", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("arpa_sqlite_db", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("arpa_dal", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("dkg_core", LevelFilter::Info))
-        .logger(log4rs::config::Logger::builder().build("threshold_bls", LevelFilter::Info))
-        .build(
-            Root::builder()
-                .appender("err_file")
-                .appender("stdout")
-                .appender("file")
-                .build(LevelFilter::Error),
-        )
-        .unwrap();
-
-    log4rs::init_config(log_config).unwrap();
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opt = Opt::from_args();
     println!("{:#?}", opt);
 
-    let config = Config::load(opt.config_path);
+    let config = Config::load(opt.config_path)?;
 
     let wallet = build_wallet_from_config(config.get_account())?;
 
@@ -158,13 +80,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     init_logger(
         &address_to_string(id_address),
         l1_chain_id,
-        logger_descriptor.get_context_logging(),
-        logger_descriptor.get_log_file_path(),
-        logger_descriptor.get_rolling_file_size(),
+        logger_descriptor,
     );
 
     info!("{:?}", config);
 
+    if let Some(telemetry_descriptor) = config.get_telemetry() {
+        init_tracer(telemetry_descriptor)?;
+    }
+
     if let Ok(Some(version)) = check_max_async!().await {
         error!(
             "Version {} is now available, please update your node.",
@@ -173,17 +97,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    if let Err(e) = start(config, wallet).await {
+    let result = match config.get_curve() {
+        BLSCurve::BN254 => start::<BN254Curve>(config, wallet).await,
+        BLSCurve::BLS12381 => start::<BLS12381Curve>(config, wallet).await,
+    };
+
+    if let Err(e) = result {
         error!("{:?}", e);
     };
 
+    shutdown_tracer();
+
     Ok(())
 }
 
-async fn start(
+/// Runs the node with public keys on G2 and signatures on G1 of the given curve.
+async fn start<C>(
     config: Config,
     wallet: Wallet<SigningKey>,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    C: CurveType<
+            G2Curve = CurveFrom<
+                <<C as CurveType>::PairingCurve as PairingCurve>::Scalar,
+                <<C as CurveType>::PairingCurve as PairingCurve>::G2,
+            >,
+        > + 'static,
+    C::PairingCurve: Send + Sync + 'static,
+{
     let id_address = wallet.address();
 
     let l1_chain_id = config.get_main_chain_id();
@@ -215,15 +156,22 @@ async fn start(
     if is_new_run {
         let rng = &mut rand::thread_rng();
 
-        let (dkg_private_key, dkg_public_key) = G2Scheme::keypair(rng);
+        let (dkg_private_key, dkg_public_key) = G2Scheme::<C::PairingCurve>::keypair(rng);
+
+        let dkg_public_key_with_proof =
+            serialize_dkg_public_key::<G2Scheme<C::PairingCurve>>(&dkg_private_key)?;
 
         info!(
             "{}",
-            build_general_payload(
+            record_payload(build_general_payload(
                 LogType::DKGKeyGenerated,
-                &format!("dkg public_key: {}", point_to_hex(&dkg_public_key)),
+                &format!(
+                    "dkg public_key: {}, to register: 0x{}",
+                    point_to_hex(&dkg_public_key),
+                    hex::encode(&dkg_public_key_with_proof)
+                ),
                 Some(l1_chain_id)
-            )
+            ))
         );
 
         node_cache
@@ -237,7 +185,7 @@ async fn start(
             )
             .await?;
 
-        dkg_public_key_to_register = Some(bincode::serialize(&dkg_public_key)?);
+        dkg_public_key_to_register = Some(dkg_public_key_with_proof);
     } else {
         if let Ok(false) = node_cache.refresh_current_node_info().await {
             return Err("It seems there is no existing node record. Please check the database or remove it.".into());
@@ -259,16 +207,25 @@ async fn start(
         group_cache.refresh_current_group_info().await?;
     }
 
-    let node_cache: Arc<RwLock<Box<dyn NodeInfoHandler<G2Curve>>>> =
+    let node_cache: Arc<RwLock<Box<dyn NodeInfoHandler<C::G2Curve>>>> =
         Arc::new(RwLock::new(Box::new(node_cache)));
 
-    let group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<G2Curve>>>> =
+    let group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<C::G2Curve>>>> =
         Arc::new(RwLock::new(Box::new(group_cache)));
 
     let randomness_tasks_cache = Arc::new(RwLock::new(db.build_randomness_tasks_cache(0)?));
 
     let randomness_result_cache = Arc::new(RwLock::new(db.build_randomness_result_cache(0).await?));
 
+    let decryption_tasks_cache = Arc::new(RwLock::new(db.build_decryption_tasks_cache()));
+
+    let decryption_result_cache = Arc::new(RwLock::new(db.build_decryption_result_cache().await?));
+
+    let blind_signature_tasks_cache = Arc::new(RwLock::new(db.build_blind_signature_tasks_cache()));
+
+    let blind_signature_result_cache =
+        Arc::new(RwLock::new(db.build_blind_signature_result_cache().await?));
+
     let provider = Arc::new(
         Provider::<Ws>::connect_with_reconnects(
             config.get_provider_endpoint(),
@@ -301,9 +258,10 @@ async fn start(
             .get_time_limits()
             .contract_transaction_retry_descriptor,
         config.get_time_limits().contract_view_retry_descriptor,
+        config.get_private_submission().cloned(),
     );
 
-    let main_chain = GeneralMainChain::<G2Curve, G2Scheme>::new(
+    let main_chain = GeneralMainChain::<C::G2Curve, G2Scheme<C::PairingCurve>>::new(
         "main chain".to_string(),
         is_eigenlayer,
         main_chain_identity.clone(),
@@ -311,14 +269,27 @@ async fn start(
         group_cache.clone(),
         randomness_tasks_cache,
         randomness_result_cache,
+        decryption_tasks_cache,
+        decryption_result_cache,
+        Arc::new(SimpleDecryptionCore::<C::PairingCurve>::new()),
+        blind_signature_tasks_cache,
+        blind_signature_result_cache,
+        Arc::new(SimpleBlindSignatureCore::<C::PairingCurve>::new()),
+        Arc::new(ProfitabilityFulfillmentPolicy::from(
+            *config.get_fulfillment_policy(),
+        )),
         *config.get_time_limits(),
         config.get_listeners().clone(),
+        config.get_dkg_protocol(),
+        config.get_dkg_board(),
     );
 
     let relayed_chains_config = config.get_relayed_chains().clone();
 
     let mut context = GeneralContext::new(main_chain, config);
 
+    context.set_db(db.clone());
+
     for relayed_chain_config in relayed_chains_config {
         let provider = Arc::new(
             Provider::<Ws>::connect_with_reconnects(
@@ -354,6 +325,7 @@ async fn start(
             relayed_chain_config
                 .get_time_limits()
                 .contract_view_retry_descriptor,
+            relayed_chain_config.get_private_submission().cloned(),
         );
 
         let randomness_tasks_cache = Arc::new(RwLock::new(
@@ -364,13 +336,16 @@ async fn start(
             db.build_randomness_result_cache(relayed_chain_id).await?,
         ));
 
-        let relayed_chain = GeneralRelayedChain::<G2Curve, G2Scheme>::new(
+        let relayed_chain = GeneralRelayedChain::<C::G2Curve, G2Scheme<C::PairingCurve>>::new(
             relayed_chain_config.get_description().to_string(),
             relayed_chain_identity,
             node_cache.clone(),
             group_cache.clone(),
             randomness_tasks_cache,
             randomness_result_cache,
+            Arc::new(ProfitabilityFulfillmentPolicy::from(
+                *relayed_chain_config.get_fulfillment_policy(),
+            )),
             *relayed_chain_config.get_time_limits(),
             relayed_chain_config.get_listeners().to_vec(),
         );
@@ -380,10 +355,10 @@ async fn start(
 
     // check if the node is registered and dkg public key matches the one in the database
     let controller_client =
-        ControllerClientBuilder::<G2Curve>::build_controller_client(&main_chain_identity);
+        ControllerClientBuilder::<C::G2Curve>::build_controller_client(&main_chain_identity);
 
     let node_registry_address =
-        ControllerViews::<G2Curve>::get_node_registry_address(&controller_client).await?;
+        ControllerViews::<C::G2Curve>::get_node_registry_address(&controller_client).await?;
 
     let node_registry_client = NodeRegistryClientBuilder::build_node_registry_client(
         &main_chain_identity,
@@ -391,8 +366,14 @@ async fn start(
     );
 
     let node = node_registry_client.get_node(id_address).await?;
+    // the registered key is followed by its proof of possession
     if !node.id_address.is_zero()
-        && node.id_public_key != bincode::serialize(&node_cache.read().await.get_dkg_public_key()?)?
+        && bincode::deserialize::<<G2Scheme<C::PairingCurve> as Scheme>::Public>(
+            &node.id_public_key,
+        )
+        .ok()
+        .as_ref()
+            != Some(node_cache.read().await.get_dkg_public_key()?)
     {
         return Err("Node is registered with different dkg public key".into());
     }
@@ -409,38 +390,38 @@ async fn start(
             Ok(receipt) => {
                 info!(
                     "{}",
-                    build_transaction_receipt_payload(
+                    record_payload(build_transaction_receipt_payload(
                         LogType::NodeRegistered,
                         "Node registered",
                         l1_chain_id,
                         receipt.transaction_hash,
                         receipt.gas_used.unwrap_or(U256::zero()),
                         receipt.effective_gas_price.unwrap_or(U256::zero()),
-                    )
+                    ))
                 );
             }
             Err(e) => match e {
                 ContractClientError::TransactionFailed(receipt) => {
                     error!(
                         "{}",
-                        build_transaction_receipt_payload(
+                        record_payload(build_transaction_receipt_payload(
                             LogType::NodeRegisterFailed,
                             "Node register failed",
                             l1_chain_id,
                             receipt.transaction_hash,
                             receipt.gas_used.unwrap_or(U256::zero()),
                             receipt.effective_gas_price.unwrap_or(U256::zero()),
-                        )
+                        ))
                     );
                 }
                 _ => {
                     error!(
                         "{}",
-                        build_general_payload(
+                        record_payload(build_general_payload(
                             LogType::NodeRegisterFailed,
                             &format!("Node register failed with error: {:?}", e),
                             Some(l1_chain_id)
-                        )
+                        ))
                     );
                 }
             },
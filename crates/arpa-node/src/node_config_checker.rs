@@ -1,9 +1,24 @@
+use arpa_contract_client::controller::{ControllerClientBuilder, ControllerViews};
+use arpa_contract_client::node_registry::{NodeRegistryClientBuilder, NodeRegistryViews};
 use arpa_core::address_to_string;
 use arpa_core::build_wallet_from_config;
 use arpa_core::Config;
-use ethers::signers::Signer;
+use arpa_core::ConfigValidation;
+use arpa_core::GeneralMainChainIdentity;
+use ethers::core::k256::ecdsa::SigningKey;
+use ethers::providers::{Middleware, Provider, Ws};
+use ethers::signers::{Signer, Wallet};
+use ethers::types::Address;
+use serde::Serialize;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
+use threshold_bls::schemes::bn254::G2Curve;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const ONLINE_CHECK_TIMEOUT_SECS: u64 = 10;
 
 #[derive(StructOpt, Debug)]
 #[structopt(name = "Arpa Config Checker")]
@@ -16,17 +31,389 @@ pub struct Opt {
         default_value = "crates/arpa-node/conf/config.yml"
     )]
     config_path: PathBuf,
+
+    /// Also check the providers, contracts, node registration and balances on chain
+    #[structopt(short = "o", long)]
+    online: bool,
+
+    /// Print the report in json format
+    #[structopt(short = "j", long)]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    valid: bool,
+    address: Option<String>,
+    #[serde(flatten)]
+    validation: ConfigValidation,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let opt = Opt::from_args();
 
-    let config = Config::load(opt.config_path);
+    let report = check(opt.config_path, opt.online).await;
 
-    let wallet = build_wallet_from_config(config.get_account())?;
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
 
-    println!("{:?}", address_to_string(wallet.address()));
+    if !report.valid {
+        std::process::exit(1);
+    }
 
     Ok(())
 }
+
+async fn check(config_path: PathBuf, online: bool) -> Report {
+    let mut validation = match Config::validate(config_path.clone()) {
+        Ok(validation) => validation,
+        Err(e) => {
+            let mut validation = ConfigValidation::default();
+            validation.add_error("config", e.to_string());
+            validation
+        }
+    };
+
+    let mut address = None;
+
+    if validation.is_valid() {
+        match Config::try_load(config_path)
+            .and_then(|config| build_wallet_from_config(config.get_account()).map(|w| (config, w)))
+        {
+            Ok((config, wallet)) => {
+                address = Some(address_to_string(wallet.address()));
+                if online {
+                    check_online(&config, wallet, &mut validation).await;
+                }
+            }
+            Err(e) => validation.add_error("config", e.to_string()),
+        }
+    }
+
+    Report {
+        valid: validation.is_valid(),
+        address,
+        validation,
+    }
+}
+
+fn print_report(report: &Report) {
+    for error in report.validation.errors.iter() {
+        println!("error: {}", error);
+    }
+    for warning in report.validation.warnings.iter() {
+        println!("warning: {}", warning);
+    }
+    if let Some(address) = &report.address {
+        println!("{:?}", address);
+    }
+}
+
+async fn check_online(
+    config: &Config,
+    wallet: Wallet<SigningKey>,
+    validation: &mut ConfigValidation,
+) {
+    let id_address = wallet.address();
+
+    if let Some(provider) = connect_provider(
+        validation,
+        "provider_endpoint",
+        config.get_provider_endpoint(),
+        config.get_main_chain_id(),
+    )
+    .await
+    {
+        let provider = Arc::new(provider);
+
+        for (item, address) in [
+            ("controller_address", config.get_controller_address()),
+            (
+                "controller_relayer_address",
+                config.get_controller_relayer_address(),
+            ),
+            ("adapter_address", config.get_adapter_address()),
+            ("arpa_contract_address", config.get_arpa_contract_address()),
+        ] {
+            check_contract_code(validation, &provider, item, address).await;
+        }
+
+        check_balance(validation, &provider, "account", id_address).await;
+
+        let main_chain_identity = GeneralMainChainIdentity::new(
+            config.get_main_chain_id(),
+            wallet.clone(),
+            provider,
+            config.get_provider_endpoint().to_string(),
+            config
+                .get_controller_address()
+                .parse()
+                .expect("bad format of controller_address"),
+            config
+                .get_controller_relayer_address()
+                .parse()
+                .expect("bad format of controller_relayer_address"),
+            config
+                .get_adapter_address()
+                .parse()
+                .expect("bad format of adapter_address"),
+            config
+                .get_time_limits()
+                .contract_transaction_retry_descriptor,
+            config.get_time_limits().contract_view_retry_descriptor,
+            config.get_private_submission().cloned(),
+        );
+        check_node_registration(validation, &main_chain_identity, id_address).await;
+    }
+
+    for relayed_chain in config.get_relayed_chains().iter() {
+        let item = format!("relayed_chains.{}", relayed_chain.get_chain_id());
+
+        if let Some(provider) = connect_provider(
+            validation,
+            &format!("{}.provider_endpoint", item),
+            relayed_chain.get_provider_endpoint(),
+            relayed_chain.get_chain_id(),
+        )
+        .await
+        {
+            for (sub_item, address) in [
+                (
+                    "controller_oracle_address",
+                    relayed_chain.get_controller_oracle_address(),
+                ),
+                ("adapter_address", relayed_chain.get_adapter_address()),
+                (
+                    "arpa_contract_address",
+                    relayed_chain.get_arpa_contract_address(),
+                ),
+            ] {
+                check_contract_code(
+                    validation,
+                    &provider,
+                    &format!("{}.{}", item, sub_item),
+                    address,
+                )
+                .await;
+            }
+
+            check_balance(
+                validation,
+                &provider,
+                &format!("{}.account", item),
+                id_address,
+            )
+            .await;
+        }
+    }
+
+    check_committer_endpoint(validation, config).await;
+}
+
+async fn connect_provider(
+    validation: &mut ConfigValidation,
+    item: &str,
+    endpoint: &str,
+    chain_id: usize,
+) -> Option<Provider<Ws>> {
+    let provider = match timeout(
+        Duration::from_secs(ONLINE_CHECK_TIMEOUT_SECS),
+        Provider::<Ws>::connect(endpoint),
+    )
+    .await
+    {
+        Ok(Ok(provider)) => provider,
+        Ok(Err(e)) => {
+            validation.add_error(item, format!("failed to connect: {}", e));
+            return None;
+        }
+        Err(_) => {
+            validation.add_error(item, "timed out connecting to the provider");
+            return None;
+        }
+    };
+
+    match provider.get_chainid().await {
+        Ok(actual_chain_id) if actual_chain_id.as_usize() == chain_id => Some(provider),
+        Ok(actual_chain_id) => {
+            validation.add_error(
+                item,
+                format!(
+                    "the provider serves chain {}, but chain {} is configured",
+                    actual_chain_id, chain_id
+                ),
+            );
+            None
+        }
+        Err(e) => {
+            validation.add_error(item, format!("failed to get chain id: {}", e));
+            None
+        }
+    }
+}
+
+async fn check_contract_code(
+    validation: &mut ConfigValidation,
+    provider: &Provider<Ws>,
+    item: &str,
+    address: &str,
+) {
+    // the format has been validated offline
+    let address = match address.parse::<Address>() {
+        Ok(address) if !address.is_zero() => address,
+        _ => return,
+    };
+
+    match provider.get_code(address, None).await {
+        Ok(code) if code.is_empty() => {
+            validation.add_error(item, format!("no contract is deployed at {:?}", address))
+        }
+        Ok(_) => {}
+        Err(e) => validation.add_error(item, format!("failed to get contract code: {}", e)),
+    }
+}
+
+async fn check_balance(
+    validation: &mut ConfigValidation,
+    provider: &Provider<Ws>,
+    item: &str,
+    address: Address,
+) {
+    match provider.get_balance(address, None).await {
+        Ok(balance) if balance.is_zero() => validation.add_warning(
+            item,
+            format!(
+                "{} has no balance to pay for the transactions",
+                address_to_string(address)
+            ),
+        ),
+        Ok(_) => {}
+        Err(e) => validation.add_error(item, format!("failed to get balance: {}", e)),
+    }
+}
+
+async fn check_node_registration(
+    validation: &mut ConfigValidation,
+    main_chain_identity: &GeneralMainChainIdentity,
+    id_address: Address,
+) {
+    let controller_client =
+        ControllerClientBuilder::<G2Curve>::build_controller_client(main_chain_identity);
+
+    let node_registry_address =
+        match ControllerViews::<G2Curve>::get_node_registry_address(&controller_client).await {
+            Ok(node_registry_address) => node_registry_address,
+            Err(e) => {
+                validation.add_error(
+                    "controller_address",
+                    format!("failed to get the node registry address: {}", e),
+                );
+                return;
+            }
+        };
+
+    let node_registry_client = NodeRegistryClientBuilder::build_node_registry_client(
+        main_chain_identity,
+        node_registry_address,
+    );
+
+    match node_registry_client.get_node(id_address).await {
+        Ok(node) if node.id_address.is_zero() => validation.add_warning(
+            "account",
+            format!(
+                "{} is not registered to the node registry yet",
+                address_to_string(id_address)
+            ),
+        ),
+        Ok(_) => {}
+        Err(e) => validation.add_error(
+            "account",
+            format!("failed to get the node from the node registry: {}", e),
+        ),
+    }
+}
+
+async fn check_committer_endpoint(validation: &mut ConfigValidation, config: &Config) {
+    let endpoint = config.get_node_advertised_committer_rpc_endpoint();
+
+    let reachable = matches!(
+        timeout(
+            Duration::from_secs(ONLINE_CHECK_TIMEOUT_SECS),
+            TcpStream::connect(endpoint),
+        )
+        .await,
+        Ok(Ok(_))
+    );
+
+    if !reachable {
+        validation.add_warning(
+            "node_advertised_committer_rpc_endpoint",
+            format!(
+                "{} is not reachable, other members can't send partial signatures to the node if it is running",
+                endpoint
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use std::{fs, io::Write};
+
+    const CONFIG: &str = r#"
+node_committer_rpc_endpoint: "0.0.0.0:50061"
+node_advertised_committer_rpc_endpoint: "10.0.0.1:50061"
+node_management_rpc_endpoint: "[::1]:50091"
+node_management_rpc_token: "for_test"
+node_statistics_http_endpoint: "http://localhost:50081"
+provider_endpoint: "ws://localhost:8545"
+chain_id: 900
+controller_address: "0x0000000000000000000000000000000000000001"
+controller_relayer_address: "0x0000000000000000000000000000000000000002"
+adapter_address: "0x0000000000000000000000000000000000000003"
+account:
+  private_key: "$ARPA_NODE_CONFIG_CHECKER_TEST_PRIVATE_KEY"
+relayed_chains: []
+"#;
+
+    async fn check_config(name: &str, config: &str) -> super::Report {
+        std::env::set_var(
+            "ARPA_NODE_CONFIG_CHECKER_TEST_PRIVATE_KEY",
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        );
+        let path = format!("config_checker_{}.yml", name);
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(config.as_bytes()).unwrap();
+        let report = check(path.clone().into(), false).await;
+        fs::remove_file(&path).unwrap();
+        report
+    }
+
+    #[tokio::test]
+    async fn test_check_valid_config() {
+        let report = check_config("valid", CONFIG).await;
+
+        assert!(report.valid, "{:?}", report.validation.errors);
+        assert_eq!(
+            report.address.as_deref(),
+            Some("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_invalid_config() {
+        let report = check_config("invalid", &CONFIG.replace("[::1]:50091", "localhost")).await;
+
+        assert!(!report.valid);
+        assert!(report.address.is_none());
+
+        let report = serde_json::to_value(&report).unwrap();
+        assert_eq!(report["valid"], false);
+        assert_eq!(report["errors"][0]["item"], "node_management_rpc_endpoint");
+    }
+}
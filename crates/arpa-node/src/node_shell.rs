@@ -2,6 +2,9 @@ use arpa_contract_client::adapter::AdapterViews;
 use arpa_contract_client::contract_stub::adapter::Adapter as AdapterContract;
 use arpa_contract_client::contract_stub::ierc20::IERC20 as ArpaContract;
 use arpa_contract_client::contract_stub::staking::Staking as StakingContract;
+use arpa_contract_client::controller::{ControllerLogs, ControllerViews};
+use arpa_contract_client::controller_oracle::ControllerOracleViews;
+use arpa_contract_client::coordinator::CoordinatorViews;
 use arpa_contract_client::ethers::adapter::AdapterClient;
 use arpa_contract_client::ethers::controller::ControllerClient;
 use arpa_contract_client::ethers::controller_oracle::ControllerOracleClient;
@@ -9,13 +12,17 @@ use arpa_contract_client::ethers::node_registry::NodeRegistryClient;
 use arpa_contract_client::node_registry::{NodeRegistryTransactions, NodeRegistryViews};
 use arpa_contract_client::{ServiceClient, TransactionCaller, ViewCaller};
 use arpa_core::{
-    address_to_string, build_wallet_from_config, pad_to_bytes32, Account, Config, ConfigError,
-    GeneralMainChainIdentity, GeneralRelayedChainIdentity, Keystore, WsWalletSigner,
+    address_to_string, build_wallet_from_config, hash_management_token, pad_to_bytes32, Account,
+    Config, ConfigError, GeneralMainChainIdentity, GeneralRelayedChainIdentity, Keystore,
+    WsWalletSigner,
 };
 use arpa_dal::NodeInfoFetcher;
 use arpa_node::context::ChainIdentityHandlerType;
 use arpa_node::management::client::GeneralManagementClient;
+use arpa_node::rpc_stub::management::{BlsResultCacheState, NodeEventSource};
 use arpa_sqlite_db::SqliteDB;
+use dkg_core::primitives::audit::{audit_transcript, DKGTranscript};
+use dkg_core::serialize_dkg_public_key;
 use ethers::prelude::k256::ecdsa::SigningKey;
 use ethers::providers::{Middleware, Provider, Ws};
 use ethers::signers::coins_bip39::English;
@@ -24,7 +31,7 @@ use ethers::signers::{LocalWallet, MnemonicBuilder};
 use ethers::types::{Address, BlockId, BlockNumber, H256, U256, U64};
 use reedline_repl_rs::clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 use reedline_repl_rs::Repl;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -34,7 +41,9 @@ use std::time::Duration;
 use structopt::StructOpt;
 use threshold_bls::curve::bn254::G2Curve;
 use threshold_bls::group::Curve;
+use threshold_bls::schemes::bn254::G2Scheme;
 use threshold_bls::serialize::scalar_to_hex;
+use threshold_bls::sig::SignatureScheme;
 
 pub const MAX_HISTORY_CAPACITY: usize = 1000;
 pub const DEFAULT_PROMPT: &str = "ARPA Node CLI";
@@ -433,13 +442,11 @@ async fn send<PC: Curve>(
 
             node_cache.refresh_current_node_info().await?;
 
-            let dkg_public_key = node_cache.get_dkg_public_key()?;
+            let dkg_public_key =
+                serialize_dkg_public_key::<G2Scheme>(node_cache.get_dkg_private_key()?)?;
 
             let trx_hash = client
-                .node_register_as_eigenlayer_operator(
-                    bincode::serialize(&dkg_public_key)?,
-                    &asset_account_signer,
-                )
+                .node_register_as_eigenlayer_operator(dkg_public_key, &asset_account_signer)
                 .await?;
 
             Ok(Some(format!(
@@ -549,7 +556,8 @@ async fn send<PC: Curve>(
 
             node_cache.refresh_current_node_info().await?;
 
-            let dkg_public_key = node_cache.get_dkg_public_key()?;
+            let dkg_public_key =
+                serialize_dkg_public_key::<G2Scheme>(node_cache.get_dkg_private_key()?)?;
 
             let controller_contract = client.prepare_service_client().await?;
 
@@ -557,8 +565,7 @@ async fn send<PC: Curve>(
                 main_chain_id,
                 "change_dkg_public_key",
                 controller_contract.client_ref(),
-                controller_contract
-                    .change_dkg_public_key(bincode::serialize(&dkg_public_key)?.into()),
+                controller_contract.change_dkg_public_key(dkg_public_key.into()),
                 context
                     .config
                     .get_time_limits()
@@ -697,6 +704,51 @@ async fn call<PC: Curve>(
                 Ok(Some(format!("{:#?}", group)))
             }
         }
+        // getGroup for all groups
+        Some(("groups", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+
+            if *chain_id == context.config.get_main_chain_id() {
+                let client = context.chain_identity(*chain_id)?.build_controller_client();
+
+                let controller_contract = client.prepare_service_client().await?;
+
+                let group_count = ControllerClient::call_contract_view(
+                    *chain_id,
+                    "group_count",
+                    controller_contract.get_group_count(),
+                    context.config.contract_view_retry_descriptor(*chain_id)?,
+                )
+                .await?;
+
+                let group_indices = (0..group_count.as_usize()).collect::<Vec<_>>();
+
+                let groups = ControllerViews::<PC>::get_groups(&client, &group_indices).await?;
+
+                Ok(Some(format!("{:#?}", groups)))
+            } else {
+                let client = context
+                    .chain_identity(*chain_id)?
+                    .build_controller_oracle_client();
+
+                let controller_oracle_contract = client.prepare_service_client().await?;
+
+                let group_count = ControllerOracleClient::call_contract_view(
+                    *chain_id,
+                    "group_count",
+                    controller_oracle_contract.get_group_count(),
+                    context.config.contract_view_retry_descriptor(*chain_id)?,
+                )
+                .await?;
+
+                let group_indices = (0..group_count.as_usize()).collect::<Vec<_>>();
+
+                let groups =
+                    ControllerOracleViews::<PC>::get_groups(&client, &group_indices).await?;
+
+                Ok(Some(format!("{:#?}", groups)))
+            }
+        }
         // getValidGroupIndices
         Some(("valid-group-indices", sub_matches)) => {
             let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
@@ -1458,6 +1510,15 @@ fn generate<PC: Curve>(
 
             Ok(Some("Mnemonic generated successfully.".to_owned()))
         }
+        Some(("management-token", _sub_matches)) => {
+            let token = hex::encode(rand::random::<[u8; 32]>());
+
+            Ok(Some(format!(
+                "token: {}\ntoken_hash: {}",
+                token,
+                hash_management_token(&token)
+            )))
+        }
 
         _ => panic!("Unknown subcommand {:?}", args.subcommand_name()),
     }
@@ -1499,21 +1560,352 @@ async fn inspect<PC: Curve>(
     args: ArgMatches,
     context: &mut Context<PC>,
 ) -> anyhow::Result<Option<String>> {
+    let management_client = GeneralManagementClient::new(
+        context.config.get_node_management_rpc_endpoint().to_owned(),
+        context.config.get_node_management_rpc_token().to_owned(),
+    );
+
     match args.subcommand() {
-        Some(("list-fixed-tasks", _sub_matches)) => {
-            let management_client = GeneralManagementClient::new(
-                context.config.get_node_management_rpc_endpoint().to_owned(),
-                context.config.get_node_management_rpc_token().to_owned(),
-            );
+        Some(("list-fixed-tasks", _sub_matches)) => Ok(Some(format!(
+            "fixed-tasks: {:#?}",
+            management_client.list_fixed_tasks().await?
+        ))),
+        Some(("list-randomness-tasks", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+            let handled = sub_matches.get_one::<bool>("handled").copied();
+            let from = sub_matches.get_one::<usize>("from").copied();
+            let to = sub_matches.get_one::<usize>("to").copied();
+            let limit = sub_matches.get_one::<u32>("limit").copied();
+            let cursor = sub_matches
+                .get_one::<String>("cursor")
+                .map(|c| hex::decode(c.trim_start_matches("0x")))
+                .transpose()?;
+
+            let (tasks, next_cursor) = management_client
+                .list_randomness_tasks(*chain_id, handled, from, to, limit, cursor)
+                .await?;
+
+            Ok(Some(format!(
+                "randomness-tasks: {:#?}\nnext-cursor: {}",
+                tasks,
+                format_next_cursor(next_cursor)
+            )))
+        }
+        Some(("get-randomness-task", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+            let request_id = sub_matches.get_one::<String>("request-id").unwrap();
+
+            let task = management_client
+                .get_randomness_task(*chain_id, hex::decode(request_id.trim_start_matches("0x"))?)
+                .await?;
+
+            Ok(Some(format!("randomness-task: {:#?}", task)))
+        }
+        Some(("list-signature-results", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+            let state = sub_matches
+                .get_one::<String>("state")
+                .map(|s| parse_bls_result_cache_state(s));
+            let from = sub_matches.get_one::<usize>("from").copied();
+            let to = sub_matches.get_one::<usize>("to").copied();
+            let limit = sub_matches.get_one::<u32>("limit").copied();
+            let cursor = sub_matches
+                .get_one::<String>("cursor")
+                .map(|c| hex::decode(c.trim_start_matches("0x")))
+                .transpose()?;
+
+            let (results, next_cursor) = management_client
+                .list_signature_results(*chain_id, state, from, to, limit, cursor)
+                .await?;
+
             Ok(Some(format!(
-                "fixed-tasks: {:#?}",
-                management_client.list_fixed_tasks().await?
+                "signature-results: {:#?}\nnext-cursor: {}",
+                results,
+                format_next_cursor(next_cursor)
             )))
         }
+        Some(("get-signature-result", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+            let request_id = sub_matches.get_one::<String>("request-id").unwrap();
+
+            let result = management_client
+                .get_signature_result(*chain_id, hex::decode(request_id.trim_start_matches("0x"))?)
+                .await?;
+
+            Ok(Some(format!("signature-result: {:#?}", result)))
+        }
+        Some(("watch", sub_matches)) => {
+            let topics = sub_matches
+                .get_many::<String>("topic")
+                .map(|topics| topics.cloned().collect())
+                .unwrap_or_default();
+            let chain_ids = sub_matches
+                .get_many::<usize>("chain-id")
+                .map(|chain_ids| chain_ids.copied().collect())
+                .unwrap_or_default();
+            let with_logs = sub_matches.get_flag("with-logs");
+
+            let mut events = management_client
+                .subscribe_events(topics, chain_ids, with_logs)
+                .await?;
+
+            println!("Watching node events, press Ctrl-C to stop...");
+
+            loop {
+                tokio::select! {
+                    event = events.message() => match event? {
+                        Some(event) => {
+                            let source = match event.source() {
+                                NodeEventSource::Event => "event",
+                                NodeEventSource::Log => "log",
+                            };
+                            let time = chrono::DateTime::from_timestamp_millis(event.timestamp)
+                                .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+                                .unwrap_or_default();
+                            println!(
+                                "{} [{}] chain {} {}: {}",
+                                time, source, event.chain_id, event.topic, event.content
+                            );
+                        }
+                        None => return Ok(Some("Event stream closed by the node.".to_owned())),
+                    },
+                    _ = tokio::signal::ctrl_c() => {
+                        return Ok(Some("Stopped watching node events.".to_owned()));
+                    }
+                }
+            }
+        }
         _ => panic!("Unknown subcommand {:?}", args.subcommand_name()),
     }
 }
 
+async fn manage<PC: Curve>(
+    args: ArgMatches,
+    context: &mut Context<PC>,
+) -> anyhow::Result<Option<String>> {
+    let mut management_client = GeneralManagementClient::new(
+        context.config.get_node_management_rpc_endpoint().to_owned(),
+        context.config.get_node_management_rpc_token().to_owned(),
+    );
+
+    match args.subcommand() {
+        Some(("reload-config", sub_matches)) => {
+            let config_path = sub_matches.get_one::<String>("config-path").cloned();
+            let dry_run = sub_matches.get_flag("dry-run");
+            if let Some(confirmation_token) = sub_matches.get_one::<String>("confirmation-token") {
+                management_client =
+                    management_client.with_confirmation_token(confirmation_token.to_owned());
+            }
+
+            let reply = management_client
+                .reload_config(config_path, dry_run)
+                .await?;
+
+            let mut output = if reply.applied {
+                "Config reloaded.".to_owned()
+            } else if dry_run {
+                "Config compared, nothing is applied.".to_owned()
+            } else {
+                "Config rejected since some changes require a restart, nothing is applied."
+                    .to_owned()
+            };
+            for change in reply.applicable.iter() {
+                output.push_str(&format!(
+                    "\n  applicable {}: {} -> {}",
+                    change.item, change.old, change.new
+                ));
+            }
+            for change in reply.rejected.iter() {
+                output.push_str(&format!(
+                    "\n  rejected {}: {} -> {}",
+                    change.item, change.old, change.new
+                ));
+            }
+
+            Ok(Some(output))
+        }
+        Some(("add-relayed-chain", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+            let config_path = sub_matches.get_one::<String>("config-path").cloned();
+            if let Some(confirmation_token) = sub_matches.get_one::<String>("confirmation-token") {
+                management_client =
+                    management_client.with_confirmation_token(confirmation_token.to_owned());
+            }
+
+            management_client
+                .add_relayed_chain(*chain_id, config_path)
+                .await?;
+
+            Ok(Some(format!("Relayed chain {} added.", chain_id)))
+        }
+        Some(("remove-relayed-chain", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<usize>("chain-id").unwrap();
+            let drain_timeout_secs = sub_matches
+                .get_one::<u64>("drain-timeout")
+                .copied()
+                .unwrap_or_default();
+            if let Some(confirmation_token) = sub_matches.get_one::<String>("confirmation-token") {
+                management_client =
+                    management_client.with_confirmation_token(confirmation_token.to_owned());
+            }
+
+            management_client
+                .remove_relayed_chain(*chain_id, drain_timeout_secs)
+                .await?;
+
+            Ok(Some(format!("Relayed chain {} removed.", chain_id)))
+        }
+        Some(("shutdown-node", sub_matches)) => {
+            let force = sub_matches.get_flag("force");
+            let drain_timeout_secs = sub_matches
+                .get_one::<u64>("drain-timeout")
+                .copied()
+                .unwrap_or_default();
+            if let Some(confirmation_token) = sub_matches.get_one::<String>("confirmation-token") {
+                management_client =
+                    management_client.with_confirmation_token(confirmation_token.to_owned());
+            }
+
+            management_client
+                .shutdown_node(force, drain_timeout_secs)
+                .await?;
+
+            Ok(Some(
+                "Node is shutting down after the in-flight tasks are drained.".to_string(),
+            ))
+        }
+        _ => panic!("Unknown subcommand {:?}", args.subcommand_name()),
+    }
+}
+
+async fn audit_dkg<PC: Curve + 'static, S: SignatureScheme<Public = PC::Point>>(
+    args: ArgMatches,
+    context: &mut Context<PC>,
+) -> anyhow::Result<Option<String>> {
+    let main_chain_id = context.config.get_main_chain_id();
+    let group_index = args.get_one::<usize>("group-index").unwrap();
+
+    let chain_identity = context.chain_identity(main_chain_id)?;
+    let controller_client = chain_identity.build_controller_client();
+
+    let coordinator_address = match args.get_one::<String>("coordinator-address") {
+        Some(coordinator_address) => coordinator_address.parse::<Address>()?,
+        None => ControllerViews::<PC>::get_coordinator(&controller_client, *group_index).await?,
+    };
+    if coordinator_address == Address::zero() {
+        return Ok(Some(
+            "There is no running dkg of the group, please specify the coordinator address of the dkg to audit."
+                .to_string(),
+        ));
+    }
+
+    let coordinator_client = chain_identity.build_coordinator_client(coordinator_address);
+
+    // the audited dkg is the dkg task which the coordinator is deployed for
+    let dkg_task = controller_client
+        .get_dkg_tasks(
+            *group_index,
+            context.config.get_adapter_deployed_block_height(),
+        )
+        .await?
+        .into_iter()
+        .find(|dkg_task| dkg_task.coordinator_address == coordinator_address);
+    let group_epoch = dkg_task.as_ref().map(|dkg_task| dkg_task.epoch);
+
+    // the DKG keys are accepted as the members did, by the requirement as of the assignment
+    let requirement_block_height = match dkg_task.as_ref() {
+        Some(dkg_task) => dkg_task.assignment_block_height as u64,
+        None => chain_identity
+            .get_provider()
+            .get_block_number()
+            .await?
+            .as_u64(),
+    };
+    let node_registry_address = context.node_registry_address().await?;
+    let is_dkg_key_proof_required = context
+        .chain_identity(main_chain_id)?
+        .build_node_registry_client(node_registry_address)
+        .is_dkg_key_proof_required(requirement_block_height)
+        .await?;
+
+    let participants = coordinator_client.get_participants().await?;
+    let (threshold, dkg_keys) = coordinator_client.get_dkg_keys().await?;
+    let transcript = DKGTranscript {
+        threshold,
+        dkg_keys,
+        shares: coordinator_client.get_shares().await?,
+        responses: coordinator_client.get_responses().await?,
+        justifications: coordinator_client.get_justifications().await?,
+        is_dkg_key_proof_required,
+    };
+
+    let report = audit_transcript::<PC, S>(&transcript)?;
+
+    let disqualified_nodes = report
+        .dealers
+        .iter()
+        .filter(|dealer| {
+            report
+                .disqualified_node_indices
+                .contains(&dealer.node_index)
+        })
+        .map(|dealer| participants[dealer.participant_index])
+        .collect::<Vec<_>>();
+
+    // the controller only keeps the commit results of the latest dkg of the group,
+    // and the one agreed by the most committers is taken
+    let on_chain_disqualified_nodes = match group_epoch {
+        Some(group_epoch) => {
+            ControllerViews::<PC>::get_dkg_commit_results(&controller_client, *group_index)
+                .await?
+                .into_iter()
+                .filter(|commit_result| commit_result.group_epoch == group_epoch)
+                .max_by_key(|commit_result| commit_result.committers.len())
+                .map(|commit_result| commit_result.disqualified_nodes)
+        }
+        None => None,
+    };
+    let matches_on_chain =
+        on_chain_disqualified_nodes
+            .as_ref()
+            .map(|on_chain_disqualified_nodes| {
+                on_chain_disqualified_nodes.iter().collect::<BTreeSet<_>>()
+                    == disqualified_nodes.iter().collect::<BTreeSet<_>>()
+            });
+
+    let output = serde_json::json!({
+        "group_index": group_index,
+        "group_epoch": group_epoch,
+        "coordinator_address": coordinator_address,
+        "participants": participants,
+        "disqualified_nodes": disqualified_nodes,
+        "on_chain_disqualified_nodes": on_chain_disqualified_nodes,
+        "matches_on_chain": matches_on_chain,
+        "is_dkg_key_proof_required": is_dkg_key_proof_required,
+        "report": report,
+    });
+
+    Ok(Some(serde_json::to_string_pretty(&output)?))
+}
+
+fn parse_bls_result_cache_state(state: &str) -> BlsResultCacheState {
+    match state {
+        "not-committed" => BlsResultCacheState::NotCommitted,
+        "committing" => BlsResultCacheState::Committing,
+        "committed" => BlsResultCacheState::Committed,
+        "committed-by-others" => BlsResultCacheState::CommittedByOthers,
+        "expired" => BlsResultCacheState::Expired,
+        "faulty" => BlsResultCacheState::Faulty,
+        _ => panic!("Unknown signature result state {:?}", state),
+    }
+}
+
+fn format_next_cursor(next_cursor: Option<Vec<u8>>) -> String {
+    next_cursor.map_or("none, this is the last page".to_string(), |c| {
+        format!("0x{}", hex::encode(c))
+    })
+}
+
 fn history<PC: Curve>(
     _args: ArgMatches,
     context: &mut Context<PC>,
@@ -1548,7 +1940,7 @@ fn read_file_line_by_line(filepath: PathBuf) -> anyhow::Result<String> {
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
 
-    let config = Config::load(opt.config_path);
+    let config = Config::load(opt.config_path)?;
 
     let wallet = build_wallet_from_config(config.get_account())?;
 
@@ -1583,6 +1975,7 @@ async fn main() -> anyhow::Result<()> {
             .get_time_limits()
             .contract_transaction_retry_descriptor,
         config.get_time_limits().contract_view_retry_descriptor,
+        config.get_private_submission().cloned(),
     );
 
     let boxed_main_chain_identity: ChainIdentityHandlerType<G2Curve> =
@@ -1619,6 +2012,7 @@ async fn main() -> anyhow::Result<()> {
             relayed_chain
                 .get_time_limits()
                 .contract_view_retry_descriptor,
+            relayed_chain.get_private_submission().cloned(),
         );
 
         let boxed_relayed_chain_identity: ChainIdentityHandlerType<G2Curve> =
@@ -1719,6 +2113,10 @@ async fn main() -> anyhow::Result<()> {
                     .about("Get group info by index")
                     .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id in decimal format"))
                     .arg(Arg::new("group-index").required(true).help("group index").value_parser(value_parser!(usize)))
+                ).subcommand(
+                    Command::new("groups").visible_alias("gs")
+                    .about("Get info of all groups in batch")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id in decimal format"))
                 ).subcommand(
                     Command::new("valid-group-indices").visible_alias("vgi")
                     .about("Get valid group indices which are ready for randomness generation")
@@ -1825,6 +2223,9 @@ async fn main() -> anyhow::Result<()> {
                     .arg(Arg::new("path").required(true).help("path to mnemonic file").value_parser(value_parser!(PathBuf)))
                     .arg(Arg::new("password").required(true).help("password to encrypt hd-wallet"))
                     .arg(Arg::new("derivation-path").required(false).help("derivation path, default is m/44'/60'/0'/0/0"))
+                ).subcommand(
+                    Command::new("management-token").visible_alias("mt")
+                    .about("Generate a management token and its hash to be configured in management_access")
                 ).about("Generate node identity(wallet) corresponding to ARPA node format"),
             generate
         ).with_command_async(
@@ -1846,8 +2247,79 @@ async fn main() -> anyhow::Result<()> {
                 .subcommand(
                     Command::new("list-fixed-tasks").visible_alias("lft")
                     .about("List fixed tasks of the node")
+                ).subcommand(
+                    Command::new("list-randomness-tasks").visible_alias("lrt")
+                    .about("List randomness tasks in the node database, in the order of assignment block heights")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id in decimal format"))
+                    .arg(Arg::new("handled").long("handled").value_parser(value_parser!(bool)).required(false).help("only list handled(true) or unhandled(false) tasks"))
+                    .arg(Arg::new("from").long("from").value_parser(value_parser!(usize)).required(false).help("lowest assignment block height, inclusive"))
+                    .arg(Arg::new("to").long("to").value_parser(value_parser!(usize)).required(false).help("highest assignment block height, inclusive"))
+                    .arg(Arg::new("limit").long("limit").value_parser(value_parser!(u32)).required(false).help("maximum number of items to list, 100 by default and 1000 at most"))
+                    .arg(Arg::new("cursor").long("cursor").required(false).help("next cursor in hex format returned by the previous listing, to list the items after it"))
+                ).subcommand(
+                    Command::new("get-randomness-task").visible_alias("grt")
+                    .about("Get randomness task in the node database by request id")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id in decimal format"))
+                    .arg(Arg::new("request-id").required(true).help("request id in hex format"))
+                ).subcommand(
+                    Command::new("list-signature-results").visible_alias("lsr")
+                    .about("List signature results collected by the node as a committer, in the order of assignment block heights")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id in decimal format"))
+                    .arg(Arg::new("state").long("state").value_parser(["not-committed", "committing", "committed", "committed-by-others", "expired", "faulty"]).required(false).help("only list signature results in the state"))
+                    .arg(Arg::new("from").long("from").value_parser(value_parser!(usize)).required(false).help("lowest assignment block height, inclusive"))
+                    .arg(Arg::new("to").long("to").value_parser(value_parser!(usize)).required(false).help("highest assignment block height, inclusive"))
+                    .arg(Arg::new("limit").long("limit").value_parser(value_parser!(u32)).required(false).help("maximum number of items to list, 100 by default and 1000 at most"))
+                    .arg(Arg::new("cursor").long("cursor").required(false).help("next cursor in hex format returned by the previous listing, to list the items after it"))
+                ).subcommand(
+                    Command::new("get-signature-result").visible_alias("gsr")
+                    .about("Get signature result collected by the node as a committer by request id")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id in decimal format"))
+                    .arg(Arg::new("request-id").required(true).help("request id in hex format"))
+                ).subcommand(
+                    Command::new("watch").visible_alias("w")
+                    .about("Watch events and structured logs of the node live")
+                    .arg(Arg::new("topic").short('t').long("topic").action(ArgAction::Append).required(false).help("topic of events or type of logs to watch, e.g. NewRandomnessTask, DKGPhase or TaskReceived, all by default"))
+                    .arg(Arg::new("chain-id").short('c').long("chain-id").action(ArgAction::Append).value_parser(value_parser!(usize)).required(false).help("chain id in decimal format to watch, all by default"))
+                    .arg(Arg::new("with-logs").short('l').long("with-logs").action(ArgAction::SetTrue).required(false).help("watch structured logs as well as events"))
                 ).about("Connect to the node client and inspect the node status"),
                 |args, context| Box::pin(inspect(args, context)),
+        ).with_command_async(
+            Command::new("manage")
+                .subcommand(
+                    Command::new("reload-config").visible_alias("rc")
+                    .about("Reload the config of the running node, the changes which require a restart are rejected as a whole")
+                    .arg(Arg::new("config-path").short('p').long("config-path").required(false).help("config file to reload from, the one the node started with by default"))
+                    .arg(Arg::new("dry-run").short('d').long("dry-run").action(ArgAction::SetTrue).required(false).help("only show the changes without applying them"))
+                    .arg(Arg::new("confirmation-token").short('t').long("confirmation-token").required(false).help("confirmation token, if the node requires one for destructive operations"))
+                )
+                .subcommand(
+                    Command::new("add-relayed-chain").visible_alias("arc")
+                    .about("Start a relayed chain configured in the config file on the running node")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id of the relayed chain in decimal format"))
+                    .arg(Arg::new("config-path").short('p').long("config-path").required(false).help("config file to read the relayed chain from, the one the node started with by default"))
+                    .arg(Arg::new("confirmation-token").short('t').long("confirmation-token").required(false).help("confirmation token, if the node requires one for destructive operations"))
+                )
+                .subcommand(
+                    Command::new("remove-relayed-chain").visible_alias("rrc")
+                    .about("Stop a relayed chain on the running node after its in-flight tasks are fulfilled")
+                    .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(usize)).help("chain id of the relayed chain in decimal format"))
+                    .arg(Arg::new("drain-timeout").short('d').long("drain-timeout").required(false).value_parser(value_parser!(u64)).help("seconds to wait for the in-flight tasks, the relayed chain is kept if they are not fulfilled in time, 300 by default"))
+                    .arg(Arg::new("confirmation-token").short('t').long("confirmation-token").required(false).help("confirmation token, if the node requires one for destructive operations"))
+                )
+                .subcommand(
+                    Command::new("shutdown-node").visible_alias("sn")
+                    .about("Shut down the running node after its in-flight tasks are drained, it is refused during a DKG phase unless forced")
+                    .arg(Arg::new("force").short('f').long("force").action(ArgAction::SetTrue).required(false).help("shut down even if the node is in a DKG phase"))
+                    .arg(Arg::new("drain-timeout").short('d').long("drain-timeout").required(false).value_parser(value_parser!(u64)).help("seconds to wait for the in-flight tasks, shutdown_drain_timeout_millis of time_limits by default"))
+                    .arg(Arg::new("confirmation-token").short('t').long("confirmation-token").required(false).help("confirmation token, if the node requires one for destructive operations"))
+                ).about("Connect to the node client and manage the running node"),
+                |args, context| Box::pin(manage(args, context)),
+        ).with_command_async(
+            Command::new("audit-dkg")
+                .arg(Arg::new("group-index").required(true).value_parser(value_parser!(usize)).help("group index in decimal format"))
+                .arg(Arg::new("coordinator-address").short('a').long("coordinator-address").required(false).help("coordinator address of the dkg to audit in hex format, the running one of the group by default"))
+                .about("Re-verify the public dkg transcript on the coordinator and report the misbehaviour of each dealer in json, no secret key is needed"),
+                |args, context| Box::pin(audit_dkg::<_, G2Scheme>(args, context)),
         ).with_on_after_command_async(|context| Box::pin(update_prompt(context)));
 
     repl.run_async().await?;
@@ -0,0 +1,102 @@
+use arpa_core::{PartialSignature, RandomnessTask};
+use ethers::types::Address;
+use std::collections::{BTreeMap, HashMap};
+
+/// A randomness fulfillment which is too expensive to be committed at the moment.
+#[derive(Debug, Clone)]
+pub struct DeferredFulfillment {
+    pub group_index: usize,
+    pub randomness_task: RandomnessTask,
+    pub signature: Vec<u8>,
+    pub partial_signatures: HashMap<Address, PartialSignature>,
+    pub deferred_block_height: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct DeferredFulfillmentQueue {
+    fulfillments: BTreeMap<Vec<u8>, DeferredFulfillment>,
+}
+
+impl DeferredFulfillmentQueue {
+    pub fn new() -> Self {
+        DeferredFulfillmentQueue::default()
+    }
+
+    /// Parks the fulfillment, returns false if it has already been deferred.
+    pub fn defer(&mut self, fulfillment: DeferredFulfillment) -> bool {
+        let request_id = fulfillment.randomness_task.request_id.clone();
+
+        if self.fulfillments.contains_key(&request_id) {
+            return false;
+        }
+
+        self.fulfillments.insert(request_id, fulfillment);
+
+        true
+    }
+
+    pub fn remove(&mut self, request_id: &[u8]) -> Option<DeferredFulfillment> {
+        self.fulfillments.remove(request_id)
+    }
+
+    pub fn contains(&self, request_id: &[u8]) -> bool {
+        self.fulfillments.contains_key(request_id)
+    }
+
+    pub fn get_all(&self) -> Vec<DeferredFulfillment> {
+        self.fulfillments.values().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fulfillments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fulfillments.is_empty()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{DeferredFulfillment, DeferredFulfillmentQueue};
+    use arpa_core::{RandomnessRequestType, RandomnessTask};
+    use ethers::types::{Address, U256};
+    use std::collections::HashMap;
+
+    fn build_deferred_fulfillment(request_id: Vec<u8>) -> DeferredFulfillment {
+        DeferredFulfillment {
+            group_index: 0,
+            randomness_task: RandomnessTask {
+                request_id,
+                subscription_id: 1,
+                group_index: 0,
+                request_type: RandomnessRequestType::Randomness,
+                params: vec![],
+                requester: Address::zero(),
+                seed: U256::from(42),
+                request_confirmations: 6,
+                callback_gas_limit: 100000,
+                callback_max_gas_price: U256::from(1_000_000_000u64),
+                assignment_block_height: 100,
+            },
+            signature: vec![],
+            partial_signatures: HashMap::new(),
+            deferred_block_height: 100,
+        }
+    }
+
+    #[test]
+    fn test_defer_and_remove() {
+        let mut queue = DeferredFulfillmentQueue::new();
+
+        assert!(queue.defer(build_deferred_fulfillment(vec![1])));
+        assert!(!queue.defer(build_deferred_fulfillment(vec![1])));
+        assert!(queue.defer(build_deferred_fulfillment(vec![2])));
+        assert_eq!(queue.len(), 2);
+
+        assert!(queue.remove(&[1]).is_some());
+        assert!(!queue.contains(&[1]));
+        assert!(queue.remove(&[1]).is_none());
+        assert_eq!(queue.get_all().len(), 1);
+    }
+}
@@ -3,21 +3,55 @@ use crate::{
     event::types::Topic,
     subscriber::{DebuggableEvent, DebuggableSubscriber},
 };
+use arpa_core::metrics::{dec_event_queue_depth, inc_event_queue_depth};
 use async_trait::async_trait;
+use chrono::Utc;
 use log::error;
 use std::collections::HashMap;
+use tokio::sync::broadcast;
+
+/// Number of events buffered for each feed subscriber, a lagging subscriber misses the oldest ones.
+pub const EVENT_FEED_CAPACITY: usize = 1024;
+
+/// A published event for subscribers outside the node, e.g. the management server.
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    pub topic: Topic,
+    /// the event in debug format
+    pub content: String,
+    pub timestamp: i64,
+}
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct EventQueue {
     subscribers: HashMap<Topic, Vec<Box<dyn DebuggableSubscriber>>>,
+    event_feed: broadcast::Sender<EventRecord>,
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        EventQueue::new()
+    }
 }
 
 impl EventQueue {
     pub fn new() -> Self {
         EventQueue {
             subscribers: HashMap::new(),
+            event_feed: broadcast::channel(EVENT_FEED_CAPACITY).0,
         }
     }
+
+    /// Subscribes to all the events published from now on, regardless of their topics.
+    pub fn subscribe_feed(&self) -> broadcast::Receiver<EventRecord> {
+        self.event_feed.subscribe()
+    }
+
+    /// Drops the subscribers of the topics on the chain, e.g. when the relayed chain is removed.
+    pub fn unsubscribe_chain(&mut self, chain_id: usize) {
+        self.subscribers
+            .retain(|topic, _| topic.chain_id() != Some(chain_id));
+    }
 }
 
 impl EventSubscriber for EventQueue {
@@ -33,12 +67,27 @@ impl<E: DebuggableEvent + Clone + Send + Sync + 'static> EventPublisher<E> for E
     async fn publish(&self, event: E) {
         let topic = event.topic();
 
+        if self.event_feed.receiver_count() > 0 {
+            // the feed subscribers may be all dropped in the meantime
+            let _ = self.event_feed.send(EventRecord {
+                topic,
+                content: format!("{:?}", event),
+                timestamp: Utc::now().timestamp_millis(),
+            });
+        }
+
         if let Some(subscribers) = self.subscribers.get(&topic) {
+            let topic_label = format!("{:?}", topic);
+
+            inc_event_queue_depth(&topic_label);
+
             for subscriber in subscribers {
                 if let Err(e) = subscriber.notify(topic, &event).await {
                     error!("{:?}", e);
                 }
             }
+
+            dec_event_queue_depth(&topic_label);
         }
     }
 }
@@ -48,7 +97,7 @@ pub mod tests {
     use super::EventPublisher;
     use crate::{
         context::ChainIdentityHandlerType,
-        event::new_block::NewBlock,
+        event::{new_block::NewBlock, types::Topic},
         listener::block::BlockListener,
         queue::event_queue::EventQueue,
         subscriber::{block::BlockSubscriber, Subscriber},
@@ -112,6 +161,7 @@ pub mod tests {
             Address::random(),
             contract_transaction_retry_descriptor,
             contract_view_retry_descriptor,
+            None,
         );
 
         let chain_identity: Arc<RwLock<ChainIdentityHandlerType<G2Curve>>> =
@@ -143,4 +193,60 @@ pub mod tests {
 
         assert_eq!(10, block_cache.clone().read().await.get_block_height());
     }
+
+    #[tokio::test]
+    async fn test_unsubscribe_chain() {
+        let eq = Arc::new(RwLock::new(EventQueue::new()));
+
+        let block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>> =
+            Arc::new(RwLock::new(Box::new(InMemoryBlockInfoCache::new(1, 12))));
+        let relayed_block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>> =
+            Arc::new(RwLock::new(Box::new(InMemoryBlockInfoCache::new(2, 2))));
+
+        BlockSubscriber::new(1, block_cache.clone(), eq.clone())
+            .subscribe()
+            .await;
+        BlockSubscriber::new(2, relayed_block_cache.clone(), eq.clone())
+            .subscribe()
+            .await;
+
+        eq.write().await.unsubscribe_chain(2);
+
+        for chain_id in [1, 2] {
+            eq.read()
+                .await
+                .publish(NewBlock {
+                    chain_id,
+                    block_height: 10,
+                })
+                .await;
+        }
+
+        assert_eq!(10, block_cache.read().await.get_block_height());
+        assert_eq!(0, relayed_block_cache.read().await.get_block_height());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_feed() {
+        let eq = EventQueue::new();
+
+        eq.publish(NewBlock {
+            chain_id: 1,
+            block_height: 1,
+        })
+        .await;
+
+        let mut feed = eq.subscribe_feed();
+
+        eq.publish(NewBlock {
+            chain_id: 1,
+            block_height: 2,
+        })
+        .await;
+
+        let record = feed.try_recv().unwrap();
+        assert_eq!(record.topic, Topic::NewBlock(1));
+        assert!(record.content.contains("block_height: 2"));
+        assert!(feed.try_recv().is_err());
+    }
 }
@@ -5,6 +5,7 @@ use super::{
     subscriber::DebuggableSubscriber,
 };
 
+pub mod deferred_fulfillment_queue;
 pub mod event_queue;
 
 pub trait EventSubscriber {
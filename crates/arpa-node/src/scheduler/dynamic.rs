@@ -1,9 +1,14 @@
 use arpa_core::SchedulerResult;
 use futures::Future;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use tokio::{
     sync::{oneshot::channel, oneshot::Receiver},
     task::JoinHandle,
 };
+use tracing::Instrument;
 
 use super::{ComponentTaskType, DynamicTaskScheduler, TaskScheduler};
 
@@ -11,14 +16,37 @@ use super::{ComponentTaskType, DynamicTaskScheduler, TaskScheduler};
 pub struct SimpleDynamicTaskScheduler {
     // TODO access control
     pub dynamic_tasks: Vec<(Receiver<()>, Option<JoinHandle<()>>)>,
+    running_tasks: Arc<AtomicUsize>,
+}
+
+// Counts a dynamic task as running until it is finished or aborted
+struct RunningTaskGuard(Arc<AtomicUsize>);
+
+impl RunningTaskGuard {
+    fn new(running_tasks: Arc<AtomicUsize>) -> Self {
+        running_tasks.fetch_add(1, Ordering::SeqCst);
+        RunningTaskGuard(running_tasks)
+    }
+}
+
+impl Drop for RunningTaskGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl SimpleDynamicTaskScheduler {
     pub fn new() -> Self {
         SimpleDynamicTaskScheduler {
             dynamic_tasks: vec![],
+            running_tasks: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Returns the number of dynamic tasks which are neither finished nor aborted.
+    pub fn get_running_task_count(&self) -> usize {
+        self.running_tasks.load(Ordering::SeqCst)
+    }
 }
 
 impl TaskScheduler for SimpleDynamicTaskScheduler {
@@ -32,11 +60,18 @@ impl TaskScheduler for SimpleDynamicTaskScheduler {
         let mut mdc = vec![];
         log_mdc::iter(|k, v| mdc.push((k.to_owned(), v.to_owned())));
 
-        tokio::spawn(async move {
-            log_mdc::extend(mdc);
-            future.await;
-            drop(send);
-        });
+        let guard = RunningTaskGuard::new(self.running_tasks.clone());
+
+        // like the mdc, the spawned task inherits the current span of the caller
+        tokio::spawn(
+            async move {
+                log_mdc::extend(mdc);
+                future.await;
+                drop(send);
+                drop(guard);
+            }
+            .in_current_span(),
+        );
 
         self.dynamic_tasks.push((recv, None));
 
@@ -58,7 +93,11 @@ impl DynamicTaskScheduler for SimpleDynamicTaskScheduler {
     {
         let (send, recv) = channel::<()>();
 
+        let guard = RunningTaskGuard::new(self.running_tasks.clone());
+
         let task = tokio::spawn(async move {
+            // the guard is dropped with the future if the task is aborted
+            let _guard = guard;
             future.await;
             drop(send);
         });
@@ -82,10 +121,50 @@ impl DynamicTaskScheduler for SimpleDynamicTaskScheduler {
 #[cfg(test)]
 pub mod tests {
 
+    use super::SimpleDynamicTaskScheduler;
+    use crate::scheduler::{DynamicTaskScheduler, TaskScheduler};
+    use arpa_core::{ComponentTaskType, ListenerType};
     use std::time::Duration;
     use tokio::task;
     use tokio::time;
 
+    #[tokio::test]
+    async fn test_running_task_count() {
+        let mut ts = SimpleDynamicTaskScheduler::new();
+
+        ts.add_task(
+            ComponentTaskType::Listener(0, ListenerType::Block),
+            time::sleep(Duration::from_millis(100)),
+        )
+        .unwrap();
+        ts.add_task_with_shutdown_signal(
+            time::sleep(Duration::from_secs(60)),
+            || async { false },
+            100,
+        );
+
+        assert_eq!(ts.get_running_task_count(), 2);
+
+        time::sleep(Duration::from_millis(500)).await;
+
+        assert_eq!(ts.get_running_task_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_running_task_count_of_aborted_task() {
+        let mut ts = SimpleDynamicTaskScheduler::new();
+
+        ts.add_task_with_shutdown_signal(
+            time::sleep(Duration::from_secs(60)),
+            || async { true },
+            10,
+        );
+
+        time::sleep(Duration::from_millis(500)).await;
+
+        assert_eq!(ts.get_running_task_count(), 0);
+    }
+
     #[tokio::test]
     async fn test() {
         let original_task = task::spawn(async {
@@ -57,9 +57,22 @@ impl FixedTaskScheduler for SimpleFixedTaskScheduler {
         Ok(())
     }
 
+    fn take(&mut self, task_type: &ComponentTaskType) -> SchedulerResult<JoinHandle<()>> {
+        self.fixed_tasks
+            .remove(task_type)
+            .ok_or(SchedulerError::TaskNotFound)
+    }
+
     fn get_tasks(&self) -> Vec<&ComponentTaskType> {
         self.fixed_tasks.keys().collect::<Vec<&ComponentTaskType>>()
     }
+
+    fn is_task_running(&self, task_type: &ComponentTaskType) -> bool {
+        self.fixed_tasks
+            .get(task_type)
+            .map(|handle| !handle.is_finished())
+            .unwrap_or(false)
+    }
 }
 
 #[cfg(test)]
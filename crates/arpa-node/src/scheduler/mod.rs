@@ -3,6 +3,7 @@ pub mod fixed;
 use arpa_core::{ComponentTaskType, SchedulerResult};
 use async_trait::async_trait;
 use futures::Future;
+use tokio::task::JoinHandle;
 
 pub trait TaskScheduler {
     fn add_task(
@@ -18,7 +19,11 @@ pub trait FixedTaskScheduler: TaskScheduler {
 
     async fn abort(&mut self, task_type: &ComponentTaskType) -> SchedulerResult<()>;
 
+    fn take(&mut self, task_type: &ComponentTaskType) -> SchedulerResult<JoinHandle<()>>;
+
     fn get_tasks(&self) -> Vec<&ComponentTaskType>;
+
+    fn is_task_running(&self, task_type: &ComponentTaskType) -> bool;
 }
 
 pub trait DynamicTaskScheduler: TaskScheduler {
@@ -0,0 +1,387 @@
+use crate::context::{
+    chain::Chain, types::GeneralContext, ChainIdentityHandlerType, Context, ContextFetcher,
+};
+use crate::queue::deferred_fulfillment_queue::DeferredFulfillmentQueue;
+use crate::scheduler::FixedTaskScheduler;
+use arpa_core::{metrics::get_listener_last_success, ComponentTaskType, DKGStatus, RpcServerType};
+use arpa_dal::{
+    cache::RandomnessResultCache, BlockInfoHandler, GroupInfoHandler, SignatureResultCacheHandler,
+};
+use ethers::providers::{Middleware, Provider, Ws};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use threshold_bls::{
+    group::Curve,
+    sig::{SignatureScheme, ThresholdScheme},
+};
+use tokio::{net::TcpStream, sync::RwLock, time::timeout};
+
+pub const HEALTH_CHECK_TIMEOUT_MILLIS: u64 = 5000;
+/// A chain is considered stalled if no new block has been seen for this many block times
+pub const BLOCK_STALENESS_FACTOR: u64 = 10;
+pub const MIN_BLOCK_STALENESS_SECONDS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthCheckKind {
+    /// Only checks the local state of the node, e.g. whether its tasks are still running
+    Liveness,
+    /// Also checks the dependencies of the node, e.g. the providers, the database and whether
+    /// the chains are still producing blocks
+    Readiness,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub committer_server: CommitterServerHealth,
+    pub database: Option<DatabaseHealth>,
+    pub chains: BTreeMap<usize, ChainHealth>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitterServerHealth {
+    pub healthy: bool,
+    pub endpoint: String,
+    pub running: bool,
+    pub bound: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseHealth {
+    pub healthy: bool,
+    pub integrity_check: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChainHealth {
+    pub healthy: bool,
+    pub description: String,
+    pub block: BlockHealth,
+    pub listeners: BTreeMap<String, ListenerHealth>,
+    pub provider: Option<ProviderHealth>,
+    pub group: GroupHealth,
+    pub backlog: BacklogHealth,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockHealth {
+    pub healthy: bool,
+    pub block_height: usize,
+    pub block_updated_at: u64,
+    pub seconds_since_last_block: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListenerHealth {
+    pub healthy: bool,
+    pub last_successful_listen: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProviderHealth {
+    pub healthy: bool,
+    pub block_number: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// The group of the node is reported for information only, since a node which is not a member
+/// of an available group, e.g. before its first DKG, is still ready to take part in one.
+#[derive(Debug, Serialize)]
+pub struct GroupHealth {
+    pub index: Option<usize>,
+    pub epoch: Option<usize>,
+    pub state: bool,
+    pub dkg_status: Option<DKGStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BacklogHealth {
+    pub unfulfilled_tasks: Option<usize>,
+    pub deferred_fulfillments: usize,
+}
+
+/// The handles of a chain which the health check needs, copied out of the context so that
+/// the context is not locked during the provider calls
+struct ChainSnapshot<PC: Curve> {
+    id: usize,
+    description: String,
+    block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>>,
+    group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<PC>>>>,
+    randomness_result_cache:
+        Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>,
+    deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
+    provider: Provider<Ws>,
+}
+
+pub async fn check_health<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    S: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: &RwLock<GeneralContext<PC, S>>,
+    kind: HealthCheckKind,
+) -> HealthReport
+where
+    <S as ThresholdScheme>::Error: Sync + Send,
+    <S as SignatureScheme>::Error: Sync + Send,
+{
+    // copy out what the checks need and release the context before any I/O below,
+    // so that a slow provider or database doesn't block the writers of the context
+    let (f_ts, committer_endpoint, db, chain_snapshots) = {
+        let context = context.read().await;
+
+        let mut chain_snapshots = vec![snapshot_chain(context.get_main_chain()).await];
+        for chain_id in context.get_supported_relayed_chains() {
+            if let Some(relayed_chain) = context.get_relayed_chain(chain_id) {
+                chain_snapshots.push(snapshot_chain(relayed_chain.as_ref()).await);
+            }
+        }
+
+        (
+            context.get_fixed_task_handler(),
+            context
+                .get_config()
+                .get_node_committer_rpc_endpoint()
+                .to_string(),
+            context.get_db().cloned(),
+            chain_snapshots,
+        )
+    };
+
+    // snapshot the task states so that the scheduler is not locked during the I/O below
+    let task_states = {
+        let f_ts = f_ts.read().await;
+
+        f_ts.get_tasks()
+            .into_iter()
+            .map(|task_type| (*task_type, f_ts.is_task_running(task_type)))
+            .collect::<Vec<_>>()
+    };
+
+    let committer_server = check_committer_server(&committer_endpoint, &task_states, kind).await;
+
+    let database = match (kind, db) {
+        (HealthCheckKind::Readiness, Some(db)) => Some(match db.integrity_check().await {
+            Ok(res) => DatabaseHealth {
+                healthy: res == "ok",
+                integrity_check: res,
+            },
+            Err(e) => DatabaseHealth {
+                healthy: false,
+                integrity_check: e.to_string(),
+            },
+        }),
+        _ => None,
+    };
+
+    let mut chains = BTreeMap::new();
+
+    for chain in chain_snapshots {
+        chains.insert(chain.id, check_chain(chain, &task_states, kind).await);
+    }
+
+    let healthy = committer_server.healthy
+        && database.as_ref().map(|d| d.healthy).unwrap_or(true)
+        && chains.values().all(|c| c.healthy);
+
+    HealthReport {
+        healthy,
+        committer_server,
+        database,
+        chains,
+    }
+}
+
+async fn snapshot_chain<PC, S, C>(chain: &C) -> ChainSnapshot<PC>
+where
+    PC: Curve,
+    S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>,
+    C: Chain<
+            PC,
+            S,
+            BlockInfoCache = Box<dyn BlockInfoHandler>,
+            GroupInfoCache = Box<dyn GroupInfoHandler<PC>>,
+            RandomnessResultCaches = Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>,
+            ChainIdentity = ChainIdentityHandlerType<PC>,
+        > + ?Sized,
+{
+    ChainSnapshot {
+        id: chain.id(),
+        description: chain.description().to_string(),
+        block_cache: chain.get_block_cache(),
+        group_cache: chain.get_group_cache(),
+        randomness_result_cache: chain.get_randomness_result_cache(),
+        deferred_fulfillment_queue: chain.get_deferred_fulfillment_queue(),
+        provider: chain
+            .get_chain_identity()
+            .read()
+            .await
+            .get_provider()
+            .clone(),
+    }
+}
+
+async fn check_committer_server(
+    endpoint: &str,
+    task_states: &[(ComponentTaskType, bool)],
+    kind: HealthCheckKind,
+) -> CommitterServerHealth {
+    let running = task_states.iter().any(|(task_type, running)| {
+        *task_type == ComponentTaskType::RpcServer(RpcServerType::Committer) && *running
+    });
+
+    let bound = match kind {
+        HealthCheckKind::Liveness => None,
+        HealthCheckKind::Readiness => Some(match endpoint.parse::<SocketAddr>() {
+            Ok(mut addr) => {
+                // the server listens on all interfaces, so we connect to it locally
+                if addr.ip().is_unspecified() {
+                    addr.set_ip(match addr.ip() {
+                        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    });
+                }
+
+                matches!(
+                    timeout(
+                        Duration::from_millis(HEALTH_CHECK_TIMEOUT_MILLIS),
+                        TcpStream::connect(addr)
+                    )
+                    .await,
+                    Ok(Ok(_))
+                )
+            }
+            Err(_) => false,
+        }),
+    };
+
+    CommitterServerHealth {
+        healthy: running && bound.unwrap_or(true),
+        endpoint: endpoint.to_string(),
+        running,
+        bound,
+    }
+}
+
+async fn check_chain<PC: Curve>(
+    chain: ChainSnapshot<PC>,
+    task_states: &[(ComponentTaskType, bool)],
+    kind: HealthCheckKind,
+) -> ChainHealth {
+    let chain_id = chain.id;
+
+    let block = {
+        let block_cache = chain.block_cache.read().await;
+
+        let block_updated_at = block_cache.get_block_updated_at();
+        let seconds_since_last_block = SystemTime::now()
+            .duration_since(block_updated_at)
+            .unwrap_or_default()
+            .as_secs();
+        let staleness_threshold = (block_cache.get_block_time() as u64 * BLOCK_STALENESS_FACTOR)
+            .max(MIN_BLOCK_STALENESS_SECONDS);
+
+        BlockHealth {
+            healthy: seconds_since_last_block <= staleness_threshold,
+            block_height: block_cache.get_block_height(),
+            block_updated_at: block_updated_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            seconds_since_last_block,
+        }
+    };
+
+    let listeners = task_states
+        .iter()
+        .filter_map(|(task_type, running)| match task_type {
+            ComponentTaskType::Listener(id, l_type) if *id == chain_id => {
+                let l_type = format!("{:?}", l_type);
+
+                Some((
+                    l_type.clone(),
+                    ListenerHealth {
+                        healthy: *running,
+                        last_successful_listen: get_listener_last_success(chain_id, &l_type),
+                    },
+                ))
+            }
+            _ => None,
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let provider = match kind {
+        HealthCheckKind::Liveness => None,
+        HealthCheckKind::Readiness => Some(
+            match timeout(
+                Duration::from_millis(HEALTH_CHECK_TIMEOUT_MILLIS),
+                chain.provider.get_block_number(),
+            )
+            .await
+            {
+                Ok(Ok(block_number)) => ProviderHealth {
+                    healthy: true,
+                    block_number: Some(block_number.as_u64()),
+                    error: None,
+                },
+                Ok(Err(e)) => ProviderHealth {
+                    healthy: false,
+                    block_number: None,
+                    error: Some(e.to_string()),
+                },
+                Err(_) => ProviderHealth {
+                    healthy: false,
+                    block_number: None,
+                    error: Some("timeout".to_string()),
+                },
+            },
+        ),
+    };
+
+    let group = {
+        let group_cache = chain.group_cache.read().await;
+
+        GroupHealth {
+            index: group_cache.get_index().ok(),
+            epoch: group_cache.get_epoch().ok(),
+            state: group_cache.get_state().unwrap_or(false),
+            dkg_status: group_cache.get_dkg_status().ok(),
+        }
+    };
+
+    let backlog = BacklogHealth {
+        unfulfilled_tasks: chain
+            .randomness_result_cache
+            .read()
+            .await
+            .get_unfulfilled_count()
+            .await
+            .ok(),
+        deferred_fulfillments: chain.deferred_fulfillment_queue.read().await.len(),
+    };
+
+    // a stalled chain is a problem of the provider or the chain rather than of the node itself,
+    // so it only fails the readiness check, otherwise the node would be restarted for nothing
+    let healthy = (kind == HealthCheckKind::Liveness || block.healthy)
+        && listeners.values().all(|l| l.healthy)
+        && provider.as_ref().map(|p| p.healthy).unwrap_or(true);
+
+    ChainHealth {
+        healthy,
+        description: chain.description,
+        block,
+        listeners,
+        provider,
+        group,
+        backlog,
+    }
+}
@@ -1,7 +1,11 @@
+pub mod health;
+
+use self::health::{check_health, HealthCheckKind};
 use crate::context::types::GeneralContext;
 use crate::context::ContextFetcher;
 use crate::scheduler::FixedTaskScheduler;
 use actix_web::{get, web, App, HttpResponse, HttpServer, Responder};
+use arpa_core::metrics::encode_metrics;
 use std::sync::Arc;
 use threshold_bls::{
     group::Curve,
@@ -45,6 +49,74 @@ async fn health<
     }
 }
 
+async fn health_check<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    SS: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: web::Data<NodeContext<PC, SS>>,
+    kind: HealthCheckKind,
+) -> HttpResponse
+where
+    <SS as ThresholdScheme>::Error: Sync + Send,
+    <SS as SignatureScheme>::Error: Sync + Send,
+{
+    let report = check_health(&context.into_inner(), kind).await;
+
+    if report.healthy {
+        HttpResponse::Ok().json(report)
+    } else {
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
+
+async fn health_live<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    SS: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: web::Data<NodeContext<PC, SS>>,
+) -> impl Responder
+where
+    <SS as ThresholdScheme>::Error: Sync + Send,
+    <SS as SignatureScheme>::Error: Sync + Send,
+{
+    health_check(context, HealthCheckKind::Liveness).await
+}
+
+async fn health_ready<
+    PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
+    SS: SignatureScheme
+        + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+>(
+    context: web::Data<NodeContext<PC, SS>>,
+) -> impl Responder
+where
+    <SS as ThresholdScheme>::Error: Sync + Send,
+    <SS as SignatureScheme>::Error: Sync + Send,
+{
+    health_check(context, HealthCheckKind::Readiness).await
+}
+
+#[get("/metrics")]
+async fn metrics() -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(encode_metrics())
+}
+
 pub async fn start_statistics_server<
     PC: Curve + std::fmt::Debug + Clone + Sync + Send + 'static,
     SS: SignatureScheme
@@ -65,6 +137,9 @@ where
         App::new()
             .app_data(web::Data::new(context.clone()))
             .route("/health", web::get().to(health::<PC, SS>))
+            .route("/health/live", web::get().to(health_live::<PC, SS>))
+            .route("/health/ready", web::get().to(health_ready::<PC, SS>))
+            .service(metrics)
             .service(greet)
     })
     .bind(endpoint)
@@ -81,6 +156,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithm::blind::SimpleBlindSignatureCore;
+    use crate::algorithm::decryption::SimpleDecryptionCore;
+    use crate::algorithm::fulfillment::ProfitabilityFulfillmentPolicy;
     use crate::context::chain::types::GeneralMainChain;
     use crate::scheduler::TaskScheduler;
     use actix_web::{
@@ -88,12 +166,14 @@ mod tests {
         test,
     };
     use arpa_core::{
-        ComponentTaskType, Config, GeneralMainChainIdentity, ListenerType, RandomnessTask,
+        BlindSignatureTask, ComponentTaskType, Config, DecryptionTask, GeneralMainChainIdentity,
+        ListenerType, RandomnessTask,
     };
     use arpa_dal::{
         cache::{
-            InMemoryBLSTasksQueue, InMemoryGroupInfoCache, InMemoryNodeInfoCache,
-            InMemorySignatureResultCache, RandomnessResultCache,
+            BlindSignatureResultCache, DecryptionResultCache, InMemoryBLSTasksQueue,
+            InMemoryGroupInfoCache, InMemoryNodeInfoCache, InMemorySignatureResultCache,
+            RandomnessResultCache,
         },
         BLSTasksHandler, GroupInfoHandler, NodeInfoHandler, SignatureResultCacheHandler,
     };
@@ -102,7 +182,10 @@ mod tests {
         types::Address,
         utils::Anvil,
     };
-    use threshold_bls::{curve::bn254::G2Curve, schemes::bn254::G2Scheme};
+    use threshold_bls::{
+        curve::bn254::{G2Curve, PairingCurve},
+        schemes::bn254::G2Scheme,
+    };
 
     async fn build_context() -> NodeContext<G2Curve, G2Scheme> {
         let config = Config::default();
@@ -128,6 +211,24 @@ mod tests {
             RandomnessResultCache,
         >::new())));
 
+        let decryption_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<DecryptionTask>>>> =
+            Arc::new(RwLock::new(Box::new(InMemoryBLSTasksQueue::new())));
+
+        let decryption_result_cache: Arc<
+            RwLock<Box<dyn SignatureResultCacheHandler<DecryptionResultCache>>>,
+        > = Arc::new(RwLock::new(Box::new(InMemorySignatureResultCache::<
+            DecryptionResultCache,
+        >::new())));
+
+        let blind_signature_tasks_cache: Arc<RwLock<Box<dyn BLSTasksHandler<BlindSignatureTask>>>> =
+            Arc::new(RwLock::new(Box::new(InMemoryBLSTasksQueue::new())));
+
+        let blind_signature_result_cache: Arc<
+            RwLock<Box<dyn SignatureResultCacheHandler<BlindSignatureResultCache>>>,
+        > = Arc::new(RwLock::new(Box::new(InMemorySignatureResultCache::<
+            BlindSignatureResultCache,
+        >::new())));
+
         let avnil = Anvil::new().spawn();
 
         let provider = Arc::new(Provider::<Ws>::connect(avnil.ws_endpoint()).await.unwrap());
@@ -149,6 +250,7 @@ mod tests {
             Address::random(),
             contract_transaction_retry_descriptor,
             contract_view_retry_descriptor,
+            None,
         );
 
         let main_chain = GeneralMainChain::<G2Curve, G2Scheme>::new(
@@ -159,8 +261,19 @@ mod tests {
             group_cache.clone(),
             randomness_tasks_cache,
             randomness_result_cache,
+            decryption_tasks_cache,
+            decryption_result_cache,
+            Arc::new(SimpleDecryptionCore::<PairingCurve>::new()),
+            blind_signature_tasks_cache,
+            blind_signature_result_cache,
+            Arc::new(SimpleBlindSignatureCore::<PairingCurve>::new()),
+            Arc::new(ProfitabilityFulfillmentPolicy::from(
+                *config.get_fulfillment_policy(),
+            )),
             *config.get_time_limits(),
             config.get_listeners().clone(),
+            config.get_dkg_protocol(),
+            config.get_dkg_board(),
         );
 
         let context = GeneralContext::new(main_chain, config);
@@ -202,6 +315,49 @@ mod tests {
         assert!(resp.status().is_client_error());
     }
 
+    #[actix_web::test]
+    async fn test_health_live() {
+        let context = build_context().await;
+        let main_chain_id = context.read().await.get_config().get_main_chain_id();
+        let app = App::new().app_data(web::Data::new(context)).route(
+            "/health/live",
+            web::get().to(health_live::<G2Curve, G2Scheme>),
+        );
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::get().uri("/health/live").to_request();
+        let resp = test::call_service(&app, req).await;
+        // the committer server is not started in the test context
+        assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["healthy"], false);
+        assert_eq!(body["committer_server"]["running"], false);
+        assert!(body["database"].is_null());
+
+        let main_chain = &body["chains"][main_chain_id.to_string()];
+        assert_eq!(main_chain["block"]["healthy"], true);
+        assert!(main_chain["provider"].is_null());
+        assert_eq!(main_chain["group"]["state"], false);
+        assert_eq!(main_chain["backlog"]["unfulfilled_tasks"], 0);
+    }
+
+    #[actix_web::test]
+    async fn test_metrics() {
+        arpa_core::metrics::inc_listener_interruptions(1, "BlockListener");
+
+        let app = App::new().service(metrics);
+        let app = test::init_service(app).await;
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = test::read_body(resp).await;
+        assert!(String::from_utf8(body.to_vec()).unwrap().contains(
+            "arpa_listener_interruptions_total{chain_id=\"1\",listener=\"BlockListener\"} 1"
+        ));
+    }
+
     #[actix_web::test]
     async fn test_greet() {
         let app = App::new().service(greet);
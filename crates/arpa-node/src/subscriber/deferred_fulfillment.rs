@@ -0,0 +1,165 @@
+use super::{
+    randomness_signature_aggregation::{FulfillRandomnessHandler, GeneralFulfillRandomnessHandler},
+    DebuggableEvent, DebuggableSubscriber, Subscriber,
+};
+use crate::{
+    algorithm::fulfillment::FulfillmentPolicy,
+    context::ChainIdentityHandlerType,
+    error::NodeResult,
+    event::types::Topic,
+    queue::{
+        deferred_fulfillment_queue::{DeferredFulfillment, DeferredFulfillmentQueue},
+        event_queue::EventQueue,
+        EventSubscriber,
+    },
+    scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
+    telemetry::randomness_task_span,
+};
+use arpa_core::{ComponentTaskType, SubscriberType};
+use arpa_dal::cache::RandomnessResultCache;
+use arpa_dal::{BlockInfoHandler, SignatureResultCacheHandler};
+use async_trait::async_trait;
+use ethers::types::Address;
+use log::{debug, error};
+use std::{marker::PhantomData, sync::Arc};
+use threshold_bls::group::Curve;
+use tokio::sync::RwLock;
+use tracing::{info_span, Instrument};
+
+/// Re-evaluates deferred randomness fulfillments on every new block until they become
+/// profitable, get fulfilled by others or expire.
+#[derive(Debug)]
+pub struct DeferredFulfillmentSubscriber<PC: Curve> {
+    chain_id: usize,
+    id_address: Address,
+    chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
+    block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>>,
+    randomness_signature_cache:
+        Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>,
+    fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+    deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
+    is_reevaluating: Arc<RwLock<bool>>,
+    eq: Arc<RwLock<EventQueue>>,
+    ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
+    pc: PhantomData<PC>,
+}
+
+impl<PC: Curve> DeferredFulfillmentSubscriber<PC> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        chain_id: usize,
+        id_address: Address,
+        chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
+        block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>>,
+        randomness_signature_cache: Arc<
+            RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>,
+        >,
+        fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+        deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
+        eq: Arc<RwLock<EventQueue>>,
+        ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
+    ) -> Self {
+        DeferredFulfillmentSubscriber {
+            chain_id,
+            id_address,
+            chain_identity,
+            block_cache,
+            randomness_signature_cache,
+            fulfillment_policy,
+            deferred_fulfillment_queue,
+            is_reevaluating: Arc::new(RwLock::new(false)),
+            eq,
+            ts,
+            pc: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber
+    for DeferredFulfillmentSubscriber<PC>
+{
+    async fn notify(&self, topic: Topic, _payload: &(dyn DebuggableEvent)) -> NodeResult<()> {
+        debug!("{:?}", topic);
+
+        if self.deferred_fulfillment_queue.read().await.is_empty() {
+            return Ok(());
+        }
+
+        // skip this block if the last round of re-evaluation has not finished yet
+        {
+            let mut is_reevaluating = self.is_reevaluating.write().await;
+            if *is_reevaluating {
+                return Ok(());
+            }
+            *is_reevaluating = true;
+        }
+
+        let deferred_fulfillments = self.deferred_fulfillment_queue.read().await.get_all();
+
+        let handler = GeneralFulfillRandomnessHandler::new(
+            self.id_address,
+            self.chain_identity.clone(),
+            self.block_cache.clone(),
+            self.randomness_signature_cache.clone(),
+            self.fulfillment_policy.clone(),
+            self.deferred_fulfillment_queue.clone(),
+        );
+
+        let is_reevaluating = self.is_reevaluating.clone();
+
+        let res = self.ts.write().await.add_task(
+            ComponentTaskType::Subscriber(self.chain_id, SubscriberType::DeferredFulfillment),
+            async move {
+                for DeferredFulfillment {
+                    group_index,
+                    randomness_task,
+                    signature,
+                    partial_signatures,
+                    ..
+                } in deferred_fulfillments
+                {
+                    let span = info_span!(
+                        parent: &randomness_task_span(&randomness_task.request_id),
+                        "fulfillment",
+                        deferred = true
+                    );
+
+                    if let Err(e) = handler
+                        .handle(group_index, randomness_task, signature, partial_signatures)
+                        .instrument(span)
+                        .await
+                    {
+                        error!("{:?}", e);
+                    }
+                }
+
+                *is_reevaluating.write().await = false;
+            },
+        );
+
+        if let Err(e) = res {
+            *self.is_reevaluating.write().await = false;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe(self) {
+        let eq = self.eq.clone();
+
+        let chain_id = self.chain_id;
+
+        let subscriber = Box::new(self);
+
+        eq.write()
+            .await
+            .subscribe(Topic::NewBlock(chain_id), subscriber);
+    }
+}
+
+impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> DebuggableSubscriber
+    for DeferredFulfillmentSubscriber<PC>
+{
+}
@@ -1,16 +1,25 @@
 use super::{DebuggableEvent, DebuggableSubscriber, Subscriber};
 use crate::{
-    algorithm::dkg::{AllPhasesDKGCore, DKGCore},
+    algorithm::{
+        dkg::{AllPhasesDKGCore, DKGCore},
+        off_chain_board::{DKGBundleStore, OffChainBoard},
+    },
+    committer::client::GeneralDKGBundleFetcher,
     context::ChainIdentityHandlerType,
     error::NodeResult,
     event::{run_dkg::RunDKG, types::Topic},
-    queue::{event_queue::EventQueue, EventSubscriber},
+    queue::{event_queue::EventQueue, EventPublisher, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, DynamicTaskScheduler},
 };
-use arpa_contract_client::{controller::ControllerTransactions, error::ContractClientError};
+use arpa_contract_client::{
+    controller::{ControllerTransactions, ControllerViews},
+    error::ContractClientError,
+    node_registry::NodeRegistryViews,
+};
 use arpa_core::{
     log::{build_group_related_payload, build_group_related_transaction_receipt_payload, LogType},
-    DKGStatus, DKGTask,
+    metrics::record_payload,
+    DKGBoard, DKGProtocol, DKGStatus, DKGTask,
 };
 use arpa_dal::{GroupInfoHandler, NodeInfoHandler};
 use async_trait::async_trait;
@@ -19,21 +28,31 @@ use ethers::types::U256;
 use log::{debug, error, info};
 use rand::{prelude::ThreadRng, RngCore};
 use std::{marker::PhantomData, sync::Arc};
-use threshold_bls::group::Curve;
+use threshold_bls::{group::Curve, sig::SignatureScheme};
 use tokio::sync::RwLock;
 
 #[derive(Debug)]
-pub struct InGroupingSubscriber<PC: Curve> {
+pub struct InGroupingSubscriber<
+    PC: Curve,
+    S: SignatureScheme<Public = PC::Point, Private = PC::Scalar>,
+> {
     chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
     node_cache: Arc<RwLock<Box<dyn NodeInfoHandler<PC>>>>,
     group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<PC>>>>,
     eq: Arc<RwLock<EventQueue>>,
     ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
     c: PhantomData<PC>,
+    s: PhantomData<S>,
     dkg_wait_for_phase_interval_millis: u64,
+    dkg_protocol: DKGProtocol,
+    dkg_board: DKGBoard,
+    dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
 }
 
-impl<PC: Curve> InGroupingSubscriber<PC> {
+impl<PC: Curve, S: SignatureScheme<Public = PC::Point, Private = PC::Scalar>>
+    InGroupingSubscriber<PC, S>
+{
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
         node_cache: Arc<RwLock<Box<dyn NodeInfoHandler<PC>>>>,
@@ -41,6 +60,9 @@ impl<PC: Curve> InGroupingSubscriber<PC> {
         eq: Arc<RwLock<EventQueue>>,
         ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
         dkg_wait_for_phase_interval_millis: u64,
+        dkg_protocol: DKGProtocol,
+        dkg_board: DKGBoard,
+        dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
     ) -> Self {
         InGroupingSubscriber {
             chain_identity,
@@ -49,27 +71,50 @@ impl<PC: Curve> InGroupingSubscriber<PC> {
             eq,
             ts,
             c: PhantomData,
+            s: PhantomData,
             dkg_wait_for_phase_interval_millis,
+            dkg_protocol,
+            dkg_board,
+            dkg_bundle_store,
         }
     }
 }
 
-pub struct AllInOneDKGHandler<F: Fn() -> R, R: RngCore, PC: Curve> {
+pub struct AllInOneDKGHandler<
+    F: Fn() -> R,
+    R: RngCore,
+    PC: Curve,
+    S: SignatureScheme<Public = PC::Point, Private = PC::Scalar>,
+> {
     rng: F,
     chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
     node_cache: Arc<RwLock<Box<dyn NodeInfoHandler<PC>>>>,
     group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<PC>>>>,
     c: PhantomData<PC>,
+    s: PhantomData<S>,
     dkg_wait_for_phase_interval_millis: u64,
+    dkg_protocol: DKGProtocol,
+    dkg_board: DKGBoard,
+    dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
 }
 
-impl<F: Fn() -> R, R: RngCore, PC: Curve> AllInOneDKGHandler<F, R, PC> {
+impl<
+        F: Fn() -> R,
+        R: RngCore,
+        PC: Curve,
+        S: SignatureScheme<Public = PC::Point, Private = PC::Scalar>,
+    > AllInOneDKGHandler<F, R, PC, S>
+{
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         rng: F,
         chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
         node_cache: Arc<RwLock<Box<dyn NodeInfoHandler<PC>>>>,
         group_cache: Arc<RwLock<Box<dyn GroupInfoHandler<PC>>>>,
         dkg_wait_for_phase_interval_millis: u64,
+        dkg_protocol: DKGProtocol,
+        dkg_board: DKGBoard,
+        dkg_bundle_store: Arc<RwLock<DKGBundleStore>>,
     ) -> Self {
         AllInOneDKGHandler {
             rng,
@@ -77,7 +122,11 @@ impl<F: Fn() -> R, R: RngCore, PC: Curve> AllInOneDKGHandler<F, R, PC> {
             node_cache,
             group_cache,
             c: PhantomData,
+            s: PhantomData,
             dkg_wait_for_phase_interval_millis,
+            dkg_protocol,
+            dkg_board,
+            dkg_bundle_store,
         }
     }
 }
@@ -95,7 +144,8 @@ impl<
         F: Fn() -> R + Debug + Send + Sync + Copy + 'static,
         R: RngCore + 'static,
         PC: Curve + Sync + Send + 'static,
-    > DKGHandler<F, R> for AllInOneDKGHandler<F, R, PC>
+        S: SignatureScheme<Public = PC::Point, Private = PC::Scalar> + Sync + Send + 'static,
+    > DKGHandler<F, R> for AllInOneDKGHandler<F, R, PC, S>
 {
     async fn handle(&mut self, task: DKGTask) -> NodeResult<()>
     where
@@ -125,13 +175,65 @@ impl<
             .await
             .build_coordinator_client(task.coordinator_address);
 
-        let mut dkg_core =
-            AllPhasesDKGCore::new(coordinator_client, self.dkg_wait_for_phase_interval_millis);
+        // the requirement is read as of the assignment of the task,
+        // so that every member accepts the same DKG keys
+        let node_registry_address =
+            ControllerViews::<PC>::get_node_registry_address(&controller_client).await?;
 
-        match dkg_core
-            .run_dkg(dkg_private_key, node_rpc_endpoint, self.rng)
+        let is_dkg_key_proof_required = self
+            .chain_identity
+            .read()
             .await
-        {
+            .build_node_registry_client(node_registry_address)
+            .is_dkg_key_proof_required(task.assignment_block_height as u64)
+            .await?;
+
+        let dkg_result = match self.dkg_board {
+            DKGBoard::OnChain => {
+                let mut dkg_core = AllPhasesDKGCore::<_, PC, S>::new(
+                    coordinator_client,
+                    self.group_cache.clone(),
+                    task,
+                    self.dkg_wait_for_phase_interval_millis,
+                    self.dkg_protocol,
+                    is_dkg_key_proof_required,
+                    None,
+                );
+
+                dkg_core
+                    .run_dkg(dkg_private_key, node_rpc_endpoint, self.rng)
+                    .await
+            }
+            DKGBoard::OffChain => {
+                let board = OffChainBoard::<_, _, PC, S>::new(
+                    coordinator_client,
+                    GeneralDKGBundleFetcher,
+                    self.dkg_bundle_store.clone(),
+                    task_group_index,
+                    task_epoch,
+                    dkg_private_key.clone(),
+                    node_rpc_endpoint.clone(),
+                    self.dkg_protocol,
+                    self.dkg_wait_for_phase_interval_millis,
+                );
+
+                let mut dkg_core = AllPhasesDKGCore::<_, PC, S>::new(
+                    board,
+                    self.group_cache.clone(),
+                    task,
+                    self.dkg_wait_for_phase_interval_millis,
+                    self.dkg_protocol,
+                    is_dkg_key_proof_required,
+                    Some(self.dkg_bundle_store.clone()),
+                );
+
+                dkg_core
+                    .run_dkg(dkg_private_key, node_rpc_endpoint, self.rng)
+                    .await
+            }
+        };
+
+        match dkg_result {
             Ok(output) => match output.disqualified_node_indices.len() {
                 0 => {
                     let (public_key, partial_public_key, disqualified_nodes) = self
@@ -143,12 +245,12 @@ impl<
 
                     info!(
                         "{}",
-                        build_group_related_payload(
+                        record_payload(build_group_related_payload(
                             LogType::DKGGroupingFinished,
                             "DKG grouping finished.",
                             chain_id,
                             self.group_cache.read().await.get_group()?
-                        )
+                        ))
                     );
 
                     match controller_client
@@ -164,7 +266,7 @@ impl<
                         Ok(receipt) => {
                             info!(
                                 "{}",
-                                build_group_related_transaction_receipt_payload(
+                                record_payload(build_group_related_transaction_receipt_payload(
                                     LogType::DKGGroupingCommitted,
                                     "DKG grouping result committed.",
                                     chain_id,
@@ -173,34 +275,36 @@ impl<
                                     receipt.transaction_hash,
                                     receipt.gas_used.unwrap_or(U256::zero()),
                                     receipt.effective_gas_price.unwrap_or(U256::zero())
-                                )
+                                ))
                             );
                         }
                         Err(e) => match e {
                             ContractClientError::TransactionFailed(receipt) => {
                                 error!(
                                     "{}",
-                                    build_group_related_transaction_receipt_payload(
-                                        LogType::DKGGroupingCommitFailed,
-                                        "DKG grouping commit failed.",
-                                        chain_id,
-                                        self.group_cache.read().await.get_group()?,
-                                        None,
-                                        receipt.transaction_hash,
-                                        receipt.gas_used.unwrap_or(U256::zero()),
-                                        receipt.effective_gas_price.unwrap_or(U256::zero())
+                                    record_payload(
+                                        build_group_related_transaction_receipt_payload(
+                                            LogType::DKGGroupingCommitFailed,
+                                            "DKG grouping commit failed.",
+                                            chain_id,
+                                            self.group_cache.read().await.get_group()?,
+                                            None,
+                                            receipt.transaction_hash,
+                                            receipt.gas_used.unwrap_or(U256::zero()),
+                                            receipt.effective_gas_price.unwrap_or(U256::zero())
+                                        )
                                     )
                                 );
                             }
                             _ => {
                                 error!(
                                     "{}",
-                                    build_group_related_payload(
+                                    record_payload(build_group_related_payload(
                                         LogType::DKGGroupingCommitFailed,
                                         &format!("DKG grouping commit failed with error: {:?}", e),
                                         chain_id,
                                         self.group_cache.read().await.get_group()?
-                                    )
+                                    ))
                                 );
                             }
                         },
@@ -227,12 +331,12 @@ impl<
 
                     info!(
                         "{}",
-                        build_group_related_payload(
+                        record_payload(build_group_related_payload(
                             LogType::DKGGroupingAborted,
                             "DKG grouping aborted due to disqualified nodes.",
                             chain_id,
                             self.group_cache.read().await.get_group()?
-                        )
+                        ))
                     );
 
                     let g_public_key = PC::point();
@@ -251,7 +355,7 @@ impl<
                         Ok(receipt) => {
                             info!(
                                 "{}",
-                                build_group_related_transaction_receipt_payload(
+                                record_payload(build_group_related_transaction_receipt_payload(
                                     LogType::DKGGroupingCommitted,
                                     "DKG grouping result committed.",
                                     chain_id,
@@ -260,34 +364,36 @@ impl<
                                     receipt.transaction_hash,
                                     receipt.gas_used.unwrap_or(U256::zero()),
                                     receipt.effective_gas_price.unwrap_or(U256::zero())
-                                )
+                                ))
                             );
                         }
                         Err(e) => match e {
                             ContractClientError::TransactionFailed(receipt) => {
                                 error!(
                                     "{}",
-                                    build_group_related_transaction_receipt_payload(
-                                        LogType::DKGGroupingCommitFailed,
-                                        "DKG grouping commit failed.",
-                                        chain_id,
-                                        self.group_cache.read().await.get_group()?,
-                                        None,
-                                        receipt.transaction_hash,
-                                        receipt.gas_used.unwrap_or(U256::zero()),
-                                        receipt.effective_gas_price.unwrap_or(U256::zero())
+                                    record_payload(
+                                        build_group_related_transaction_receipt_payload(
+                                            LogType::DKGGroupingCommitFailed,
+                                            "DKG grouping commit failed.",
+                                            chain_id,
+                                            self.group_cache.read().await.get_group()?,
+                                            None,
+                                            receipt.transaction_hash,
+                                            receipt.gas_used.unwrap_or(U256::zero()),
+                                            receipt.effective_gas_price.unwrap_or(U256::zero())
+                                        )
                                     )
                                 );
                             }
                             _ => {
                                 error!(
                                     "{}",
-                                    build_group_related_payload(
+                                    record_payload(build_group_related_payload(
                                         LogType::DKGGroupingCommitFailed,
                                         &format!("DKG grouping commit failed with error: {:?}", e),
                                         chain_id,
                                         self.group_cache.read().await.get_group()?
-                                    )
+                                    ))
                                 );
                             }
                         },
@@ -297,12 +403,12 @@ impl<
             Err(e) => {
                 error!(
                     "{}",
-                    build_group_related_payload(
+                    record_payload(build_group_related_payload(
                         LogType::DKGGroupingFailed,
                         &format!("DKG grouping failed with error: {:?}", e),
                         chain_id,
                         self.group_cache.read().await.get_group()?
-                    )
+                    ))
                 );
             }
         }
@@ -312,7 +418,11 @@ impl<
 }
 
 #[async_trait]
-impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber for InGroupingSubscriber<PC> {
+impl<
+        PC: Curve + std::fmt::Debug + Sync + Send + 'static,
+        S: SignatureScheme<Public = PC::Point, Private = PC::Scalar> + Sync + Send + 'static,
+    > Subscriber for InGroupingSubscriber<PC, S>
+{
     async fn notify(&self, topic: Topic, payload: &(dyn DebuggableEvent)) -> NodeResult<()> {
         debug!("{:?}", topic);
 
@@ -331,12 +441,15 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber for InGroup
 
         let task_epoch = task.epoch;
 
-        let mut handler = AllInOneDKGHandler::new(
+        let mut handler = AllInOneDKGHandler::<_, _, PC, S>::new(
             RNG_FN,
             chain_identity,
             self.node_cache.clone(),
             self.group_cache.clone(),
             self.dkg_wait_for_phase_interval_millis,
+            self.dkg_protocol,
+            self.dkg_board,
+            self.dkg_bundle_store.clone(),
         );
 
         self.ts.write().await.add_task_with_shutdown_signal(
@@ -373,13 +486,41 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber for InGroup
     async fn subscribe(self) {
         let eq = self.eq.clone();
 
+        let group_cache = self.group_cache.clone();
+
         let subscriber = Box::new(self);
 
         eq.write().await.subscribe(Topic::RunDKG, subscriber);
+
+        // resume the DKG interrupted by the last shutdown from its checkpoint
+        let checkpoint = {
+            let group_cache = group_cache.read().await;
+
+            match group_cache.get_dkg_status() {
+                Ok(DKGStatus::InPhase) => group_cache.get_dkg_checkpoint().ok().flatten().cloned(),
+                _ => None,
+            }
+        };
+
+        if let Some(checkpoint) = checkpoint {
+            info!(
+                "found DKG checkpoint of group {} epoch {}, resuming the DKG",
+                checkpoint.task.group_index, checkpoint.task.epoch
+            );
+
+            eq.read()
+                .await
+                .publish(RunDKG {
+                    dkg_task: checkpoint.task,
+                })
+                .await;
+        }
     }
 }
 
-impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> DebuggableSubscriber
-    for InGroupingSubscriber<PC>
+impl<
+        PC: Curve + std::fmt::Debug + Sync + Send + 'static,
+        S: SignatureScheme<Public = PC::Point, Private = PC::Scalar> + Sync + Send + 'static,
+    > DebuggableSubscriber for InGroupingSubscriber<PC, S>
 {
 }
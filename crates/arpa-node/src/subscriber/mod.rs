@@ -1,4 +1,5 @@
 pub mod block;
+pub mod deferred_fulfillment;
 pub mod in_grouping;
 pub mod post_grouping;
 pub mod post_success_grouping;
@@ -12,6 +12,7 @@ use arpa_contract_client::{
 };
 use arpa_core::{
     log::{build_group_related_transaction_receipt_payload, LogType},
+    metrics::record_payload,
     ComponentTaskType, DKGStatus, SubscriberType, PLACEHOLDER_ADDRESS,
 };
 use arpa_dal::GroupInfoHandler;
@@ -99,7 +100,7 @@ impl<PC: Curve + Sync + Send + 'static> DKGPostProcessHandler for GeneralDKGPost
                 {
                     info!(
                         "{}",
-                        build_group_related_transaction_receipt_payload(
+                        record_payload(build_group_related_transaction_receipt_payload(
                             LogType::DKGPostProcessFinished,
                             "DKG post process finished.",
                             chain_id,
@@ -108,7 +109,7 @@ impl<PC: Curve + Sync + Send + 'static> DKGPostProcessHandler for GeneralDKGPost
                             receipt.transaction_hash,
                             receipt.gas_used.unwrap_or(U256::zero()),
                             receipt.effective_gas_price.unwrap_or(U256::zero())
-                        )
+                        ))
                     );
                 }
 
@@ -120,7 +121,7 @@ impl<PC: Curve + Sync + Send + 'static> DKGPostProcessHandler for GeneralDKGPost
                         {
                             info!(
                                 "{}",
-                                build_group_related_transaction_receipt_payload(
+                                record_payload(build_group_related_transaction_receipt_payload(
                                     LogType::DKGPostProcessGroupRelayFinished,
                                     "DKG post process group relay finished.",
                                     chain_id,
@@ -129,7 +130,7 @@ impl<PC: Curve + Sync + Send + 'static> DKGPostProcessHandler for GeneralDKGPost
                                     receipt.transaction_hash,
                                     receipt.gas_used.unwrap_or(U256::zero()),
                                     receipt.effective_gas_price.unwrap_or(U256::zero())
-                                )
+                                ))
                             );
                         }
                     }
@@ -6,6 +6,7 @@ use crate::{
 };
 use arpa_core::{
     log::{build_group_related_payload, LogType},
+    metrics::record_payload,
     DKGStatus,
 };
 use arpa_dal::GroupInfoHandler;
@@ -69,12 +70,12 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber
             {
                 error!(
                     "{}",
-                    build_group_related_payload(
+                    record_payload(build_group_related_payload(
                         LogType::DKGGroupingTwisted,
                         "Group public key is different from the one saved in DKG process.",
                         chain_id,
                         self.group_cache.read().await.get_group()?
-                    )
+                    ))
                 );
                 return Err(NodeError::DKGGroupingTwisted);
             }
@@ -82,12 +83,12 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber
             if !group.members.contains_key(&id_address) {
                 error!(
                     "{}",
-                    build_group_related_payload(
+                    record_payload(build_group_related_payload(
                         LogType::DKGGroupingTwisted,
                         "This node is not in the group, skip the process.",
                         chain_id,
                         self.group_cache.read().await.get_group()?
-                    )
+                    ))
                 );
                 return Err(NodeError::DKGGroupingTwisted);
             }
@@ -102,12 +103,12 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber
             {
                 error!(
                     "{}",
-                    build_group_related_payload(
+                    record_payload(build_group_related_payload(
                         LogType::DKGGroupingMemberMisMatch,
                         "Group members are not matched, attempt to run with contract records.",
                         chain_id,
                         self.group_cache.read().await.get_group()?
-                    )
+                    ))
                 );
             }
 
@@ -120,12 +121,12 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber
 
             info!(
                 "{}",
-                build_group_related_payload(
+                record_payload(build_group_related_payload(
                     LogType::DKGGroupingAvailable,
                     "Group is available, committers saved.",
                     chain_id,
                     self.group_cache.read().await.get_group()?
-                )
+                ))
             );
         }
 
@@ -6,6 +6,7 @@ use crate::{
 };
 use arpa_core::{
     log::{build_group_related_payload, LogType},
+    metrics::record_payload,
     DKGStatus,
 };
 use arpa_dal::GroupInfoHandler;
@@ -84,12 +85,12 @@ impl<C: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber for PreGroup
 
                 info!(
                     "{}",
-                    build_group_related_payload(
+                    record_payload(build_group_related_payload(
                         LogType::DKGGroupingStarted,
                         "start handling new DKG task.",
                         chain_id,
                         self.group_cache.read().await.get_group()?
-                    )
+                    ))
                 );
             }
         }
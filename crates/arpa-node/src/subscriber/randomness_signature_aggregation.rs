@@ -1,11 +1,19 @@
 use super::{DebuggableEvent, DebuggableSubscriber, Subscriber};
 use crate::{
-    algorithm::bls::{BLSCore, SimpleBLSCore},
+    algorithm::{
+        bls::{BLSCore, SimpleBLSCore},
+        fulfillment::{FulfillmentDecision, FulfillmentPolicy},
+    },
     context::ChainIdentityHandlerType,
     error::{NodeError, NodeResult},
     event::{ready_to_fulfill_randomness_task::ReadyToFulfillRandomnessTask, types::Topic},
-    queue::{event_queue::EventQueue, EventSubscriber},
+    queue::{
+        deferred_fulfillment_queue::{DeferredFulfillment, DeferredFulfillmentQueue},
+        event_queue::EventQueue,
+        EventSubscriber,
+    },
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
+    telemetry::{finish_randomness_task_span, randomness_task_span},
 };
 use arpa_contract_client::{
     adapter::{AdapterTransactions, AdapterViews},
@@ -13,8 +21,9 @@ use arpa_contract_client::{
 };
 use arpa_core::{
     log::{build_task_related_payload, build_task_related_transaction_receipt_payload, LogType},
+    metrics::record_payload,
     BLSTaskType, ComponentTaskType, PartialSignature, RandomnessTask, SubscriberType, TaskType,
-    DEFAULT_MAX_RANDOMNESS_FULFILLMENT_ATTEMPTS,
+    DEFAULT_MAX_RANDOMNESS_FULFILLMENT_ATTEMPTS, RANDOMNESS_TASK_EXPIRATION_SECONDS,
 };
 use arpa_dal::{cache::RandomnessResultCache, BLSResultCacheState};
 use arpa_dal::{BlockInfoHandler, SignatureResultCacheHandler};
@@ -25,10 +34,11 @@ use serde_json::json;
 use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 use threshold_bls::{
     group::Curve,
-    poly::Eval,
+    poly::{Eval, LagrangeCache},
     sig::{SignatureScheme, ThresholdScheme},
 };
 use tokio::sync::RwLock;
+use tracing::{info_span, Instrument};
 
 #[derive(Debug)]
 pub struct RandomnessSignatureAggregationSubscriber<
@@ -41,8 +51,13 @@ pub struct RandomnessSignatureAggregationSubscriber<
     block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>>,
     randomness_signature_cache:
         Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>,
+    fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+    deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
     eq: Arc<RwLock<EventQueue>>,
     ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
+    // the committee of a group rarely changes between tasks, so the lagrange
+    // coefficients of its index sets are reused across aggregations
+    lagrange_cache: RwLock<LagrangeCache<PC::Scalar>>,
     c: PhantomData<PC>,
     s: PhantomData<S>,
 }
@@ -50,6 +65,7 @@ pub struct RandomnessSignatureAggregationSubscriber<
 impl<PC: Curve, S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private = PC::Scalar>>
     RandomnessSignatureAggregationSubscriber<PC, S>
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         chain_id: usize,
         id_address: Address,
@@ -58,6 +74,8 @@ impl<PC: Curve, S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private
         randomness_signature_cache: Arc<
             RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>,
         >,
+        fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+        deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
         eq: Arc<RwLock<EventQueue>>,
         ts: Arc<RwLock<SimpleDynamicTaskScheduler>>,
     ) -> Self {
@@ -67,8 +85,11 @@ impl<PC: Curve, S: SignatureScheme + ThresholdScheme<Public = PC::Point, Private
             chain_identity,
             block_cache,
             randomness_signature_cache,
+            fulfillment_policy,
+            deferred_fulfillment_queue,
             eq,
             ts,
+            lagrange_cache: RwLock::new(LagrangeCache::default()),
             c: PhantomData,
             s: PhantomData,
         }
@@ -92,9 +113,34 @@ pub struct GeneralFulfillRandomnessHandler<PC: Curve> {
     block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>>,
     randomness_signature_cache:
         Arc<RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>>,
+    fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+    deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
     pc: PhantomData<PC>,
 }
 
+impl<PC: Curve> GeneralFulfillRandomnessHandler<PC> {
+    pub fn new(
+        id_address: Address,
+        chain_identity: Arc<RwLock<ChainIdentityHandlerType<PC>>>,
+        block_cache: Arc<RwLock<Box<dyn BlockInfoHandler>>>,
+        randomness_signature_cache: Arc<
+            RwLock<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>>,
+        >,
+        fulfillment_policy: Arc<dyn FulfillmentPolicy>,
+        deferred_fulfillment_queue: Arc<RwLock<DeferredFulfillmentQueue>>,
+    ) -> Self {
+        GeneralFulfillRandomnessHandler {
+            id_address,
+            chain_identity,
+            block_cache,
+            randomness_signature_cache,
+            fulfillment_policy,
+            deferred_fulfillment_queue,
+            pc: PhantomData,
+        }
+    }
+}
+
 #[async_trait]
 impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC> {
     async fn handle(
@@ -117,9 +163,11 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
         let randomness_task_json = json!(randomness_task);
 
         if client.is_task_pending(&randomness_task_request_id).await? {
-            if self.block_cache.read().await.get_block_height()
-                - randomness_task.assignment_block_height
-                > 86400 / self.block_cache.read().await.get_block_time()
+            let current_block_height = self.block_cache.read().await.get_block_height();
+
+            if current_block_height - randomness_task.assignment_block_height
+                > RANDOMNESS_TASK_EXPIRATION_SECONDS
+                    / self.block_cache.read().await.get_block_time()
             {
                 self.randomness_signature_cache
                     .write()
@@ -127,51 +175,150 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                     .update_commit_result(&randomness_task_request_id, BLSResultCacheState::Expired)
                     .await?;
 
-                info!("mark randomness task as expired. task request id: {}, assignment_block_height:{:?}",
-                    format!("0x{}", hex::encode(randomness_task_request_id)), randomness_task.assignment_block_height);
-
-                return Ok(());
-            }
-
-            let wei_per_gas = self
-                .chain_identity
-                .read()
-                .await
-                .get_current_gas_price()
-                .await?;
-
-            if wei_per_gas > randomness_task.callback_max_gas_price {
-                self.randomness_signature_cache
+                let was_deferred = self
+                    .deferred_fulfillment_queue
                     .write()
                     .await
-                    .update_commit_result(
+                    .remove(&randomness_task_request_id)
+                    .is_some();
+
+                info!(
+                    "{}",
+                    record_payload(build_task_related_payload(
+                        LogType::FulfillmentExpired,
+                        &format!(
+                            "Randomness task marked as expired. was_deferred: {}",
+                            was_deferred
+                        ),
+                        chain_id,
                         &randomness_task_request_id,
-                        BLSResultCacheState::NotCommitted,
-                    )
-                    .await?;
+                        TaskType::BLS(BLSTaskType::Randomness),
+                        randomness_task_json,
+                        None,
+                    ))
+                );
 
-                // TODO add a special retry mechanism for gas price too high
-                self.randomness_signature_cache
-                    .write()
-                    .await
-                    .incr_committed_times(&randomness_task_request_id)
-                    .await?;
-
-                info!("cancel fulfilling randomness as gas price is too high! task request id: {}, current_gas_price:{:?}, max_gas_price: {:?}",
-                format!("0x{}", hex::encode(&randomness_task_request_id)), wei_per_gas, randomness_task.callback_max_gas_price);
+                finish_randomness_task_span(&randomness_task_request_id);
 
                 return Ok(());
             }
 
-            match client
-                .fulfill_randomness(
+            let fulfillment = match client
+                .estimate_fulfill_randomness(
                     group_index,
-                    randomness_task,
-                    signature.clone(),
-                    partial_signatures,
+                    &randomness_task,
+                    &signature,
+                    &partial_signatures,
                 )
                 .await
             {
+                Ok(gas_estimate) => {
+                    let wei_per_gas = self
+                        .chain_identity
+                        .read()
+                        .await
+                        .get_current_gas_price()
+                        .await?;
+
+                    // the gas limit of the transaction comes from the same estimate the policy
+                    // weighs, so that a deferral and the fulfillment price the same gas
+                    let estimate = match self.fulfillment_policy.evaluate(
+                        &randomness_task,
+                        &gas_estimate,
+                        wei_per_gas,
+                    ) {
+                        FulfillmentDecision::Defer(estimate) => {
+                            // the result stays in Committing state so that it won't be aggregated again,
+                            // the deferred fulfillment will be re-evaluated as new blocks come in. The
+                            // queue is not persisted, but the Committing results are reset to
+                            // NotCommitted on startup, so they are aggregated and evaluated again.
+                            let is_newly_deferred =
+                                self.deferred_fulfillment_queue.write().await.defer(
+                                    DeferredFulfillment {
+                                        group_index,
+                                        randomness_task,
+                                        signature,
+                                        partial_signatures,
+                                        deferred_block_height: current_block_height,
+                                    },
+                                );
+
+                            if is_newly_deferred {
+                                info!(
+                                    "{}",
+                                    record_payload(build_task_related_payload(
+                                        LogType::FulfillmentDeferred,
+                                        &format!(
+                                            "Randomness fulfillment deferred as it is not profitable now. gas_limit: {}, wei_per_gas: {}, gas_cost: {}, expected_reward: {}",
+                                            estimate.gas_limit,
+                                            estimate.wei_per_gas,
+                                            estimate.gas_cost,
+                                            estimate.expected_reward
+                                        ),
+                                        chain_id,
+                                        &randomness_task_request_id,
+                                        TaskType::BLS(BLSTaskType::Randomness),
+                                        randomness_task_json,
+                                        None,
+                                    ))
+                                );
+                            }
+
+                            return Ok(());
+                        }
+                        FulfillmentDecision::Fulfill(estimate) => {
+                            if let Some(deferred_fulfillment) = self
+                                .deferred_fulfillment_queue
+                                .write()
+                                .await
+                                .remove(&randomness_task_request_id)
+                            {
+                                info!(
+                                    "{}",
+                                    record_payload(build_task_related_payload(
+                                        LogType::FulfillmentResumed,
+                                        &format!(
+                                            "Deferred randomness fulfillment resumed. deferred_block_height: {}, wei_per_gas: {}, gas_cost: {}, expected_reward: {}",
+                                            deferred_fulfillment.deferred_block_height,
+                                            estimate.wei_per_gas,
+                                            estimate.gas_cost,
+                                            estimate.expected_reward
+                                        ),
+                                        chain_id,
+                                        &randomness_task_request_id,
+                                        TaskType::BLS(BLSTaskType::Randomness),
+                                        randomness_task_json.clone(),
+                                        None,
+                                    ))
+                                );
+                            }
+
+                            estimate
+                        }
+                    };
+
+                    client
+                        .fulfill_randomness(
+                            group_index,
+                            randomness_task,
+                            signature.clone(),
+                            partial_signatures,
+                            estimate.gas_limit,
+                        )
+                        .await
+                }
+                Err(e) => {
+                    // the result leaves the Committing state below, so it is no longer deferred
+                    self.deferred_fulfillment_queue
+                        .write()
+                        .await
+                        .remove(&randomness_task_request_id);
+
+                    Err(e)
+                }
+            };
+
+            match fulfillment {
                 Ok(receipt) => {
                     self.randomness_signature_cache
                         .write()
@@ -184,7 +331,7 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
 
                     info!(
                         "{}",
-                        build_task_related_transaction_receipt_payload(
+                        record_payload(build_task_related_transaction_receipt_payload(
                             LogType::FulfillmentFinished,
                             "Randomness fulfilled successfully.",
                             chain_id,
@@ -194,8 +341,68 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                             receipt.transaction_hash,
                             receipt.gas_used.unwrap_or(U256::zero()),
                             receipt.effective_gas_price.unwrap_or(U256::zero()),
+                        ))
+                    );
+
+                    finish_randomness_task_span(&randomness_task_request_id);
+                }
+                Err(ContractClientError::TaskAlreadyFulfilled) => {
+                    self.randomness_signature_cache
+                        .write()
+                        .await
+                        .update_commit_result(
+                            &randomness_task_request_id,
+                            BLSResultCacheState::CommittedByOthers,
                         )
+                        .await?;
+
+                    info!(
+                        "{}",
+                        record_payload(build_task_related_payload(
+                            LogType::FulfillmentFailed,
+                            "Randomness fulfillment not sent as the simulation shows the task has already been fulfilled.",
+                            chain_id,
+                            &randomness_task_request_id,
+                            TaskType::BLS(BLSTaskType::Randomness),
+                            randomness_task_json,
+                            None,
+                        ))
+                    );
+
+                    finish_randomness_task_span(&randomness_task_request_id);
+
+                    return Ok(());
+                }
+                Err(ContractClientError::InvalidSignature(reason)) => {
+                    // retrying with the same aggregated signature won't help
+                    self.randomness_signature_cache
+                        .write()
+                        .await
+                        .update_commit_result(
+                            &randomness_task_request_id,
+                            BLSResultCacheState::FAULTY,
+                        )
+                        .await?;
+
+                    error!(
+                        "{}",
+                        record_payload(build_task_related_payload(
+                            LogType::FulfillmentFailed,
+                            &format!(
+                                "Randomness fulfillment not sent as the simulation shows the signature is invalid: {:?}",
+                                reason
+                            ),
+                            chain_id,
+                            &randomness_task_request_id,
+                            TaskType::BLS(BLSTaskType::Randomness),
+                            randomness_task_json,
+                            None,
+                        ))
                     );
+
+                    finish_randomness_task_span(&randomness_task_request_id);
+
+                    return Ok(());
                 }
                 Err(e) => {
                     self.randomness_signature_cache
@@ -211,7 +418,7 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                         ContractClientError::TransactionFailed(receipt) => {
                             error!(
                                 "{}",
-                                build_task_related_transaction_receipt_payload(
+                                record_payload(build_task_related_transaction_receipt_payload(
                                     LogType::FulfillmentFailed,
                                     "Randomness fulfillment reverted.",
                                     chain_id,
@@ -221,13 +428,13 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                                     receipt.transaction_hash,
                                     receipt.gas_used.unwrap_or(U256::zero()),
                                     receipt.effective_gas_price.unwrap_or(U256::zero()),
-                                )
+                                ))
                             );
                         }
                         _ => {
                             error!(
                                 "{}",
-                                build_task_related_payload(
+                                record_payload(build_task_related_payload(
                                     LogType::FulfillmentFailed,
                                     &format!("Randomness fulfillment failed with error: {:?}", e),
                                     chain_id,
@@ -235,7 +442,7 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                                     TaskType::BLS(BLSTaskType::Randomness),
                                     randomness_task_json,
                                     None,
-                                )
+                                ))
                             );
                         }
                     }
@@ -248,6 +455,11 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                 .incr_committed_times(&randomness_task_request_id)
                 .await?;
         } else {
+            self.deferred_fulfillment_queue
+                .write()
+                .await
+                .remove(&randomness_task_request_id);
+
             self.randomness_signature_cache
                 .write()
                 .await
@@ -256,6 +468,8 @@ impl<PC: Curve> FulfillRandomnessHandler for GeneralFulfillRandomnessHandler<PC>
                     BLSResultCacheState::CommittedByOthers,
                 )
                 .await?;
+
+            finish_randomness_task_span(&randomness_task_request_id);
         }
 
         Ok(())
@@ -307,6 +521,8 @@ where
                 error!("mark randomness task as faulty for too many failed fulfillment attempts. task request id: {}",
                 format!("0x{}", hex::encode(&randomness_task.request_id)));
 
+                finish_randomness_task_span(&randomness_task.request_id);
+
                 continue;
             }
 
@@ -315,11 +531,25 @@ where
                 .cloned()
                 .collect::<Vec<Vec<u8>>>();
 
-            match SimpleBLSCore::<PC, S>::aggregate(threshold, &partials) {
+            let root_span = randomness_task_span(&randomness_task.request_id);
+
+            let aggregation = {
+                let mut lagrange_cache = self.lagrange_cache.write().await;
+
+                info_span!(parent: &root_span, "aggregation").in_scope(|| {
+                    SimpleBLSCore::<PC, S>::aggregate_with_cache(
+                        threshold,
+                        &partials,
+                        &mut lagrange_cache,
+                    )
+                })
+            };
+
+            match aggregation {
                 Ok(signature) => {
                     info!(
                         "{}",
-                        build_task_related_payload(
+                        record_payload(build_task_related_payload(
                             LogType::AggregatedSignatureFinished,
                             "Randomness signature aggregated successfully.",
                             self.chain_id,
@@ -327,7 +557,7 @@ where
                             TaskType::BLS(BLSTaskType::Randomness),
                             json!(randomness_task),
                             None
-                        )
+                        ))
                     );
 
                     let partial_signatures = partial_signatures
@@ -350,19 +580,24 @@ where
 
                     let randomness_signature_cache = self.randomness_signature_cache.clone();
 
+                    let fulfillment_policy = self.fulfillment_policy.clone();
+
+                    let deferred_fulfillment_queue = self.deferred_fulfillment_queue.clone();
+
                     self.ts.write().await.add_task(
                         ComponentTaskType::Subscriber(
                             self.chain_identity.read().await.get_chain_id(),
                             SubscriberType::RandomnessSignatureAggregation,
                         ),
                         async move {
-                            let handler = GeneralFulfillRandomnessHandler {
+                            let handler = GeneralFulfillRandomnessHandler::new(
                                 id_address,
                                 chain_identity,
                                 block_cache,
                                 randomness_signature_cache,
-                                pc: PhantomData,
-                            };
+                                fulfillment_policy,
+                                deferred_fulfillment_queue,
+                            );
 
                             if let Err(e) = handler
                                 .handle(
@@ -375,13 +610,14 @@ where
                             {
                                 error!("{:?}", e);
                             }
-                        },
+                        }
+                        .instrument(info_span!(parent: &root_span, "fulfillment")),
                     )?;
                 }
                 Err(e) => {
                     error!(
                         "{}",
-                        build_task_related_payload(
+                        record_payload(build_task_related_payload(
                             LogType::AggregatedSignatureFailed,
                             &format!(
                                 "Randomness signature aggregation failed with error: {:?}",
@@ -392,7 +628,7 @@ where
                             TaskType::BLS(BLSTaskType::Randomness),
                             json!(randomness_task),
                             None
-                        )
+                        ))
                     );
                 }
             }
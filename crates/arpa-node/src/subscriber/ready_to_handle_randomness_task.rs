@@ -7,16 +7,19 @@ use crate::{
     event::{ready_to_handle_randomness_task::ReadyToHandleRandomnessTask, types::Topic},
     queue::{event_queue::EventQueue, EventSubscriber},
     scheduler::{dynamic::SimpleDynamicTaskScheduler, TaskScheduler},
+    telemetry::randomness_task_span,
 };
 use arpa_core::{
     log::{build_task_related_payload, LogType},
-    u256_to_vec, BLSTaskType, ComponentTaskType, ExponentialBackoffRetryDescriptor, RandomnessTask,
+    metrics::record_payload,
+    randcast_verify::build_randomness_message,
+    BLSTaskType, ComponentTaskType, ExponentialBackoffRetryDescriptor, RandomnessTask,
     SubscriberType, TaskType,
 };
 use arpa_dal::cache::RandomnessResultCache;
 use arpa_dal::{BLSTasksHandler, GroupInfoHandler, SignatureResultCacheHandler};
 use async_trait::async_trait;
-use ethers::types::{Address, U256};
+use ethers::types::Address;
 use log::{debug, error, info};
 use serde_json::json;
 use std::{marker::PhantomData, sync::Arc};
@@ -25,6 +28,7 @@ use threshold_bls::{
     sig::{SignatureScheme, ThresholdScheme},
 };
 use tokio::sync::RwLock;
+use tracing::{info_span, Instrument};
 
 use super::{DebuggableEvent, DebuggableSubscriber, Subscriber};
 
@@ -140,20 +144,21 @@ where
 {
     async fn handle(self) -> NodeResult<()> {
         for task in self.tasks.iter() {
-            let actual_seed = [
-                &u256_to_vec(&task.seed)[..],
-                &u256_to_vec(&U256::from(task.assignment_block_height))[..],
-            ]
-            .concat();
-
-            match SimpleBLSCore::<PC, S>::partial_sign(
-                self.group_cache.read().await.get_secret_share()?,
-                &actual_seed,
-            ) {
+            let actual_seed = build_randomness_message(&task.seed, task.assignment_block_height);
+
+            let partial_signature_res = {
+                let group_cache = self.group_cache.read().await;
+                let secret_share = group_cache.get_secret_share()?;
+
+                info_span!(parent: &randomness_task_span(&task.request_id), "partial_signing")
+                    .in_scope(|| SimpleBLSCore::<PC, S>::partial_sign(secret_share, &actual_seed))
+            };
+
+            match partial_signature_res {
                 Ok(partial_signature) => {
                     info!(
                         "{}",
-                        build_task_related_payload(
+                        record_payload(build_task_related_payload(
                             LogType::PartialSignatureFinished,
                             "Partial signature generated.",
                             self.chain_id,
@@ -161,7 +166,7 @@ where
                             TaskType::BLS(BLSTaskType::Randomness),
                             json!(task),
                             None
-                        )
+                        ))
                     );
 
                     self.send_partial_signature(task, actual_seed, partial_signature)
@@ -170,7 +175,7 @@ where
                 Err(e) => {
                     error!(
                         "{}",
-                        build_task_related_payload(
+                        record_payload(build_task_related_payload(
                             LogType::PartialSignatureFailed,
                             &format!("Partial signature generation failed with error: {:?}", e),
                             self.chain_id,
@@ -178,7 +183,7 @@ where
                             TaskType::BLS(BLSTaskType::Randomness),
                             json!(task),
                             None
-                        )
+                        ))
                     );
                 }
             }
@@ -243,12 +248,16 @@ where
             let actual_seed = actual_seed.clone();
             let partial_signature = partial_signature.clone();
             let task_json = json!(task);
+            let committer_id = committer.get_committer_id_address();
+            let span = info_span!(
+                parent: &randomness_task_span(&request_id),
+                "commit_partial_signature",
+                committer = ?committer_id
+            );
 
             self.ts.write().await.add_task(
                 ComponentTaskType::Subscriber(chain_id, SubscriberType::SendingPartialSignature),
                 async move {
-                    let committer_id = committer.get_committer_id_address();
-
                     match committer
                         .commit_partial_signature(
                             chain_id,
@@ -262,7 +271,7 @@ where
                         Ok(true) => {
                             info!(
                                 "{}",
-                                build_task_related_payload(
+                                record_payload(build_task_related_payload(
                                     LogType::PartialSignatureSent,
                                     "Partial signature sent and accepted.",
                                     chain_id,
@@ -270,13 +279,13 @@ where
                                     TaskType::BLS(BLSTaskType::Randomness),
                                     task_json,
                                     Some(committer_id)
-                                )
+                                ))
                             );
                         }
                         Ok(false) => {
                             info!(
                                 "{}",
-                                build_task_related_payload(
+                                record_payload(build_task_related_payload(
                                     LogType::PartialSignatureSendingRejected,
                                     "Partial signature sent and rejected.",
                                     chain_id,
@@ -284,13 +293,13 @@ where
                                     TaskType::BLS(BLSTaskType::Randomness),
                                     task_json,
                                     Some(committer_id)
-                                )
+                                ))
                             );
                         }
                         Err(e) => {
                             error!(
                                 "{}",
-                                build_task_related_payload(
+                                record_payload(build_task_related_payload(
                                     LogType::PartialSignatureSendingFailed,
                                     &format!(
                                         "Partial signature sending failed with error: {:?}",
@@ -301,11 +310,12 @@ where
                                     TaskType::BLS(BLSTaskType::Randomness),
                                     task_json,
                                     Some(committer_id)
-                                )
+                                ))
                             );
                         }
                     }
-                },
+                }
+                .instrument(span),
             )?;
         }
 
@@ -6,7 +6,10 @@ use crate::{
     queue::{event_queue::EventQueue, EventSubscriber},
 };
 use arpa_contract_client::{error::ContractClientError, node_registry::NodeRegistryTransactions};
-use arpa_core::log::{build_general_payload, build_transaction_receipt_payload, LogType};
+use arpa_core::{
+    log::{build_general_payload, build_transaction_receipt_payload, LogType},
+    metrics::record_payload,
+};
 use async_trait::async_trait;
 use ethers::types::U256;
 use log::{debug, error, info};
@@ -60,38 +63,38 @@ impl<PC: Curve + std::fmt::Debug + Sync + Send + 'static> Subscriber
             Ok(receipt) => {
                 info!(
                     "{}",
-                    build_transaction_receipt_payload(
+                    record_payload(build_transaction_receipt_payload(
                         LogType::NodeActivated,
                         "Node activated",
                         chain_id,
                         receipt.transaction_hash,
                         receipt.gas_used.unwrap_or(U256::zero()),
                         receipt.effective_gas_price.unwrap_or(U256::zero()),
-                    )
+                    ))
                 );
             }
             Err(e) => match e {
                 ContractClientError::TransactionFailed(receipt) => {
                     error!(
                         "{}",
-                        build_transaction_receipt_payload(
+                        record_payload(build_transaction_receipt_payload(
                             LogType::NodeActivationFailed,
                             "Node activate failed",
                             chain_id,
                             receipt.transaction_hash,
                             receipt.gas_used.unwrap_or(U256::zero()),
                             receipt.effective_gas_price.unwrap_or(U256::zero()),
-                        )
+                        ))
                     );
                 }
                 _ => {
                     error!(
                         "{}",
-                        build_general_payload(
+                        record_payload(build_general_payload(
                             LogType::NodeActivationFailed,
                             &format!("Node activate failed with error: {:?}", e),
                             Some(chain_id)
-                        )
+                        ))
                     );
                 }
             },
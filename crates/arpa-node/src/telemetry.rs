@@ -0,0 +1,165 @@
+use arpa_core::{TelemetryDescriptor, RANDOMNESS_TASK_EXPIRATION_SECONDS};
+use lazy_static::lazy_static;
+use opentelemetry::{
+    global,
+    propagation::{Extractor, Injector},
+    trace::TraceError,
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{self, Sampler},
+    Resource,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tonic::metadata::{KeyRef, MetadataKey, MetadataMap, MetadataValue};
+use tracing::{info_span, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+lazy_static! {
+    /// Root spans of the randomness tasks in progress, keyed by request id
+    static ref RANDOMNESS_TASK_SPANS: Mutex<HashMap<Vec<u8>, (Span, Instant)>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Installs the OTLP exporter as the global tracing subscriber,
+/// and propagates the trace context in the W3C trace context format.
+pub fn init_tracer(telemetry_descriptor: &TelemetryDescriptor) -> Result<(), TraceError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&telemetry_descriptor.otlp_endpoint),
+        )
+        .with_trace_config(
+            trace::config()
+                .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                    telemetry_descriptor.sampling_ratio,
+                ))))
+                .with_resource(Resource::new(vec![KeyValue::new(
+                    "service.name",
+                    telemetry_descriptor.service_name.clone(),
+                )])),
+        )
+        .install_batch(runtime::Tokio)?;
+
+    let subscriber = Registry::default().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+    tracing::subscriber::set_global_default(subscriber).map_err(|e| TraceError::Other(Box::new(e)))
+}
+
+/// Flushes the pending spans to the collector.
+pub fn shutdown_tracer() {
+    global::shutdown_tracer_provider();
+}
+
+/// Starts the root span of a randomness task, which lasts until the task is finished.
+pub fn start_randomness_task_span(chain_id: usize, request_id: &[u8]) -> Span {
+    let span = info_span!(
+        parent: None,
+        "randomness_task",
+        chain_id,
+        request_id = %hex::encode(request_id)
+    );
+
+    let mut spans = RANDOMNESS_TASK_SPANS.lock();
+    // the node may miss the end of a task, e.g. when another committer fulfills it
+    let expiration = Duration::from_secs(RANDOMNESS_TASK_EXPIRATION_SECONDS as u64);
+    spans.retain(|_, (_, started_at)| started_at.elapsed() < expiration);
+    spans.insert(request_id.to_vec(), (span.clone(), Instant::now()));
+
+    span
+}
+
+/// Returns the root span of the randomness task, or a disabled span if the task is unknown.
+pub fn randomness_task_span(request_id: &[u8]) -> Span {
+    RANDOMNESS_TASK_SPANS
+        .lock()
+        .get(request_id)
+        .map(|(span, _)| span.clone())
+        .unwrap_or_else(Span::none)
+}
+
+/// Ends the root span of the randomness task once its last child span is closed.
+pub fn finish_randomness_task_span(request_id: &[u8]) {
+    RANDOMNESS_TASK_SPANS.lock().remove(request_id);
+}
+
+struct MetadataInjector<'a>(&'a mut MetadataMap);
+
+impl<'a> Injector for MetadataInjector<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(key), Ok(value)) = (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::try_from(&value),
+        ) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+struct MetadataExtractor<'a>(&'a MetadataMap);
+
+impl<'a> Extractor for MetadataExtractor<'a> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0
+            .keys()
+            .map(|key| match key {
+                KeyRef::Ascii(key) => key.as_str(),
+                KeyRef::Binary(key) => key.as_str(),
+            })
+            .collect()
+    }
+}
+
+/// Injects the context of the current span into the metadata of the gRPC request.
+pub fn inject_trace_context<T>(request: &mut tonic::Request<T>) {
+    let context = Span::current().context();
+
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut MetadataInjector(request.metadata_mut()))
+    });
+}
+
+/// Makes the span a child of the remote span carried by the metadata of the gRPC request.
+pub fn set_remote_parent<T>(span: &Span, request: &tonic::Request<T>) {
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&MetadataExtractor(request.metadata()))
+    });
+
+    span.set_parent(parent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finish_randomness_task_span, randomness_task_span, start_randomness_task_span};
+
+    #[test]
+    fn test_randomness_task_span() {
+        let request_id = vec![1, 2, 3];
+
+        assert!(randomness_task_span(&request_id).is_none());
+
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry());
+
+        let span = start_randomness_task_span(1, &request_id);
+        assert_eq!(span.id(), randomness_task_span(&request_id).id());
+
+        finish_randomness_task_span(&request_id);
+        assert!(randomness_task_span(&request_id).is_none());
+    }
+}
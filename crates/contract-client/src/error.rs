@@ -1,3 +1,6 @@
+use crate::{
+    contract_stub::adapter::AdapterErrors, ethers::private_submission::PrivateWsWalletSigner,
+};
 use arpa_core::{HttpWalletSigner, WsWalletSigner};
 use ethers::{
     prelude::{ContractError, ProviderError, WalletError},
@@ -21,6 +24,8 @@ pub enum ContractClientError {
     #[error(transparent)]
     HttpContractError(#[from] ContractError<HttpWalletSigner>),
     #[error(transparent)]
+    PrivateContractError(#[from] ContractError<PrivateWsWalletSigner>),
+    #[error(transparent)]
     AddressParseError(#[from] FromHexError),
     #[error(transparent)]
     WalletError(#[from] WalletError),
@@ -42,4 +47,18 @@ pub enum ContractClientError {
     NoTransactionReceipt,
     #[error("Transaction failed with status equal to 0x0")]
     TransactionFailed(TransactionReceipt),
+    #[error("the task has already been fulfilled")]
+    TaskAlreadyFulfilled,
+    #[error("the signature is rejected by the adapter: {0:?}")]
+    InvalidSignature(AdapterErrors),
+    #[error("the adapter reverted with {0:?}")]
+    AdapterReverted(AdapterErrors),
+    #[error("multicall failed: {0}")]
+    MulticallError(String),
+    #[error("the request id 0x{0} is longer than 32 bytes")]
+    InvalidRequestId(String),
+    #[error("the dkg bundle committed by participant {0} is not available")]
+    DKGBundleUnavailable(usize),
+    #[error(transparent)]
+    SerializationError(#[from] bincode::Error),
 }
@@ -1,17 +1,24 @@
 use crate::{
-    adapter::{AdapterClientBuilder, AdapterLogs, AdapterTransactions, AdapterViews},
-    contract_stub::adapter::{
-        Adapter, PartialSignature as ContractPartialSignature, RandomnessRequestFilter,
-        RequestDetail,
+    adapter::{
+        AdapterClientBuilder, AdapterLogs, AdapterTransactions, AdapterViews,
+        FulfillmentGasEstimate,
+    },
+    contract_stub::{
+        adapter::{
+            Adapter, AdapterErrors, PartialSignature as ContractPartialSignature,
+            RandomnessRequestFilter, RequestDetail,
+        },
+        i_gas_price_oracle::IGasPriceOracle,
     },
     error::{ContractClientError, ContractClientResult},
+    ethers::private_submission::{PrivateSubmissionMiddleware, PrivateWsWalletSigner},
     ServiceClient, TransactionCaller, ViewCaller,
 };
 use arpa_core::{
-    pad_to_bytes32, ChainIdentity, ExponentialBackoffRetryDescriptor, GeneralMainChainIdentity,
-    GeneralRelayedChainIdentity, PartialSignature, RandomnessRequestType, RandomnessTask,
-    WsWalletSigner, DEFAULT_MINIMUM_THRESHOLD, FULFILL_RANDOMNESS_GAS_EXCEPT_CALLBACK,
-    RANDOMNESS_REWARD_GAS, VERIFICATION_GAS_OVER_MINIMUM_THRESHOLD,
+    buffer_fulfill_randomness_gas, l1_data_fee_mode, pad_to_bytes32, ChainIdentity,
+    ExponentialBackoffRetryDescriptor, GeneralMainChainIdentity, GeneralRelayedChainIdentity,
+    L1DataFeeMode, PartialSignature, PrivateSubmissionDescriptor, RandomnessRequestType,
+    RandomnessTask, WsWalletSigner, OP_GAS_PRICE_ORACLE_ADDRESS,
 };
 use async_trait::async_trait;
 use ethers::{prelude::*, utils::hex};
@@ -26,6 +33,7 @@ pub struct AdapterClient {
     client: Arc<WsWalletSigner>,
     contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
     contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    private_submission: Option<PrivateSubmissionDescriptor>,
 }
 
 impl AdapterClient {
@@ -36,6 +44,7 @@ impl AdapterClient {
         client: Arc<WsWalletSigner>,
         contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
         contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+        private_submission: Option<PrivateSubmissionDescriptor>,
     ) -> Self {
         AdapterClient {
             chain_id,
@@ -44,6 +53,7 @@ impl AdapterClient {
             client,
             contract_transaction_retry_descriptor,
             contract_view_retry_descriptor,
+            private_submission,
         }
     }
 }
@@ -59,6 +69,7 @@ impl AdapterClientBuilder for GeneralMainChainIdentity {
             self.get_client(),
             self.get_contract_transaction_retry_descriptor(),
             self.get_contract_view_retry_descriptor(),
+            self.get_private_submission_descriptor().cloned(),
         )
     }
 }
@@ -74,12 +85,68 @@ impl AdapterClientBuilder for GeneralRelayedChainIdentity {
             self.get_client(),
             self.get_contract_transaction_retry_descriptor(),
             self.get_contract_view_retry_descriptor(),
+            self.get_private_submission_descriptor().cloned(),
         )
     }
 }
 
 type AdapterContract = Adapter<WsWalletSigner>;
 
+impl AdapterClient {
+    /// Runs the fulfillment through eth_call and eth_estimateGas so that a transaction
+    /// which is doomed to revert is never broadcast.
+    async fn simulate_fulfill_randomness(
+        &self,
+        call: &ContractCall<WsWalletSigner, ()>,
+    ) -> ContractClientResult<U256> {
+        // eth_call is issued first as some providers don't return the revert data on eth_estimateGas
+        call.call().await.map_err(decode_adapter_revert)?;
+
+        call.estimate_gas().await.map_err(decode_adapter_revert)
+    }
+
+    /// The L1 data fee is not covered by eth_estimateGas on OP Stack chains, so it is quoted
+    /// by the GasPriceOracle predeploy. ArbOS folds it into the gas used instead.
+    async fn estimate_l1_data_fee(
+        &self,
+        call: ContractCall<WsWalletSigner, ()>,
+    ) -> ContractClientResult<U256> {
+        match l1_data_fee_mode(self.chain_id) {
+            L1DataFeeMode::OpStack => {
+                let gas_price_oracle = IGasPriceOracle::new(
+                    OP_GAS_PRICE_ORACLE_ADDRESS.parse::<Address>().unwrap(),
+                    self.client.clone(),
+                );
+
+                AdapterClient::call_contract_view(
+                    self.chain_id,
+                    "get_l1_fee",
+                    gas_price_oracle.get_l1_fee(call.tx.rlp()),
+                    self.contract_view_retry_descriptor,
+                )
+                .await
+            }
+            L1DataFeeMode::Arbitrum | L1DataFeeMode::None => Ok(U256::zero()),
+        }
+    }
+}
+
+fn decode_adapter_revert(e: ContractError<WsWalletSigner>) -> ContractClientError {
+    match e.decode_contract_revert::<AdapterErrors>() {
+        // the request commitment is deleted once the task is fulfilled
+        Some(AdapterErrors::NoCorrespondingRequest(_)) => ContractClientError::TaskAlreadyFulfilled,
+        Some(
+            err @ (AdapterErrors::InvalidSignature(_)
+            | AdapterErrors::InvalidSignatureFormat(_)
+            | AdapterErrors::InvalidPartialSignatures(_)
+            | AdapterErrors::InvalidPartialSignatureFormat(_)
+            | AdapterErrors::EmptyPartialSignatures(_)),
+        ) => ContractClientError::InvalidSignature(err),
+        Some(err) => ContractClientError::AdapterReverted(err),
+        None => e.into(),
+    }
+}
+
 #[async_trait]
 impl ServiceClient<AdapterContract> for AdapterClient {
     async fn prepare_service_client(&self) -> ContractClientResult<AdapterContract> {
@@ -97,67 +164,96 @@ impl ViewCaller for AdapterClient {}
 
 #[async_trait]
 impl AdapterTransactions for AdapterClient {
+    async fn estimate_fulfill_randomness(
+        &self,
+        group_index: usize,
+        task: &RandomnessTask,
+        signature: &[u8],
+        partial_signatures: &HashMap<Address, PartialSignature>,
+    ) -> ContractClientResult<FulfillmentGasEstimate> {
+        let adapter_contract =
+            ServiceClient::<AdapterContract>::prepare_service_client(self).await?;
+
+        let call = build_fulfill_randomness_call(
+            &adapter_contract,
+            group_index,
+            task,
+            signature,
+            partial_signatures,
+        );
+
+        let estimated_gas = self.simulate_fulfill_randomness(&call).await?;
+        let gas_limit = buffer_fulfill_randomness_gas(estimated_gas);
+        let l1_data_fee = self.estimate_l1_data_fee(call.gas(gas_limit)).await?;
+
+        info!(
+            "fulfill_randomness simulated successfully with chain_id({}), estimated gas: {}, gas limit: {}, l1 data fee: {}",
+            self.chain_id, estimated_gas, gas_limit, l1_data_fee
+        );
+
+        Ok(FulfillmentGasEstimate {
+            estimated_gas,
+            gas_limit,
+            l1_data_fee,
+        })
+    }
+
     async fn fulfill_randomness(
         &self,
         group_index: usize,
         task: RandomnessTask,
         signature: Vec<u8>,
         partial_signatures: HashMap<Address, PartialSignature>,
+        gas_limit: U256,
     ) -> ContractClientResult<TransactionReceipt> {
-        let adapter_contract =
-            ServiceClient::<AdapterContract>::prepare_service_client(self).await?;
+        if let Some(private_submission) = &self.private_submission {
+            let private_client = PrivateSubmissionMiddleware::new(
+                self.client.clone(),
+                self.client.inner().signer().clone(),
+                &private_submission.relay_endpoint,
+                private_submission.fallback_blocks,
+            )
+            .map_err(|e| ProviderError::CustomError(e.to_string()))?;
 
-        let r_id = pad_to_bytes32(&task.request_id).unwrap();
+            let private_adapter_contract: Adapter<PrivateWsWalletSigner> =
+                Adapter::new(self.adapter_address, Arc::new(private_client));
 
-        let sig = U256::from(signature.as_slice());
+            let call = build_fulfill_randomness_call(
+                &private_adapter_contract,
+                group_index,
+                &task,
+                &signature,
+                &partial_signatures,
+            )
+            .gas(gas_limit);
 
-        let ps: Vec<ContractPartialSignature> = partial_signatures
-            .values()
-            .map(|ps| {
-                let sig: U256 = U256::from(ps.signature.as_slice());
-                ContractPartialSignature {
-                    index: ps.index.into(),
-                    partial_signature: sig,
-                }
-            })
-            .collect();
-
-        let rd = RequestDetail {
-            sub_id: task.subscription_id,
-            group_index: task.group_index,
-            request_type: task.request_type.to_u8(),
-            params: task.params.into(),
-            callback_contract: task.requester,
-            seed: task.seed,
-            request_confirmations: task.request_confirmations,
-            callback_gas_limit: task.callback_gas_limit,
-            callback_max_gas_price: task.callback_max_gas_price,
-            block_num: task.assignment_block_height.into(),
-        };
-
-        let call = adapter_contract.fulfill_randomness(group_index as u32, r_id, sig, rd, ps);
-
-        let partial_signers_count = partial_signatures.len() as u32;
-
-        let extra_verification_gas = if partial_signers_count > DEFAULT_MINIMUM_THRESHOLD {
-            VERIFICATION_GAS_OVER_MINIMUM_THRESHOLD
-                * (partial_signers_count - DEFAULT_MINIMUM_THRESHOLD)
-        } else {
-            0
-        };
-
-        let extra_add_reward_gas = partial_signers_count * RANDOMNESS_REWARD_GAS;
+            return AdapterClient::call_contract_transaction(
+                self.chain_id,
+                "fulfill_randomness",
+                private_adapter_contract.client_ref(),
+                call,
+                self.contract_transaction_retry_descriptor,
+                false,
+            )
+            .await;
+        }
+
+        let adapter_contract =
+            ServiceClient::<AdapterContract>::prepare_service_client(self).await?;
+
+        let call = build_fulfill_randomness_call(
+            &adapter_contract,
+            group_index,
+            &task,
+            &signature,
+            &partial_signatures,
+        );
 
         AdapterClient::call_contract_transaction(
             self.chain_id,
             "fulfill_randomness",
             adapter_contract.client_ref(),
-            call.gas(
-                task.callback_gas_limit
-                    + FULFILL_RANDOMNESS_GAS_EXCEPT_CALLBACK
-                    + extra_verification_gas
-                    + extra_add_reward_gas,
-            ),
+            call.gas(gas_limit),
             self.contract_transaction_retry_descriptor,
             false,
         )
@@ -165,6 +261,44 @@ impl AdapterTransactions for AdapterClient {
     }
 }
 
+fn build_fulfill_randomness_call<M: Middleware>(
+    adapter_contract: &Adapter<M>,
+    group_index: usize,
+    task: &RandomnessTask,
+    signature: &[u8],
+    partial_signatures: &HashMap<Address, PartialSignature>,
+) -> ContractCall<M, ()> {
+    let r_id = pad_to_bytes32(&task.request_id).unwrap();
+
+    let sig = U256::from(signature);
+
+    let ps: Vec<ContractPartialSignature> = partial_signatures
+        .values()
+        .map(|ps| {
+            let sig: U256 = U256::from(ps.signature.as_slice());
+            ContractPartialSignature {
+                index: ps.index.into(),
+                partial_signature: sig,
+            }
+        })
+        .collect();
+
+    let rd = RequestDetail {
+        sub_id: task.subscription_id,
+        group_index: task.group_index,
+        request_type: task.request_type.to_u8(),
+        params: task.params.clone().into(),
+        callback_contract: task.requester,
+        seed: task.seed,
+        request_confirmations: task.request_confirmations,
+        callback_gas_limit: task.callback_gas_limit,
+        callback_max_gas_price: task.callback_max_gas_price,
+        block_num: task.assignment_block_height.into(),
+    };
+
+    adapter_contract.fulfill_randomness(group_index as u32, r_id, sig, rd, ps)
+}
+
 #[async_trait]
 impl AdapterViews for AdapterClient {
     async fn get_last_randomness(&self) -> ContractClientResult<U256> {
@@ -197,6 +331,31 @@ impl AdapterViews for AdapterClient {
             !r.is_zero()
         })
     }
+
+    async fn are_tasks_pending(&self, request_ids: &[Vec<u8>]) -> ContractClientResult<Vec<bool>> {
+        let adapter_contract =
+            ServiceClient::<AdapterContract>::prepare_service_client(self).await?;
+
+        let calls = request_ids
+            .iter()
+            .map(|request_id| {
+                let r_id = pad_to_bytes32(request_id).ok_or_else(|| {
+                    ContractClientError::InvalidRequestId(hex::encode(request_id))
+                })?;
+                Ok(adapter_contract.get_pending_request_commitment(r_id))
+            })
+            .collect::<ContractClientResult<Vec<_>>>()?;
+
+        AdapterClient::call_contract_views_batched(
+            self.chain_id,
+            "get_pending_request",
+            adapter_contract.client(),
+            calls,
+            self.contract_view_retry_descriptor,
+        )
+        .await
+        .map(|rs| rs.into_iter().map(|r| !U256::from(r).is_zero()).collect())
+    }
 }
 
 #[async_trait]
@@ -255,3 +414,49 @@ impl AdapterLogs for AdapterClient {
         Err(ContractClientError::FetchingRandomnessTaskError)
     }
 }
+
+#[cfg(test)]
+pub mod adapter_tests {
+    use super::decode_adapter_revert;
+    use crate::contract_stub::adapter::{
+        AdapterErrors, GroupNotExist, InvalidSignature, NoCorrespondingRequest,
+    };
+    use crate::error::ContractClientError;
+    use arpa_core::WsWalletSigner;
+    use ethers::abi::AbiEncode;
+    use ethers::prelude::ContractError;
+
+    fn revert_with(err: AdapterErrors) -> ContractError<WsWalletSigner> {
+        ContractError::Revert(err.encode().into())
+    }
+
+    #[test]
+    fn test_decode_adapter_revert() {
+        assert!(matches!(
+            decode_adapter_revert(revert_with(AdapterErrors::NoCorrespondingRequest(
+                NoCorrespondingRequest
+            ))),
+            ContractClientError::TaskAlreadyFulfilled
+        ));
+
+        assert!(matches!(
+            decode_adapter_revert(revert_with(AdapterErrors::InvalidSignature(
+                InvalidSignature
+            ))),
+            ContractClientError::InvalidSignature(AdapterErrors::InvalidSignature(_))
+        ));
+
+        assert!(matches!(
+            decode_adapter_revert(revert_with(AdapterErrors::GroupNotExist(GroupNotExist {
+                group_index: 1.into()
+            }))),
+            ContractClientError::AdapterReverted(AdapterErrors::GroupNotExist(_))
+        ));
+
+        // a revert which is not from the adapter is kept as it is
+        assert!(matches!(
+            decode_adapter_revert(ContractError::Revert(vec![0xde, 0xad].into())),
+            ContractClientError::WsContractError(ContractError::Revert(_))
+        ));
+    }
+}
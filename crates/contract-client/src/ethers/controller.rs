@@ -10,7 +10,7 @@ use crate::{
 };
 use crate::{TransactionCaller, ViewCaller};
 use arpa_core::{
-    u256_to_vec, ChainIdentity, DKGTask, ExponentialBackoffRetryDescriptor,
+    u256_to_vec, ChainIdentity, DKGCommitResult, DKGTask, ExponentialBackoffRetryDescriptor,
     GeneralMainChainIdentity, GeneralRelayedChainIdentity, Group, MainChainIdentity, Member,
     WsWalletSigner,
 };
@@ -157,6 +157,26 @@ impl<C: Curve> ControllerViews<C> for ControllerClient {
         .map(parse_contract_group)
     }
 
+    async fn get_groups(&self, group_indices: &[usize]) -> ContractClientResult<Vec<Group<C>>> {
+        let controller_contract =
+            ServiceClient::<ControllerContract>::prepare_service_client(self).await?;
+
+        let calls = group_indices
+            .iter()
+            .map(|group_index| controller_contract.get_group((*group_index).into()))
+            .collect::<Vec<_>>();
+
+        ControllerClient::call_contract_views_batched(
+            self.chain_id,
+            "get_group",
+            controller_contract.client(),
+            calls,
+            self.contract_view_retry_descriptor,
+        )
+        .await
+        .map(|groups| groups.into_iter().map(parse_contract_group).collect())
+    }
+
     async fn get_coordinator(&self, group_index: usize) -> ContractClientResult<Address> {
         let controller_contract =
             ServiceClient::<ControllerContract>::prepare_service_client(self).await?;
@@ -184,6 +204,32 @@ impl<C: Curve> ControllerViews<C> for ControllerClient {
 
         Ok(config.0)
     }
+
+    async fn get_dkg_commit_results(
+        &self,
+        group_index: usize,
+    ) -> ContractClientResult<Vec<DKGCommitResult>> {
+        let controller_contract =
+            ServiceClient::<ControllerContract>::prepare_service_client(self).await?;
+
+        let group = ControllerClient::call_contract_view(
+            self.chain_id,
+            "get_group",
+            controller_contract.get_group(group_index.into()),
+            self.contract_view_retry_descriptor,
+        )
+        .await?;
+
+        Ok(group
+            .commit_cache_list
+            .into_iter()
+            .map(|commit_cache| DKGCommitResult {
+                group_epoch: commit_cache.commit_result.group_epoch.as_usize(),
+                committers: commit_cache.node_id_address,
+                disqualified_nodes: commit_cache.commit_result.disqualified_nodes,
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -236,6 +282,38 @@ impl ControllerLogs for ControllerClient {
         }
         Err(ContractClientError::FetchingDkgTaskError)
     }
+
+    async fn get_dkg_tasks(
+        &self,
+        group_index: usize,
+        from_block: u64,
+    ) -> ContractClientResult<Vec<DKGTask>> {
+        let contract = Controller::new(self.controller_address, self.client.clone());
+
+        let events = contract
+            .event::<DkgTaskFilter>()
+            .topic2(U256::from(group_index))
+            .from_block(from_block);
+
+        let mut tasks = events
+            .query_with_meta()
+            .await?
+            .into_iter()
+            .map(|(evt, meta)| DKGTask {
+                group_index: evt.group_index.as_usize(),
+                epoch: evt.group_epoch.as_usize(),
+                size: evt.size.as_usize(),
+                threshold: evt.threshold.as_usize(),
+                members: evt.members,
+                assignment_block_height: meta.block_number.as_usize(),
+                coordinator_address: evt.coordinator_address,
+            })
+            .collect::<Vec<_>>();
+
+        tasks.sort_by_key(|task| task.epoch);
+
+        Ok(tasks)
+    }
 }
 
 fn parse_contract_group<C: Curve>(cg: ContractGroup) -> Group<C> {
@@ -118,6 +118,26 @@ impl<C: Curve> ControllerOracleViews<C> for ControllerOracleClient {
         .await
         .map(parse_contract_group)
     }
+
+    async fn get_groups(&self, group_indices: &[usize]) -> ContractClientResult<Vec<Group<C>>> {
+        let controller_oracle_contract =
+            ServiceClient::<ControllerOracleContract>::prepare_service_client(self).await?;
+
+        let calls = group_indices
+            .iter()
+            .map(|group_index| controller_oracle_contract.get_group((*group_index).into()))
+            .collect::<Vec<_>>();
+
+        ControllerOracleClient::call_contract_views_batched(
+            self.chain_id,
+            "get_group",
+            controller_oracle_contract.client(),
+            calls,
+            self.contract_view_retry_descriptor,
+        )
+        .await
+        .map(|groups| groups.into_iter().map(parse_contract_group).collect())
+    }
 }
 
 fn parse_contract_group<C: Curve>(cg: ContractGroup) -> Group<C> {
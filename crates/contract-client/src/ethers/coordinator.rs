@@ -230,6 +230,7 @@ pub mod coordinator_tests {
     use crate::contract_stub::coordinator::Coordinator;
     use crate::coordinator::CoordinatorTransactions;
     use crate::error::ContractClientError;
+    use crate::ViewCaller;
     use arpa_core::build_client;
     use arpa_core::eip1559_gas_price_estimator;
     use arpa_core::Config;
@@ -302,6 +303,38 @@ pub mod coordinator_tests {
         println!("{:?}", res);
     }
 
+    #[tokio::test]
+    async fn test_call_contract_views_batched_without_multicall() {
+        let anvil = start_chain();
+        let coordinator_contract = deploy_contract(&anvil).await;
+        let chain_id = anvil.chain_id() as usize;
+
+        let calls = vec![
+            coordinator_contract.in_phase(),
+            coordinator_contract.in_phase(),
+        ];
+
+        // anvil doesn't come with Multicall3, so the views are called one by one
+        let res = CoordinatorClient::call_contract_views_batched(
+            chain_id,
+            "in_phase",
+            coordinator_contract.client(),
+            calls,
+            Config::default()
+                .get_time_limits()
+                .contract_view_retry_descriptor,
+        )
+        .await
+        .unwrap();
+
+        let expected = coordinator_contract.in_phase().call().await.unwrap();
+        assert_eq!(res, vec![expected, expected]);
+        assert_eq!(
+            crate::MULTICALL_DEPLOYED.read().unwrap().get(&chain_id),
+            Some(&false)
+        );
+    }
+
     #[tokio::test]
     async fn test_publish_to_coordinator() {
         SimpleLogger::new()
@@ -360,6 +393,7 @@ pub mod coordinator_tests {
                 .get_time_limits()
                 .contract_transaction_retry_descriptor,
             config.get_time_limits().contract_view_retry_descriptor,
+            None,
         );
 
         let client = CoordinatorClient::new(
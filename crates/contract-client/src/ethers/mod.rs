@@ -4,6 +4,7 @@ pub mod controller_oracle;
 pub mod controller_relayer;
 pub mod coordinator;
 pub mod node_registry;
+pub mod private_submission;
 pub mod provider;
 
 #[cfg(test)]
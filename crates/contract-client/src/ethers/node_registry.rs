@@ -254,4 +254,19 @@ impl NodeRegistryViews for NodeRegistryClient {
             pending_until_block: n.pending_until_block.as_usize(),
         })
     }
+
+    async fn is_dkg_key_proof_required(&self, block_height: u64) -> ContractClientResult<bool> {
+        let node_registry_contract =
+            ServiceClient::<NodeRegistryContract>::prepare_service_client(self).await?;
+
+        NodeRegistryClient::call_contract_view(
+            self.chain_id,
+            "is_dkg_key_proof_required",
+            node_registry_contract
+                .is_dkg_key_proof_required()
+                .block(block_height),
+            self.contract_view_retry_descriptor,
+        )
+        .await
+    }
 }
@@ -0,0 +1,304 @@
+use arpa_core::WsWalletSigner;
+use async_trait::async_trait;
+use ethers::{
+    prelude::*,
+    types::transaction::eip2718::TypedTransaction,
+    utils::{hex, keccak256},
+};
+use log::{error, info};
+use serde::Serialize;
+use std::sync::Arc;
+use thiserror::Error;
+
+pub type PrivateWsWalletSigner = PrivateSubmissionMiddleware<Arc<WsWalletSigner>>;
+
+/// Sends signed transactions to a private relay with `eth_sendPrivateTransaction` instead of
+/// the public mempool. If the transaction is not included within `fallback_blocks`, or the relay
+/// is unavailable, the same signed transaction is broadcast publicly.
+#[derive(Debug)]
+pub struct PrivateSubmissionMiddleware<M> {
+    inner: M,
+    signer: LocalWallet,
+    relay: Provider<Http>,
+    fallback_blocks: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PrivateTransactionRequest {
+    tx: Bytes,
+    max_block_number: U64,
+}
+
+impl<M> PrivateSubmissionMiddleware<M>
+where
+    M: Middleware,
+{
+    pub fn new(
+        inner: M,
+        signer: LocalWallet,
+        relay_endpoint: &str,
+        fallback_blocks: usize,
+    ) -> Result<Self, PrivateSubmissionMiddlewareError<M>> {
+        let relay = Provider::<Http>::try_from(relay_endpoint)
+            .map_err(|e| PrivateSubmissionMiddlewareError::InvalidRelayEndpoint(e.to_string()))?;
+
+        Ok(PrivateSubmissionMiddleware {
+            inner,
+            signer,
+            relay,
+            fallback_blocks,
+        })
+    }
+
+    async fn broadcast_publicly(
+        &self,
+        raw_tx: Bytes,
+    ) -> Result<PendingTransaction<'_, M::Provider>, PrivateSubmissionMiddlewareError<M>> {
+        self.inner
+            .send_raw_transaction(raw_tx)
+            .await
+            .map_err(PrivateSubmissionMiddlewareError::MiddlewareError)
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for PrivateSubmissionMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = PrivateSubmissionMiddlewareError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        let mut tx = tx.into();
+
+        // nonce, gas and fees are still filled by the inner middlewares
+        self.inner
+            .fill_transaction(&mut tx, block)
+            .await
+            .map_err(PrivateSubmissionMiddlewareError::MiddlewareError)?;
+
+        let signature = self.signer.sign_transaction(&tx).await?;
+
+        let raw_tx = tx.rlp_signed(&signature);
+
+        let tx_hash = H256::from(keccak256(&raw_tx));
+
+        let max_block_number = self
+            .inner
+            .get_block_number()
+            .await
+            .map_err(PrivateSubmissionMiddlewareError::MiddlewareError)?
+            + U64::from(self.fallback_blocks);
+
+        if let Err(e) = self
+            .relay
+            .request::<_, H256>(
+                "eth_sendPrivateTransaction",
+                [PrivateTransactionRequest {
+                    tx: raw_tx.clone(),
+                    max_block_number,
+                }],
+            )
+            .await
+        {
+            error!(
+                "Private relay rejected transaction 0x{}, broadcasting publicly. error: {:?}",
+                hex::encode(tx_hash),
+                e
+            );
+
+            return self.broadcast_publicly(raw_tx).await;
+        }
+
+        info!(
+            "Transaction 0x{} submitted to private relay, max_block_number: {}",
+            hex::encode(tx_hash),
+            max_block_number
+        );
+
+        loop {
+            if self
+                .inner
+                .get_transaction_receipt(tx_hash)
+                .await
+                .map_err(PrivateSubmissionMiddlewareError::MiddlewareError)?
+                .is_some()
+            {
+                return Ok(PendingTransaction::new(tx_hash, self.provider()));
+            }
+
+            let current_block_number = self
+                .inner
+                .get_block_number()
+                .await
+                .map_err(PrivateSubmissionMiddlewareError::MiddlewareError)?;
+
+            if current_block_number > max_block_number {
+                break;
+            }
+
+            tokio::time::sleep(self.provider().get_interval()).await;
+        }
+
+        info!(
+            "Transaction 0x{} was not included by private relay before block {}, broadcasting publicly.",
+            hex::encode(tx_hash),
+            max_block_number
+        );
+
+        self.broadcast_publicly(raw_tx).await
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PrivateSubmissionMiddlewareError<M: Middleware> {
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+    #[error(transparent)]
+    SignerError(#[from] WalletError),
+    #[error("invalid private relay endpoint: {0}")]
+    InvalidRelayEndpoint(String),
+}
+
+impl<M: Middleware> MiddlewareError for PrivateSubmissionMiddlewareError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        PrivateSubmissionMiddlewareError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            PrivateSubmissionMiddlewareError::MiddlewareError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod private_submission_tests {
+    use super::PrivateSubmissionMiddleware;
+    use arpa_core::build_client;
+    use ethers::prelude::*;
+    use ethers::utils::Anvil;
+    use std::{sync::Arc, time::Duration};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::Mutex,
+    };
+
+    /// A relay which accepts every private transaction but never includes it.
+    async fn start_mock_relay(received_methods: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let received_methods = received_methods.clone();
+
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 65536];
+                    let mut len = 0;
+                    // read until the whole body declared by content-length has arrived
+                    let body = loop {
+                        let n = socket.read(&mut buf[len..]).await.unwrap();
+                        if n == 0 {
+                            return;
+                        }
+                        len += n;
+                        let request = String::from_utf8_lossy(&buf[..len]).to_string();
+                        if let Some((headers, body)) = request.split_once("\r\n\r\n") {
+                            let content_length = headers
+                                .lines()
+                                .find_map(|l| {
+                                    l.to_lowercase()
+                                        .strip_prefix("content-length:")
+                                        .map(|v| v.trim().parse::<usize>().unwrap())
+                                })
+                                .unwrap_or(0);
+                            if body.len() >= content_length {
+                                break body.to_string();
+                            }
+                        }
+                    };
+
+                    let request: serde_json::Value = serde_json::from_str(&body).unwrap();
+                    received_methods
+                        .lock()
+                        .await
+                        .push(request["method"].as_str().unwrap().to_string());
+
+                    let response = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": format!("0x{}", "00".repeat(32)),
+                    })
+                    .to_string();
+
+                    socket
+                        .write_all(
+                            format!(
+                                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                                response.len(),
+                                response
+                            )
+                            .as_bytes(),
+                        )
+                        .await
+                        .unwrap();
+                });
+            }
+        });
+
+        endpoint
+    }
+
+    #[tokio::test]
+    async fn test_fallback_to_public_broadcast() {
+        let anvil = Anvil::new().block_time(1u64).spawn();
+
+        let wallet: LocalWallet = anvil.keys()[0].clone().into();
+        let wallet = wallet.with_chain_id(anvil.chain_id());
+
+        let provider = Arc::new(
+            Provider::<Ws>::connect(anvil.ws_endpoint())
+                .await
+                .unwrap()
+                .interval(Duration::from_millis(200)),
+        );
+
+        let client = build_client(wallet.clone(), anvil.chain_id() as usize, provider);
+
+        let received_methods = Arc::new(Mutex::new(vec![]));
+        let relay_endpoint = start_mock_relay(received_methods.clone()).await;
+
+        let private_client =
+            PrivateSubmissionMiddleware::new(client, wallet, &relay_endpoint, 2).unwrap();
+
+        let receipt = private_client
+            .send_transaction(TransactionRequest::pay(Address::random(), 1u64), None)
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+
+        assert!(receipt.is_some());
+        assert_eq!(
+            *received_methods.lock().await,
+            vec!["eth_sendPrivateTransaction".to_string()]
+        );
+    }
+}
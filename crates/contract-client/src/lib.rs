@@ -1,16 +1,22 @@
 use crate::error::ContractClientError;
 use ::ethers::abi::Detokenize;
+use ::ethers::contract::{Multicall, MULTICALL_ADDRESS};
 use ::ethers::prelude::builders::ContractCall;
 use ::ethers::prelude::ContractError;
 use ::ethers::providers::{Middleware, ProviderError};
-use ::ethers::types::{BlockNumber, TransactionReceipt, U64};
+use ::ethers::types::{BlockNumber, TransactionReceipt, U256, U64};
 use arpa_core::{
-    eip1559_gas_price_estimator, fallback_eip1559_gas_price_estimator, jitter, supports_eip1559,
-    ExponentialBackoffRetryDescriptor,
+    eip1559_gas_price_estimator, fallback_eip1559_gas_price_estimator, fee_estimator_strategy,
+    jitter, metrics::observe_rpc_latency, supports_eip1559, ExponentialBackoffRetryDescriptor,
+    FeeEstimatorStrategy,
 };
 use async_trait::async_trait;
 use error::ContractClientResult;
+use lazy_static::lazy_static;
 use log::{error, info};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 use tokio_retry::strategy::ExponentialBackoff;
 use tokio_retry::{Retry, RetryIf};
 
@@ -18,6 +24,36 @@ pub mod contract_stub;
 pub mod error;
 pub mod ethers;
 
+/// The maximum number of calls aggregated into a single Multicall3 request
+const MULTICALL_BATCH_SIZE: usize = 100;
+
+lazy_static! {
+    /// Whether Multicall3 is deployed on each chain, so that its code is only fetched once
+    static ref MULTICALL_DEPLOYED: RwLock<HashMap<usize, bool>> = RwLock::new(HashMap::new());
+}
+
+async fn is_multicall_deployed<M: Middleware>(
+    chain_id: usize,
+    client: &M,
+) -> ContractClientResult<bool> {
+    if let Some(deployed) = MULTICALL_DEPLOYED.read().unwrap().get(&chain_id) {
+        return Ok(*deployed);
+    }
+
+    let deployed = !client
+        .get_code(MULTICALL_ADDRESS, None)
+        .await
+        .map_err(|e| ContractClientError::MulticallError(e.to_string()))?
+        .is_empty();
+
+    MULTICALL_DEPLOYED
+        .write()
+        .unwrap()
+        .insert(chain_id, deployed);
+
+    Ok(deployed)
+}
+
 #[async_trait]
 pub trait ServiceClient<C> {
     async fn prepare_service_client(&self) -> ContractClientResult<C>;
@@ -57,10 +93,17 @@ pub trait TransactionCaller {
         }
         // set gas price for EIP-1559 trxs
         else if let Some(tx) = call.tx.as_eip1559_mut() {
-            let (max_fee, max_priority_fee) = match client
-                .estimate_eip1559_fees(Some(eip1559_gas_price_estimator))
-                .await
-            {
+            let fee_history_estimation = match fee_estimator_strategy(chain_id) {
+                FeeEstimatorStrategy::FeeHistory => {
+                    client
+                        .estimate_eip1559_fees(Some(eip1559_gas_price_estimator))
+                        .await
+                }
+                // a zero priority fee leads to the estimation by eth_gasPrice below
+                FeeEstimatorStrategy::GasPrice => Ok((U256::zero(), U256::zero())),
+            };
+
+            let (max_fee, max_priority_fee) = match fee_history_estimation {
                 // if max_priority_fee is zero, it usually means that the chain is a testnet,
                 // we will use the legacy method to set a priority fee, to avoid the transaction being underpriced
                 Ok((max_fee, max_priority_fee)) if !max_priority_fee.is_zero() => {
@@ -96,7 +139,13 @@ pub trait TransactionCaller {
         let transaction_receipt = RetryIf::spawn(
             retry_strategy,
             || async {
-                let pending_tx = call.send().await.map_err(|e| {
+                let sent_at = Instant::now();
+
+                let pending_tx = call.send().await;
+
+                observe_rpc_latency(chain_id, info, sent_at.elapsed());
+
+                let pending_tx = pending_tx.map_err(|e| {
                     let e: ContractClientError = e.into();
                     e
                 })?;
@@ -167,7 +216,13 @@ pub trait ViewCaller {
             .take(contract_view_retry_descriptor.max_attempts);
 
         let res = Retry::spawn(retry_strategy, || async {
-            let result = call.call().await.map_err(|e| {
+            let called_at = Instant::now();
+
+            let result = call.call().await;
+
+            observe_rpc_latency(chain_id, info, called_at.elapsed());
+
+            let result = result.map_err(|e| {
                 let e: ContractClientError = e.into();
                 e
             })?;
@@ -217,6 +272,100 @@ pub trait ViewCaller {
 
         Ok(res)
     }
+
+    /// Aggregates the view calls into Multicall3 requests to save RPC round trips. The results
+    /// are returned in the same order as the calls. Falls back to calling the views one by one
+    /// if Multicall3 is not deployed on the chain, e.g. on a local devnet, which is only
+    /// checked on the first call for each chain.
+    async fn call_contract_views_batched<
+        M: Middleware + 'static,
+        D: Detokenize + std::fmt::Debug + Send + Sync + 'static,
+    >(
+        chain_id: usize,
+        info: &str,
+        client: Arc<M>,
+        calls: Vec<ContractCall<M, D>>,
+        contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    ) -> ContractClientResult<Vec<D>>
+    where
+        ContractClientError: From<ContractError<M>>,
+    {
+        if calls.is_empty() {
+            return Ok(vec![]);
+        }
+
+        if !is_multicall_deployed(chain_id, client.as_ref()).await? {
+            info!(
+                "Multicall3 is not deployed with chain_id({}), calling contract views {} one by one",
+                chain_id, info
+            );
+
+            let mut results = Vec::with_capacity(calls.len());
+            for call in calls {
+                results.push(
+                    Self::call_contract_view_without_log(call, contract_view_retry_descriptor)
+                        .await?,
+                );
+            }
+
+            return Ok(results);
+        }
+
+        let retry_strategy = ExponentialBackoff::from_millis(contract_view_retry_descriptor.base)
+            .factor(contract_view_retry_descriptor.factor)
+            .map(|e| {
+                if contract_view_retry_descriptor.use_jitter {
+                    jitter(e)
+                } else {
+                    e
+                }
+            })
+            .take(contract_view_retry_descriptor.max_attempts);
+
+        let mut results = Vec::with_capacity(calls.len());
+
+        for batch in calls.chunks(MULTICALL_BATCH_SIZE) {
+            let batch_results = Retry::spawn(retry_strategy.clone(), || async {
+                let mut multicall = Multicall::new(client.clone(), Some(MULTICALL_ADDRESS))
+                    .await
+                    .map_err(|e| ContractClientError::MulticallError(e.to_string()))?;
+
+                for call in batch {
+                    multicall.add_call(call.clone(), false);
+                }
+
+                let tokens = multicall
+                    .call_raw()
+                    .await
+                    .map_err(|e| ContractClientError::MulticallError(e.to_string()))?;
+
+                tokens
+                    .into_iter()
+                    .map(|token| {
+                        let token = token.map_err(|revert_data| {
+                            ContractClientError::MulticallError(format!(
+                                "call reverted with {}",
+                                revert_data
+                            ))
+                        })?;
+
+                        D::from_tokens(vec![token])
+                            .map_err(|e| ContractClientError::MulticallError(e.to_string()))
+                    })
+                    .collect::<ContractClientResult<Vec<D>>>()
+            })
+            .await?;
+
+            results.extend(batch_results);
+        }
+
+        info!(
+            "Calling contract views {} with chain_id({}) by multicall, results: {:?}",
+            info, chain_id, results
+        );
+
+        Ok(results)
+    }
 }
 
 pub mod node_registry {
@@ -253,6 +402,9 @@ pub mod node_registry {
     #[async_trait]
     pub trait NodeRegistryViews {
         async fn get_node(&self, id_address: Address) -> ContractClientResult<Node>;
+
+        /// Whether a DKG public key has to carry its proof of possession, as of the given block
+        async fn is_dkg_key_proof_required(&self, block_height: u64) -> ContractClientResult<bool>;
     }
 
     pub trait NodeRegistryClientBuilder {
@@ -267,7 +419,7 @@ pub mod node_registry {
 
 pub mod controller {
     use crate::error::ContractClientResult;
-    use arpa_core::{DKGTask, Group};
+    use arpa_core::{DKGCommitResult, DKGTask, Group};
     use async_trait::async_trait;
     use ethers::core::types::Address;
     use ethers::types::TransactionReceipt;
@@ -296,9 +448,19 @@ pub mod controller {
     pub trait ControllerViews<C: Curve> {
         async fn get_group(&self, group_index: usize) -> ContractClientResult<Group<C>>;
 
+        /// Fetches all the groups in batch, in the same order as the group indices
+        async fn get_groups(&self, group_indices: &[usize]) -> ContractClientResult<Vec<Group<C>>>;
+
         async fn get_coordinator(&self, group_index: usize) -> ContractClientResult<Address>;
 
         async fn get_node_registry_address(&self) -> ContractClientResult<Address>;
+
+        /// The DKG results committed for the latest epoch of the group, which are cleared
+        /// when the next DKG of the group starts
+        async fn get_dkg_commit_results(
+            &self,
+            group_index: usize,
+        ) -> ContractClientResult<Vec<DKGCommitResult>>;
     }
 
     #[async_trait]
@@ -310,6 +472,13 @@ pub mod controller {
             &self,
             cb: C,
         ) -> ContractClientResult<()>;
+
+        /// The DKG tasks of the group emitted since the given block, in the order of their epochs
+        async fn get_dkg_tasks(
+            &self,
+            group_index: usize,
+            from_block: u64,
+        ) -> ContractClientResult<Vec<DKGTask>>;
     }
 
     pub trait ControllerClientBuilder<C: Curve> {
@@ -341,6 +510,9 @@ pub mod controller_oracle {
     #[async_trait]
     pub trait ControllerOracleViews<C: Curve> {
         async fn get_group(&self, group_index: usize) -> ContractClientResult<Group<C>>;
+
+        /// Fetches all the groups in batch, in the same order as the group indices
+        async fn get_groups(&self, group_indices: &[usize]) -> ContractClientResult<Vec<Group<C>>>;
     }
 
     pub trait ControllerOracleClientBuilder<C: Curve> {
@@ -440,14 +612,36 @@ pub mod adapter {
 
     use crate::error::ContractClientResult;
 
+    /// What it takes to fulfill a randomness task, simulated against the latest state of the chain
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FulfillmentGasEstimate {
+        /// Result of eth_estimateGas
+        pub estimated_gas: U256,
+        /// Gas limit of the fulfillment transaction, with a buffer over the estimated gas
+        pub gas_limit: U256,
+        /// Fee in wei charged on top of the gas for posting the transaction data to the L1
+        pub l1_data_fee: U256,
+    }
+
     #[async_trait]
     pub trait AdapterTransactions {
+        /// Simulates the fulfillment, so that a task which has been fulfilled or a signature
+        /// which doesn't verify is reported before any transaction is sent
+        async fn estimate_fulfill_randomness(
+            &self,
+            group_index: usize,
+            task: &RandomnessTask,
+            signature: &[u8],
+            partial_signatures: &HashMap<Address, PartialSignature>,
+        ) -> ContractClientResult<FulfillmentGasEstimate>;
+
         async fn fulfill_randomness(
             &self,
             group_index: usize,
             task: RandomnessTask,
             signature: Vec<u8>,
             partial_signatures: HashMap<Address, PartialSignature>,
+            gas_limit: U256,
         ) -> ContractClientResult<TransactionReceipt>;
     }
 
@@ -456,6 +650,12 @@ pub mod adapter {
         async fn get_last_randomness(&self) -> ContractClientResult<U256>;
 
         async fn is_task_pending(&self, request_id: &[u8]) -> ContractClientResult<bool>;
+
+        /// Checks the pending status of all the tasks in batch, in the same order as the request ids
+        async fn are_tasks_pending(
+            &self,
+            request_ids: &[Vec<u8>],
+        ) -> ContractClientResult<Vec<bool>>;
     }
 
     #[async_trait]
@@ -495,3 +695,26 @@ pub mod provider {
         ) -> ContractClientResult<()>;
     }
 }
+
+#[cfg(test)]
+pub mod multicall_tests {
+    use crate::is_multicall_deployed;
+    use ::ethers::providers::Provider;
+    use ::ethers::types::Bytes;
+
+    #[tokio::test]
+    async fn test_is_multicall_deployed_is_cached() {
+        let (provider, mock) = Provider::mocked();
+
+        mock.push::<Bytes, _>(Bytes::from(vec![0x60, 0x80]))
+            .unwrap();
+        assert!(is_multicall_deployed(31337000, &provider).await.unwrap());
+
+        // no more response is mocked, so the code must not be fetched again
+        assert!(is_multicall_deployed(31337000, &provider).await.unwrap());
+
+        mock.push::<Bytes, _>(Bytes::new()).unwrap();
+        assert!(!is_multicall_deployed(31337001, &provider).await.unwrap());
+        assert!(!is_multicall_deployed(31337001, &provider).await.unwrap());
+    }
+}
@@ -4,3 +4,5 @@ mod utils;
 pub use types::*;
 pub use utils::*;
 pub mod log;
+pub mod metrics;
+pub mod randcast_verify;
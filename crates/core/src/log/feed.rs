@@ -0,0 +1,81 @@
+use super::Payload;
+use chrono::Utc;
+use lazy_static::lazy_static;
+use tokio::sync::broadcast;
+
+/// Number of records buffered for each subscriber, a lagging subscriber misses the oldest ones.
+pub const PAYLOAD_FEED_CAPACITY: usize = 1024;
+
+lazy_static! {
+    static ref PAYLOAD_FEED: broadcast::Sender<PayloadRecord> =
+        broadcast::channel(PAYLOAD_FEED_CAPACITY).0;
+}
+
+/// An owned copy of a structured log record for subscribers of the payload feed.
+#[derive(Debug, Clone)]
+pub struct PayloadRecord {
+    pub log_type: String,
+    pub chain_id: Option<usize>,
+    /// the payload in json format, the same as the one written to node.log
+    pub content: String,
+    pub timestamp: i64,
+}
+
+/// Subscribes to the structured log records recorded from now on.
+pub fn subscribe_payloads() -> broadcast::Receiver<PayloadRecord> {
+    PAYLOAD_FEED.subscribe()
+}
+
+/// Forwards the payload to the subscribers, if any.
+pub fn feed_payload(payload: &Payload) {
+    if PAYLOAD_FEED.receiver_count() == 0 {
+        return;
+    }
+
+    let record = PayloadRecord {
+        log_type: format!("{:?}", payload.log_type),
+        chain_id: payload.chain_id,
+        content: payload.to_string(),
+        timestamp: Utc::now().timestamp_millis(),
+    };
+
+    // the subscribers may be all dropped in the meantime
+    let _ = PAYLOAD_FEED.send(record);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{feed_payload, subscribe_payloads};
+    use crate::log::{build_general_payload, LogType};
+
+    #[test]
+    fn test_feed_payload() {
+        feed_payload(&build_general_payload(
+            LogType::NodeActivated,
+            "fed before subscribing",
+            Some(1),
+        ));
+
+        let mut receiver = subscribe_payloads();
+
+        feed_payload(&build_general_payload(
+            LogType::NodeActivated,
+            "fed after subscribing",
+            Some(1),
+        ));
+
+        // other tests may record payloads concurrently
+        let records = std::iter::from_fn(|| receiver.try_recv().ok()).collect::<Vec<_>>();
+
+        assert!(!records
+            .iter()
+            .any(|r| r.content.contains("fed before subscribing")));
+
+        let record = records
+            .iter()
+            .find(|r| r.content.contains("fed after subscribing"))
+            .unwrap();
+        assert_eq!(record.log_type, "NodeActivated");
+        assert_eq!(record.chain_id, Some(1));
+    }
+}
@@ -10,6 +10,7 @@ use threshold_bls::group::Curve;
 use threshold_bls::serialize::point_to_hex;
 
 pub mod encoder;
+pub mod feed;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub enum LogType {
@@ -39,6 +40,10 @@ pub enum LogType {
     AggregatedSignatureFailed,
     FulfillmentFinished,
     FulfillmentFailed,
+    FulfillmentDeferred,
+    FulfillmentResumed,
+    FulfillmentExpired,
+    FulfillmentRecorded,
     ListenerInterrupted,
 }
 
@@ -0,0 +1,282 @@
+use crate::log::{feed::feed_payload, LogType, Payload};
+use chrono::Utc;
+use lazy_static::lazy_static;
+use prometheus::{
+    exponential_buckets, Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::time::Duration;
+
+const WEI_PER_GWEI: f64 = 1_000_000_000.0;
+
+lazy_static! {
+    static ref REGISTRY: Registry = Registry::new();
+    static ref NODE_EVENTS: IntCounterVec = register_int_counter_vec(
+        "arpa_node_events_total",
+        "Number of structured log events emitted by the node",
+        &["chain_id", "log_type"],
+    );
+    static ref RANDOMNESS_TASKS: IntCounterVec = register_int_counter_vec(
+        "arpa_randomness_tasks_total",
+        "Number of randomness tasks which reached the stage",
+        &["chain_id", "stage"],
+    );
+    static ref PARTIAL_SIGNATURES: IntCounterVec = register_int_counter_vec(
+        "arpa_partial_signatures_total",
+        "Number of partial signatures sent to committers by result",
+        &["chain_id", "committer", "result"],
+    );
+    static ref DKG_OUTCOMES: IntCounterVec = register_int_counter_vec(
+        "arpa_dkg_outcomes_total",
+        "Number of DKG grouping outcomes",
+        &["chain_id", "outcome"],
+    );
+    static ref DKG_PHASE_DURATION: HistogramVec = register_histogram_vec(
+        "arpa_dkg_phase_duration_seconds",
+        "Duration of DKG phases observed by the node",
+        &["phase"],
+        exponential_buckets(1.0, 2.0, 12).unwrap(),
+    );
+    static ref FULFILLMENT_GAS_USED: HistogramVec = register_histogram_vec(
+        "arpa_fulfillment_gas_used",
+        "Gas used by randomness fulfillment transactions",
+        &["chain_id"],
+        exponential_buckets(100_000.0, 1.5, 12).unwrap(),
+    );
+    static ref FULFILLMENT_EFFECTIVE_GAS_PRICE: GaugeVec = register_gauge_vec(
+        "arpa_fulfillment_effective_gas_price_gwei",
+        "Effective gas price of the last randomness fulfillment transaction",
+        &["chain_id"],
+    );
+    static ref LISTENER_INTERRUPTIONS: IntCounterVec = register_int_counter_vec(
+        "arpa_listener_interruptions_total",
+        "Number of listener interruptions",
+        &["chain_id", "listener"],
+    );
+    static ref LISTENER_LAST_SUCCESS: IntGaugeVec = register_int_gauge_vec(
+        "arpa_listener_last_success_timestamp_seconds",
+        "Unix timestamp of the last successful listen of the listener",
+        &["chain_id", "listener"],
+    );
+    static ref EVENT_QUEUE_DEPTH: IntGaugeVec = register_int_gauge_vec(
+        "arpa_event_queue_depth",
+        "Number of events being dispatched to subscribers",
+        &["topic"],
+    );
+    static ref RPC_LATENCY: HistogramVec = register_histogram_vec(
+        "arpa_rpc_latency_seconds",
+        "Latency of contract calls to the chain provider",
+        &["chain_id", "method"],
+        exponential_buckets(0.01, 2.0, 12).unwrap(),
+    );
+}
+
+fn register_int_counter_vec(name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let metric = IntCounterVec::new(Opts::new(name, help), labels).unwrap();
+    REGISTRY.register(Box::new(metric.clone())).unwrap();
+    metric
+}
+
+fn register_int_gauge_vec(name: &str, help: &str, labels: &[&str]) -> IntGaugeVec {
+    let metric = IntGaugeVec::new(Opts::new(name, help), labels).unwrap();
+    REGISTRY.register(Box::new(metric.clone())).unwrap();
+    metric
+}
+
+fn register_gauge_vec(name: &str, help: &str, labels: &[&str]) -> GaugeVec {
+    let metric = GaugeVec::new(Opts::new(name, help), labels).unwrap();
+    REGISTRY.register(Box::new(metric.clone())).unwrap();
+    metric
+}
+
+fn register_histogram_vec(
+    name: &str,
+    help: &str,
+    labels: &[&str],
+    buckets: Vec<f64>,
+) -> HistogramVec {
+    let metric =
+        HistogramVec::new(HistogramOpts::new(name, help).buckets(buckets), labels).unwrap();
+    REGISTRY.register(Box::new(metric.clone())).unwrap();
+    metric
+}
+
+/// Updates the metrics with the payload, forwards it to the payload feed
+/// and hands it back to be logged.
+pub fn record_payload(payload: Payload<'_>) -> Payload<'_> {
+    feed_payload(&payload);
+
+    let chain_id = payload
+        .chain_id
+        .map(|chain_id| chain_id.to_string())
+        .unwrap_or_default();
+    let log_type = format!("{:?}", payload.log_type);
+
+    NODE_EVENTS.with_label_values(&[&chain_id, &log_type]).inc();
+
+    match payload.log_type {
+        LogType::TaskReceived => {
+            RANDOMNESS_TASKS
+                .with_label_values(&[&chain_id, "received"])
+                .inc();
+        }
+        LogType::PartialSignatureFinished => {
+            RANDOMNESS_TASKS
+                .with_label_values(&[&chain_id, "signed"])
+                .inc();
+        }
+        LogType::AggregatedSignatureFinished => {
+            RANDOMNESS_TASKS
+                .with_label_values(&[&chain_id, "aggregated"])
+                .inc();
+        }
+        LogType::FulfillmentFinished => {
+            RANDOMNESS_TASKS
+                .with_label_values(&[&chain_id, "fulfilled"])
+                .inc();
+
+            if let Some(receipt) = payload.transaction_receipt_log.as_ref() {
+                FULFILLMENT_GAS_USED
+                    .with_label_values(&[&chain_id])
+                    .observe(receipt.gas_used.low_u128() as f64);
+                FULFILLMENT_EFFECTIVE_GAS_PRICE
+                    .with_label_values(&[&chain_id])
+                    .set(receipt.effective_gas_price.low_u128() as f64 / WEI_PER_GWEI);
+            }
+        }
+        LogType::PartialSignatureSent
+        | LogType::PartialSignatureSendingRejected
+        | LogType::PartialSignatureSendingFailed => {
+            let committer = payload
+                .task_log
+                .as_ref()
+                .and_then(|task_log| task_log.committer_id_address)
+                .map(|committer| format!("{:?}", committer))
+                .unwrap_or_default();
+            let result = match payload.log_type {
+                LogType::PartialSignatureSent => "sent",
+                LogType::PartialSignatureSendingRejected => "rejected",
+                _ => "failed",
+            };
+
+            PARTIAL_SIGNATURES
+                .with_label_values(&[&chain_id, &committer, result])
+                .inc();
+        }
+        LogType::DKGGroupingFinished
+        | LogType::DKGGroupingAborted
+        | LogType::DKGGroupingFailed
+        | LogType::DKGGroupingCommitted
+        | LogType::DKGGroupingCommitFailed
+        | LogType::DKGGroupingAvailable
+        | LogType::DKGGroupingTwisted
+        | LogType::DKGGroupingMemberMisMatch => {
+            DKG_OUTCOMES
+                .with_label_values(&[&chain_id, &log_type])
+                .inc();
+        }
+        _ => {}
+    }
+
+    payload
+}
+
+pub fn observe_dkg_phase_duration(phase: usize, duration: Duration) {
+    DKG_PHASE_DURATION
+        .with_label_values(&[&phase.to_string()])
+        .observe(duration.as_secs_f64());
+}
+
+pub fn inc_listener_interruptions(chain_id: usize, listener: &str) {
+    LISTENER_INTERRUPTIONS
+        .with_label_values(&[&chain_id.to_string(), listener])
+        .inc();
+}
+
+pub fn set_listener_last_success(chain_id: usize, listener: &str) {
+    LISTENER_LAST_SUCCESS
+        .with_label_values(&[&chain_id.to_string(), listener])
+        .set(Utc::now().timestamp());
+}
+
+/// Returns the unix timestamp of the last successful listen, if the listener ever succeeded.
+pub fn get_listener_last_success(chain_id: usize, listener: &str) -> Option<i64> {
+    LISTENER_LAST_SUCCESS
+        .get_metric_with_label_values(&[&chain_id.to_string(), listener])
+        .ok()
+        .map(|gauge| gauge.get())
+        .filter(|timestamp| *timestamp > 0)
+}
+
+pub fn inc_event_queue_depth(topic: &str) {
+    EVENT_QUEUE_DEPTH.with_label_values(&[topic]).inc();
+}
+
+pub fn dec_event_queue_depth(topic: &str) {
+    EVENT_QUEUE_DEPTH.with_label_values(&[topic]).dec();
+}
+
+pub fn observe_rpc_latency(chain_id: usize, method: &str, latency: Duration) {
+    RPC_LATENCY
+        .with_label_values(&[&chain_id.to_string(), method])
+        .observe(latency.as_secs_f64());
+}
+
+/// Renders all the metrics in the Prometheus text format.
+pub fn encode_metrics() -> String {
+    let mut buffer = vec![];
+
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .unwrap();
+
+    String::from_utf8(buffer).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_metrics, record_payload};
+    use crate::log::{
+        build_task_related_payload, build_task_related_transaction_receipt_payload, LogType,
+    };
+    use crate::{BLSTaskType, TaskType};
+    use ethers_core::types::{Address, H256, U256};
+    use serde_json::json;
+
+    #[test]
+    fn test_record_payload() {
+        let request_id = vec![1, 2, 3];
+
+        record_payload(build_task_related_payload(
+            LogType::PartialSignatureSendingRejected,
+            "Partial signature sent and rejected.",
+            1,
+            &request_id,
+            TaskType::BLS(BLSTaskType::Randomness),
+            json!({}),
+            Some(Address::zero()),
+        ));
+
+        record_payload(build_task_related_transaction_receipt_payload(
+            LogType::FulfillmentFinished,
+            "Randomness fulfilled successfully.",
+            1,
+            &request_id,
+            TaskType::BLS(BLSTaskType::Randomness),
+            json!({}),
+            H256::zero(),
+            U256::from(300000),
+            U256::from(2_000_000_000u64),
+        ));
+
+        let metrics = encode_metrics();
+
+        assert!(metrics.contains(
+            "arpa_partial_signatures_total{chain_id=\"1\",committer=\"0x0000000000000000000000000000000000000000\",result=\"rejected\"} 1"
+        ));
+        assert!(
+            metrics.contains("arpa_randomness_tasks_total{chain_id=\"1\",stage=\"fulfilled\"} 1")
+        );
+        assert!(metrics.contains("arpa_fulfillment_effective_gas_price_gwei{chain_id=\"1\"} 2"));
+    }
+}
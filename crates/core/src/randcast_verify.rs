@@ -0,0 +1,244 @@
+//! Offline verification of fulfilled randomness requests.
+//!
+//! Everything here reproduces what the node signs and what the adapter contract derives from
+//! the group signature, so that a consumer or an auditor can check a fulfillment without
+//! trusting the node which committed it.
+
+use crate::u256_to_vec;
+use ethers_core::abi::{decode, encode, ParamType, Token};
+use ethers_core::types::U256;
+use ethers_core::utils::keccak256;
+use thiserror::Error;
+use threshold_bls::sig::SignatureScheme;
+
+use crate::RandomnessRequestType;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RandcastVerifyError {
+    #[error("the signature doesn't verify under the group public key: {0}")]
+    VerificationFailed(String),
+    #[error("the params of a {0:?} request can't be decoded as uint32")]
+    InvalidParams(RandomnessRequestType),
+    #[error("the upper bound of a shuffling request must be positive")]
+    EmptyShuffling,
+}
+
+/// The derived output of a fulfilled request, as passed to the consumer callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RandomnessOutput {
+    Randomness(U256),
+    RandomWords(Vec<U256>),
+    Shuffling(Vec<U256>),
+}
+
+/// Builds the message the group signs for a request, i.e. `seed || assignment_block_height`
+/// with both encoded as 32 bytes big-endian words.
+pub fn build_randomness_message(seed: &U256, assignment_block_height: usize) -> Vec<u8> {
+    [
+        &u256_to_vec(seed)[..],
+        &u256_to_vec(&U256::from(assignment_block_height))[..],
+    ]
+    .concat()
+}
+
+/// Verifies the group signature of a request and returns the randomness derived from it.
+pub fn verify_randomness_signature<S: SignatureScheme>(
+    group_public_key: &S::Public,
+    seed: &U256,
+    assignment_block_height: usize,
+    signature: &[u8],
+) -> Result<U256, RandcastVerifyError> {
+    let msg = build_randomness_message(seed, assignment_block_height);
+
+    S::verify(group_public_key, &msg, signature)
+        .map_err(|e| RandcastVerifyError::VerificationFailed(e.to_string()))?;
+
+    Ok(derive_randomness(signature))
+}
+
+/// Derives the randomness from the group signature as the adapter does,
+/// i.e. `uint256(keccak256(abi.encode(signature)))`.
+pub fn derive_randomness(signature: &[u8]) -> U256 {
+    let signature = U256::from(signature);
+
+    U256::from(keccak256(encode(&[Token::Uint(signature)])))
+}
+
+/// Expands the randomness into `num_words` words as the adapter does for
+/// `RandomWords` requests, i.e. `uint256(keccak256(abi.encode(randomness, i)))`.
+pub fn random_words(randomness: U256, num_words: u32) -> Vec<U256> {
+    (0..num_words)
+        .map(|i| {
+            U256::from(keccak256(encode(&[
+                Token::Uint(randomness),
+                Token::Uint(U256::from(i)),
+            ])))
+        })
+        .collect()
+}
+
+/// Shuffles `[0, upper)` with the randomness as the adapter does for `Shuffling` requests,
+/// which rehashes the randomness after every swap.
+pub fn shuffle(upper: u32, randomness: U256) -> Result<Vec<U256>, RandcastVerifyError> {
+    // the contract reverts on an empty array, so such a request can never be fulfilled
+    if upper == 0 {
+        return Err(RandcastVerifyError::EmptyShuffling);
+    }
+
+    let mut arr = (0..upper).map(U256::from).collect::<Vec<_>>();
+    let mut randomness = randomness;
+
+    for i in (1..arr.len()).rev() {
+        let j = (randomness % U256::from(i)).as_usize();
+        randomness = U256::from(keccak256(encode(&[Token::Uint(randomness)])));
+        arr.swap(i, j);
+    }
+
+    Ok(arr)
+}
+
+/// Derives the output of a request of the given type from its randomness, decoding
+/// the number of words or the upper bound of the shuffling from the request params.
+pub fn derive_randomness_output(
+    request_type: RandomnessRequestType,
+    params: &[u8],
+    randomness: U256,
+) -> Result<RandomnessOutput, RandcastVerifyError> {
+    let decode_uint32 = || {
+        decode(&[ParamType::Uint(32)], params)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next())
+            .and_then(|token| token.into_uint())
+            .filter(|n| *n <= U256::from(u32::MAX))
+            .map(|n| n.as_u32())
+            .ok_or(RandcastVerifyError::InvalidParams(request_type))
+    };
+
+    match request_type {
+        RandomnessRequestType::Randomness => Ok(RandomnessOutput::Randomness(randomness)),
+        RandomnessRequestType::RandomWords => Ok(RandomnessOutput::RandomWords(random_words(
+            randomness,
+            decode_uint32()?,
+        ))),
+        RandomnessRequestType::Shuffling => Ok(RandomnessOutput::Shuffling(shuffle(
+            decode_uint32()?,
+            randomness,
+        )?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::utils::hex;
+    use threshold_bls::curve::bn254::G2;
+    use threshold_bls::schemes::bn254::G2Scheme;
+
+    // the group public key, signature and message of the fixtures in RandcastTestHelper.sol
+    const GROUP_PUBLIC_KEY: &str = "27effe7b4b2737fc0b7b5257473a5d94ea40e1537e3132e61eb0ab870697ce75099ba18cd50d2e27614e2e41033dac811a2a9483fc8e83667c279be179eb163518b09aae20fa812653d84a7bcd362a2130870688faa574fb39fe3b2d20a27bf50aaf5bc665ec5bcc9179ec0544976326ac7af92eba5a455c3b193599db5b88b4";
+
+    const SIGNATURE: &str = "2d6e04ae42003d4de3d00061da94fbf25a52a99cdeb1fdb894c7bf237102b6fb";
+
+    const SEED: &str = "84a4be63154932c9cca9a34f9a931f1f0fba5d28f76c67f8f192fc9a3f66920d";
+
+    const ASSIGNMENT_BLOCK_HEIGHT: usize = 0x12;
+
+    // expected outputs computed with the expansion in Adapter.sol and Utils.sol
+    const RANDOMNESS: &str = "ed49ffa53049ee2be5477e8b18968e6ca2b58803e45b83d16b9d6686b2c99266";
+
+    const RANDOM_WORDS: [&str; 3] = [
+        "709db23d813f4218c6076ae25311f6a4f4fd42fb13551d1689429beda6c39678",
+        "bfbe405749387ca1c0fab43159a1ac9e068f640bb035ba6876cfd1c5a7047b4f",
+        "344553f214312da6223bff89643ffff27adc1929de504c602a90b6f970534903",
+    ];
+
+    const SHUFFLED_ARRAY: [u64; 10] = [7, 8, 9, 6, 1, 4, 5, 3, 2, 0];
+
+    fn group_public_key() -> G2 {
+        bincode::deserialize(&hex::decode(GROUP_PUBLIC_KEY).unwrap()).unwrap()
+    }
+
+    fn u256(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    #[test]
+    fn test_verify_randomness_signature() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+
+        let randomness = verify_randomness_signature::<G2Scheme>(
+            &group_public_key(),
+            &u256(SEED),
+            ASSIGNMENT_BLOCK_HEIGHT,
+            &signature,
+        )
+        .unwrap();
+
+        assert_eq!(randomness, u256(RANDOMNESS));
+
+        // the same signature is invalid for another assignment block height
+        assert!(matches!(
+            verify_randomness_signature::<G2Scheme>(
+                &group_public_key(),
+                &u256(SEED),
+                ASSIGNMENT_BLOCK_HEIGHT + 1,
+                &signature,
+            ),
+            Err(RandcastVerifyError::VerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_randomness_message() {
+        let msg = build_randomness_message(&u256(SEED), ASSIGNMENT_BLOCK_HEIGHT);
+
+        assert_eq!(
+            hex::encode(msg),
+            format!("{}{:064x}", SEED, ASSIGNMENT_BLOCK_HEIGHT)
+        );
+    }
+
+    #[test]
+    fn test_derive_randomness_output() {
+        let randomness = u256(RANDOMNESS);
+
+        assert_eq!(
+            derive_randomness_output(RandomnessRequestType::Randomness, &[], randomness),
+            Ok(RandomnessOutput::Randomness(randomness))
+        );
+
+        let params = encode(&[Token::Uint(U256::from(3))]);
+        assert_eq!(
+            derive_randomness_output(RandomnessRequestType::RandomWords, &params, randomness),
+            Ok(RandomnessOutput::RandomWords(
+                RANDOM_WORDS.iter().map(|w| u256(w)).collect()
+            ))
+        );
+
+        let params = encode(&[Token::Uint(U256::from(10))]);
+        assert_eq!(
+            derive_randomness_output(RandomnessRequestType::Shuffling, &params, randomness),
+            Ok(RandomnessOutput::Shuffling(
+                SHUFFLED_ARRAY.iter().map(|i| U256::from(*i)).collect()
+            ))
+        );
+
+        assert_eq!(
+            derive_randomness_output(RandomnessRequestType::RandomWords, &[1, 2], randomness),
+            Err(RandcastVerifyError::InvalidParams(
+                RandomnessRequestType::RandomWords
+            ))
+        );
+    }
+
+    #[test]
+    fn test_shuffle_bounds() {
+        let randomness = u256(RANDOMNESS);
+
+        assert_eq!(shuffle(1, randomness), Ok(vec![U256::zero()]));
+        assert_eq!(
+            shuffle(0, randomness),
+            Err(RandcastVerifyError::EmptyShuffling)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    BASE_GOERLI_TESTNET_CHAIN_ID, BASE_MAINNET_CHAIN_ID, BASE_SEPOLIA_TESTNET_CHAIN_ID,
+    LOOT_MAINNET_CHAIN_ID, LOOT_TESTNET_CHAIN_ID, OP_DEVNET_CHAIN_ID, OP_GOERLI_TESTNET_CHAIN_ID,
+    OP_MAINNET_CHAIN_ID, OP_SEPOLIA_TESTNET_CHAIN_ID, REDSTONE_GARNET_TESTNET_CHAIN_ID,
+    REDSTONE_HOLESKY_TESTNET_CHAIN_ID, REDSTONE_MAINNET_CHAIN_ID, TAIKO_KATLA_TEST_CHAIN_ID,
+};
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+lazy_static! {
+    static ref CHAIN_PROFILES: RwLock<ChainProfileRegistry> =
+        RwLock::new(ChainProfileRegistry::builtin());
+}
+
+/// How the fees of an EIP-1559 transaction are estimated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeEstimatorStrategy {
+    /// Estimates the priority fee from the fee history, and falls back to eth_gasPrice
+    /// when the fee history has no rewards
+    FeeHistory,
+    /// Takes the difference between eth_gasPrice and the base fee as the priority fee
+    GasPrice,
+}
+
+/// How the chain charges for posting the transaction data to its L1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1DataFeeMode {
+    None,
+    /// Charged separately via the GasPriceOracle predeploy
+    OpStack,
+    /// Folded into the gas used of the transaction by ArbOS
+    Arbitrum,
+}
+
+/// The set of tables where the tasks and signatures of a chain are persisted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum StorageNamespace {
+    Main,
+    OP,
+    Base,
+    Redstone,
+    Loot,
+    Taiko,
+    /// Tables shared by any number of chains, where the rows are keyed by the chain id
+    Relayed,
+}
+
+impl StorageNamespace {
+    /// Whether the tables can hold the tasks of more than one chain
+    pub fn is_shared(&self) -> bool {
+        matches!(self, StorageNamespace::Relayed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainProfile {
+    pub chain_id: usize,
+    pub description: String,
+    pub supports_eip1559: bool,
+    pub fee_estimator: FeeEstimatorStrategy,
+    /// Block time in seconds
+    pub block_time: usize,
+    pub l1_data_fee: L1DataFeeMode,
+    pub storage_namespace: StorageNamespace,
+}
+
+impl ChainProfile {
+    fn builtin(
+        chain_id: usize,
+        description: &str,
+        block_time: usize,
+        l1_data_fee: L1DataFeeMode,
+        storage_namespace: StorageNamespace,
+    ) -> Self {
+        ChainProfile {
+            chain_id,
+            description: description.to_string(),
+            supports_eip1559: true,
+            fee_estimator: FeeEstimatorStrategy::FeeHistory,
+            block_time,
+            l1_data_fee,
+            storage_namespace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChainProfileRegistry {
+    profiles: BTreeMap<usize, ChainProfile>,
+}
+
+impl ChainProfileRegistry {
+    /// Profiles of the relayed chains we support out of the box
+    pub fn builtin() -> Self {
+        let mut registry = ChainProfileRegistry::default();
+
+        for (chain_id, description) in [
+            (OP_MAINNET_CHAIN_ID, "OP Mainnet"),
+            (OP_GOERLI_TESTNET_CHAIN_ID, "OP Goerli"),
+            (OP_SEPOLIA_TESTNET_CHAIN_ID, "OP Sepolia"),
+            (OP_DEVNET_CHAIN_ID, "OP Devnet"),
+        ] {
+            registry.register(ChainProfile::builtin(
+                chain_id,
+                description,
+                2,
+                L1DataFeeMode::OpStack,
+                StorageNamespace::OP,
+            ));
+        }
+
+        for (chain_id, description) in [
+            (BASE_MAINNET_CHAIN_ID, "Base Mainnet"),
+            (BASE_GOERLI_TESTNET_CHAIN_ID, "Base Goerli"),
+            (BASE_SEPOLIA_TESTNET_CHAIN_ID, "Base Sepolia"),
+        ] {
+            registry.register(ChainProfile::builtin(
+                chain_id,
+                description,
+                2,
+                L1DataFeeMode::OpStack,
+                StorageNamespace::Base,
+            ));
+        }
+
+        for (chain_id, description) in [
+            (REDSTONE_MAINNET_CHAIN_ID, "Redstone Mainnet"),
+            (REDSTONE_HOLESKY_TESTNET_CHAIN_ID, "Redstone Holesky"),
+            (REDSTONE_GARNET_TESTNET_CHAIN_ID, "Redstone Garnet"),
+        ] {
+            registry.register(ChainProfile::builtin(
+                chain_id,
+                description,
+                2,
+                L1DataFeeMode::OpStack,
+                StorageNamespace::Redstone,
+            ));
+        }
+
+        for (chain_id, description) in [
+            (LOOT_MAINNET_CHAIN_ID, "Loot Mainnet"),
+            (LOOT_TESTNET_CHAIN_ID, "Loot Testnet"),
+        ] {
+            registry.register(ChainProfile {
+                supports_eip1559: false,
+                ..ChainProfile::builtin(
+                    chain_id,
+                    description,
+                    1,
+                    L1DataFeeMode::Arbitrum,
+                    StorageNamespace::Loot,
+                )
+            });
+        }
+
+        registry.register(ChainProfile::builtin(
+            TAIKO_KATLA_TEST_CHAIN_ID,
+            "Taiko Katla",
+            12,
+            L1DataFeeMode::None,
+            StorageNamespace::Taiko,
+        ));
+
+        registry
+    }
+
+    /// Registers the profile, replacing the existing one with the same chain id
+    pub fn register(&mut self, profile: ChainProfile) {
+        self.profiles.insert(profile.chain_id, profile);
+    }
+
+    pub fn get(&self, chain_id: usize) -> Option<&ChainProfile> {
+        self.profiles.get(&chain_id)
+    }
+
+    pub fn get_all(&self) -> Vec<&ChainProfile> {
+        self.profiles.values().collect()
+    }
+}
+
+/// Registers the profiles loaded from config into the global registry,
+/// on top of the built-in ones.
+pub fn register_chain_profiles(profiles: &[ChainProfile]) {
+    let mut registry = CHAIN_PROFILES.write();
+
+    for profile in profiles {
+        registry.register(profile.clone());
+    }
+}
+
+pub fn get_chain_profile(chain_id: usize) -> Option<ChainProfile> {
+    CHAIN_PROFILES.read().get(chain_id).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_chain_profile, register_chain_profiles, ChainProfile, ChainProfileRegistry,
+        FeeEstimatorStrategy, L1DataFeeMode, StorageNamespace,
+    };
+    use crate::{supports_eip1559, LOOT_MAINNET_CHAIN_ID, OP_MAINNET_CHAIN_ID};
+
+    #[test]
+    fn test_builtin_profiles() {
+        let registry = ChainProfileRegistry::builtin();
+
+        let op = registry.get(OP_MAINNET_CHAIN_ID).unwrap();
+        assert!(op.supports_eip1559);
+        assert_eq!(op.l1_data_fee, L1DataFeeMode::OpStack);
+        assert_eq!(op.storage_namespace, StorageNamespace::OP);
+
+        let loot = registry.get(LOOT_MAINNET_CHAIN_ID).unwrap();
+        assert!(!loot.supports_eip1559);
+        assert_eq!(loot.storage_namespace, StorageNamespace::Loot);
+
+        assert!(registry.get(1).is_none());
+    }
+
+    #[test]
+    fn test_register_new_chain_profile() {
+        let chain_id = 7777777;
+
+        assert!(get_chain_profile(chain_id).is_none());
+        assert!(supports_eip1559(chain_id));
+
+        register_chain_profiles(&[ChainProfile {
+            chain_id,
+            description: "New Chain".to_string(),
+            supports_eip1559: false,
+            fee_estimator: FeeEstimatorStrategy::GasPrice,
+            block_time: 2,
+            l1_data_fee: L1DataFeeMode::OpStack,
+            storage_namespace: StorageNamespace::Relayed,
+        }]);
+
+        let profile = get_chain_profile(chain_id).unwrap();
+        assert_eq!(profile.block_time, 2);
+        assert_eq!(profile.storage_namespace, StorageNamespace::Relayed);
+        assert!(!supports_eip1559(chain_id));
+    }
+}
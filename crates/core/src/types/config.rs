@@ -1,14 +1,27 @@
-use crate::{ConfigError, SchedulerError};
+use crate::{
+    register_chain_profiles, ChainProfile, ChainProfileRegistry, ConfigError, FeeEstimatorStrategy,
+    L1DataFeeMode, SchedulerError, StorageNamespace,
+};
 use ethers_core::rand::{thread_rng, Rng};
-use ethers_core::{k256::ecdsa::SigningKey, types::Address};
+use ethers_core::{
+    k256::ecdsa::SigningKey,
+    types::{Address, U256},
+    utils::hex,
+};
 use ethers_signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Wallet};
+use log::LevelFilter;
 use serde::de;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
 use std::fmt::{self};
+use std::str::FromStr;
 use std::time::Duration;
 use std::{fs::read_to_string, path::PathBuf};
 
+mod validation;
+pub use validation::*;
+
 pub const PLACEHOLDER_ADDRESS: Address = Address::zero();
 
 pub const GAS_RAISE_PERCENTAGE: u32 = 20;
@@ -46,15 +59,40 @@ pub const DEFAULT_PROVIDER_POLLING_INTERVAL_MILLIS: u64 = 10000;
 
 pub const DEFAULT_DYNAMIC_TASK_CLEANER_INTERVAL_MILLIS: u64 = 1000;
 
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MILLIS: u64 = 60000;
+pub const SHUTDOWN_DRAIN_CHECK_INTERVAL_MILLIS: u64 = 1000;
+
+pub const DEFAULT_RELAYED_CHAIN_DRAIN_TIMEOUT_SECS: u64 = 300;
+pub const RELAYED_CHAIN_DRAIN_CHECK_INTERVAL_MILLIS: u64 = 1000;
+
+pub const DEFAULT_MANAGEMENT_LIST_LIMIT: usize = 100;
+pub const MAX_MANAGEMENT_LIST_LIMIT: usize = 1000;
+
 pub const FULFILL_RANDOMNESS_GAS_EXCEPT_CALLBACK: u32 = 670000;
 pub const RANDOMNESS_REWARD_GAS: u32 = 9000;
 pub const VERIFICATION_GAS_OVER_MINIMUM_THRESHOLD: u32 = 50000;
+pub const FULFILL_RANDOMNESS_GAS_BUFFER_PERCENTAGE: u64 = 20;
 pub const DEFAULT_MINIMUM_THRESHOLD: u32 = 3;
 
+pub const DEFAULT_MANAGEMENT_TOKEN_NAME: &str = "default";
+
 pub const DEFAULT_ROLLING_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024 * 1024;
+pub const DEFAULT_LOG_LEVEL: &str = "info";
 
 pub const DEFAULT_BLOCK_TIME: usize = 12;
 pub const DEFAULT_MAX_RANDOMNESS_FULFILLMENT_ATTEMPTS: usize = 3;
+// Randomness tasks are considered expired by the adapter after this period
+pub const RANDOMNESS_TASK_EXPIRATION_SECONDS: usize = 86400;
+
+pub const DEFAULT_FULFILLMENT_EXTRA_REWARD_GWEI: u64 = 0;
+pub const DEFAULT_FULFILLMENT_MAX_LOSS_GWEI: u64 = 0;
+
+pub const DEFAULT_BLIND_SIGNATURE_MAX_ISSUANCES_PER_WINDOW: u32 = 100;
+pub const DEFAULT_BLIND_SIGNATURE_ISSUANCE_WINDOW_SECS: u64 = 3600;
+
+pub const DEFAULT_PRIVATE_SUBMISSION_FALLBACK_BLOCKS: usize = 25;
+pub const DEFAULT_TELEMETRY_SERVICE_NAME: &str = "arpa-node";
+pub const DEFAULT_TELEMETRY_SAMPLING_RATIO: f64 = 1.0;
 
 pub const DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES: usize = 1000000;
 
@@ -62,6 +100,13 @@ pub fn jitter(duration: Duration) -> Duration {
     duration.mul_f64(thread_rng().gen_range(0.5..=1.0))
 }
 
+/// Gas limit of the fulfillment transaction from the result of eth_estimateGas. The state can
+/// change before the transaction is included, and the adapter forwards the callback gas with
+/// callWithExactGas, so an estimate taken as it is would make the fulfillment revert.
+pub fn buffer_fulfill_randomness_gas(estimated_gas: U256) -> U256 {
+    estimated_gas.saturating_add(estimated_gas * FULFILL_RANDOMNESS_GAS_BUFFER_PERCENTAGE / 100)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ConfigHolder {
     pub node_committer_rpc_endpoint: String,
@@ -84,6 +129,15 @@ struct ConfigHolder {
     pub listeners: Option<Vec<ListenerDescriptorHolder>>,
     pub logger: Option<LoggerDescriptorHolder>,
     pub time_limits: Option<TimeLimitDescriptorHolder>,
+    pub fulfillment_policy: Option<FulfillmentPolicyDescriptorHolder>,
+    pub private_submission: Option<PrivateSubmissionDescriptorHolder>,
+    pub telemetry: Option<TelemetryDescriptorHolder>,
+    pub management_access: Option<ManagementAccessDescriptorHolder>,
+    pub blind_signature_issuance: Option<BlindSignatureIssuanceDescriptorHolder>,
+    pub curve: Option<BLSCurve>,
+    pub dkg_protocol: Option<DKGProtocol>,
+    pub dkg_board: Option<DKGBoard>,
+    pub chain_profiles: Option<Vec<ChainProfileHolder>>,
     pub relayed_chains: Vec<RelayedChainHolder>,
 }
 
@@ -109,6 +163,15 @@ impl Default for ConfigHolder {
             listeners: Default::default(),
             logger: Default::default(),
             time_limits: Default::default(),
+            fulfillment_policy: Default::default(),
+            private_submission: Default::default(),
+            telemetry: Default::default(),
+            management_access: Default::default(),
+            blind_signature_issuance: Default::default(),
+            curve: Default::default(),
+            dkg_protocol: Default::default(),
+            dkg_board: Default::default(),
+            chain_profiles: Default::default(),
             relayed_chains: vec![],
         }
     }
@@ -119,6 +182,7 @@ pub struct LoggerDescriptorHolder {
     log_file_path: Option<String>,
     #[serde(deserialize_with = "deserialize_limit")]
     rolling_file_size: u64,
+    log_level: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +191,7 @@ pub struct LoggerDescriptor {
     log_file_path: String,
     #[serde(deserialize_with = "deserialize_limit")]
     rolling_file_size: u64,
+    log_level: String,
 }
 
 impl Default for LoggerDescriptor {
@@ -135,6 +200,7 @@ impl Default for LoggerDescriptor {
             context_logging: false,
             log_file_path: "log/".to_string(),
             rolling_file_size: DEFAULT_ROLLING_LOG_FILE_SIZE,
+            log_level: DEFAULT_LOG_LEVEL.to_string(),
         }
     }
 }
@@ -148,11 +214,15 @@ impl LoggerDescriptor {
             logger_descriptor_holder.log_file_path.unwrap()
         };
         let rolling_file_size = logger_descriptor_holder.rolling_file_size;
+        let log_level = logger_descriptor_holder
+            .log_level
+            .unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string());
 
         Self {
             context_logging,
             log_file_path,
             rolling_file_size,
+            log_level,
         }
     }
 
@@ -167,6 +237,11 @@ impl LoggerDescriptor {
     pub fn get_rolling_file_size(&self) -> u64 {
         self.rolling_file_size
     }
+
+    /// Level of the logs emitted by the node crates, the errors of the other crates are always logged.
+    pub fn get_log_level(&self) -> LevelFilter {
+        LevelFilter::from_str(&self.log_level).unwrap_or(LevelFilter::Info)
+    }
 }
 
 fn deserialize_limit<'de, D>(d: D) -> Result<u64, D::Error>
@@ -254,7 +329,7 @@ struct ListenerDescriptorHolder {
     pub reset_descriptor: Option<FixedIntervalRetryDescriptor>,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ListenerDescriptor {
     pub l_type: ListenerType,
     pub interval_millis: u64,
@@ -321,6 +396,7 @@ pub struct TimeLimitDescriptorHolder {
     pub contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
     pub contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
     pub commit_partial_signature_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    pub shutdown_drain_timeout_millis: Option<u64>,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
@@ -335,6 +411,7 @@ pub struct TimeLimitDescriptor {
     pub contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
     pub contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
     pub commit_partial_signature_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    pub shutdown_drain_timeout_millis: u64,
 }
 
 impl Default for TimeLimitDescriptor {
@@ -369,6 +446,7 @@ impl Default for TimeLimitDescriptor {
                 max_attempts: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_MAX_ATTEMPTS,
                 use_jitter: DEFAULT_COMMIT_PARTIAL_SIGNATURE_RETRY_USE_JITTER,
             },
+            shutdown_drain_timeout_millis: DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MILLIS,
         }
     }
 }
@@ -413,6 +491,12 @@ impl From<TimeLimitDescriptorHolder> for TimeLimitDescriptor {
             time_limit_descriptor_holder.contract_view_retry_descriptor;
         let commit_partial_signature_retry_descriptor =
             time_limit_descriptor_holder.commit_partial_signature_retry_descriptor;
+        let shutdown_drain_timeout_millis =
+            match time_limit_descriptor_holder.shutdown_drain_timeout_millis {
+                None => DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MILLIS,
+                Some(0) => DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MILLIS,
+                Some(v) => v,
+            };
 
         TimeLimitDescriptor {
             block_time,
@@ -425,11 +509,414 @@ impl From<TimeLimitDescriptorHolder> for TimeLimitDescriptor {
             contract_transaction_retry_descriptor,
             contract_view_retry_descriptor,
             commit_partial_signature_retry_descriptor,
+            shutdown_drain_timeout_millis,
         }
     }
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct FulfillmentPolicyDescriptorHolder {
+    pub extra_reward_gwei: Option<u64>,
+    pub max_loss_gwei: Option<u64>,
+}
+
+/// Describes how a committer weighs the gas cost of a randomness fulfillment against its reward.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct FulfillmentPolicyDescriptor {
+    /// Reward expected on top of the gas reimbursement, valued in gwei of the native token.
+    pub extra_reward_gwei: u64,
+    /// Maximum loss in gwei that is acceptable for a single fulfillment.
+    pub max_loss_gwei: u64,
+}
+
+impl Default for FulfillmentPolicyDescriptor {
+    fn default() -> Self {
+        FulfillmentPolicyDescriptor {
+            extra_reward_gwei: DEFAULT_FULFILLMENT_EXTRA_REWARD_GWEI,
+            max_loss_gwei: DEFAULT_FULFILLMENT_MAX_LOSS_GWEI,
+        }
+    }
+}
+
+impl From<FulfillmentPolicyDescriptorHolder> for FulfillmentPolicyDescriptor {
+    fn from(fulfillment_policy_descriptor_holder: FulfillmentPolicyDescriptorHolder) -> Self {
+        let extra_reward_gwei = fulfillment_policy_descriptor_holder
+            .extra_reward_gwei
+            .unwrap_or(DEFAULT_FULFILLMENT_EXTRA_REWARD_GWEI);
+        let max_loss_gwei = fulfillment_policy_descriptor_holder
+            .max_loss_gwei
+            .unwrap_or(DEFAULT_FULFILLMENT_MAX_LOSS_GWEI);
+
+        FulfillmentPolicyDescriptor {
+            extra_reward_gwei,
+            max_loss_gwei,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateSubmissionDescriptorHolder {
+    pub relay_endpoint: String,
+    pub fallback_blocks: Option<usize>,
+}
+
+/// Describes a relay which accepts `eth_sendPrivateTransaction` requests, so that fulfillment
+/// transactions stay out of the public mempool until they are included.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PrivateSubmissionDescriptor {
+    pub relay_endpoint: String,
+    /// Number of blocks to wait for the inclusion by the relay before broadcasting publicly.
+    pub fallback_blocks: usize,
+}
+
+impl std::fmt::Debug for PrivateSubmissionDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateSubmissionDescriptor")
+            .field("relay_endpoint", &"ignored")
+            .field("fallback_blocks", &self.fallback_blocks)
+            .finish()
+    }
+}
+
+impl From<PrivateSubmissionDescriptorHolder> for PrivateSubmissionDescriptor {
+    fn from(private_submission_descriptor_holder: PrivateSubmissionDescriptorHolder) -> Self {
+        let relay_endpoint =
+            resolve_env(&private_submission_descriptor_holder.relay_endpoint, None);
+        let fallback_blocks = match private_submission_descriptor_holder.fallback_blocks {
+            None => DEFAULT_PRIVATE_SUBMISSION_FALLBACK_BLOCKS,
+            Some(0) => DEFAULT_PRIVATE_SUBMISSION_FALLBACK_BLOCKS,
+            Some(v) => v,
+        };
+
+        PrivateSubmissionDescriptor {
+            relay_endpoint,
+            fallback_blocks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryDescriptorHolder {
+    pub otlp_endpoint: String,
+    pub service_name: Option<String>,
+    pub sampling_ratio: Option<f64>,
+}
+
+/// Describes the OTLP collector which receives the tracing spans of the node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryDescriptor {
+    pub otlp_endpoint: String,
+    pub service_name: String,
+    /// Ratio of the root spans to be sampled, between 0.0 and 1.0.
+    pub sampling_ratio: f64,
+}
+
+impl From<TelemetryDescriptorHolder> for TelemetryDescriptor {
+    fn from(telemetry_descriptor_holder: TelemetryDescriptorHolder) -> Self {
+        let otlp_endpoint = resolve_env(&telemetry_descriptor_holder.otlp_endpoint, None);
+        let service_name = telemetry_descriptor_holder
+            .service_name
+            .unwrap_or_else(|| DEFAULT_TELEMETRY_SERVICE_NAME.to_string());
+        let sampling_ratio = telemetry_descriptor_holder
+            .sampling_ratio
+            .unwrap_or(DEFAULT_TELEMETRY_SAMPLING_RATIO)
+            .clamp(0.0, 1.0);
+
+        TelemetryDescriptor {
+            otlp_endpoint,
+            service_name,
+            sampling_ratio,
+        }
+    }
+}
+
+/// The curve the node runs its groups on, which must be the same for every member of a group
+/// and match the curve the contracts verify the signatures on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BLSCurve {
+    /// BN254, verified with the EIP-196 and EIP-197 precompiles
+    #[default]
+    #[serde(rename = "bn254")]
+    BN254,
+    /// BLS12-381, verified with the EIP-2537 precompiles, which the contracts don't support yet
+    #[serde(rename = "bls12_381")]
+    BLS12381,
+}
+
+/// The DKG protocol a node runs when it is grouped, which must be the same
+/// for every member of a group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DKGProtocol {
+    /// Joint-Feldman with the complaint and justification phases
+    #[default]
+    JointFeldman,
+    /// Publicly verifiable secret sharing without any complaint, on the off-chain board only.
+    /// The bad dealers are only excluded deterministically if every member judges the same
+    /// dealings, so it relies on the board leaving out the dealings that any member attests
+    /// it couldn't get, rather than letting the member regard the dealer as absent on its own.
+    Pvss,
+}
+
+/// Where the DKG bundles are exchanged, which must be the same for every member of a group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DKGBoard {
+    /// The bundles are published to the coordinator contract
+    #[default]
+    OnChain,
+    /// The bundles are exchanged between the members over the committer rpc, and only their
+    /// hash commitments are published to the coordinator contract
+    OffChain,
+}
+
+/// Permissions of a management token, each management RPC requires one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagementScope {
+    /// Read-only inspection of the node status
+    Inspect,
+    /// Starting and shutting down listeners
+    Listener,
+    /// Operations with the node keys, e.g. signing and sending transactions
+    Key,
+    /// Serving the requesters of the groups of the node, e.g. decrypting their ciphertexts
+    /// or signing their blinded messages, which grants nothing else of the node keys
+    Request,
+    /// Actions which stop the node from serving, e.g. quitting the network
+    Destructive,
+}
+
+impl ManagementScope {
+    pub fn all() -> Vec<ManagementScope> {
+        vec![
+            ManagementScope::Inspect,
+            ManagementScope::Listener,
+            ManagementScope::Key,
+            ManagementScope::Request,
+            ManagementScope::Destructive,
+        ]
+    }
+}
+
+/// Hashes the management token in the format of `token_hash` in the config.
+pub fn hash_management_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn normalize_token_hash(token_hash: &str) -> String {
+    let token_hash = resolve_env(token_hash, None);
+
+    token_hash.trim_start_matches("0x").to_lowercase()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagementTokenHolder {
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: Vec<ManagementScope>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManagementAccessDescriptorHolder {
+    pub tokens: Option<Vec<ManagementTokenHolder>>,
+    pub confirmation_token_hash: Option<String>,
+}
+
+/// A named management token, only the sha256 hash of the token is kept.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManagementToken {
+    pub name: String,
+    token_hash: String,
+    pub scopes: Vec<ManagementScope>,
+}
+
+impl std::fmt::Debug for ManagementToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManagementToken")
+            .field("name", &self.name)
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+impl ManagementToken {
+    pub fn has_scope(&self, scope: ManagementScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+impl From<ManagementTokenHolder> for ManagementToken {
+    fn from(management_token_holder: ManagementTokenHolder) -> Self {
+        ManagementToken {
+            name: management_token_holder.name,
+            token_hash: normalize_token_hash(&management_token_holder.token_hash),
+            scopes: management_token_holder.scopes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlindSignatureIssuanceDescriptorHolder {
+    pub allowed_callers: Option<Vec<String>>,
+    pub max_issuances_per_window: Option<u32>,
+    pub window_secs: Option<u64>,
+}
+
+/// Describes to whom and how often the node issues blind partial signatures, since a blinded
+/// message can't be told apart from any other and every issuance may be an anonymous token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlindSignatureIssuanceDescriptor {
+    /// Names of the management tokens which may request blind signatures, or any token
+    /// granted the `request` scope if not set.
+    pub allowed_callers: Option<Vec<String>>,
+    /// Maximum number of blind partial signatures issued to the same caller, and for the same
+    /// requester, within the window.
+    pub max_issuances_per_window: u32,
+    pub window_secs: u64,
+}
+
+impl Default for BlindSignatureIssuanceDescriptor {
+    fn default() -> Self {
+        BlindSignatureIssuanceDescriptor {
+            allowed_callers: None,
+            max_issuances_per_window: DEFAULT_BLIND_SIGNATURE_MAX_ISSUANCES_PER_WINDOW,
+            window_secs: DEFAULT_BLIND_SIGNATURE_ISSUANCE_WINDOW_SECS,
+        }
+    }
+}
+
+impl From<BlindSignatureIssuanceDescriptorHolder> for BlindSignatureIssuanceDescriptor {
+    fn from(
+        blind_signature_issuance_descriptor_holder: BlindSignatureIssuanceDescriptorHolder,
+    ) -> Self {
+        BlindSignatureIssuanceDescriptor {
+            allowed_callers: blind_signature_issuance_descriptor_holder.allowed_callers,
+            max_issuances_per_window: blind_signature_issuance_descriptor_holder
+                .max_issuances_per_window
+                .unwrap_or(DEFAULT_BLIND_SIGNATURE_MAX_ISSUANCES_PER_WINDOW),
+            window_secs: blind_signature_issuance_descriptor_holder
+                .window_secs
+                .unwrap_or(DEFAULT_BLIND_SIGNATURE_ISSUANCE_WINDOW_SECS),
+        }
+    }
+}
+
+/// Describes the tokens accepted by the management server.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManagementAccessDescriptor {
+    tokens: Vec<ManagementToken>,
+    /// If set, destructive RPCs require the confirmation token besides the authorization token.
+    confirmation_token_hash: Option<String>,
+}
+
+impl std::fmt::Debug for ManagementAccessDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManagementAccessDescriptor")
+            .field("tokens", &self.tokens)
+            .field(
+                "confirmation_required",
+                &self.confirmation_token_hash.is_some(),
+            )
+            .finish()
+    }
+}
+
+impl ManagementAccessDescriptor {
+    /// Builds the descriptor with `node_management_rpc_token` as a token with all the scopes.
+    /// An empty `node_management_rpc_token` is not accepted.
+    fn build(
+        node_management_rpc_token: &str,
+        management_access_descriptor_holder: ManagementAccessDescriptorHolder,
+    ) -> Self {
+        let mut tokens = vec![];
+
+        if !node_management_rpc_token.is_empty() {
+            tokens.push(ManagementToken {
+                name: DEFAULT_MANAGEMENT_TOKEN_NAME.to_string(),
+                token_hash: hash_management_token(node_management_rpc_token),
+                scopes: ManagementScope::all(),
+            });
+        }
+
+        for token in management_access_descriptor_holder
+            .tokens
+            .unwrap_or_default()
+            .into_iter()
+            .map(ManagementToken::from)
+        {
+            if tokens.iter().any(|t| t.name == token.name) {
+                panic!(
+                    "Management token {} is configured more than once",
+                    token.name
+                );
+            }
+            tokens.push(token);
+        }
+
+        let confirmation_token_hash = management_access_descriptor_holder
+            .confirmation_token_hash
+            .map(|h| normalize_token_hash(&h));
+
+        ManagementAccessDescriptor {
+            tokens,
+            confirmation_token_hash,
+        }
+    }
+
+    /// Returns the management token matching the token in plain text.
+    pub fn authenticate(&self, token: &str) -> Option<&ManagementToken> {
+        let token_hash = hash_management_token(token);
+        self.tokens.iter().find(|t| t.token_hash == token_hash)
+    }
+
+    pub fn requires_confirmation(&self, scope: ManagementScope) -> bool {
+        scope == ManagementScope::Destructive && self.confirmation_token_hash.is_some()
+    }
+
+    pub fn confirm(&self, confirmation_token: &str) -> bool {
+        self.confirmation_token_hash
+            .as_ref()
+            .map_or(false, |h| *h == hash_management_token(confirmation_token))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainProfileHolder {
+    pub chain_id: usize,
+    pub description: Option<String>,
+    pub supports_eip1559: Option<bool>,
+    pub fee_estimator: Option<FeeEstimatorStrategy>,
+    pub block_time: Option<usize>,
+    pub l1_data_fee: Option<L1DataFeeMode>,
+    pub storage_namespace: StorageNamespace,
+}
+
+impl From<ChainProfileHolder> for ChainProfile {
+    fn from(chain_profile_holder: ChainProfileHolder) -> Self {
+        let block_time = match chain_profile_holder.block_time {
+            None => DEFAULT_BLOCK_TIME,
+            Some(0) => DEFAULT_BLOCK_TIME,
+            Some(v) => v,
+        };
+
+        ChainProfile {
+            chain_id: chain_profile_holder.chain_id,
+            description: chain_profile_holder.description.unwrap_or_default(),
+            supports_eip1559: chain_profile_holder.supports_eip1559.unwrap_or(true),
+            fee_estimator: chain_profile_holder
+                .fee_estimator
+                .unwrap_or(FeeEstimatorStrategy::FeeHistory),
+            block_time,
+            l1_data_fee: chain_profile_holder
+                .l1_data_fee
+                .unwrap_or(L1DataFeeMode::None),
+            storage_namespace: chain_profile_holder.storage_namespace,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FixedIntervalRetryDescriptor {
     pub interval_millis: u64,
     pub max_attempts: usize,
@@ -466,7 +953,18 @@ pub struct Config {
     listeners: Vec<ListenerDescriptor>,
     logger: LoggerDescriptor,
     time_limits: TimeLimitDescriptor,
+    fulfillment_policy: FulfillmentPolicyDescriptor,
+    private_submission: Option<PrivateSubmissionDescriptor>,
+    telemetry: Option<TelemetryDescriptor>,
+    management_access: ManagementAccessDescriptor,
+    blind_signature_issuance: BlindSignatureIssuanceDescriptor,
+    curve: BLSCurve,
+    dkg_protocol: DKGProtocol,
+    dkg_board: DKGBoard,
+    chain_profiles: Vec<ChainProfile>,
     relayed_chains: Vec<RelayedChain>,
+    // The file the config is loaded from, if any
+    source_path: Option<PathBuf>,
 }
 
 impl std::fmt::Debug for Config {
@@ -512,13 +1010,45 @@ impl std::fmt::Debug for Config {
             .field("listeners", &self.listeners)
             .field("logger", &self.logger)
             .field("time_limits", &self.time_limits)
+            .field("fulfillment_policy", &self.fulfillment_policy)
+            .field("private_submission", &self.private_submission)
+            .field("telemetry", &self.telemetry)
+            .field("management_access", &self.management_access)
+            .field("blind_signature_issuance", &self.blind_signature_issuance)
+            .field("curve", &self.curve)
+            .field("dkg_protocol", &self.dkg_protocol)
+            .field("dkg_board", &self.dkg_board)
+            .field("chain_profiles", &self.chain_profiles)
             .field("relayed_chains", &self.relayed_chains)
+            .field("source_path", &self.source_path)
             .finish()
     }
 }
 
-impl From<ConfigHolder> for Config {
-    fn from(config_holder: ConfigHolder) -> Self {
+impl TryFrom<ConfigHolder> for Config {
+    type Error = ConfigError;
+
+    fn try_from(config_holder: ConfigHolder) -> Result<Self, Self::Error> {
+        let validation = config_holder.validate();
+        if !validation.is_valid() {
+            return Err(ConfigError::InvalidConfig(
+                validation
+                    .errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ));
+        }
+
+        Ok(Config::from_validated(config_holder))
+    }
+}
+
+impl Config {
+    /// Converts the config holder which has passed the validation, so that the values
+    /// checked there, e.g. the environment variables, are taken as they are.
+    fn from_validated(config_holder: ConfigHolder) -> Self {
         let node_committer_rpc_endpoint = config_holder.node_committer_rpc_endpoint.clone();
         let node_advertised_committer_rpc_endpoint = if config_holder
             .node_advertised_committer_rpc_endpoint
@@ -532,29 +1062,13 @@ impl From<ConfigHolder> for Config {
                 .unwrap()
         };
         let node_management_rpc_endpoint = config_holder.node_management_rpc_endpoint.clone();
-        let node_management_rpc_token = if config_holder.node_management_rpc_token.starts_with('$')
-        {
-            env::var(
-                config_holder
-                    .node_management_rpc_token
-                    .trim_start_matches('$'),
-            )
-            .unwrap()
-        } else if config_holder.node_management_rpc_token.eq("env") {
-            env::var("ARPA_NODE_MANAGEMENT_SERVER_TOKEN").unwrap()
-        } else {
-            config_holder.node_management_rpc_token.clone()
-        };
+        let node_management_rpc_token = resolve_env(
+            &config_holder.node_management_rpc_token,
+            Some("ARPA_NODE_MANAGEMENT_SERVER_TOKEN"),
+        );
 
         let node_statistics_http_endpoint = config_holder.node_statistics_http_endpoint.clone();
-        let provider_endpoint = if config_holder.provider_endpoint.starts_with('$') {
-            env::var(config_holder.provider_endpoint.trim_start_matches('$')).unwrap()
-        } else {
-            config_holder.provider_endpoint
-        };
-        if !provider_endpoint.starts_with("ws") {
-            panic!("Provider endpoint must be a websocket endpoint");
-        }
+        let provider_endpoint = resolve_env(&config_holder.provider_endpoint, None);
         let chain_id = config_holder.chain_id;
         let is_eigenlayer = if config_holder.is_eigenlayer.is_none() {
             false
@@ -592,11 +1106,43 @@ impl From<ConfigHolder> for Config {
         } else {
             LoggerDescriptor::from(config_holder.logger.unwrap())
         };
+        let chain_profiles: Vec<ChainProfile> = config_holder
+            .chain_profiles
+            .map(|p| p.into_iter().map(|p| p.into()).collect())
+            .unwrap_or_default();
+        let mut chain_profile_registry = ChainProfileRegistry::builtin();
+        for profile in chain_profiles.iter() {
+            chain_profile_registry.register(profile.clone());
+        }
         let time_limits = if config_holder.time_limits.is_none() {
-            TimeLimitDescriptor::default()
+            // fall back to the block time of the chain profile if any
+            TimeLimitDescriptor {
+                block_time: chain_profile_registry
+                    .get(chain_id)
+                    .map_or(DEFAULT_BLOCK_TIME, |p| p.block_time),
+                ..TimeLimitDescriptor::default()
+            }
         } else {
             config_holder.time_limits.unwrap().into()
         };
+        let fulfillment_policy = if config_holder.fulfillment_policy.is_none() {
+            FulfillmentPolicyDescriptor::default()
+        } else {
+            config_holder.fulfillment_policy.unwrap().into()
+        };
+        let private_submission = config_holder.private_submission.map(|p| p.into());
+        let telemetry = config_holder.telemetry.map(|t| t.into());
+        let management_access = ManagementAccessDescriptor::build(
+            &node_management_rpc_token,
+            config_holder.management_access.unwrap_or_default(),
+        );
+        let blind_signature_issuance = config_holder
+            .blind_signature_issuance
+            .map(|b| b.into())
+            .unwrap_or_default();
+        let curve = config_holder.curve.unwrap_or_default();
+        let dkg_protocol = config_holder.dkg_protocol.unwrap_or_default();
+        let dkg_board = config_holder.dkg_board.unwrap_or_default();
         let listeners = if config_holder.listeners.is_none() {
             vec![
                 ListenerDescriptor::build(
@@ -651,8 +1197,17 @@ impl From<ConfigHolder> for Config {
         let relayed_chains = config_holder
             .relayed_chains
             .into_iter()
-            .map(|c| c.into())
-            .collect();
+            .map(|c| {
+                let uses_default_time_limits = c.time_limits.is_none();
+                let mut relayed_chain: RelayedChain = c.into();
+                if uses_default_time_limits {
+                    if let Some(profile) = chain_profile_registry.get(relayed_chain.chain_id) {
+                        relayed_chain.time_limits.block_time = profile.block_time;
+                    }
+                }
+                relayed_chain
+            })
+            .collect::<Vec<RelayedChain>>();
 
         Self {
             node_committer_rpc_endpoint,
@@ -674,30 +1229,84 @@ impl From<ConfigHolder> for Config {
             listeners,
             logger,
             time_limits,
+            fulfillment_policy,
+            private_submission,
+            telemetry,
+            management_access,
+            blind_signature_issuance,
+            curve,
+            dkg_protocol,
+            dkg_board,
+            chain_profiles,
             relayed_chains,
+            source_path: None,
         }
     }
 }
 
 impl Default for Config {
     fn default() -> Self {
-        ConfigHolder::default().into()
+        Config::from_validated(ConfigHolder::default())
+    }
+}
+
+/// Resolves the value referring to an environment variable by `$<KEY>`, or by `env`
+/// if the item has a default key. The variable is checked to be set by the validation.
+fn resolve_env(value: &str, default_env_key: Option<&str>) -> String {
+    let env_key = if let Some(env_key) = value.strip_prefix('$') {
+        Some(env_key)
+    } else if value == "env" {
+        default_env_key
+    } else {
+        None
+    };
+
+    match env_key {
+        Some(env_key) => env::var(env_key).unwrap_or_default(),
+        None => value.to_string(),
     }
 }
 
 impl Config {
-    pub fn load(config_path: PathBuf) -> Config {
-        let config_str = &read_to_string(&config_path).unwrap_or_else(|e| {
-            panic!(
-                "Error loading configuration file: {:?} because {:?}, please check the configuration!",
-                config_path, e
-            )
-        });
+    /// Loads the configuration file and registers its chain profiles, returning the
+    /// validation errors instead of panicking so that the binaries can report them.
+    pub fn load(config_path: PathBuf) -> Result<Config, ConfigError> {
+        let config = Config::try_load(config_path)?;
 
-        let config: ConfigHolder =
-            serde_yaml::from_str(config_str).expect("Error parsing configuration file");
+        register_chain_profiles(config.get_chain_profiles());
+
+        Ok(config)
+    }
+
+    /// Loads the configuration like `load`, but without registering the chain profiles,
+    /// so that a running node can check a configuration file before applying it.
+    pub fn try_load(config_path: PathBuf) -> Result<Config, ConfigError> {
+        let config_str = read_to_string(&config_path)
+            .map_err(|e| ConfigError::ReadingFileError(format!("{:?}: {}", config_path, e)))?;
 
-        config.into()
+        let config: ConfigHolder = serde_yaml::from_str(&config_str)
+            .map_err(|e| ConfigError::ParsingError(e.to_string()))?;
+
+        let mut config = Config::try_from(config)?;
+        config.source_path = Some(config_path);
+
+        Ok(config)
+    }
+
+    /// Validates the configuration file without loading it, returning all the errors and
+    /// warnings found instead of panicking on the first invalid value.
+    pub fn validate(config_path: PathBuf) -> Result<ConfigValidation, ConfigError> {
+        let config_str = read_to_string(&config_path)
+            .map_err(|e| ConfigError::ReadingFileError(format!("{:?}: {}", config_path, e)))?;
+
+        let config: ConfigHolder = serde_yaml::from_str(&config_str)
+            .map_err(|e| ConfigError::ParsingError(e.to_string()))?;
+
+        Ok(config.validate())
+    }
+
+    pub fn get_source_path(&self) -> Option<&PathBuf> {
+        self.source_path.as_ref()
     }
 
     pub fn is_eigenlayer(&self) -> bool {
@@ -732,6 +1341,14 @@ impl Config {
         &self.node_management_rpc_token
     }
 
+    pub fn get_management_access(&self) -> &ManagementAccessDescriptor {
+        &self.management_access
+    }
+
+    pub fn get_blind_signature_issuance(&self) -> &BlindSignatureIssuanceDescriptor {
+        &self.blind_signature_issuance
+    }
+
     pub fn get_node_statistics_http_endpoint(&self) -> &str {
         &self.node_statistics_http_endpoint
     }
@@ -860,10 +1477,63 @@ impl Config {
         &self.time_limits
     }
 
+    pub fn get_fulfillment_policy(&self) -> &FulfillmentPolicyDescriptor {
+        &self.fulfillment_policy
+    }
+
+    pub fn get_private_submission(&self) -> Option<&PrivateSubmissionDescriptor> {
+        self.private_submission.as_ref()
+    }
+
+    pub fn get_telemetry(&self) -> Option<&TelemetryDescriptor> {
+        self.telemetry.as_ref()
+    }
+
+    pub fn get_curve(&self) -> BLSCurve {
+        self.curve
+    }
+
+    pub fn get_dkg_protocol(&self) -> DKGProtocol {
+        self.dkg_protocol
+    }
+
+    pub fn get_dkg_board(&self) -> DKGBoard {
+        self.dkg_board
+    }
+
+    pub fn get_chain_profiles(&self) -> &[ChainProfile] {
+        &self.chain_profiles
+    }
+
     pub fn get_relayed_chains(&self) -> &Vec<RelayedChain> {
         &self.relayed_chains
     }
 
+    /// Adds a relayed chain started at runtime, along with its chain profile if it is not builtin.
+    pub fn add_relayed_chain(
+        &mut self,
+        relayed_chain: RelayedChain,
+        chain_profile: Option<ChainProfile>,
+    ) {
+        if let Some(chain_profile) = chain_profile {
+            self.chain_profiles
+                .retain(|p| p.chain_id != chain_profile.chain_id);
+            self.chain_profiles.push(chain_profile);
+        }
+        self.relayed_chains
+            .retain(|c| c.chain_id != relayed_chain.chain_id);
+        self.relayed_chains.push(relayed_chain);
+    }
+
+    /// Removes a relayed chain stopped at runtime, its chain profile is kept for the persisted tasks.
+    pub fn remove_relayed_chain(&mut self, chain_id: usize) -> Option<RelayedChain> {
+        let index = self
+            .relayed_chains
+            .iter()
+            .position(|c| c.chain_id == chain_id)?;
+        Some(self.relayed_chains.remove(index))
+    }
+
     pub fn contract_transaction_retry_descriptor(
         &self,
         chain_id: usize,
@@ -906,6 +1576,8 @@ struct RelayedChainHolder {
     pub arpa_contract_address: Option<String>,
     pub listeners: Option<Vec<ListenerDescriptorHolder>>,
     pub time_limits: Option<TimeLimitDescriptorHolder>,
+    pub fulfillment_policy: Option<FulfillmentPolicyDescriptorHolder>,
+    pub private_submission: Option<PrivateSubmissionDescriptorHolder>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -919,6 +1591,8 @@ pub struct RelayedChain {
     arpa_contract_address: String,
     listeners: Vec<ListenerDescriptor>,
     time_limits: TimeLimitDescriptor,
+    fulfillment_policy: FulfillmentPolicyDescriptor,
+    private_submission: Option<PrivateSubmissionDescriptor>,
 }
 
 impl std::fmt::Debug for RelayedChain {
@@ -936,6 +1610,8 @@ impl std::fmt::Debug for RelayedChain {
             .field("arpa_contract_address", &self.arpa_contract_address)
             .field("listeners", &self.listeners)
             .field("time_limits", &self.time_limits)
+            .field("fulfillment_policy", &self.fulfillment_policy)
+            .field("private_submission", &self.private_submission)
             .finish()
     }
 }
@@ -944,19 +1620,7 @@ impl From<RelayedChainHolder> for RelayedChain {
     fn from(relayed_chain_holder: RelayedChainHolder) -> Self {
         let chain_id = relayed_chain_holder.chain_id;
         let description = relayed_chain_holder.description;
-        let provider_endpoint = if relayed_chain_holder.provider_endpoint.starts_with('$') {
-            env::var(
-                relayed_chain_holder
-                    .provider_endpoint
-                    .trim_start_matches('$'),
-            )
-            .unwrap()
-        } else {
-            relayed_chain_holder.provider_endpoint
-        };
-        if !provider_endpoint.starts_with("ws") {
-            panic!("Provider endpoint must be a websocket endpoint");
-        }
+        let provider_endpoint = resolve_env(&relayed_chain_holder.provider_endpoint, None);
         let controller_oracle_address = relayed_chain_holder.controller_oracle_address;
         let adapter_address = relayed_chain_holder.adapter_address;
         let adapter_deployed_block_height =
@@ -977,6 +1641,14 @@ impl From<RelayedChainHolder> for RelayedChain {
             relayed_chain_holder.time_limits.unwrap().into()
         };
 
+        let fulfillment_policy = if relayed_chain_holder.fulfillment_policy.is_none() {
+            FulfillmentPolicyDescriptor::default()
+        } else {
+            relayed_chain_holder.fulfillment_policy.unwrap().into()
+        };
+
+        let private_submission = relayed_chain_holder.private_submission.map(|p| p.into());
+
         let listeners = if relayed_chain_holder.listeners.is_none() {
             vec![
                 ListenerDescriptor::build(
@@ -1023,6 +1695,8 @@ impl From<RelayedChainHolder> for RelayedChain {
             arpa_contract_address,
             listeners,
             time_limits,
+            fulfillment_policy,
+            private_submission,
         }
     }
 }
@@ -1063,6 +1737,14 @@ impl RelayedChain {
     pub fn get_time_limits(&self) -> &TimeLimitDescriptor {
         &self.time_limits
     }
+
+    pub fn get_fulfillment_policy(&self) -> &FulfillmentPolicyDescriptor {
+        &self.fulfillment_policy
+    }
+
+    pub fn get_private_submission(&self) -> Option<&PrivateSubmissionDescriptor> {
+        self.private_submission.as_ref()
+    }
 }
 
 #[derive(Debug, Eq, Clone, Copy, Hash, PartialEq)]
@@ -1154,6 +1836,7 @@ pub enum SubscriberType {
     PostGrouping,
     ReadyToHandleRandomnessTask,
     RandomnessSignatureAggregation,
+    DeferredFulfillment,
     SendingPartialSignature,
 }
 
@@ -1169,6 +1852,7 @@ impl std::fmt::Display for SubscriberType {
             SubscriberType::RandomnessSignatureAggregation => {
                 write!(f, "RandomnessSignatureAggregation")
             }
+            SubscriberType::DeferredFulfillment => write!(f, "DeferredFulfillment"),
             SubscriberType::SendingPartialSignature => write!(f, "SendingPartialSignature"),
         }
     }
@@ -1272,7 +1956,38 @@ mod tests {
         time::Duration,
     };
 
-    use crate::{jitter, types::config::ConfigHolder, Config, ListenerType};
+    use ethers_core::types::U256;
+
+    use crate::{
+        buffer_fulfill_randomness_gas, hash_management_token, jitter,
+        types::config::{
+            Account, BLSCurve, ChainProfileHolder, ConfigHolder, DKGBoard, DKGProtocol,
+            ManagementAccessDescriptorHolder, ManagementTokenHolder, RelayedChainHolder,
+            TimeLimitDescriptor, TimeLimitDescriptorHolder,
+        },
+        ChainProfile, Config, ListenerType, ManagementScope, RelayedChain, StorageNamespace,
+        DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MILLIS, FULFILL_RANDOMNESS_GAS_BUFFER_PERCENTAGE,
+        OP_DEVNET_CHAIN_ID, PLACEHOLDER_ADDRESS,
+    };
+
+    /// A config holder which passes the validation, as the default one has no account
+    /// and placeholder values for the endpoints and addresses.
+    fn test_config_holder() -> ConfigHolder {
+        ConfigHolder {
+            provider_endpoint: "ws://localhost:8545".to_string(),
+            controller_address: format!("{:?}", PLACEHOLDER_ADDRESS),
+            controller_relayer_address: format!("{:?}", PLACEHOLDER_ADDRESS),
+            adapter_address: format!("{:?}", PLACEHOLDER_ADDRESS),
+            account: Account {
+                hdwallet: None,
+                keystore: None,
+                private_key: Some(
+                    "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".to_string(),
+                ),
+            },
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn test_enum_serialization() {
@@ -1293,7 +2008,7 @@ mod tests {
         let config: ConfigHolder =
             serde_yaml::from_str(config_str).expect("Error loading configuration file");
 
-        println!("config = {:#?}", Config::from(config));
+        println!("config = {:#?}", Config::try_from(config).unwrap());
     }
 
     #[test]
@@ -1314,11 +2029,193 @@ mod tests {
         let config: ConfigHolder =
             serde_yaml::from_str(config_str).expect("Error loading configuration file");
 
-        println!("config = {:#?}", Config::from(config));
+        println!("config = {:#?}", Config::from_validated(config));
 
         fs::remove_file("config.yml").unwrap();
     }
 
+    #[test]
+    fn test_block_time_falls_back_to_chain_profile() {
+        let config_holder = ConfigHolder {
+            chain_id: OP_DEVNET_CHAIN_ID,
+            ..test_config_holder()
+        };
+        assert_eq!(
+            Config::try_from(config_holder)
+                .unwrap()
+                .get_time_limits()
+                .block_time,
+            2
+        );
+
+        let config_holder = ConfigHolder {
+            chain_id: 7777778,
+            chain_profiles: Some(vec![ChainProfileHolder {
+                chain_id: 7777778,
+                description: None,
+                supports_eip1559: None,
+                fee_estimator: None,
+                block_time: Some(5),
+                l1_data_fee: None,
+                storage_namespace: StorageNamespace::Base,
+            }]),
+            ..test_config_holder()
+        };
+        let config = Config::try_from(config_holder).unwrap();
+        assert_eq!(config.get_time_limits().block_time, 5);
+    }
+
+    #[test]
+    fn test_shutdown_drain_timeout() {
+        let time_limits = r#"
+block_time: 3
+listener_interval_millis: 10000
+randomness_task_exclusive_window: 10
+provider_polling_interval_millis: 10000
+provider_reset_descriptor:
+  interval_millis: 5000
+  max_attempts: 17280
+  use_jitter: false
+contract_transaction_retry_descriptor:
+  base: 2
+  factor: 1000
+  max_attempts: 3
+  use_jitter: true
+contract_view_retry_descriptor:
+  base: 2
+  factor: 500
+  max_attempts: 5
+  use_jitter: true
+commit_partial_signature_retry_descriptor:
+  base: 2
+  factor: 1000
+  max_attempts: 5
+  use_jitter: false
+"#;
+        let holder: TimeLimitDescriptorHolder = serde_yaml::from_str(time_limits).unwrap();
+        assert_eq!(
+            TimeLimitDescriptor::from(holder).shutdown_drain_timeout_millis,
+            DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MILLIS
+        );
+
+        let holder: TimeLimitDescriptorHolder = serde_yaml::from_str(&format!(
+            "{}shutdown_drain_timeout_millis: 120000",
+            time_limits
+        ))
+        .unwrap();
+        assert_eq!(
+            TimeLimitDescriptor::from(holder).shutdown_drain_timeout_millis,
+            120000
+        );
+    }
+
+    #[test]
+    fn test_add_and_remove_relayed_chain() {
+        let mut config = Config::try_from(test_config_holder()).unwrap();
+
+        let relayed_chain_holder: RelayedChainHolder = serde_yaml::from_str(
+            r#"
+chain_id: 7777778
+description: "Custom"
+provider_endpoint: "ws://localhost:9545"
+controller_oracle_address: "0x0000000000000000000000000000000000000004"
+adapter_address: "0x0000000000000000000000000000000000000005"
+"#,
+        )
+        .unwrap();
+        let chain_profile = ChainProfile::from(ChainProfileHolder {
+            chain_id: 7777778,
+            description: None,
+            supports_eip1559: None,
+            fee_estimator: None,
+            block_time: None,
+            l1_data_fee: None,
+            storage_namespace: StorageNamespace::Base,
+        });
+
+        config.add_relayed_chain(
+            RelayedChain::from(relayed_chain_holder),
+            Some(chain_profile),
+        );
+        assert_eq!(config.get_relayed_chain_ids(), vec![7777778]);
+        assert_eq!(config.get_chain_profiles().len(), 1);
+
+        assert!(config.remove_relayed_chain(7777778).is_some());
+        assert!(config.remove_relayed_chain(7777778).is_none());
+        assert!(config.get_relayed_chains().is_empty());
+        assert_eq!(config.get_chain_profiles().len(), 1);
+    }
+
+    #[test]
+    fn test_management_access() {
+        let config_holder = ConfigHolder {
+            node_management_rpc_token: "admin_token".to_string(),
+            management_access: Some(ManagementAccessDescriptorHolder {
+                tokens: Some(vec![ManagementTokenHolder {
+                    name: "monitor".to_string(),
+                    token_hash: format!("0x{}", hash_management_token("monitor_token")),
+                    scopes: vec![ManagementScope::Inspect],
+                }]),
+                confirmation_token_hash: Some(hash_management_token("confirmation_token")),
+            }),
+            ..test_config_holder()
+        };
+        let config = Config::try_from(config_holder).unwrap();
+        let management_access = config.get_management_access();
+
+        let admin = management_access.authenticate("admin_token").unwrap();
+        assert_eq!(admin.name, "default");
+        assert!(admin.has_scope(ManagementScope::Destructive));
+
+        let monitor = management_access.authenticate("monitor_token").unwrap();
+        assert_eq!(monitor.name, "monitor");
+        assert!(monitor.has_scope(ManagementScope::Inspect));
+        assert!(!monitor.has_scope(ManagementScope::Key));
+        assert!(!monitor.has_scope(ManagementScope::Request));
+
+        assert!(management_access.authenticate("unknown_token").is_none());
+
+        assert!(management_access.requires_confirmation(ManagementScope::Destructive));
+        assert!(!management_access.requires_confirmation(ManagementScope::Key));
+        assert!(management_access.confirm("confirmation_token"));
+        assert!(!management_access.confirm("admin_token"));
+
+        assert!(!format!("{:?}", config).contains(&hash_management_token("monitor_token")));
+    }
+
+    #[test]
+    fn test_dkg_config() {
+        let config = Config::try_from(test_config_holder()).unwrap();
+        assert_eq!(config.get_curve(), BLSCurve::BN254);
+        assert_eq!(config.get_dkg_protocol(), DKGProtocol::JointFeldman);
+        assert_eq!(config.get_dkg_board(), DKGBoard::OnChain);
+
+        let config_holder: ConfigHolder = serde_yaml::from_str(
+            r#"
+node_committer_rpc_endpoint: "[::1]:50060"
+node_management_rpc_endpoint: "[::1]:50090"
+node_management_rpc_token: "for_test"
+node_statistics_http_endpoint: "http://localhost:50080"
+provider_endpoint: "ws://localhost:8545"
+chain_id: 900
+controller_address: "0x0000000000000000000000000000000000000001"
+controller_relayer_address: "0x0000000000000000000000000000000000000002"
+adapter_address: "0x0000000000000000000000000000000000000003"
+account:
+  private_key: "0x0000000000000000000000000000000000000000000000000000000000000001"
+curve: bn254
+dkg_protocol: pvss
+dkg_board: off_chain
+relayed_chains: []
+"#,
+        )
+        .unwrap();
+        let config = Config::try_from(config_holder).unwrap();
+        assert_eq!(config.get_curve(), BLSCurve::BN254);
+        assert_eq!(config.get_dkg_protocol(), DKGProtocol::Pvss);
+        assert_eq!(config.get_dkg_board(), DKGBoard::OffChain);
+    }
+
     #[test]
     fn test_jitter() {
         for _ in 0..100 {
@@ -1327,4 +2224,13 @@ mod tests {
             assert!(500 <= jitter.as_millis() && jitter.as_millis() <= 1000);
         }
     }
+
+    #[test]
+    fn test_buffer_fulfill_randomness_gas() {
+        assert_eq!(
+            buffer_fulfill_randomness_gas(U256::from(1000000)),
+            U256::from(1000000 + 1000000 * FULFILL_RANDOMNESS_GAS_BUFFER_PERCENTAGE / 100)
+        );
+        assert_eq!(buffer_fulfill_randomness_gas(U256::zero()), U256::zero());
+    }
 }
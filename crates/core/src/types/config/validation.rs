@@ -0,0 +1,704 @@
+use super::{
+    Account, BLSCurve, ConfigHolder, DKGBoard, DKGProtocol, ListenerDescriptorHolder,
+    ManagementAccessDescriptorHolder, RelayedChainHolder, TimeLimitDescriptorHolder,
+    DEFAULT_MANAGEMENT_TOKEN_NAME,
+};
+use crate::{ChainProfile, ChainProfileRegistry, StorageNamespace};
+use ethers_core::types::Address;
+use ethers_signers::LocalWallet;
+use log::LevelFilter;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::str::FromStr;
+
+const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// A problem found in the config, located by the dotted path of the item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigIssue {
+    pub item: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.item, self.message)
+    }
+}
+
+/// Result of validating a config. The node refuses to start with any error,
+/// while the warnings point out the settings which are likely to be unintended.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConfigValidation {
+    pub errors: Vec<ConfigIssue>,
+    pub warnings: Vec<ConfigIssue>,
+}
+
+impl ConfigValidation {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn add_error(&mut self, item: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(ConfigIssue {
+            item: item.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn add_warning(&mut self, item: impl Into<String>, message: impl Into<String>) {
+        self.warnings.push(ConfigIssue {
+            item: item.into(),
+            message: message.into(),
+        });
+    }
+}
+
+impl ConfigHolder {
+    /// Checks the config without going online, collecting all the problems instead of
+    /// stopping at the first one. The conversion into `Config` relies on it.
+    pub(super) fn validate(&self) -> ConfigValidation {
+        let mut validation = ConfigValidation::default();
+
+        if let Some(endpoint) = validate_socket_address(
+            &mut validation,
+            "node_committer_rpc_endpoint",
+            &self.node_committer_rpc_endpoint,
+        ) {
+            if endpoint.ip().is_unspecified()
+                && self.node_advertised_committer_rpc_endpoint.is_none()
+            {
+                validation.add_warning(
+                    "node_advertised_committer_rpc_endpoint",
+                    "not set while node_committer_rpc_endpoint listens on all interfaces, other members can't reach an unspecified address",
+                );
+            }
+        }
+        validate_socket_address(
+            &mut validation,
+            "node_management_rpc_endpoint",
+            &self.node_management_rpc_endpoint,
+        );
+
+        if let Some(token) = resolve_env(
+            &mut validation,
+            "node_management_rpc_token",
+            &self.node_management_rpc_token,
+            Some("ARPA_NODE_MANAGEMENT_SERVER_TOKEN"),
+        ) {
+            if token.is_empty() {
+                validation.add_warning(
+                    "node_management_rpc_token",
+                    "empty, the default management token is disabled",
+                );
+            }
+        }
+
+        validate_provider_endpoint(
+            &mut validation,
+            "provider_endpoint",
+            &self.provider_endpoint,
+        );
+        validate_address(
+            &mut validation,
+            "controller_address",
+            &self.controller_address,
+        );
+        validate_address(
+            &mut validation,
+            "controller_relayer_address",
+            &self.controller_relayer_address,
+        );
+        validate_address(&mut validation, "adapter_address", &self.adapter_address);
+        if let Some(arpa_contract_address) = &self.arpa_contract_address {
+            validate_address(
+                &mut validation,
+                "arpa_contract_address",
+                arpa_contract_address,
+            );
+        }
+
+        validate_account(&mut validation, &self.account);
+
+        if let Some(listeners) = &self.listeners {
+            validate_listeners(&mut validation, "listeners", listeners);
+        }
+
+        if let Some(logger) = &self.logger {
+            if let Some(log_level) = &logger.log_level {
+                if LevelFilter::from_str(log_level).is_err() {
+                    validation.add_error(
+                        "logger.log_level",
+                        format!(
+                            "unknown log level {}, expected one of off, error, warn, info, debug and trace",
+                            log_level
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(time_limits) = &self.time_limits {
+            validate_time_limits(&mut validation, "time_limits", time_limits);
+        }
+
+        if let Some(private_submission) = &self.private_submission {
+            resolve_env(
+                &mut validation,
+                "private_submission.relay_endpoint",
+                &private_submission.relay_endpoint,
+                None,
+            );
+        }
+
+        if let Some(telemetry) = &self.telemetry {
+            resolve_env(
+                &mut validation,
+                "telemetry.otlp_endpoint",
+                &telemetry.otlp_endpoint,
+                None,
+            );
+            if let Some(sampling_ratio) = telemetry.sampling_ratio {
+                if !(0.0..=1.0).contains(&sampling_ratio) {
+                    validation.add_warning(
+                        "telemetry.sampling_ratio",
+                        format!("{} is clamped into [0.0, 1.0]", sampling_ratio),
+                    );
+                }
+            }
+        }
+
+        if let Some(management_access) = &self.management_access {
+            validate_management_access(
+                &mut validation,
+                &self.node_management_rpc_token,
+                management_access,
+            );
+        }
+
+        if self
+            .blind_signature_issuance
+            .as_ref()
+            .and_then(|b| b.window_secs)
+            == Some(0)
+        {
+            validation.add_error(
+                "blind_signature_issuance.window_secs",
+                "must be greater than 0",
+            );
+        }
+
+        // NodeRegistry parses the DKG public key as a BN254 G2 point
+        if self.curve == Some(BLSCurve::BLS12381) {
+            validation.add_error(
+                "curve",
+                "bls12_381 is not supported by the contracts yet, please use bn254",
+            );
+        }
+
+        // a pvss bundle takes about 50KB per member, far beyond the size limit of a transaction
+        if self.dkg_protocol == Some(DKGProtocol::Pvss)
+            && self.dkg_board.unwrap_or_default() == DKGBoard::OnChain
+        {
+            validation.add_error(
+                "dkg_protocol",
+                "pvss bundles are too large to be posted on chain, please use dkg_board: off_chain",
+            );
+        }
+
+        let mut chain_profile_registry = ChainProfileRegistry::builtin();
+        for profile in self.chain_profiles.iter().flatten() {
+            chain_profile_registry.register(ChainProfile::from(profile.clone()));
+        }
+
+        let mut chain_ids = HashSet::from([self.chain_id]);
+        let mut storage_namespaces = vec![StorageNamespace::Main];
+        for (index, relayed_chain) in self.relayed_chains.iter().enumerate() {
+            let item = format!("relayed_chains[{}]", index);
+
+            if !chain_ids.insert(relayed_chain.chain_id) {
+                validation.add_error(
+                    format!("{}.chain_id", item),
+                    format!(
+                        "chain {} is configured more than once",
+                        relayed_chain.chain_id
+                    ),
+                );
+            } else if let Some(profile) = chain_profile_registry.get(relayed_chain.chain_id) {
+                // the tables of the other namespaces can only hold the tasks of one chain
+                if !profile.storage_namespace.is_shared()
+                    && storage_namespaces.contains(&profile.storage_namespace)
+                {
+                    validation.add_error(
+                        format!("{}.chain_id", item),
+                        format!(
+                            "storage namespace {:?} is already in use by another chain, please use the Relayed namespace",
+                            profile.storage_namespace
+                        ),
+                    );
+                }
+                storage_namespaces.push(profile.storage_namespace);
+            } else {
+                validation.add_error(
+                    format!("{}.chain_id", item),
+                    format!(
+                        "chain {} has no chain profile, please add one to chain_profiles to persist its tasks",
+                        relayed_chain.chain_id
+                    ),
+                );
+            }
+
+            validate_relayed_chain(&mut validation, &item, relayed_chain);
+        }
+
+        validation
+    }
+}
+
+fn validate_relayed_chain(
+    validation: &mut ConfigValidation,
+    item: &str,
+    relayed_chain: &RelayedChainHolder,
+) {
+    validate_provider_endpoint(
+        validation,
+        &format!("{}.provider_endpoint", item),
+        &relayed_chain.provider_endpoint,
+    );
+    validate_address(
+        validation,
+        &format!("{}.controller_oracle_address", item),
+        &relayed_chain.controller_oracle_address,
+    );
+    validate_address(
+        validation,
+        &format!("{}.adapter_address", item),
+        &relayed_chain.adapter_address,
+    );
+    if let Some(arpa_contract_address) = &relayed_chain.arpa_contract_address {
+        validate_address(
+            validation,
+            &format!("{}.arpa_contract_address", item),
+            arpa_contract_address,
+        );
+    }
+    if let Some(listeners) = &relayed_chain.listeners {
+        validate_listeners(validation, &format!("{}.listeners", item), listeners);
+    }
+    if let Some(time_limits) = &relayed_chain.time_limits {
+        validate_time_limits(validation, &format!("{}.time_limits", item), time_limits);
+    }
+    if let Some(private_submission) = &relayed_chain.private_submission {
+        resolve_env(
+            validation,
+            &format!("{}.private_submission.relay_endpoint", item),
+            &private_submission.relay_endpoint,
+            None,
+        );
+    }
+}
+
+/// Resolves the value referring to an environment variable by `$<KEY>`,
+/// or by `env` if the item has a default key.
+fn resolve_env(
+    validation: &mut ConfigValidation,
+    item: &str,
+    value: &str,
+    default_env_key: Option<&str>,
+) -> Option<String> {
+    let env_key = if let Some(env_key) = value.strip_prefix('$') {
+        Some(env_key)
+    } else if value == "env" {
+        default_env_key
+    } else {
+        None
+    };
+
+    match env_key {
+        None => Some(value.to_string()),
+        Some(env_key) => match env::var(env_key) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                validation.add_error(item, format!("environment variable {} is not set", env_key));
+                None
+            }
+        },
+    }
+}
+
+fn validate_socket_address(
+    validation: &mut ConfigValidation,
+    item: &str,
+    value: &str,
+) -> Option<SocketAddr> {
+    match value.parse::<SocketAddr>() {
+        Ok(address) => Some(address),
+        Err(_) => {
+            validation.add_error(
+                item,
+                format!(
+                    "{} is not a socket address, e.g. 0.0.0.0:50061 or [::1]:50061",
+                    value
+                ),
+            );
+            None
+        }
+    }
+}
+
+fn validate_provider_endpoint(validation: &mut ConfigValidation, item: &str, value: &str) {
+    if let Some(endpoint) = resolve_env(validation, item, value, None) {
+        if !endpoint.starts_with("ws") {
+            validation.add_error(item, "must be a websocket endpoint");
+        }
+    }
+}
+
+fn validate_address(validation: &mut ConfigValidation, item: &str, value: &str) {
+    match value.parse::<Address>() {
+        Ok(address) if address.is_zero() => {
+            validation.add_warning(item, "the placeholder zero address is configured");
+        }
+        Ok(_) => {}
+        Err(_) => validation.add_error(item, format!("bad format of address {}", value)),
+    }
+}
+
+fn validate_account(validation: &mut ConfigValidation, account: &Account) {
+    let configured = [
+        account.hdwallet.is_some(),
+        account.keystore.is_some(),
+        account.private_key.is_some(),
+    ]
+    .iter()
+    .filter(|c| **c)
+    .count();
+
+    if configured == 0 {
+        validation.add_error(
+            "account",
+            "please provide at least a hdwallet, keystore or plain private key(not recommended)",
+        );
+        return;
+    }
+    if configured > 1 {
+        validation.add_warning(
+            "account",
+            "more than one account type is configured, only the first of hdwallet, keystore and private_key is used",
+        );
+    }
+
+    if let Some(hdwallet) = &account.hdwallet {
+        if let Some(mnemonic) = resolve_env(
+            validation,
+            "account.hdwallet.mnemonic",
+            &hdwallet.mnemonic,
+            Some("ARPA_NODE_HD_ACCOUNT_MNEMONIC"),
+        ) {
+            let word_count = mnemonic.split_whitespace().count();
+            if !MNEMONIC_WORD_COUNTS.contains(&word_count) {
+                validation.add_error(
+                    "account.hdwallet.mnemonic",
+                    format!(
+                        "a mnemonic consists of 12, 15, 18, 21 or 24 words, got {}",
+                        word_count
+                    ),
+                );
+            }
+        }
+    } else if let Some(keystore) = &account.keystore {
+        if !Path::new(&keystore.path).exists() {
+            validation.add_error(
+                "account.keystore.path",
+                format!("keystore file {} does not exist", keystore.path),
+            );
+        }
+        resolve_env(
+            validation,
+            "account.keystore.password",
+            &keystore.password,
+            Some("ARPA_NODE_ACCOUNT_KEYSTORE_PASSWORD"),
+        );
+    } else if let Some(private_key) = &account.private_key {
+        if !private_key.starts_with('$') && private_key != "env" {
+            validation.add_warning(
+                "account.private_key",
+                "plain private key in the config file is not recommended",
+            );
+        }
+        if let Some(private_key) = resolve_env(
+            validation,
+            "account.private_key",
+            private_key,
+            Some("ARPA_NODE_ACCOUNT_PRIVATE_KEY"),
+        ) {
+            if private_key.parse::<LocalWallet>().is_err() {
+                validation.add_error("account.private_key", "bad format of private key");
+            }
+        }
+    }
+}
+
+fn validate_listeners(
+    validation: &mut ConfigValidation,
+    item: &str,
+    listeners: &[ListenerDescriptorHolder],
+) {
+    let mut listener_types = HashSet::new();
+    for (index, listener) in listeners.iter().enumerate() {
+        if !listener_types.insert(listener.l_type) {
+            validation.add_error(
+                format!("{}[{}].l_type", item, index),
+                format!("listener {} is configured more than once", listener.l_type),
+            );
+        }
+    }
+}
+
+fn validate_time_limits(
+    validation: &mut ConfigValidation,
+    item: &str,
+    time_limits: &TimeLimitDescriptorHolder,
+) {
+    if time_limits.block_time == 0 {
+        validation.add_error(format!("{}.block_time", item), "must be greater than 0");
+    }
+    if time_limits.listener_interval_millis == 0 {
+        validation.add_error(
+            format!("{}.listener_interval_millis", item),
+            "must be greater than 0",
+        );
+    }
+    if time_limits.provider_polling_interval_millis == 0 {
+        validation.add_error(
+            format!("{}.provider_polling_interval_millis", item),
+            "must be greater than 0",
+        );
+    }
+}
+
+fn validate_management_access(
+    validation: &mut ConfigValidation,
+    node_management_rpc_token: &str,
+    management_access: &ManagementAccessDescriptorHolder,
+) {
+    let mut names = HashSet::new();
+    if !node_management_rpc_token.is_empty() {
+        names.insert(DEFAULT_MANAGEMENT_TOKEN_NAME.to_string());
+    }
+
+    for (index, token) in management_access.tokens.iter().flatten().enumerate() {
+        let item = format!("management_access.tokens[{}]", index);
+        if !names.insert(token.name.clone()) {
+            validation.add_error(
+                format!("{}.name", item),
+                format!(
+                    "management token {} is configured more than once",
+                    token.name
+                ),
+            );
+        }
+        if token.scopes.is_empty() {
+            validation.add_warning(format!("{}.scopes", item), "no scope is granted");
+        }
+        validate_token_hash(
+            validation,
+            &format!("{}.token_hash", item),
+            &token.token_hash,
+        );
+    }
+
+    if let Some(confirmation_token_hash) = &management_access.confirmation_token_hash {
+        validate_token_hash(
+            validation,
+            "management_access.confirmation_token_hash",
+            confirmation_token_hash,
+        );
+    }
+}
+
+fn validate_token_hash(validation: &mut ConfigValidation, item: &str, value: &str) {
+    if let Some(token_hash) = resolve_env(validation, item, value, None) {
+        let token_hash = token_hash.trim_start_matches("0x");
+        if token_hash.len() != 64 || !token_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            validation.add_error(item, "must be a sha256 hash in hex format");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::config::ConfigHolder;
+
+    const VALID_CONFIG: &str = r#"
+node_committer_rpc_endpoint: "0.0.0.0:50061"
+node_advertised_committer_rpc_endpoint: "10.0.0.1:50061"
+node_management_rpc_endpoint: "[::1]:50091"
+node_management_rpc_token: "for_test"
+node_statistics_http_endpoint: "http://localhost:50081"
+provider_endpoint: "ws://localhost:8545"
+chain_id: 900
+controller_address: "0x0000000000000000000000000000000000000001"
+controller_relayer_address: "0x0000000000000000000000000000000000000002"
+adapter_address: "0x0000000000000000000000000000000000000003"
+account:
+  private_key: "$ARPA_NODE_VALIDATION_TEST_PRIVATE_KEY"
+relayed_chains:
+  - chain_id: 901
+    description: "OP"
+    provider_endpoint: "ws://localhost:9545"
+    controller_oracle_address: "0x0000000000000000000000000000000000000004"
+    adapter_address: "0x0000000000000000000000000000000000000005"
+"#;
+
+    fn validate(config: &str) -> super::ConfigValidation {
+        std::env::set_var(
+            "ARPA_NODE_VALIDATION_TEST_PRIVATE_KEY",
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        );
+        let config_holder: ConfigHolder = serde_yaml::from_str(config).unwrap();
+        config_holder.validate()
+    }
+
+    #[test]
+    fn test_validate_valid_config() {
+        let validation = validate(VALID_CONFIG);
+
+        assert!(validation.is_valid(), "{:?}", validation.errors);
+        assert!(validation.warnings.is_empty(), "{:?}", validation.warnings);
+    }
+
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let config = VALID_CONFIG
+            .replace("ws://localhost:8545", "http://localhost:8545")
+            .replace(
+                "0x0000000000000000000000000000000000000002",
+                "not_an_address",
+            )
+            .replace(
+                "$ARPA_NODE_VALIDATION_TEST_PRIVATE_KEY",
+                "$ARPA_NODE_VALIDATION_TEST_NOT_EXISTED",
+            )
+            .replace("chain_id: 901", "chain_id: 900");
+
+        let validation = validate(&config);
+
+        let items = validation
+            .errors
+            .iter()
+            .map(|e| e.item.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                "provider_endpoint",
+                "controller_relayer_address",
+                "account.private_key",
+                "relayed_chains[0].chain_id",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_dkg_protocol() {
+        let validation = validate(&format!("{}dkg_protocol: pvss\n", VALID_CONFIG));
+        assert_eq!(validation.errors[0].item, "dkg_protocol");
+
+        let validation = validate(&format!(
+            "{}dkg_protocol: pvss\ndkg_board: off_chain\n",
+            VALID_CONFIG
+        ));
+        assert!(validation.is_valid(), "{:?}", validation.errors);
+    }
+
+    #[test]
+    fn test_validate_curve() {
+        let validation = validate(&format!("{}curve: bls12_381\n", VALID_CONFIG));
+        assert_eq!(validation.errors[0].item, "curve");
+
+        let validation = validate(&format!("{}curve: bn254\n", VALID_CONFIG));
+        assert!(validation.is_valid(), "{:?}", validation.errors);
+    }
+
+    #[test]
+    fn test_validate_blind_signature_issuance() {
+        let validation = validate(&format!(
+            "{}blind_signature_issuance:\n  window_secs: 0\n",
+            VALID_CONFIG
+        ));
+        assert_eq!(
+            validation.errors[0].item,
+            "blind_signature_issuance.window_secs"
+        );
+
+        let validation = validate(&format!(
+            "{}blind_signature_issuance:\n  allowed_callers: [\"wallet\"]\n  max_issuances_per_window: 10\n",
+            VALID_CONFIG
+        ));
+        assert!(validation.is_valid(), "{:?}", validation.errors);
+    }
+
+    #[test]
+    fn test_validate_storage_namespaces() {
+        let config = format!(
+            r#"{}  - chain_id: 902
+    description: "Another OP"
+    provider_endpoint: "ws://localhost:9546"
+    controller_oracle_address: "0x0000000000000000000000000000000000000004"
+    adapter_address: "0x0000000000000000000000000000000000000005"
+chain_profiles:
+  - chain_id: 901
+    storage_namespace: NAMESPACE
+  - chain_id: 902
+    storage_namespace: NAMESPACE
+"#,
+            VALID_CONFIG
+        );
+
+        let validation = validate(&config.replace("NAMESPACE", "OP"));
+        assert_eq!(validation.errors.len(), 1);
+        assert_eq!(validation.errors[0].item, "relayed_chains[1].chain_id");
+
+        let validation = validate(&config.replace("NAMESPACE", "Relayed"));
+        assert!(validation.is_valid(), "{:?}", validation.errors);
+    }
+
+    #[test]
+    fn test_validate_warnings() {
+        let config = VALID_CONFIG
+            .replace(
+                "node_advertised_committer_rpc_endpoint: \"10.0.0.1:50061\"\n",
+                "",
+            )
+            .replace(
+                "0x0000000000000000000000000000000000000003",
+                "0x0000000000000000000000000000000000000000",
+            )
+            .replace(
+                "$ARPA_NODE_VALIDATION_TEST_PRIVATE_KEY",
+                "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            );
+
+        let validation = validate(&config);
+
+        assert!(validation.is_valid(), "{:?}", validation.errors);
+        let items = validation
+            .warnings
+            .iter()
+            .map(|w| w.item.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                "node_advertised_committer_rpc_endpoint",
+                "adapter_address",
+                "account.private_key",
+            ]
+        );
+    }
+}
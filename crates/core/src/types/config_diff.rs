@@ -0,0 +1,364 @@
+use crate::{Config, ListenerDescriptor};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+const REDACTED: &str = "<redacted>";
+const ABSENT: &str = "<none>";
+
+// Items holding credentials, whose values are never shown in a diff
+const SECRET_ITEMS: [&str; 5] = [
+    "node_management_rpc_token",
+    "provider_endpoint",
+    "account",
+    "management_access",
+    "private_submission",
+];
+
+// Time limits which are read by the components on every use, instead of only at start
+const RELOADABLE_TIME_LIMITS: [&str; 4] = [
+    "listener_interval_millis",
+    "contract_transaction_retry_descriptor",
+    "contract_view_retry_descriptor",
+    "shutdown_drain_timeout_millis",
+];
+
+/// A single item which differs between two configurations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigChange {
+    /// Dotted path of the item, listeners are keyed by type and relayed chains by chain id.
+    pub item: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.item, self.old, self.new)
+    }
+}
+
+/// Changes between the running configuration and a reloaded one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConfigDiff {
+    /// Changes which can be applied to a running node.
+    pub applicable: Vec<ConfigChange>,
+    /// Changes which require a restart of the node.
+    pub rejected: Vec<ConfigChange>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.applicable.is_empty() && self.rejected.is_empty()
+    }
+
+    /// Whether the new configuration can be applied without restarting the node.
+    pub fn is_applicable(&self) -> bool {
+        self.rejected.is_empty()
+    }
+}
+
+impl Config {
+    /// Compares the configuration with a new one, and splits the changes into the ones
+    /// which can be applied at runtime and the ones which can't.
+    pub fn diff(&self, new: &Config) -> ConfigDiff {
+        let mut old_view = self.diff_view();
+        let mut new_view = new.diff_view();
+
+        let mut diff = ConfigDiff::default();
+
+        // adding or removing a relayed chain is reported as a whole
+        let old_chains = take_relayed_chains(&mut old_view);
+        let new_chains = take_relayed_chains(&mut new_view);
+        let chain_ids = old_chains
+            .keys()
+            .chain(new_chains.keys())
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        for chain_id in chain_ids {
+            match (old_chains.get(&chain_id), new_chains.get(&chain_id)) {
+                (Some(old_chain), Some(new_chain)) => {
+                    let prefix = format!("relayed_chains.{}", chain_id);
+                    diff_values(&prefix, old_chain, new_chain, &mut diff);
+                }
+                (old_chain, new_chain) => diff.rejected.push(ConfigChange {
+                    item: format!("relayed_chains.{}", chain_id),
+                    old: old_chain.map_or(ABSENT, |_| "configured").to_string(),
+                    new: new_chain.map_or(ABSENT, |_| "configured").to_string(),
+                }),
+            }
+        }
+
+        diff_values("", &old_view, &new_view, &mut diff);
+
+        diff
+    }
+
+    fn diff_view(&self) -> Value {
+        json!({
+            "node_committer_rpc_endpoint": self.get_node_committer_rpc_endpoint(),
+            "node_advertised_committer_rpc_endpoint": self.get_node_advertised_committer_rpc_endpoint(),
+            "node_management_rpc_endpoint": self.get_node_management_rpc_endpoint(),
+            "node_management_rpc_token": self.get_node_management_rpc_token(),
+            "node_statistics_http_endpoint": self.get_node_statistics_http_endpoint(),
+            "provider_endpoint": self.get_provider_endpoint(),
+            "chain_id": self.get_main_chain_id(),
+            "is_eigenlayer": self.is_eigenlayer(),
+            "is_consistent_asset_and_node_account": self.is_consistent_asset_and_node_account(),
+            "controller_address": self.get_controller_address(),
+            "controller_relayer_address": self.get_controller_relayer_address(),
+            "adapter_address": self.get_adapter_address(),
+            "adapter_deployed_block_height": self.get_adapter_deployed_block_height(),
+            "arpa_contract_address": self.get_arpa_contract_address(),
+            "data_path": self.get_data_path(),
+            "account": self.get_account(),
+            "listeners": listeners_view(self.get_listeners()),
+            "logger": self.get_logger_descriptor(),
+            "time_limits": self.get_time_limits(),
+            "fulfillment_policy": self.get_fulfillment_policy(),
+            "private_submission": self.get_private_submission(),
+            "telemetry": self.get_telemetry(),
+            "management_access": self.get_management_access(),
+            "blind_signature_issuance": self.get_blind_signature_issuance(),
+            "curve": self.get_curve(),
+            "dkg_protocol": self.get_dkg_protocol(),
+            "dkg_board": self.get_dkg_board(),
+            "chain_profiles": self.get_chain_profiles(),
+            "relayed_chains": self
+                .get_relayed_chains()
+                .iter()
+                .map(|c| {
+                    let mut chain = json!(c);
+                    chain["listeners"] = listeners_view(c.get_listeners());
+                    (c.get_chain_id().to_string(), chain)
+                })
+                .collect::<Map<String, Value>>(),
+        })
+    }
+}
+
+fn listeners_view(listeners: &[ListenerDescriptor]) -> Value {
+    listeners
+        .iter()
+        .map(|l| (l.l_type.to_string(), json!(l)))
+        .collect::<Map<String, Value>>()
+        .into()
+}
+
+fn take_relayed_chains(view: &mut Value) -> BTreeMap<String, Value> {
+    match view["relayed_chains"].take() {
+        Value::Object(chains) => chains.into_iter().collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+fn diff_values(item: &str, old: &Value, new: &Value, diff: &mut ConfigDiff) {
+    if old == new {
+        return;
+    }
+
+    if let (Value::Object(old_fields), Value::Object(new_fields)) = (old, new) {
+        let keys = old_fields
+            .keys()
+            .chain(new_fields.keys())
+            .collect::<BTreeSet<_>>();
+        for key in keys {
+            let sub_item = if item.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", item, key)
+            };
+            diff_values(
+                &sub_item,
+                old_fields.get(key).unwrap_or(&Value::Null),
+                new_fields.get(key).unwrap_or(&Value::Null),
+                diff,
+            );
+        }
+        return;
+    }
+
+    let change = ConfigChange {
+        item: item.to_string(),
+        old: display_value(item, old),
+        new: display_value(item, new),
+    };
+
+    if is_reloadable(item) {
+        diff.applicable.push(change);
+    } else {
+        diff.rejected.push(change);
+    }
+}
+
+fn display_value(item: &str, value: &Value) -> String {
+    let segments = item.split('.').collect::<Vec<_>>();
+    let is_secret = segments.iter().any(|s| SECRET_ITEMS.contains(s));
+
+    match value {
+        Value::Null => ABSENT.to_string(),
+        _ if is_secret => REDACTED.to_string(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+fn is_reloadable(item: &str) -> bool {
+    let segments = item.split('.').collect::<Vec<_>>();
+
+    // the relayed chain has been checked to exist in both configurations
+    let segments = match segments.as_slice() {
+        ["relayed_chains", _, "provider_endpoint"] => return true,
+        ["relayed_chains", _, rest @ ..] => rest,
+        _ => segments.as_slice(),
+    };
+
+    match segments {
+        ["logger", "log_level"] => true,
+        ["listeners", ..] => true,
+        ["time_limits", limit, ..] => RELOADABLE_TIME_LIMITS.contains(limit),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use std::{fs, io::Write};
+
+    const BASE_CONFIG: &str = r#"
+node_committer_rpc_endpoint: "[::1]:50060"
+node_management_rpc_endpoint: "[::1]:50090"
+node_management_rpc_token: "for_test"
+node_statistics_http_endpoint: "http://localhost:50080"
+provider_endpoint: "ws://localhost:8545"
+chain_id: 900
+controller_address: "0x0000000000000000000000000000000000000001"
+controller_relayer_address: "0x0000000000000000000000000000000000000002"
+adapter_address: "0x0000000000000000000000000000000000000003"
+account:
+  private_key: "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+logger:
+  context_logging: false
+  rolling_file_size: 10 gb
+  log_level: info
+listeners:
+  - l_type: Block
+    interval_millis: 1000
+    use_jitter: true
+relayed_chains:
+  - chain_id: 901
+    description: "OP"
+    provider_endpoint: "ws://localhost:9545"
+    controller_oracle_address: "0x0000000000000000000000000000000000000004"
+    adapter_address: "0x0000000000000000000000000000000000000005"
+"#;
+
+    fn load(name: &str, config: &str) -> Config {
+        let path = format!("config_diff_{}.yml", name);
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(config.as_bytes()).unwrap();
+        let config = Config::try_load(path.clone().into());
+        fs::remove_file(&path).unwrap();
+        config.unwrap()
+    }
+
+    #[test]
+    fn test_diff_identical_config() {
+        let old = load("identical_old", BASE_CONFIG);
+        let new = load("identical_new", BASE_CONFIG);
+
+        assert!(old.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_applicable_changes() {
+        let old = load("applicable_old", BASE_CONFIG);
+        let new = load(
+            "applicable_new",
+            &BASE_CONFIG
+                .replace("log_level: info", "log_level: debug")
+                .replace("interval_millis: 1000", "interval_millis: 2000")
+                .replace("ws://localhost:9545", "ws://localhost:9546"),
+        );
+
+        let diff = old.diff(&new);
+        assert!(diff.is_applicable());
+
+        let items = diff
+            .applicable
+            .iter()
+            .map(|c| c.item.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                "relayed_chains.901.provider_endpoint",
+                "listeners.Block.interval_millis",
+                "logger.log_level",
+            ]
+        );
+        assert_eq!(diff.applicable[0].new, "<redacted>");
+        assert_eq!(
+            diff.applicable[2].to_string(),
+            "logger.log_level: info -> debug"
+        );
+    }
+
+    #[test]
+    fn test_diff_rejected_changes() {
+        let old = load("rejected_old", BASE_CONFIG);
+        let new = load(
+            "rejected_new",
+            &BASE_CONFIG
+                .replace("chain_id: 900", "chain_id: 902")
+                .replace(
+                    "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "5c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                )
+                .replace("chain_id: 901", "chain_id: 8453"),
+        );
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_applicable());
+
+        let items = diff
+            .rejected
+            .iter()
+            .map(|c| c.item.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            vec![
+                "relayed_chains.8453",
+                "relayed_chains.901",
+                "account.private_key",
+                "chain_id",
+            ]
+        );
+        assert!(diff.rejected.iter().all(|c| !c
+            .old
+            .contains("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+            && !c
+                .new
+                .contains("5c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")));
+    }
+
+    #[test]
+    fn test_try_load_invalid_config() {
+        let path = "config_diff_invalid.yml";
+        let mut file = fs::File::create(path).unwrap();
+        file.write_all(
+            BASE_CONFIG
+                .replace("ws://localhost:8545", "http://localhost:8545")
+                .as_bytes(),
+        )
+        .unwrap();
+        let config = Config::try_load(path.into());
+        fs::remove_file(path).unwrap();
+
+        assert!(config.is_err());
+        assert!(Config::try_load("not_existed.yml".into()).is_err());
+    }
+}
@@ -52,4 +52,10 @@ pub enum ConfigError {
     InvalidChainId(usize),
     #[error("lack of ARPA contract address")]
     LackOfARPAContractAddress,
+    #[error("failed to read the configuration file {0}")]
+    ReadingFileError(String),
+    #[error("failed to parse the configuration file: {0}")]
+    ParsingError(String),
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
 }
@@ -8,7 +8,7 @@ pub use gas_middleware::*;
 mod types;
 pub use types::*;
 
-use crate::ExponentialBackoffRetryDescriptor;
+use crate::{ExponentialBackoffRetryDescriptor, PrivateSubmissionDescriptor};
 
 #[async_trait]
 pub trait ChainIdentity {
@@ -24,6 +24,18 @@ pub trait ChainIdentity {
 
     fn get_contract_view_retry_descriptor(&self) -> ExponentialBackoffRetryDescriptor;
 
+    fn set_contract_transaction_retry_descriptor(
+        &mut self,
+        contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    );
+
+    fn set_contract_view_retry_descriptor(
+        &mut self,
+        contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    );
+
+    fn get_private_submission_descriptor(&self) -> Option<&PrivateSubmissionDescriptor>;
+
     async fn get_current_gas_price(&self) -> Result<U256, ProviderError>;
 
     async fn get_block_timestamp(
@@ -48,5 +60,15 @@ pub trait ChainProviderManager {
 
     fn get_provider_endpoint(&self) -> &str;
 
+    /// Connects to the endpoint with the polling interval of the current provider,
+    /// without switching to it.
+    async fn connect_provider(
+        &self,
+        provider_endpoint: &str,
+    ) -> Result<Provider<Ws>, ProviderError>;
+
+    /// Switches to the provider connected to the endpoint.
+    fn set_provider(&mut self, provider_endpoint: String, provider: Provider<Ws>);
+
     async fn reset_provider(&mut self) -> Result<(), ProviderError>;
 }
@@ -1,7 +1,8 @@
 use crate::{
-    eip1559_gas_price_estimator, supports_eip1559, ChainProviderManager,
-    ExponentialBackoffRetryDescriptor, GasMiddleware, RelayedChainIdentity,
-    DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES, GAS_RAISE_PERCENTAGE,
+    eip1559_gas_price_estimator, fee_estimator_strategy, supports_eip1559, ChainProviderManager,
+    ExponentialBackoffRetryDescriptor, FeeEstimatorStrategy, GasMiddleware,
+    PrivateSubmissionDescriptor, RelayedChainIdentity, DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
+    GAS_RAISE_PERCENTAGE,
 };
 
 use super::{ChainIdentity, MainChainIdentity};
@@ -47,6 +48,7 @@ pub struct GeneralMainChainIdentity {
     adapter_address: Address,
     contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
     contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    private_submission_descriptor: Option<PrivateSubmissionDescriptor>,
 }
 
 impl GeneralMainChainIdentity {
@@ -61,6 +63,7 @@ impl GeneralMainChainIdentity {
         adapter_address: Address,
         contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
         contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+        private_submission_descriptor: Option<PrivateSubmissionDescriptor>,
     ) -> Self {
         let address = wallet.address();
 
@@ -76,6 +79,7 @@ impl GeneralMainChainIdentity {
             adapter_address,
             contract_transaction_retry_descriptor,
             contract_view_retry_descriptor,
+            private_submission_descriptor,
         }
     }
 }
@@ -106,8 +110,28 @@ impl ChainIdentity for GeneralMainChainIdentity {
         self.contract_view_retry_descriptor
     }
 
+    fn set_contract_transaction_retry_descriptor(
+        &mut self,
+        contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    ) {
+        self.contract_transaction_retry_descriptor = contract_transaction_retry_descriptor;
+    }
+
+    fn set_contract_view_retry_descriptor(
+        &mut self,
+        contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    ) {
+        self.contract_view_retry_descriptor = contract_view_retry_descriptor;
+    }
+
+    fn get_private_submission_descriptor(&self) -> Option<&PrivateSubmissionDescriptor> {
+        self.private_submission_descriptor.as_ref()
+    }
+
     async fn get_current_gas_price(&self) -> Result<U256, ProviderError> {
-        if !supports_eip1559(self.chain_id) {
+        if !supports_eip1559(self.chain_id)
+            || fee_estimator_strategy(self.chain_id) == FeeEstimatorStrategy::GasPrice
+        {
             return self.client.provider().get_gas_price().await;
         }
         let (max_fee, _) = self
@@ -152,21 +176,31 @@ impl ChainProviderManager for GeneralMainChainIdentity {
         &self.provider_endpoint
     }
 
-    async fn reset_provider(&mut self) -> Result<(), ProviderError> {
-        let provider = Arc::new(
-            Provider::<Ws>::connect_with_reconnects(
-                &self.provider_endpoint,
-                DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
-            )
-            .await?
-            .interval(self.get_provider().get_interval()),
-        );
+    async fn connect_provider(
+        &self,
+        provider_endpoint: &str,
+    ) -> Result<Provider<Ws>, ProviderError> {
+        Ok(Provider::<Ws>::connect_with_reconnects(
+            provider_endpoint,
+            DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
+        )
+        .await?
+        .interval(self.get_provider().get_interval()))
+    }
 
+    fn set_provider(&mut self, provider_endpoint: String, provider: Provider<Ws>) {
+        self.provider_endpoint = provider_endpoint;
         self.client = build_client(
             self.client.inner().signer().clone(),
             self.chain_id,
-            provider,
+            Arc::new(provider),
         );
+    }
+
+    async fn reset_provider(&mut self) -> Result<(), ProviderError> {
+        let provider = self.connect_provider(&self.provider_endpoint).await?;
+
+        self.set_provider(self.provider_endpoint.clone(), provider);
 
         Ok(())
     }
@@ -182,6 +216,7 @@ pub struct GeneralRelayedChainIdentity {
     adapter_address: Address,
     contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
     contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    private_submission_descriptor: Option<PrivateSubmissionDescriptor>,
 }
 
 impl GeneralRelayedChainIdentity {
@@ -195,6 +230,7 @@ impl GeneralRelayedChainIdentity {
         adapter_address: Address,
         contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
         contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+        private_submission_descriptor: Option<PrivateSubmissionDescriptor>,
     ) -> Self {
         let address = wallet.address();
 
@@ -209,6 +245,7 @@ impl GeneralRelayedChainIdentity {
             adapter_address,
             contract_transaction_retry_descriptor,
             contract_view_retry_descriptor,
+            private_submission_descriptor,
         }
     }
 }
@@ -239,8 +276,28 @@ impl ChainIdentity for GeneralRelayedChainIdentity {
         self.contract_view_retry_descriptor
     }
 
+    fn set_contract_transaction_retry_descriptor(
+        &mut self,
+        contract_transaction_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    ) {
+        self.contract_transaction_retry_descriptor = contract_transaction_retry_descriptor;
+    }
+
+    fn set_contract_view_retry_descriptor(
+        &mut self,
+        contract_view_retry_descriptor: ExponentialBackoffRetryDescriptor,
+    ) {
+        self.contract_view_retry_descriptor = contract_view_retry_descriptor;
+    }
+
+    fn get_private_submission_descriptor(&self) -> Option<&PrivateSubmissionDescriptor> {
+        self.private_submission_descriptor.as_ref()
+    }
+
     async fn get_current_gas_price(&self) -> Result<U256, ProviderError> {
-        if !supports_eip1559(self.chain_id) {
+        if !supports_eip1559(self.chain_id)
+            || fee_estimator_strategy(self.chain_id) == FeeEstimatorStrategy::GasPrice
+        {
             return self.client.provider().get_gas_price().await;
         }
         let (max_fee, _) = self
@@ -280,21 +337,31 @@ impl ChainProviderManager for GeneralRelayedChainIdentity {
         &self.provider_endpoint
     }
 
-    async fn reset_provider(&mut self) -> Result<(), ProviderError> {
-        let provider = Arc::new(
-            Provider::<Ws>::connect_with_reconnects(
-                &self.provider_endpoint,
-                DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
-            )
-            .await?
-            .interval(self.get_provider().get_interval()),
-        );
+    async fn connect_provider(
+        &self,
+        provider_endpoint: &str,
+    ) -> Result<Provider<Ws>, ProviderError> {
+        Ok(Provider::<Ws>::connect_with_reconnects(
+            provider_endpoint,
+            DEFAULT_WEBSOCKET_PROVIDER_RECONNECT_TIMES,
+        )
+        .await?
+        .interval(self.get_provider().get_interval()))
+    }
 
+    fn set_provider(&mut self, provider_endpoint: String, provider: Provider<Ws>) {
+        self.provider_endpoint = provider_endpoint;
         self.client = build_client(
             self.client.inner().signer().clone(),
             self.chain_id,
-            provider,
+            Arc::new(provider),
         );
+    }
+
+    async fn reset_provider(&mut self) -> Result<(), ProviderError> {
+        let provider = self.connect_provider(&self.provider_endpoint).await?;
+
+        self.set_provider(self.provider_endpoint.clone(), provider);
 
         Ok(())
     }
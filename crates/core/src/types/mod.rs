@@ -1,10 +1,14 @@
+mod chain_profile;
 mod config;
+mod config_diff;
 mod contract;
 mod error;
 mod identity;
 mod node;
 
+pub use chain_profile::*;
 pub use config::*;
+pub use config_diff::*;
 pub use contract::*;
 pub use error::*;
 pub use identity::*;
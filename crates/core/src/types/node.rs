@@ -10,6 +10,8 @@ use threshold_bls::{group::Curve, serialize::point_to_hex};
 
 pub trait Task {
     fn request_id(&self) -> &[u8];
+
+    fn assignment_block_height(&self) -> usize;
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +24,10 @@ impl Task for RandomnessTask {
     fn request_id(&self) -> &[u8] {
         &self.request_id
     }
+
+    fn assignment_block_height(&self) -> usize {
+        self.assignment_block_height
+    }
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -57,6 +63,88 @@ impl std::fmt::Debug for RandomnessTask {
     }
 }
 
+impl Task for DecryptionTask {
+    fn request_id(&self) -> &[u8] {
+        &self.request_id
+    }
+
+    fn assignment_block_height(&self) -> usize {
+        self.assignment_block_height
+    }
+}
+
+/// A request to decrypt a ciphertext encrypted to the public key of the group,
+/// which is revealed once `threshold` decryption shares are committed.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecryptionTask {
+    #[serde(serialize_with = "ser_bytes_in_hex_string")]
+    pub request_id: Vec<u8>,
+    pub group_index: u32,
+    pub requester: Address,
+    /// The serialized `ThresholdCipher` in G1 of the pairing of the group curve
+    #[serde(serialize_with = "ser_bytes_in_hex_string")]
+    pub ciphertext: Vec<u8>,
+    pub request_confirmations: u16,
+    pub assignment_block_height: usize,
+}
+
+impl std::fmt::Debug for DecryptionTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecryptionTask")
+            .field(
+                "request_id",
+                &format!("0x{}", hex::encode(&self.request_id)),
+            )
+            .field("group_index", &self.group_index)
+            .field("requester", &self.requester)
+            .field("assignment_block_height", &self.assignment_block_height)
+            .finish()
+    }
+}
+
+impl Task for BlindSignatureTask {
+    fn request_id(&self) -> &[u8] {
+        &self.request_id
+    }
+
+    fn assignment_block_height(&self) -> usize {
+        self.assignment_block_height
+    }
+}
+
+/// A request to sign a message blinded by the requester, who unblinds the aggregated
+/// signature of the group without revealing the message to the members.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlindSignatureTask {
+    #[serde(serialize_with = "ser_bytes_in_hex_string")]
+    pub request_id: Vec<u8>,
+    pub group_index: u32,
+    pub requester: Address,
+    /// The serialized point of the blinded message in the signature group
+    #[serde(serialize_with = "ser_bytes_in_hex_string")]
+    pub blinded_message: Vec<u8>,
+    pub request_confirmations: u16,
+    pub assignment_block_height: usize,
+}
+
+impl std::fmt::Debug for BlindSignatureTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlindSignatureTask")
+            .field(
+                "request_id",
+                &format!("0x{}", hex::encode(&self.request_id)),
+            )
+            .field("group_index", &self.group_index)
+            .field("requester", &self.requester)
+            .field(
+                "blinded_message",
+                &format!("0x{}", hex::encode(&self.blinded_message)),
+            )
+            .field("assignment_block_height", &self.assignment_block_height)
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DKGTask {
     pub group_index: usize,
@@ -68,6 +156,14 @@ pub struct DKGTask {
     pub coordinator_address: Address,
 }
 
+/// A DKG result committed to the controller, along with the committers who agree on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DKGCommitResult {
+    pub group_epoch: usize,
+    pub committers: Vec<Address>,
+    pub disqualified_nodes: Vec<Address>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupRelayTask {
     pub controller_global_epoch: usize,
@@ -175,6 +271,8 @@ pub enum BLSTaskType {
     Randomness,
     GroupRelay,
     GroupRelayConfirmation,
+    Decryption,
+    BlindSignature,
 }
 
 impl BLSTaskType {
@@ -183,6 +281,8 @@ impl BLSTaskType {
             BLSTaskType::Randomness => 0,
             BLSTaskType::GroupRelay => 1,
             BLSTaskType::GroupRelayConfirmation => 2,
+            BLSTaskType::Decryption => 3,
+            BLSTaskType::BlindSignature => 4,
         }
     }
 }
@@ -192,6 +292,8 @@ impl From<i32> for BLSTaskType {
         match b {
             1 => BLSTaskType::GroupRelay,
             2 => BLSTaskType::GroupRelayConfirmation,
+            3 => BLSTaskType::Decryption,
+            4 => BLSTaskType::BlindSignature,
             _ => BLSTaskType::Randomness,
         }
     }
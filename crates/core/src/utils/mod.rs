@@ -1,3 +1,4 @@
+use crate::{get_chain_profile, FeeEstimatorStrategy, L1DataFeeMode};
 use chrono::Local;
 use ethers_core::{
     types::{Address, I256, U256},
@@ -21,9 +22,25 @@ pub const REDSTONE_GARNET_TESTNET_CHAIN_ID: usize = 17069;
 pub const LOOT_MAINNET_CHAIN_ID: usize = 5151706;
 pub const LOOT_TESTNET_CHAIN_ID: usize = 9088912;
 pub const TAIKO_KATLA_TEST_CHAIN_ID: usize = 167008;
+pub const OP_GAS_PRICE_ORACLE_ADDRESS: &str = "0x420000000000000000000000000000000000000F";
 
+/// Chains without a registered profile are assumed to support EIP-1559
 pub fn supports_eip1559(chain_id: usize) -> bool {
-    chain_id != LOOT_MAINNET_CHAIN_ID && chain_id != LOOT_TESTNET_CHAIN_ID
+    get_chain_profile(chain_id)
+        .map(|profile| profile.supports_eip1559)
+        .unwrap_or(true)
+}
+
+pub fn fee_estimator_strategy(chain_id: usize) -> FeeEstimatorStrategy {
+    get_chain_profile(chain_id)
+        .map(|profile| profile.fee_estimator)
+        .unwrap_or(FeeEstimatorStrategy::FeeHistory)
+}
+
+pub fn l1_data_fee_mode(chain_id: usize) -> L1DataFeeMode {
+    get_chain_profile(chain_id)
+        .map(|profile| profile.l1_data_fee)
+        .unwrap_or(L1DataFeeMode::None)
 }
 
 pub fn format_now_date() -> String {
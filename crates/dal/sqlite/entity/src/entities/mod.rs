@@ -4,6 +4,10 @@ pub mod prelude;
 
 pub mod base_randomness_result;
 pub mod base_randomness_task;
+pub mod blind_signature_result;
+pub mod blind_signature_task;
+pub mod decryption_result;
+pub mod decryption_task;
 pub mod group_info;
 pub mod loot_randomness_result;
 pub mod loot_randomness_task;
@@ -14,5 +18,7 @@ pub mod randomness_result;
 pub mod randomness_task;
 pub mod redstone_randomness_result;
 pub mod redstone_randomness_task;
+pub mod relayed_randomness_result;
+pub mod relayed_randomness_task;
 pub mod taiko_randomness_result;
 pub mod taiko_randomness_task;
@@ -2,6 +2,10 @@
 
 pub use super::base_randomness_result::Entity as BaseRandomnessResult;
 pub use super::base_randomness_task::Entity as BaseRandomnessTask;
+pub use super::blind_signature_result::Entity as BlindSignatureResult;
+pub use super::blind_signature_task::Entity as BlindSignatureTask;
+pub use super::decryption_result::Entity as DecryptionResult;
+pub use super::decryption_task::Entity as DecryptionTask;
 pub use super::group_info::Entity as GroupInfo;
 pub use super::loot_randomness_result::Entity as LootRandomnessResult;
 pub use super::loot_randomness_task::Entity as LootRandomnessTask;
@@ -12,5 +16,7 @@ pub use super::randomness_result::Entity as RandomnessResult;
 pub use super::randomness_task::Entity as RandomnessTask;
 pub use super::redstone_randomness_result::Entity as RedstoneRandomnessResult;
 pub use super::redstone_randomness_task::Entity as RedstoneRandomnessTask;
+pub use super::relayed_randomness_result::Entity as RelayedRandomnessResult;
+pub use super::relayed_randomness_task::Entity as RelayedRandomnessTask;
 pub use super::taiko_randomness_result::Entity as TaikoRandomnessResult;
 pub use super::taiko_randomness_task::Entity as TaikoRandomnessTask;
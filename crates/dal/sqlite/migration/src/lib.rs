@@ -28,6 +28,19 @@ mod m20240318_000025_create_taiko_randomness_task_table;
 mod m20240318_000026_create_taiko_randomness_task_index;
 mod m20240318_000027_create_taiko_randomness_result_table;
 mod m20240318_000028_create_taiko_randomness_result_index;
+mod m20241019_000029_update_group_info_table;
+mod m20241101_000030_create_blind_signature_task_table;
+mod m20241101_000031_create_blind_signature_task_index;
+mod m20241101_000032_create_blind_signature_result_table;
+mod m20241101_000033_create_blind_signature_result_index;
+mod m20241101_000034_create_decryption_task_table;
+mod m20241101_000035_create_decryption_task_index;
+mod m20241101_000036_create_decryption_result_table;
+mod m20241101_000037_create_decryption_result_index;
+mod m20241201_000038_create_relayed_randomness_task_table;
+mod m20241201_000039_create_relayed_randomness_task_index;
+mod m20241201_000040_create_relayed_randomness_result_table;
+mod m20241201_000041_create_relayed_randomness_result_index;
 
 pub use m20220920_000001_create_node_info_table::NodeInfo;
 pub use m20220920_000002_create_group_info_table::GroupInfo;
@@ -45,6 +58,13 @@ pub use m20240312_000021_create_loot_randomness_task_table::LootRandomnessTask;
 pub use m20240312_000023_create_loot_randomness_result_table::LootRandomnessResult;
 pub use m20240318_000025_create_taiko_randomness_task_table::TaikoRandomnessTask;
 pub use m20240318_000027_create_taiko_randomness_result_table::TaikoRandomnessResult;
+pub use m20241019_000029_update_group_info_table::GroupInfoNewColumn;
+pub use m20241101_000030_create_blind_signature_task_table::BlindSignatureTask;
+pub use m20241101_000032_create_blind_signature_result_table::BlindSignatureResult;
+pub use m20241101_000034_create_decryption_task_table::DecryptionTask;
+pub use m20241101_000036_create_decryption_result_table::DecryptionResult;
+pub use m20241201_000038_create_relayed_randomness_task_table::RelayedRandomnessTask;
+pub use m20241201_000040_create_relayed_randomness_result_table::RelayedRandomnessResult;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -79,6 +99,19 @@ impl MigratorTrait for Migrator {
             Box::new(m20240318_000026_create_taiko_randomness_task_index::Migration),
             Box::new(m20240318_000027_create_taiko_randomness_result_table::Migration),
             Box::new(m20240318_000028_create_taiko_randomness_result_index::Migration),
+            Box::new(m20241019_000029_update_group_info_table::Migration),
+            Box::new(m20241101_000030_create_blind_signature_task_table::Migration),
+            Box::new(m20241101_000031_create_blind_signature_task_index::Migration),
+            Box::new(m20241101_000032_create_blind_signature_result_table::Migration),
+            Box::new(m20241101_000033_create_blind_signature_result_index::Migration),
+            Box::new(m20241101_000034_create_decryption_task_table::Migration),
+            Box::new(m20241101_000035_create_decryption_task_index::Migration),
+            Box::new(m20241101_000036_create_decryption_result_table::Migration),
+            Box::new(m20241101_000037_create_decryption_result_index::Migration),
+            Box::new(m20241201_000038_create_relayed_randomness_task_table::Migration),
+            Box::new(m20241201_000039_create_relayed_randomness_task_index::Migration),
+            Box::new(m20241201_000040_create_relayed_randomness_result_table::Migration),
+            Box::new(m20241201_000041_create_relayed_randomness_result_index::Migration),
         ]
     }
 }
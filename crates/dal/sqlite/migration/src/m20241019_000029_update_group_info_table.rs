@@ -0,0 +1,38 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220920_000002_create_group_info_table::GroupInfo;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GroupInfo::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(GroupInfoNewColumn::DkgCheckpoint).blob(BlobSize::Medium),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GroupInfo::Table)
+                    .drop_column(GroupInfoNewColumn::DkgCheckpoint)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum GroupInfoNewColumn {
+    DkgCheckpoint,
+}
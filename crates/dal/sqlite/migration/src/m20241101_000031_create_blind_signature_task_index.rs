@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241101_000030_create_blind_signature_task_table::BlindSignatureTask;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .table(BlindSignatureTask::Table)
+                    .name("blind_signature_task_request_id")
+                    .col(BlindSignatureTask::RequestId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(BlindSignatureTask::Table)
+                    .name("blind_signature_task_group_index")
+                    .col(BlindSignatureTask::GroupIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(BlindSignatureTask::Table)
+                    .name("blind_signature_task_assignment_block_height")
+                    .col(BlindSignatureTask::AssignmentBlockHeight)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("blind_signature_task_request_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("blind_signature_task_group_index")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("blind_signature_task_assignment_block_height")
+                    .to_owned(),
+            )
+            .await
+    }
+}
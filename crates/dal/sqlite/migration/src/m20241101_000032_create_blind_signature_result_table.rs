@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BlindSignatureResult::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::RequestId)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::GroupIndex)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::Message)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::Threshold)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::PartialSignatures)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::CommittedTimes)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::State)
+                            .tiny_unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::CreateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BlindSignatureResult::UpdateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BlindSignatureResult::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum BlindSignatureResult {
+    Table,
+    Id,
+    RequestId,
+    GroupIndex,
+    Message,
+    Threshold,
+    PartialSignatures,
+    CommittedTimes,
+    State,
+    CreateAt,
+    UpdateAt,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241101_000032_create_blind_signature_result_table::BlindSignatureResult;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .table(BlindSignatureResult::Table)
+                    .name("blind_signature_result_request_id")
+                    .col(BlindSignatureResult::RequestId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(BlindSignatureResult::Table)
+                    .name("blind_signature_result_group_index")
+                    .col(BlindSignatureResult::GroupIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(BlindSignatureResult::Table)
+                    .name("blind_signature_result_state")
+                    .col(BlindSignatureResult::State)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("blind_signature_result_request_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("blind_signature_result_group_index")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("blind_signature_result_state")
+                    .to_owned(),
+            )
+            .await
+    }
+}
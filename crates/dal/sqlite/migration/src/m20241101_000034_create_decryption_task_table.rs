@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DecryptionTask::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DecryptionTask::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::RequestId)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::GroupIndex)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DecryptionTask::Requester).text().not_null())
+                    .col(
+                        ColumnDef::new(DecryptionTask::Ciphertext)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::RequestConfirmations)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::AssignmentBlockHeight)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::State)
+                            .tiny_unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::CreateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionTask::UpdateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DecryptionTask::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum DecryptionTask {
+    Table,
+    Id,
+    RequestId,
+    GroupIndex,
+    Requester,
+    Ciphertext,
+    RequestConfirmations,
+    AssignmentBlockHeight,
+    State,
+    CreateAt,
+    UpdateAt,
+}
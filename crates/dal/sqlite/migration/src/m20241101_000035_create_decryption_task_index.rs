@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241101_000034_create_decryption_task_table::DecryptionTask;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .table(DecryptionTask::Table)
+                    .name("decryption_task_request_id")
+                    .col(DecryptionTask::RequestId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(DecryptionTask::Table)
+                    .name("decryption_task_group_index")
+                    .col(DecryptionTask::GroupIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(DecryptionTask::Table)
+                    .name("decryption_task_assignment_block_height")
+                    .col(DecryptionTask::AssignmentBlockHeight)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("decryption_task_request_id").to_owned())
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("decryption_task_group_index").to_owned())
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("decryption_task_assignment_block_height")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DecryptionResult::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DecryptionResult::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::RequestId)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::GroupIndex)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::Message)
+                            .blob(BlobSize::Medium)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::Threshold)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::DecryptionShares)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::CommittedTimes)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::State)
+                            .tiny_unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::CreateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DecryptionResult::UpdateAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DecryptionResult::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum DecryptionResult {
+    Table,
+    Id,
+    RequestId,
+    GroupIndex,
+    Message,
+    Threshold,
+    DecryptionShares,
+    CommittedTimes,
+    State,
+    CreateAt,
+    UpdateAt,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241101_000036_create_decryption_result_table::DecryptionResult;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .table(DecryptionResult::Table)
+                    .name("decryption_result_request_id")
+                    .col(DecryptionResult::RequestId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(DecryptionResult::Table)
+                    .name("decryption_result_group_index")
+                    .col(DecryptionResult::GroupIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(DecryptionResult::Table)
+                    .name("decryption_result_state")
+                    .col(DecryptionResult::State)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("decryption_result_request_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("decryption_result_group_index")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("decryption_result_state").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241201_000038_create_relayed_randomness_task_table::RelayedRandomnessTask;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .table(RelayedRandomnessTask::Table)
+                    .name("relayed_randomness_task_chain_id_request_id")
+                    .col(RelayedRandomnessTask::ChainId)
+                    .col(RelayedRandomnessTask::RequestId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(RelayedRandomnessTask::Table)
+                    .name("relayed_randomness_task_chain_id_group_index")
+                    .col(RelayedRandomnessTask::ChainId)
+                    .col(RelayedRandomnessTask::GroupIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(RelayedRandomnessTask::Table)
+                    .name("relayed_randomness_task_chain_id_assignment_block_height")
+                    .col(RelayedRandomnessTask::ChainId)
+                    .col(RelayedRandomnessTask::AssignmentBlockHeight)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("relayed_randomness_task_chain_id_request_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("relayed_randomness_task_chain_id_group_index")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("relayed_randomness_task_chain_id_assignment_block_height")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241201_000040_create_relayed_randomness_result_table::RelayedRandomnessResult;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .table(RelayedRandomnessResult::Table)
+                    .name("relayed_randomness_result_chain_id_request_id")
+                    .col(RelayedRandomnessResult::ChainId)
+                    .col(RelayedRandomnessResult::RequestId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(RelayedRandomnessResult::Table)
+                    .name("relayed_randomness_result_chain_id_group_index")
+                    .col(RelayedRandomnessResult::ChainId)
+                    .col(RelayedRandomnessResult::GroupIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(RelayedRandomnessResult::Table)
+                    .name("relayed_randomness_result_chain_id_state")
+                    .col(RelayedRandomnessResult::ChainId)
+                    .col(RelayedRandomnessResult::State)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("relayed_randomness_result_chain_id_request_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("relayed_randomness_result_chain_id_group_index")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("relayed_randomness_result_chain_id_state")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -8,7 +8,7 @@ use arpa_dal::cache::InMemoryGroupInfoCache;
 use arpa_dal::error::DataAccessResult;
 use arpa_dal::error::GroupError;
 use arpa_dal::ContextInfoUpdater;
-use arpa_dal::{DKGOutput, GroupInfoFetcher, GroupInfoUpdater};
+use arpa_dal::{DKGCheckpoint, DKGOutput, GroupInfoFetcher, GroupInfoUpdater};
 use async_trait::async_trait;
 use entity::group_info;
 use entity::prelude::GroupInfo;
@@ -61,6 +61,13 @@ impl<C: Curve> std::fmt::Debug for GroupInfoDBClient<C> {
                     .as_ref()
                     .map(|e| e.get_dkg_start_block_height()),
             )
+            .field(
+                "dkg_checkpoint",
+                &self.group_info_cache.as_ref().map(|e| {
+                    e.get_dkg_checkpoint()
+                        .map(|c| c.map(|checkpoint| checkpoint.completed_phases))
+                }),
+            )
             .finish()
     }
 }
@@ -98,6 +105,10 @@ impl<C: Curve> GroupInfoDBClient<C> {
                     (group_info.dkg_status as usize).into(),
                     group_info.self_member_index as usize,
                     group_info.dkg_start_block_height as usize,
+                    group_info
+                        .dkg_checkpoint
+                        .as_ref()
+                        .map(|bytes| bincode::deserialize(bytes).unwrap()),
                 );
 
                 group_info_cache.refresh_context_entry();
@@ -216,6 +227,12 @@ impl<C: Curve> GroupInfoFetcher<C> for GroupInfoDBClient<C> {
         group_info_cache.get_dkg_status()
     }
 
+    fn get_dkg_checkpoint(&self) -> DataAccessResult<Option<&DKGCheckpoint>> {
+        let group_info_cache = self.get_group_info_cache()?;
+
+        group_info_cache.get_dkg_checkpoint()
+    }
+
     fn is_committer(&self, id_address: Address) -> DataAccessResult<bool> {
         let group_info_cache = self.get_group_info_cache()?;
 
@@ -385,7 +402,7 @@ impl<C: Curve + Sync + Send> GroupInfoUpdater<C> for GroupInfoDBClient<C> {
 
         group.remove_disqualified_nodes(&disqualified_nodes);
 
-        GroupMutation::update_members(
+        let group_info = GroupMutation::update_members(
             self.get_connection(),
             self.group_info_cache_model.to_owned().unwrap(),
             group.members.len() as i32,
@@ -397,6 +414,13 @@ impl<C: Curve + Sync + Send> GroupInfoUpdater<C> for GroupInfoDBClient<C> {
             e
         })?;
 
+        GroupMutation::update_dkg_checkpoint(self.get_connection(), group_info, None)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+
         self.refresh_current_group_info().await?;
 
         Ok(disqualified_nodes)
@@ -531,6 +555,44 @@ impl<C: Curve + Sync + Send> GroupInfoUpdater<C> for GroupInfoDBClient<C> {
 
         Ok(true)
     }
+
+    async fn save_dkg_checkpoint(
+        &mut self,
+        index: usize,
+        epoch: usize,
+        checkpoint: DKGCheckpoint,
+    ) -> DataAccessResult<()> {
+        let group_info_cache = self.get_group_info_cache()?;
+
+        let group = group_info_cache.get_group()?;
+
+        if group.index != index {
+            return Err(GroupError::GroupIndexObsolete(group.index).into());
+        }
+
+        if group.epoch != epoch {
+            return Err(GroupError::GroupEpochObsolete(group.epoch).into());
+        }
+
+        if group.state {
+            return Err(GroupError::GroupAlreadyReady.into());
+        }
+
+        GroupMutation::update_dkg_checkpoint(
+            self.get_connection(),
+            self.group_info_cache_model.to_owned().unwrap(),
+            Some(bincode::serialize(&checkpoint)?),
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.refresh_current_group_info().await?;
+
+        Ok(())
+    }
 }
 
 pub struct GroupQuery;
@@ -604,6 +666,7 @@ impl GroupMutation {
         group_info.public_key = Set(Some(public_key));
         group_info.share = Set(Some(share));
         group_info.members = Set(members);
+        group_info.dkg_checkpoint = Set(None);
 
         group_info.update_at = Set(format_now_date());
 
@@ -640,4 +703,18 @@ impl GroupMutation {
 
         group_info.update(db).await
     }
+
+    pub async fn update_dkg_checkpoint(
+        db: &DbConn,
+        model: group_info::Model,
+        dkg_checkpoint: Option<Vec<u8>>,
+    ) -> Result<group_info::Model, DbErr> {
+        let mut group_info: group_info::ActiveModel = model.into();
+
+        group_info.dkg_checkpoint = Set(dkg_checkpoint);
+
+        group_info.update_at = Set(format_now_date());
+
+        group_info.update(db).await
+    }
 }
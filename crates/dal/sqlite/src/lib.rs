@@ -6,27 +6,24 @@ mod test_helper;
 mod types;
 pub use crate::group::GroupInfoDBClient;
 pub use crate::node::NodeInfoDBClient;
+pub use crate::result::BlindSignatureResultDBClient;
+pub use crate::result::DecryptionResultDBClient;
 pub use crate::result::OPSignatureResultDBClient;
 pub use crate::result::SignatureResultDBClient;
 pub use crate::task::BLSTasksDBClient;
+pub use crate::task::BlindSignatureTasksDBClient;
+pub use crate::task::DecryptionTasksDBClient;
 pub use crate::task::OPBLSTasksDBClient;
 pub use crate::types::DBError;
 pub use crate::types::DBResult;
 pub use crate::types::SqliteDB;
+use arpa_core::get_chain_profile;
+use arpa_core::BlindSignatureTask;
+use arpa_core::DecryptionTask;
 use arpa_core::RandomnessTask;
-use arpa_core::BASE_GOERLI_TESTNET_CHAIN_ID;
-use arpa_core::BASE_MAINNET_CHAIN_ID;
-use arpa_core::BASE_SEPOLIA_TESTNET_CHAIN_ID;
-use arpa_core::LOOT_MAINNET_CHAIN_ID;
-use arpa_core::LOOT_TESTNET_CHAIN_ID;
-use arpa_core::OP_DEVNET_CHAIN_ID;
-use arpa_core::OP_GOERLI_TESTNET_CHAIN_ID;
-use arpa_core::OP_MAINNET_CHAIN_ID;
-use arpa_core::OP_SEPOLIA_TESTNET_CHAIN_ID;
-use arpa_core::REDSTONE_GARNET_TESTNET_CHAIN_ID;
-use arpa_core::REDSTONE_HOLESKY_TESTNET_CHAIN_ID;
-use arpa_core::REDSTONE_MAINNET_CHAIN_ID;
-use arpa_core::TAIKO_KATLA_TEST_CHAIN_ID;
+use arpa_core::StorageNamespace;
+use arpa_dal::cache::BlindSignatureResultCache;
+use arpa_dal::cache::DecryptionResultCache;
 use arpa_dal::cache::RandomnessResultCache;
 use arpa_dal::error::DataAccessError;
 use arpa_dal::error::DataAccessResult;
@@ -43,6 +40,7 @@ use migration::UpdateStatement;
 use result::BaseSignatureResultDBClient;
 use result::LootSignatureResultDBClient;
 use result::RedstoneSignatureResultDBClient;
+use result::RelayedSignatureResultDBClient;
 use result::TaikoSignatureResultDBClient;
 use sea_orm::ConnectOptions;
 use sea_orm::ConnectionTrait;
@@ -54,6 +52,7 @@ use std::time::Duration;
 use task::BaseBLSTasksDBClient;
 use task::LootBLSTasksDBClient;
 use task::RedstoneBLSTasksDBClient;
+use task::RelayedBLSTasksDBClient;
 use task::TaikoBLSTasksDBClient;
 use threshold_bls::group::Curve;
 
@@ -99,33 +98,54 @@ impl SqliteDB {
             .map_err(|e| e.into())
     }
 
+    /// Writes the pending changes back to the database file and closes the connections.
+    /// The caches built from the database can't be used afterwards.
+    pub async fn close(&self) -> DBResult<()> {
+        self.connection
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                "PRAGMA wal_checkpoint(TRUNCATE);".to_owned(),
+            ))
+            .await?;
+
+        self.connection.clone().close().await?;
+
+        Ok(())
+    }
+
+    /// Resolves the storage namespace of the chain, the main chain is always identified by 0.
+    fn storage_namespace(chain_id: usize) -> DataAccessResult<StorageNamespace> {
+        if chain_id == 0 {
+            return Ok(StorageNamespace::Main);
+        }
+
+        get_chain_profile(chain_id)
+            .map(|profile| profile.storage_namespace)
+            .ok_or(DataAccessError::InvalidChainId(chain_id))
+    }
+
     pub fn build_randomness_tasks_cache(
         &self,
         chain_id: usize,
     ) -> DataAccessResult<Box<dyn BLSTasksHandler<RandomnessTask>>> {
-        match chain_id {
-            0 => Ok(Box::new(self.get_bls_tasks_client::<RandomnessTask>())),
-            OP_MAINNET_CHAIN_ID
-            | OP_GOERLI_TESTNET_CHAIN_ID
-            | OP_SEPOLIA_TESTNET_CHAIN_ID
-            | OP_DEVNET_CHAIN_ID => Ok(Box::new(self.get_op_bls_tasks_client::<RandomnessTask>())),
-            BASE_MAINNET_CHAIN_ID
-            | BASE_GOERLI_TESTNET_CHAIN_ID
-            | BASE_SEPOLIA_TESTNET_CHAIN_ID => {
+        match Self::storage_namespace(chain_id)? {
+            StorageNamespace::Main => Ok(Box::new(self.get_bls_tasks_client::<RandomnessTask>())),
+            StorageNamespace::OP => Ok(Box::new(self.get_op_bls_tasks_client::<RandomnessTask>())),
+            StorageNamespace::Base => {
                 Ok(Box::new(self.get_base_bls_tasks_client::<RandomnessTask>()))
             }
-            REDSTONE_HOLESKY_TESTNET_CHAIN_ID
-            | REDSTONE_MAINNET_CHAIN_ID
-            | REDSTONE_GARNET_TESTNET_CHAIN_ID => Ok(Box::new(
+            StorageNamespace::Redstone => Ok(Box::new(
                 self.get_redstone_bls_tasks_client::<RandomnessTask>(),
             )),
-            LOOT_MAINNET_CHAIN_ID | LOOT_TESTNET_CHAIN_ID => {
+            StorageNamespace::Loot => {
                 Ok(Box::new(self.get_loot_bls_tasks_client::<RandomnessTask>()))
             }
-            TAIKO_KATLA_TEST_CHAIN_ID => Ok(Box::new(
+            StorageNamespace::Taiko => Ok(Box::new(
                 self.get_taiko_bls_tasks_client::<RandomnessTask>(),
             )),
-            _ => Err(DataAccessError::InvalidChainId(chain_id)),
+            StorageNamespace::Relayed => Ok(Box::new(
+                self.get_relayed_bls_tasks_client::<RandomnessTask>(chain_id),
+            )),
         }
     }
 
@@ -133,32 +153,47 @@ impl SqliteDB {
         &self,
         chain_id: usize,
     ) -> DataAccessResult<Box<dyn SignatureResultCacheHandler<RandomnessResultCache>>> {
-        match chain_id {
-            0 => Ok(Box::new(self.get_randomness_result_client().await?)),
-            OP_MAINNET_CHAIN_ID
-            | OP_GOERLI_TESTNET_CHAIN_ID
-            | OP_SEPOLIA_TESTNET_CHAIN_ID
-            | OP_DEVNET_CHAIN_ID => Ok(Box::new(self.get_op_randomness_result_client().await?)),
-            BASE_MAINNET_CHAIN_ID
-            | BASE_GOERLI_TESTNET_CHAIN_ID
-            | BASE_SEPOLIA_TESTNET_CHAIN_ID => {
-                Ok(Box::new(self.get_base_randomness_result_client().await?))
-            }
-            REDSTONE_HOLESKY_TESTNET_CHAIN_ID
-            | REDSTONE_MAINNET_CHAIN_ID
-            | REDSTONE_GARNET_TESTNET_CHAIN_ID => Ok(Box::new(
+        match Self::storage_namespace(chain_id)? {
+            StorageNamespace::Main => Ok(Box::new(self.get_randomness_result_client().await?)),
+            StorageNamespace::OP => Ok(Box::new(self.get_op_randomness_result_client().await?)),
+            StorageNamespace::Base => Ok(Box::new(self.get_base_randomness_result_client().await?)),
+            StorageNamespace::Redstone => Ok(Box::new(
                 self.get_redstone_randomness_result_client().await?,
             )),
-            LOOT_MAINNET_CHAIN_ID | LOOT_TESTNET_CHAIN_ID => {
-                Ok(Box::new(self.get_loot_randomness_result_client().await?))
-            }
-            TAIKO_KATLA_TEST_CHAIN_ID => {
+            StorageNamespace::Loot => Ok(Box::new(self.get_loot_randomness_result_client().await?)),
+            StorageNamespace::Taiko => {
                 Ok(Box::new(self.get_taiko_randomness_result_client().await?))
             }
-            _ => Err(DataAccessError::InvalidChainId(chain_id)),
+            StorageNamespace::Relayed => Ok(Box::new(
+                self.get_relayed_randomness_result_client(chain_id).await?,
+            )),
         }
     }
 
+    /// Decryption tasks are only requested on the main chain.
+    pub fn build_decryption_tasks_cache(&self) -> Box<dyn BLSTasksHandler<DecryptionTask>> {
+        Box::new(self.get_decryption_tasks_client())
+    }
+
+    pub async fn build_decryption_result_cache(
+        &self,
+    ) -> DataAccessResult<Box<dyn SignatureResultCacheHandler<DecryptionResultCache>>> {
+        Ok(Box::new(self.get_decryption_result_client().await?))
+    }
+
+    /// Blind signature tasks are only requested on the main chain.
+    pub fn build_blind_signature_tasks_cache(
+        &self,
+    ) -> Box<dyn BLSTasksHandler<BlindSignatureTask>> {
+        Box::new(self.get_blind_signature_tasks_client())
+    }
+
+    pub async fn build_blind_signature_result_cache(
+        &self,
+    ) -> DataAccessResult<Box<dyn SignatureResultCacheHandler<BlindSignatureResultCache>>> {
+        Ok(Box::new(self.get_blind_signature_result_client().await?))
+    }
+
     pub(crate) async fn execute_update_statement(
         &self,
         stmt: &UpdateStatement,
@@ -215,6 +250,9 @@ impl BLSTasksHandler<RandomnessTask> for BaseBLSTasksDBClient<RandomnessTask> {}
 impl BLSTasksHandler<RandomnessTask> for RedstoneBLSTasksDBClient<RandomnessTask> {}
 impl BLSTasksHandler<RandomnessTask> for LootBLSTasksDBClient<RandomnessTask> {}
 impl BLSTasksHandler<RandomnessTask> for TaikoBLSTasksDBClient<RandomnessTask> {}
+impl BLSTasksHandler<RandomnessTask> for RelayedBLSTasksDBClient<RandomnessTask> {}
+impl BLSTasksHandler<DecryptionTask> for DecryptionTasksDBClient {}
+impl BLSTasksHandler<BlindSignatureTask> for BlindSignatureTasksDBClient {}
 
 impl SignatureResultCacheHandler<RandomnessResultCache>
     for SignatureResultDBClient<RandomnessResultCache>
@@ -240,23 +278,37 @@ impl SignatureResultCacheHandler<RandomnessResultCache>
     for TaikoSignatureResultDBClient<RandomnessResultCache>
 {
 }
+impl SignatureResultCacheHandler<RandomnessResultCache>
+    for RelayedSignatureResultDBClient<RandomnessResultCache>
+{
+}
+impl SignatureResultCacheHandler<DecryptionResultCache> for DecryptionResultDBClient {}
+impl SignatureResultCacheHandler<BlindSignatureResultCache> for BlindSignatureResultDBClient {}
 
 #[cfg(test)]
 pub mod sqlite_tests {
     use crate::test_helper;
     use crate::SqliteDB;
+    use arpa_core::BlindSignatureTask;
     use arpa_core::DKGStatus;
     use arpa_core::DKGTask;
+    use arpa_core::DecryptionTask;
     use arpa_core::RandomnessRequestType;
     use arpa_core::RandomnessTask;
     use arpa_core::DEFAULT_RANDOMNESS_TASK_EXCLUSIVE_WINDOW;
     use arpa_core::PLACEHOLDER_ADDRESS;
     use arpa_dal::BLSTasksFetcher;
     use arpa_dal::BLSTasksUpdater;
+    use arpa_dal::BlockHeightRange;
+    use arpa_dal::DKGCheckpoint;
     use arpa_dal::GroupInfoFetcher;
     use arpa_dal::GroupInfoUpdater;
+    use arpa_dal::ListCursor;
+    use arpa_dal::ListPage;
     use arpa_dal::NodeInfoFetcher;
     use arpa_dal::NodeInfoUpdater;
+    use arpa_dal::SignatureResultCacheFetcher;
+    use arpa_dal::SignatureResultCacheUpdater;
     use ethers_core::types::Address;
     use ethers_core::types::U256;
     use std::{fs, path::PathBuf};
@@ -306,6 +358,19 @@ pub mod sqlite_tests {
         teardown();
     }
 
+    #[tokio::test]
+    async fn test_close() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        assert!(db.close().await.is_ok());
+
+        assert!(db.integrity_check().await.is_err());
+
+        teardown();
+    }
+
     #[tokio::test]
     async fn test_save_node_info() {
         setup();
@@ -587,6 +652,69 @@ pub mod sqlite_tests {
         teardown();
     }
 
+    #[tokio::test]
+    async fn test_save_dkg_checkpoint() {
+        setup();
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_group_info_client::<G2Curve>();
+        let member_1 = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        let member_2 = "0x0000000000000000000000000000000000000002"
+            .parse()
+            .unwrap();
+        let member_3 = "0x0000000000000000000000000000000000000003"
+            .parse()
+            .unwrap();
+        let members: Vec<Address> = [member_1, member_2, member_3].to_vec();
+
+        let task = DKGTask {
+            group_index: 1,
+            epoch: 1,
+            size: 3,
+            threshold: 3,
+            members,
+            assignment_block_height: 100,
+            coordinator_address: "0x00000000000000000000000000000000000000c1"
+                .parse()
+                .unwrap(),
+        };
+
+        db.save_task_info(0, task.clone()).await.unwrap();
+
+        assert!(db.get_dkg_checkpoint().unwrap().is_none());
+
+        let checkpoint = DKGCheckpoint {
+            task,
+            completed_phases: 1,
+            state: vec![1, 2, 3],
+            bundle: Some(vec![4, 5, 6]),
+        };
+
+        assert!(db
+            .save_dkg_checkpoint(1, 2, checkpoint.clone())
+            .await
+            .is_err());
+
+        db.save_dkg_checkpoint(1, 1, checkpoint).await.unwrap();
+
+        // the checkpoint is read back from the db after a restart
+        db.refresh_current_group_info().await.unwrap();
+
+        let res = db.get_dkg_checkpoint().unwrap().unwrap();
+
+        assert_eq!(1, res.completed_phases);
+        assert_eq!(vec![1, 2, 3], res.state);
+        assert_eq!(Some(vec![4, 5, 6]), res.bundle);
+
+        db.save_failed_output(1, 1, vec![2]).await.unwrap();
+
+        assert!(db.get_dkg_checkpoint().unwrap().is_none());
+
+        teardown();
+    }
+
     #[tokio::test]
     async fn test_add_and_get_randomness_task_with_assigned_group() {
         setup();
@@ -728,4 +856,390 @@ pub mod sqlite_tests {
 
         teardown();
     }
+
+    #[tokio::test]
+    async fn test_list_randomness_tasks() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>();
+
+        for (request_id, assignment_block_height) in
+            [(vec![1], 300), (vec![2], 100), (vec![3], 200)]
+        {
+            let task = RandomnessTask {
+                request_id,
+                subscription_id: 0,
+                group_index: 2,
+                request_type: RandomnessRequestType::Randomness,
+                params: vec![],
+                requester: PLACEHOLDER_ADDRESS,
+                seed: U256::zero(),
+                request_confirmations: 0,
+                callback_gas_limit: 0,
+                callback_max_gas_price: 0.into(),
+                assignment_block_height,
+            };
+
+            db.add(task).await.unwrap();
+        }
+
+        let tasks = db
+            .list(None, BlockHeightRange::default(), ListPage::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![vec![2], vec![3], vec![1]],
+            tasks
+                .iter()
+                .map(|t| t.task.request_id.clone())
+                .collect::<Vec<_>>()
+        );
+
+        let range = BlockHeightRange {
+            from: Some(150),
+            to: Some(300),
+        };
+        let tasks = db.list(None, range, ListPage::default()).await.unwrap();
+        assert_eq!(2, tasks.len());
+
+        db.check_and_get_available_tasks(100, 2, DEFAULT_RANDOMNESS_TASK_EXCLUSIVE_WINDOW)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            3,
+            db.list(Some(true), BlockHeightRange::default(), ListPage::default())
+                .await
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            0,
+            db.list(
+                Some(false),
+                BlockHeightRange::default(),
+                ListPage::default()
+            )
+            .await
+            .unwrap()
+            .len()
+        );
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_list_randomness_tasks_by_page() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut db = db.get_bls_tasks_client::<RandomnessTask>();
+
+        for (request_id, assignment_block_height) in [
+            (vec![5], 300),
+            (vec![4], 100),
+            (vec![3], 200),
+            (vec![2], 200),
+            (vec![1], 300),
+        ] {
+            let task = RandomnessTask {
+                request_id,
+                subscription_id: 0,
+                group_index: 2,
+                request_type: RandomnessRequestType::Randomness,
+                params: vec![],
+                requester: PLACEHOLDER_ADDRESS,
+                seed: U256::zero(),
+                request_confirmations: 0,
+                callback_gas_limit: 0,
+                callback_max_gas_price: 0.into(),
+                assignment_block_height,
+            };
+
+            db.add(task).await.unwrap();
+        }
+
+        let mut page = ListPage {
+            limit: Some(2),
+            after: None,
+        };
+        let mut request_ids = vec![];
+        loop {
+            let tasks = db
+                .list(None, BlockHeightRange::default(), page.clone())
+                .await
+                .unwrap();
+            assert!(tasks.len() <= 2);
+
+            let Some(last) = tasks.last() else {
+                break;
+            };
+            let cursor = ListCursor::of(&last.task);
+            assert_eq!(
+                ListCursor::from_bytes(&cursor.to_bytes()),
+                Some(cursor.clone())
+            );
+            page.after = Some(cursor);
+
+            request_ids.extend(tasks.iter().map(|t| t.task.request_id.clone()));
+        }
+
+        assert_eq!(
+            vec![vec![4], vec![2], vec![3], vec![1], vec![5]],
+            request_ids
+        );
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_relayed_randomness_tasks_and_results_are_keyed_by_chain_id() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let request_id = vec![1];
+
+        let task = RandomnessTask {
+            request_id: request_id.clone(),
+            subscription_id: 0,
+            group_index: 2,
+            request_type: RandomnessRequestType::Randomness,
+            params: vec![],
+            requester: PLACEHOLDER_ADDRESS,
+            seed: U256::zero(),
+            request_confirmations: 0,
+            callback_gas_limit: 0,
+            callback_max_gas_price: 0.into(),
+            assignment_block_height: 100,
+        };
+
+        let mut tasks_client = db.get_relayed_bls_tasks_client::<RandomnessTask>(7777777);
+        let mut other_tasks_client = db.get_relayed_bls_tasks_client::<RandomnessTask>(7777778);
+
+        // the same request id is used on both chains
+        tasks_client.add(task.clone()).await.unwrap();
+        other_tasks_client.add(task.clone()).await.unwrap();
+
+        let available_tasks = tasks_client
+            .check_and_get_available_tasks(100, 2, DEFAULT_RANDOMNESS_TASK_EXCLUSIVE_WINDOW)
+            .await
+            .unwrap();
+        assert_eq!(vec![task.clone()], available_tasks);
+        assert!(tasks_client.is_handled(&request_id).await.unwrap());
+        assert!(!other_tasks_client.is_handled(&request_id).await.unwrap());
+
+        let mut result_client = db
+            .get_relayed_randomness_result_client(7777777)
+            .await
+            .unwrap();
+        result_client
+            .add(2, task.clone(), vec![1, 2, 3], 3)
+            .await
+            .unwrap();
+        result_client
+            .add_partial_signature(request_id.clone(), PLACEHOLDER_ADDRESS, vec![4, 5, 6])
+            .await
+            .unwrap();
+
+        let other_result_client = db
+            .get_relayed_randomness_result_client(7777778)
+            .await
+            .unwrap();
+        assert!(!other_result_client.contains(&request_id).await.unwrap());
+        assert!(other_result_client
+            .list(None, BlockHeightRange::default(), ListPage::default())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // the not committed results are loaded from the db after a restart
+        let result_client = db
+            .get_relayed_randomness_result_client(7777777)
+            .await
+            .unwrap();
+        let result = result_client.get(&request_id).await.unwrap();
+        assert_eq!(task, result.result_cache.randomness_task);
+        assert_eq!(1, result.result_cache.partial_signatures.len());
+        assert_eq!(1, result_client.get_unfulfilled_count().await.unwrap());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_committing_randomness_results_are_committed_again_after_restart() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let request_id = vec![1];
+
+        let task = RandomnessTask {
+            request_id: request_id.clone(),
+            subscription_id: 0,
+            group_index: 2,
+            request_type: RandomnessRequestType::Randomness,
+            params: vec![],
+            requester: PLACEHOLDER_ADDRESS,
+            seed: U256::zero(),
+            request_confirmations: 0,
+            callback_gas_limit: 0,
+            callback_max_gas_price: 0.into(),
+            assignment_block_height: 100,
+        };
+
+        let mut tasks_client = db.get_bls_tasks_client::<RandomnessTask>();
+        tasks_client.add(task.clone()).await.unwrap();
+
+        let mut result_client = db.get_randomness_result_client().await.unwrap();
+        result_client
+            .add(2, task.clone(), vec![1, 2, 3], 1)
+            .await
+            .unwrap();
+        result_client
+            .add_partial_signature(request_id.clone(), PLACEHOLDER_ADDRESS, vec![4, 5, 6])
+            .await
+            .unwrap();
+
+        // e.g. a fulfillment deferred by the fulfillment policy stays in Committing state
+        let ready_signatures = result_client
+            .get_ready_to_commit_signatures(200)
+            .await
+            .unwrap();
+        assert_eq!(1, ready_signatures.len());
+        assert!(result_client
+            .get_ready_to_commit_signatures(200)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let mut result_client = db.get_randomness_result_client().await.unwrap();
+        let ready_signatures = result_client
+            .get_ready_to_commit_signatures(200)
+            .await
+            .unwrap();
+        assert_eq!(1, ready_signatures.len());
+        assert_eq!(task, ready_signatures[0].randomness_task);
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_add_blind_signature_task_and_partial_signatures() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut tasks_client = db.get_blind_signature_tasks_client();
+
+        let request_id = vec![1];
+
+        let task = BlindSignatureTask {
+            request_id: request_id.clone(),
+            group_index: 2,
+            requester: PLACEHOLDER_ADDRESS,
+            blinded_message: vec![1, 9, 6, 9],
+            request_confirmations: 0,
+            assignment_block_height: 100,
+        };
+
+        tasks_client.add(task.clone()).await.unwrap();
+
+        assert_eq!(task, tasks_client.get(&request_id).await.unwrap());
+        assert!(!tasks_client.is_handled(&request_id).await.unwrap());
+
+        let available_tasks = tasks_client
+            .check_and_get_available_tasks(100, 2, DEFAULT_RANDOMNESS_TASK_EXCLUSIVE_WINDOW)
+            .await
+            .unwrap();
+        assert_eq!(vec![task.clone()], available_tasks);
+        assert!(tasks_client.is_handled(&request_id).await.unwrap());
+
+        let mut result_client = db.get_blind_signature_result_client().await.unwrap();
+
+        result_client
+            .add(2, task.clone(), task.blinded_message.clone(), 1)
+            .await
+            .unwrap();
+
+        let member = Address::random();
+        result_client
+            .add_partial_signature(request_id.clone(), member, vec![4, 5, 6])
+            .await
+            .unwrap();
+
+        // the not committed results are loaded from the db after a restart
+        let result_client = db.get_blind_signature_result_client().await.unwrap();
+
+        let result = result_client.get(&request_id).await.unwrap();
+        assert_eq!(task, result.result_cache.blind_signature_task);
+        assert_eq!(
+            Some(&vec![4, 5, 6]),
+            result.result_cache.partial_signatures.get(&member)
+        );
+        assert_eq!(1, result_client.get_unfulfilled_count().await.unwrap());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    async fn test_add_decryption_task_and_decryption_shares() {
+        setup();
+
+        let db = build_sqlite_db().await.unwrap();
+
+        let mut tasks_client = db.get_decryption_tasks_client();
+
+        let request_id = vec![1];
+
+        let task = DecryptionTask {
+            request_id: request_id.clone(),
+            group_index: 2,
+            requester: PLACEHOLDER_ADDRESS,
+            ciphertext: vec![1, 9, 6, 9],
+            request_confirmations: 0,
+            assignment_block_height: 100,
+        };
+
+        tasks_client.add(task.clone()).await.unwrap();
+
+        assert_eq!(task, tasks_client.get(&request_id).await.unwrap());
+        assert!(!tasks_client.is_handled(&request_id).await.unwrap());
+
+        let available_tasks = tasks_client
+            .check_and_get_available_tasks(100, 2, DEFAULT_RANDOMNESS_TASK_EXCLUSIVE_WINDOW)
+            .await
+            .unwrap();
+        assert_eq!(vec![task.clone()], available_tasks);
+        assert!(tasks_client.is_handled(&request_id).await.unwrap());
+
+        let mut result_client = db.get_decryption_result_client().await.unwrap();
+
+        result_client
+            .add(2, task.clone(), task.ciphertext.clone(), 1)
+            .await
+            .unwrap();
+
+        let member = Address::random();
+        result_client
+            .add_partial_signature(request_id.clone(), member, vec![4, 5, 6])
+            .await
+            .unwrap();
+
+        // the not committed results are loaded from the db after a restart
+        let result_client = db.get_decryption_result_client().await.unwrap();
+
+        let result = result_client.get(&request_id).await.unwrap();
+        assert_eq!(task, result.result_cache.decryption_task);
+        assert_eq!(
+            Some(&vec![4, 5, 6]),
+            result.result_cache.decryption_shares.get(&member)
+        );
+        assert_eq!(1, result_client.get_unfulfilled_count().await.unwrap());
+
+        teardown();
+    }
 }
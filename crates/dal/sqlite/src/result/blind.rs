@@ -0,0 +1,531 @@
+use crate::types::BlindSignatureRecord;
+use crate::types::DBError;
+use crate::types::SqliteDB;
+use arpa_core::format_now_date;
+use arpa_core::BLSTaskError;
+use arpa_core::{BlindSignatureTask, Task};
+use arpa_dal::cache::BLSResultCache;
+use arpa_dal::cache::BlindSignatureResultCache;
+use arpa_dal::cache::InMemorySignatureResultCache;
+use arpa_dal::error::DataAccessResult;
+use arpa_dal::BLSResultCacheState;
+use arpa_dal::BlockHeightRange;
+use arpa_dal::ListPage;
+use arpa_dal::SignatureResultCacheFetcher;
+use arpa_dal::SignatureResultCacheUpdater;
+use async_trait::async_trait;
+use entity::blind_signature_result;
+use entity::prelude::BlindSignatureResult;
+use ethers_core::types::Address;
+use migration::Expr;
+use migration::Order;
+use migration::Query;
+use migration::SelectStatement;
+use migration::SimpleExpr;
+use migration::{
+    BlindSignatureResult as BlindSignatureResultTable,
+    BlindSignatureTask as BlindSignatureTaskTable,
+};
+use sea_orm::TransactionTrait;
+use sea_orm::{ActiveModelTrait, DbConn, DbErr, Set};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+impl SqliteDB {
+    pub async fn get_blind_signature_result_client(
+        &self,
+    ) -> DataAccessResult<BlindSignatureResultDBClient> {
+        let txn = self.connection.begin().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        // set commit result of committing records(if any) to not committed
+        let update_stmt = Query::update()
+            .table(BlindSignatureResultTable::Table)
+            .values([
+                (
+                    BlindSignatureResultTable::State,
+                    BLSResultCacheState::NotCommitted.to_i32().into(),
+                ),
+                (
+                    BlindSignatureResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(
+                Expr::col(BlindSignatureResultTable::State)
+                    .eq(BLSResultCacheState::Committing.to_i32()),
+            )
+            .to_owned();
+
+        self.execute_update_statement(&update_stmt).await?;
+
+        // load all not committed records
+        let query_stmt = build_blind_signature_record_query(Some(
+            Expr::col((
+                BlindSignatureResultTable::Table,
+                BlindSignatureResultTable::State,
+            ))
+            .eq(BLSResultCacheState::NotCommitted.to_i32()),
+        ));
+        let blind_signature_results: Vec<BlindSignatureRecord> =
+            self.query_all_statement(&query_stmt).await?;
+
+        txn.commit().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        let results = blind_signature_results
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<_>>();
+
+        Ok(BlindSignatureResultDBClient {
+            db_client: Arc::new(self.clone()),
+            signature_results_cache:
+                InMemorySignatureResultCache::<BlindSignatureResultCache>::rebuild(results),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlindSignatureResultDBClient {
+    db_client: Arc<SqliteDB>,
+    signature_results_cache: InMemorySignatureResultCache<BlindSignatureResultCache>,
+}
+
+impl BlindSignatureResultDBClient {
+    pub fn get_connection(&self) -> &DbConn {
+        &self.db_client.connection
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheFetcher<BlindSignatureResultCache> for BlindSignatureResultDBClient {
+    async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let model =
+            BlindSignatureResultQuery::select_by_request_id(self.get_connection(), task_request_id)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(model.is_some())
+    }
+
+    async fn get(
+        &self,
+        task_request_id: &[u8],
+    ) -> DataAccessResult<BLSResultCache<BlindSignatureResultCache>> {
+        let query_stmt = build_blind_signature_record_query(Some(
+            Expr::col((
+                BlindSignatureResultTable::Table,
+                BlindSignatureResultTable::RequestId,
+            ))
+            .eq(task_request_id),
+        ));
+        if let Some(blind_signature_record) = self
+            .db_client
+            .query_one_statement::<BlindSignatureRecord>(&query_stmt)
+            .await?
+        {
+            return Ok(blind_signature_record.into());
+        }
+        return Err(BLSTaskError::CommitterCacheNotExisted.into());
+    }
+
+    async fn get_unfulfilled_count(&self) -> DataAccessResult<usize> {
+        self.signature_results_cache.get_unfulfilled_count().await
+    }
+
+    async fn list(
+        &self,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSResultCache<BlindSignatureResultCache>>> {
+        // the in-memory cache only holds the results which are not committed yet
+        let and_where = [
+            state.map(|state| {
+                Expr::col((
+                    BlindSignatureResultTable::Table,
+                    BlindSignatureResultTable::State,
+                ))
+                .eq(state.to_i32())
+            }),
+            block_height_range.from.map(|from| {
+                Expr::col((
+                    BlindSignatureTaskTable::Table,
+                    BlindSignatureTaskTable::AssignmentBlockHeight,
+                ))
+                .gte(from as i32)
+            }),
+            block_height_range.to.map(|to| {
+                Expr::col((
+                    BlindSignatureTaskTable::Table,
+                    BlindSignatureTaskTable::AssignmentBlockHeight,
+                ))
+                .lte(to as i32)
+            }),
+            page.after.map(|after| {
+                Expr::col((
+                    BlindSignatureTaskTable::Table,
+                    BlindSignatureTaskTable::AssignmentBlockHeight,
+                ))
+                .gt(after.block_height as i32)
+                .or(Expr::col((
+                    BlindSignatureTaskTable::Table,
+                    BlindSignatureTaskTable::AssignmentBlockHeight,
+                ))
+                .eq(after.block_height as i32)
+                .and(
+                    Expr::col((
+                        BlindSignatureResultTable::Table,
+                        BlindSignatureResultTable::RequestId,
+                    ))
+                    .gt(after.request_id),
+                ))
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(SimpleExpr::and);
+
+        let mut query_stmt = build_blind_signature_record_query(and_where);
+        query_stmt
+            .order_by(
+                (
+                    BlindSignatureTaskTable::Table,
+                    BlindSignatureTaskTable::AssignmentBlockHeight,
+                ),
+                Order::Asc,
+            )
+            .order_by(
+                (
+                    BlindSignatureResultTable::Table,
+                    BlindSignatureResultTable::RequestId,
+                ),
+                Order::Asc,
+            );
+        if let Some(limit) = page.limit {
+            query_stmt.limit(limit as u64);
+        }
+
+        let blind_signature_records: Vec<BlindSignatureRecord> =
+            self.db_client.query_all_statement(&query_stmt).await?;
+
+        let results = blind_signature_records
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<BLSResultCache<BlindSignatureResultCache>>>();
+
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheUpdater<BlindSignatureResultCache> for BlindSignatureResultDBClient {
+    async fn get_ready_to_commit_signatures(
+        &mut self,
+        current_block_height: usize,
+    ) -> DataAccessResult<Vec<BlindSignatureResultCache>> {
+        let ready_to_commit_signatures = self
+            .signature_results_cache
+            .get_ready_to_commit_signatures(current_block_height)
+            .await?;
+
+        if ready_to_commit_signatures.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let request_ids = ready_to_commit_signatures
+            .iter()
+            .map(|s| s.request_id())
+            .collect::<Vec<_>>();
+
+        let update_stmt = Query::update()
+            .table(BlindSignatureResultTable::Table)
+            .values([
+                (
+                    BlindSignatureResultTable::State,
+                    BLSResultCacheState::Committing.to_i32().into(),
+                ),
+                (
+                    BlindSignatureResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(BlindSignatureResultTable::RequestId).is_in(request_ids))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        Ok(ready_to_commit_signatures)
+    }
+
+    async fn update_commit_result(
+        &mut self,
+        task_request_id: &[u8],
+        status: BLSResultCacheState,
+    ) -> DataAccessResult<()> {
+        let update_stmt = Query::update()
+            .table(BlindSignatureResultTable::Table)
+            .values([
+                (BlindSignatureResultTable::State, status.to_i32().into()),
+                (
+                    BlindSignatureResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(BlindSignatureResultTable::RequestId).eq(task_request_id))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        self.signature_results_cache
+            .update_commit_result(task_request_id, status)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add(
+        &mut self,
+        group_index: usize,
+        task: BlindSignatureTask,
+        message: Vec<u8>,
+        threshold: usize,
+    ) -> DataAccessResult<bool> {
+        BlindSignatureResultMutation::add(
+            self.get_connection(),
+            task.request_id.clone(),
+            group_index as i32,
+            message.clone(),
+            threshold as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.signature_results_cache
+            .add(group_index, task, message, threshold)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn add_partial_signature(
+        &mut self,
+        task_request_id: Vec<u8>,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> DataAccessResult<bool> {
+        let txn = self.get_connection().begin().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        let model = BlindSignatureResultQuery::select_by_request_id(
+            self.get_connection(),
+            &task_request_id,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?
+        .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        BlindSignatureResultMutation::add_partial_signature(
+            self.get_connection(),
+            model,
+            member_address,
+            partial_signature.clone(),
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        txn.commit().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.signature_results_cache
+            .add_partial_signature(task_request_id, member_address, partial_signature)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn incr_committed_times(&mut self, task_request_id: &[u8]) -> DataAccessResult<()> {
+        let update_stmt = Query::update()
+            .table(BlindSignatureResultTable::Table)
+            .values([
+                (
+                    BlindSignatureResultTable::CommittedTimes,
+                    Expr::col(BlindSignatureResultTable::CommittedTimes).add(1),
+                ),
+                (
+                    BlindSignatureResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(BlindSignatureResultTable::RequestId).eq(task_request_id))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        self.signature_results_cache
+            .incr_committed_times(task_request_id)
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub struct BlindSignatureResultQuery;
+
+impl BlindSignatureResultQuery {
+    pub async fn select_by_request_id(
+        db: &DbConn,
+        request_id: &[u8],
+    ) -> Result<Option<blind_signature_result::Model>, DbErr> {
+        BlindSignatureResult::find()
+            .filter(blind_signature_result::Column::RequestId.eq(request_id))
+            .one(db)
+            .await
+    }
+}
+
+pub struct BlindSignatureResultMutation;
+
+impl BlindSignatureResultMutation {
+    pub async fn add(
+        db: &DbConn,
+        request_id: Vec<u8>,
+        group_index: i32,
+        message: Vec<u8>,
+        threshold: i32,
+    ) -> Result<blind_signature_result::ActiveModel, DbErr> {
+        blind_signature_result::ActiveModel {
+            request_id: Set(request_id),
+            group_index: Set(group_index),
+            message: Set(message),
+            threshold: Set(threshold),
+            partial_signatures: Set(
+                serde_json::to_string(&BTreeMap::<Address, Vec<u8>>::new()).unwrap()
+            ),
+            committed_times: Set(0),
+            create_at: Set(format_now_date()),
+            update_at: Set(format_now_date()),
+            state: Set(BLSResultCacheState::NotCommitted.to_i32()),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
+    pub async fn add_partial_signature(
+        db: &DbConn,
+        model: blind_signature_result::Model,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> Result<blind_signature_result::Model, DbErr> {
+        let mut partial_signatures: BTreeMap<Address, Vec<u8>> =
+            serde_json::from_str(&model.partial_signatures).unwrap();
+
+        partial_signatures.insert(member_address, partial_signature);
+
+        let mut blind_signature_result: blind_signature_result::ActiveModel = model.into();
+
+        blind_signature_result.partial_signatures =
+            Set(serde_json::to_string(&partial_signatures).unwrap());
+
+        blind_signature_result.update_at = Set(format_now_date());
+
+        blind_signature_result.update(db).await
+    }
+}
+
+pub(crate) fn build_blind_signature_record_query(and_where: Option<SimpleExpr>) -> SelectStatement {
+    Query::select()
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::RequestId,
+        ))
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::GroupIndex,
+        ))
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::Message,
+        ))
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::Threshold,
+        ))
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::PartialSignatures,
+        ))
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::CommittedTimes,
+        ))
+        .column((
+            BlindSignatureResultTable::Table,
+            BlindSignatureResultTable::State,
+        ))
+        .column((
+            BlindSignatureTaskTable::Table,
+            BlindSignatureTaskTable::Requester,
+        ))
+        .column((
+            BlindSignatureTaskTable::Table,
+            BlindSignatureTaskTable::BlindedMessage,
+        ))
+        .column((
+            BlindSignatureTaskTable::Table,
+            BlindSignatureTaskTable::RequestConfirmations,
+        ))
+        .column((
+            BlindSignatureTaskTable::Table,
+            BlindSignatureTaskTable::AssignmentBlockHeight,
+        ))
+        .from(BlindSignatureResultTable::Table)
+        .inner_join(
+            BlindSignatureTaskTable::Table,
+            Expr::col((
+                BlindSignatureResultTable::Table,
+                BlindSignatureResultTable::RequestId,
+            ))
+            .equals((
+                BlindSignatureTaskTable::Table,
+                BlindSignatureTaskTable::RequestId,
+            )),
+        )
+        .conditions(
+            and_where.is_some(),
+            |x| {
+                x.and_where(and_where.unwrap());
+            },
+            |_x| {},
+        )
+        .to_owned()
+}
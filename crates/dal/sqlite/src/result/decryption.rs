@@ -0,0 +1,492 @@
+use crate::types::DBError;
+use crate::types::DecryptionRecord;
+use crate::types::SqliteDB;
+use arpa_core::format_now_date;
+use arpa_core::BLSTaskError;
+use arpa_core::{DecryptionTask, Task};
+use arpa_dal::cache::BLSResultCache;
+use arpa_dal::cache::DecryptionResultCache;
+use arpa_dal::cache::InMemorySignatureResultCache;
+use arpa_dal::error::DataAccessResult;
+use arpa_dal::BLSResultCacheState;
+use arpa_dal::BlockHeightRange;
+use arpa_dal::ListPage;
+use arpa_dal::SignatureResultCacheFetcher;
+use arpa_dal::SignatureResultCacheUpdater;
+use async_trait::async_trait;
+use entity::decryption_result;
+use entity::prelude::DecryptionResult;
+use ethers_core::types::Address;
+use migration::Expr;
+use migration::Order;
+use migration::Query;
+use migration::SelectStatement;
+use migration::SimpleExpr;
+use migration::{DecryptionResult as DecryptionResultTable, DecryptionTask as DecryptionTaskTable};
+use sea_orm::TransactionTrait;
+use sea_orm::{ActiveModelTrait, DbConn, DbErr, Set};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+impl SqliteDB {
+    pub async fn get_decryption_result_client(&self) -> DataAccessResult<DecryptionResultDBClient> {
+        let txn = self.connection.begin().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        // set commit result of committing records(if any) to not committed
+        let update_stmt = Query::update()
+            .table(DecryptionResultTable::Table)
+            .values([
+                (
+                    DecryptionResultTable::State,
+                    BLSResultCacheState::NotCommitted.to_i32().into(),
+                ),
+                (DecryptionResultTable::UpdateAt, format_now_date().into()),
+            ])
+            .and_where(
+                Expr::col(DecryptionResultTable::State)
+                    .eq(BLSResultCacheState::Committing.to_i32()),
+            )
+            .to_owned();
+
+        self.execute_update_statement(&update_stmt).await?;
+
+        // load all not committed records
+        let query_stmt = build_decryption_record_query(Some(
+            Expr::col((DecryptionResultTable::Table, DecryptionResultTable::State))
+                .eq(BLSResultCacheState::NotCommitted.to_i32()),
+        ));
+        let decryption_results: Vec<DecryptionRecord> =
+            self.query_all_statement(&query_stmt).await?;
+
+        txn.commit().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        let results = decryption_results
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<_>>();
+
+        Ok(DecryptionResultDBClient {
+            db_client: Arc::new(self.clone()),
+            signature_results_cache: InMemorySignatureResultCache::<DecryptionResultCache>::rebuild(
+                results,
+            ),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecryptionResultDBClient {
+    db_client: Arc<SqliteDB>,
+    signature_results_cache: InMemorySignatureResultCache<DecryptionResultCache>,
+}
+
+impl DecryptionResultDBClient {
+    pub fn get_connection(&self) -> &DbConn {
+        &self.db_client.connection
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheFetcher<DecryptionResultCache> for DecryptionResultDBClient {
+    async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let model =
+            DecryptionResultQuery::select_by_request_id(self.get_connection(), task_request_id)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(model.is_some())
+    }
+
+    async fn get(
+        &self,
+        task_request_id: &[u8],
+    ) -> DataAccessResult<BLSResultCache<DecryptionResultCache>> {
+        let query_stmt = build_decryption_record_query(Some(
+            Expr::col((
+                DecryptionResultTable::Table,
+                DecryptionResultTable::RequestId,
+            ))
+            .eq(task_request_id),
+        ));
+        if let Some(decryption_record) = self
+            .db_client
+            .query_one_statement::<DecryptionRecord>(&query_stmt)
+            .await?
+        {
+            return Ok(decryption_record.into());
+        }
+        return Err(BLSTaskError::CommitterCacheNotExisted.into());
+    }
+
+    async fn get_unfulfilled_count(&self) -> DataAccessResult<usize> {
+        self.signature_results_cache.get_unfulfilled_count().await
+    }
+
+    async fn list(
+        &self,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSResultCache<DecryptionResultCache>>> {
+        // the in-memory cache only holds the results which are not committed yet
+        let and_where = [
+            state.map(|state| {
+                Expr::col((DecryptionResultTable::Table, DecryptionResultTable::State))
+                    .eq(state.to_i32())
+            }),
+            block_height_range.from.map(|from| {
+                Expr::col((
+                    DecryptionTaskTable::Table,
+                    DecryptionTaskTable::AssignmentBlockHeight,
+                ))
+                .gte(from as i32)
+            }),
+            block_height_range.to.map(|to| {
+                Expr::col((
+                    DecryptionTaskTable::Table,
+                    DecryptionTaskTable::AssignmentBlockHeight,
+                ))
+                .lte(to as i32)
+            }),
+            page.after.map(|after| {
+                Expr::col((
+                    DecryptionTaskTable::Table,
+                    DecryptionTaskTable::AssignmentBlockHeight,
+                ))
+                .gt(after.block_height as i32)
+                .or(Expr::col((
+                    DecryptionTaskTable::Table,
+                    DecryptionTaskTable::AssignmentBlockHeight,
+                ))
+                .eq(after.block_height as i32)
+                .and(
+                    Expr::col((
+                        DecryptionResultTable::Table,
+                        DecryptionResultTable::RequestId,
+                    ))
+                    .gt(after.request_id),
+                ))
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(SimpleExpr::and);
+
+        let mut query_stmt = build_decryption_record_query(and_where);
+        query_stmt
+            .order_by(
+                (
+                    DecryptionTaskTable::Table,
+                    DecryptionTaskTable::AssignmentBlockHeight,
+                ),
+                Order::Asc,
+            )
+            .order_by(
+                (
+                    DecryptionResultTable::Table,
+                    DecryptionResultTable::RequestId,
+                ),
+                Order::Asc,
+            );
+        if let Some(limit) = page.limit {
+            query_stmt.limit(limit as u64);
+        }
+
+        let decryption_records: Vec<DecryptionRecord> =
+            self.db_client.query_all_statement(&query_stmt).await?;
+
+        let results = decryption_records
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<BLSResultCache<DecryptionResultCache>>>();
+
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheUpdater<DecryptionResultCache> for DecryptionResultDBClient {
+    async fn get_ready_to_commit_signatures(
+        &mut self,
+        current_block_height: usize,
+    ) -> DataAccessResult<Vec<DecryptionResultCache>> {
+        let ready_to_commit_signatures = self
+            .signature_results_cache
+            .get_ready_to_commit_signatures(current_block_height)
+            .await?;
+
+        if ready_to_commit_signatures.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let request_ids = ready_to_commit_signatures
+            .iter()
+            .map(|s| s.request_id())
+            .collect::<Vec<_>>();
+
+        let update_stmt = Query::update()
+            .table(DecryptionResultTable::Table)
+            .values([
+                (
+                    DecryptionResultTable::State,
+                    BLSResultCacheState::Committing.to_i32().into(),
+                ),
+                (DecryptionResultTable::UpdateAt, format_now_date().into()),
+            ])
+            .and_where(Expr::col(DecryptionResultTable::RequestId).is_in(request_ids))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        Ok(ready_to_commit_signatures)
+    }
+
+    async fn update_commit_result(
+        &mut self,
+        task_request_id: &[u8],
+        status: BLSResultCacheState,
+    ) -> DataAccessResult<()> {
+        let update_stmt = Query::update()
+            .table(DecryptionResultTable::Table)
+            .values([
+                (DecryptionResultTable::State, status.to_i32().into()),
+                (DecryptionResultTable::UpdateAt, format_now_date().into()),
+            ])
+            .and_where(Expr::col(DecryptionResultTable::RequestId).eq(task_request_id))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        self.signature_results_cache
+            .update_commit_result(task_request_id, status)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add(
+        &mut self,
+        group_index: usize,
+        task: DecryptionTask,
+        message: Vec<u8>,
+        threshold: usize,
+    ) -> DataAccessResult<bool> {
+        DecryptionResultMutation::add(
+            self.get_connection(),
+            task.request_id.clone(),
+            group_index as i32,
+            message.clone(),
+            threshold as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.signature_results_cache
+            .add(group_index, task, message, threshold)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn add_partial_signature(
+        &mut self,
+        task_request_id: Vec<u8>,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> DataAccessResult<bool> {
+        let txn = self.get_connection().begin().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        let model =
+            DecryptionResultQuery::select_by_request_id(self.get_connection(), &task_request_id)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?
+                .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        DecryptionResultMutation::add_partial_signature(
+            self.get_connection(),
+            model,
+            member_address,
+            partial_signature.clone(),
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        txn.commit().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.signature_results_cache
+            .add_partial_signature(task_request_id, member_address, partial_signature)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn incr_committed_times(&mut self, task_request_id: &[u8]) -> DataAccessResult<()> {
+        let update_stmt = Query::update()
+            .table(DecryptionResultTable::Table)
+            .values([
+                (
+                    DecryptionResultTable::CommittedTimes,
+                    Expr::col(DecryptionResultTable::CommittedTimes).add(1),
+                ),
+                (DecryptionResultTable::UpdateAt, format_now_date().into()),
+            ])
+            .and_where(Expr::col(DecryptionResultTable::RequestId).eq(task_request_id))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        self.signature_results_cache
+            .incr_committed_times(task_request_id)
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub struct DecryptionResultQuery;
+
+impl DecryptionResultQuery {
+    pub async fn select_by_request_id(
+        db: &DbConn,
+        request_id: &[u8],
+    ) -> Result<Option<decryption_result::Model>, DbErr> {
+        DecryptionResult::find()
+            .filter(decryption_result::Column::RequestId.eq(request_id))
+            .one(db)
+            .await
+    }
+}
+
+pub struct DecryptionResultMutation;
+
+impl DecryptionResultMutation {
+    pub async fn add(
+        db: &DbConn,
+        request_id: Vec<u8>,
+        group_index: i32,
+        message: Vec<u8>,
+        threshold: i32,
+    ) -> Result<decryption_result::ActiveModel, DbErr> {
+        decryption_result::ActiveModel {
+            request_id: Set(request_id),
+            group_index: Set(group_index),
+            message: Set(message),
+            threshold: Set(threshold),
+            decryption_shares: Set(
+                serde_json::to_string(&BTreeMap::<Address, Vec<u8>>::new()).unwrap()
+            ),
+            committed_times: Set(0),
+            create_at: Set(format_now_date()),
+            update_at: Set(format_now_date()),
+            state: Set(BLSResultCacheState::NotCommitted.to_i32()),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
+    pub async fn add_partial_signature(
+        db: &DbConn,
+        model: decryption_result::Model,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> Result<decryption_result::Model, DbErr> {
+        let mut decryption_shares: BTreeMap<Address, Vec<u8>> =
+            serde_json::from_str(&model.decryption_shares).unwrap();
+
+        decryption_shares.insert(member_address, partial_signature);
+
+        let mut decryption_result: decryption_result::ActiveModel = model.into();
+
+        decryption_result.decryption_shares =
+            Set(serde_json::to_string(&decryption_shares).unwrap());
+
+        decryption_result.update_at = Set(format_now_date());
+
+        decryption_result.update(db).await
+    }
+}
+
+pub(crate) fn build_decryption_record_query(and_where: Option<SimpleExpr>) -> SelectStatement {
+    Query::select()
+        .column((
+            DecryptionResultTable::Table,
+            DecryptionResultTable::RequestId,
+        ))
+        .column((
+            DecryptionResultTable::Table,
+            DecryptionResultTable::GroupIndex,
+        ))
+        .column((DecryptionResultTable::Table, DecryptionResultTable::Message))
+        .column((
+            DecryptionResultTable::Table,
+            DecryptionResultTable::Threshold,
+        ))
+        .column((
+            DecryptionResultTable::Table,
+            DecryptionResultTable::DecryptionShares,
+        ))
+        .column((
+            DecryptionResultTable::Table,
+            DecryptionResultTable::CommittedTimes,
+        ))
+        .column((DecryptionResultTable::Table, DecryptionResultTable::State))
+        .column((DecryptionTaskTable::Table, DecryptionTaskTable::Requester))
+        .column((DecryptionTaskTable::Table, DecryptionTaskTable::Ciphertext))
+        .column((
+            DecryptionTaskTable::Table,
+            DecryptionTaskTable::RequestConfirmations,
+        ))
+        .column((
+            DecryptionTaskTable::Table,
+            DecryptionTaskTable::AssignmentBlockHeight,
+        ))
+        .from(DecryptionResultTable::Table)
+        .inner_join(
+            DecryptionTaskTable::Table,
+            Expr::col((
+                DecryptionResultTable::Table,
+                DecryptionResultTable::RequestId,
+            ))
+            .equals((DecryptionTaskTable::Table, DecryptionTaskTable::RequestId)),
+        )
+        .conditions(
+            and_where.is_some(),
+            |x| {
+                x.and_where(and_where.unwrap());
+            },
+            |_x| {},
+        )
+        .to_owned()
+}
@@ -1,13 +1,19 @@
 mod base;
+mod blind;
+mod decryption;
 mod loot;
 mod main;
 mod op;
 mod redstone;
+mod relayed;
 mod taiko;
 
 pub use base::BaseSignatureResultDBClient;
+pub use blind::BlindSignatureResultDBClient;
+pub use decryption::DecryptionResultDBClient;
 pub use loot::LootSignatureResultDBClient;
 pub use main::SignatureResultDBClient;
 pub use op::OPSignatureResultDBClient;
 pub use redstone::RedstoneSignatureResultDBClient;
+pub use relayed::RelayedSignatureResultDBClient;
 pub use taiko::TaikoSignatureResultDBClient;
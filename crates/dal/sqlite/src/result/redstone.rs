@@ -9,6 +9,8 @@ use arpa_dal::cache::InMemorySignatureResultCache;
 use arpa_dal::cache::RandomnessResultCache;
 use arpa_dal::error::DataAccessResult;
 use arpa_dal::BLSResultCacheState;
+use arpa_dal::BlockHeightRange;
+use arpa_dal::ListPage;
 use arpa_dal::ResultCache;
 use arpa_dal::SignatureResultCacheFetcher;
 use arpa_dal::SignatureResultCacheUpdater;
@@ -17,6 +19,7 @@ use entity::prelude::RedstoneRandomnessResult;
 use entity::redstone_randomness_result;
 use ethers_core::types::Address;
 use migration::Expr;
+use migration::Order;
 use migration::Query;
 use migration::SelectStatement;
 use migration::SimpleExpr;
@@ -140,6 +143,94 @@ impl SignatureResultCacheFetcher<RandomnessResultCache>
         }
         return Err(BLSTaskError::CommitterCacheNotExisted.into());
     }
+
+    async fn get_unfulfilled_count(&self) -> DataAccessResult<usize> {
+        self.signature_results_cache.get_unfulfilled_count().await
+    }
+
+    async fn list(
+        &self,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSResultCache<RandomnessResultCache>>> {
+        // the in-memory cache only holds the results which are not committed yet
+        let and_where = [
+            state.map(|state| {
+                Expr::col((
+                    RedstoneRandomnessResultTable::Table,
+                    RedstoneRandomnessResultTable::State,
+                ))
+                .eq(state.to_i32())
+            }),
+            block_height_range.from.map(|from| {
+                Expr::col((
+                    RedstoneRandomnessTaskTable::Table,
+                    RedstoneRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .gte(from as i32)
+            }),
+            block_height_range.to.map(|to| {
+                Expr::col((
+                    RedstoneRandomnessTaskTable::Table,
+                    RedstoneRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .lte(to as i32)
+            }),
+            page.after.map(|after| {
+                Expr::col((
+                    RedstoneRandomnessTaskTable::Table,
+                    RedstoneRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .gt(after.block_height as i32)
+                .or(Expr::col((
+                    RedstoneRandomnessTaskTable::Table,
+                    RedstoneRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .eq(after.block_height as i32)
+                .and(
+                    Expr::col((
+                        RedstoneRandomnessResultTable::Table,
+                        RedstoneRandomnessResultTable::RequestId,
+                    ))
+                    .gt(after.request_id),
+                ))
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(SimpleExpr::and);
+
+        let mut query_stmt = build_randomness_record_query(and_where);
+        query_stmt
+            .order_by(
+                (
+                    RedstoneRandomnessTaskTable::Table,
+                    RedstoneRandomnessTaskTable::AssignmentBlockHeight,
+                ),
+                Order::Asc,
+            )
+            .order_by(
+                (
+                    RedstoneRandomnessResultTable::Table,
+                    RedstoneRandomnessResultTable::RequestId,
+                ),
+                Order::Asc,
+            );
+        if let Some(limit) = page.limit {
+            query_stmt.limit(limit as u64);
+        }
+
+        let randomness_records: Vec<RandomnessRecord> =
+            self.db_client.query_all_statement(&query_stmt).await?;
+
+        let results = randomness_records
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<BLSResultCache<RandomnessResultCache>>>();
+
+        Ok(results)
+    }
 }
 
 #[async_trait]
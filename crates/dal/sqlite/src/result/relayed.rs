@@ -0,0 +1,601 @@
+use crate::types::DBError;
+use crate::types::RandomnessRecord;
+use crate::types::SqliteDB;
+use arpa_core::format_now_date;
+use arpa_core::BLSTaskError;
+use arpa_core::{RandomnessTask, Task};
+use arpa_dal::cache::BLSResultCache;
+use arpa_dal::cache::InMemorySignatureResultCache;
+use arpa_dal::cache::RandomnessResultCache;
+use arpa_dal::error::DataAccessResult;
+use arpa_dal::BLSResultCacheState;
+use arpa_dal::BlockHeightRange;
+use arpa_dal::ListPage;
+use arpa_dal::ResultCache;
+use arpa_dal::SignatureResultCacheFetcher;
+use arpa_dal::SignatureResultCacheUpdater;
+use async_trait::async_trait;
+use entity::prelude::RelayedRandomnessResult;
+use entity::relayed_randomness_result;
+use ethers_core::types::Address;
+use migration::Expr;
+use migration::Order;
+use migration::Query;
+use migration::SelectStatement;
+use migration::SimpleExpr;
+use migration::{
+    RelayedRandomnessResult as RelayedRandomnessResultTable,
+    RelayedRandomnessTask as RelayedRandomnessTaskTable,
+};
+use sea_orm::TransactionTrait;
+use sea_orm::{ActiveModelTrait, DbConn, DbErr, Set};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+impl SqliteDB {
+    /// Results of the chains in the relayed storage namespace share the same table,
+    /// the client only sees the ones of the given chain.
+    pub async fn get_relayed_randomness_result_client(
+        &self,
+        chain_id: usize,
+    ) -> DataAccessResult<RelayedSignatureResultDBClient<RandomnessResultCache>> {
+        let txn = self.connection.begin().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        // set commit result of committing records(if any) to not committed
+        let update_stmt = Query::update()
+            .table(RelayedRandomnessResultTable::Table)
+            .values([
+                (
+                    RelayedRandomnessResultTable::State,
+                    BLSResultCacheState::NotCommitted.to_i32().into(),
+                ),
+                (
+                    RelayedRandomnessResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(RelayedRandomnessResultTable::ChainId).eq(chain_id as i64))
+            .and_where(
+                Expr::col(RelayedRandomnessResultTable::State)
+                    .eq(BLSResultCacheState::Committing.to_i32()),
+            )
+            .to_owned();
+
+        self.execute_update_statement(&update_stmt).await?;
+
+        // load all not committed records
+        let query_stmt = build_randomness_record_query(
+            chain_id,
+            Some(
+                Expr::col((
+                    RelayedRandomnessResultTable::Table,
+                    RelayedRandomnessResultTable::State,
+                ))
+                .eq(BLSResultCacheState::NotCommitted.to_i32()),
+            ),
+        );
+        let randomness_results: Vec<RandomnessRecord> =
+            self.query_all_statement(&query_stmt).await?;
+
+        txn.commit().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        let results = randomness_results
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<_>>();
+
+        Ok(RelayedSignatureResultDBClient {
+            db_client: Arc::new(self.clone()),
+            chain_id,
+            signature_results_cache: InMemorySignatureResultCache::<RandomnessResultCache>::rebuild(
+                results,
+            ),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RelayedSignatureResultDBClient<C: ResultCache> {
+    db_client: Arc<SqliteDB>,
+    chain_id: usize,
+    signature_results_cache: InMemorySignatureResultCache<C>,
+}
+
+impl RelayedSignatureResultDBClient<RandomnessResultCache> {
+    pub fn get_connection(&self) -> &DbConn {
+        &self.db_client.connection
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheFetcher<RandomnessResultCache>
+    for RelayedSignatureResultDBClient<RandomnessResultCache>
+{
+    async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let model = RelayedRandomnessResultQuery::select_by_request_id(
+            self.get_connection(),
+            self.chain_id,
+            task_request_id,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(model.is_some())
+    }
+
+    async fn get(
+        &self,
+        task_request_id: &[u8],
+    ) -> DataAccessResult<BLSResultCache<RandomnessResultCache>> {
+        let query_stmt = build_randomness_record_query(
+            self.chain_id,
+            Some(
+                Expr::col((
+                    RelayedRandomnessResultTable::Table,
+                    RelayedRandomnessResultTable::RequestId,
+                ))
+                .eq(task_request_id),
+            ),
+        );
+        if let Some(randomness_record) = self
+            .db_client
+            .query_one_statement::<RandomnessRecord>(&query_stmt)
+            .await?
+        {
+            return Ok(randomness_record.into());
+        }
+        return Err(BLSTaskError::CommitterCacheNotExisted.into());
+    }
+
+    async fn get_unfulfilled_count(&self) -> DataAccessResult<usize> {
+        self.signature_results_cache.get_unfulfilled_count().await
+    }
+
+    async fn list(
+        &self,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSResultCache<RandomnessResultCache>>> {
+        // the in-memory cache only holds the results which are not committed yet
+        let and_where = [
+            state.map(|state| {
+                Expr::col((
+                    RelayedRandomnessResultTable::Table,
+                    RelayedRandomnessResultTable::State,
+                ))
+                .eq(state.to_i32())
+            }),
+            block_height_range.from.map(|from| {
+                Expr::col((
+                    RelayedRandomnessTaskTable::Table,
+                    RelayedRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .gte(from as i32)
+            }),
+            block_height_range.to.map(|to| {
+                Expr::col((
+                    RelayedRandomnessTaskTable::Table,
+                    RelayedRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .lte(to as i32)
+            }),
+            page.after.map(|after| {
+                Expr::col((
+                    RelayedRandomnessTaskTable::Table,
+                    RelayedRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .gt(after.block_height as i32)
+                .or(Expr::col((
+                    RelayedRandomnessTaskTable::Table,
+                    RelayedRandomnessTaskTable::AssignmentBlockHeight,
+                ))
+                .eq(after.block_height as i32)
+                .and(
+                    Expr::col((
+                        RelayedRandomnessResultTable::Table,
+                        RelayedRandomnessResultTable::RequestId,
+                    ))
+                    .gt(after.request_id),
+                ))
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(SimpleExpr::and);
+
+        let mut query_stmt = build_randomness_record_query(self.chain_id, and_where);
+        query_stmt
+            .order_by(
+                (
+                    RelayedRandomnessTaskTable::Table,
+                    RelayedRandomnessTaskTable::AssignmentBlockHeight,
+                ),
+                Order::Asc,
+            )
+            .order_by(
+                (
+                    RelayedRandomnessResultTable::Table,
+                    RelayedRandomnessResultTable::RequestId,
+                ),
+                Order::Asc,
+            );
+        if let Some(limit) = page.limit {
+            query_stmt.limit(limit as u64);
+        }
+
+        let randomness_records: Vec<RandomnessRecord> =
+            self.db_client.query_all_statement(&query_stmt).await?;
+
+        let results = randomness_records
+            .into_iter()
+            .map(|r| r.into())
+            .collect::<Vec<BLSResultCache<RandomnessResultCache>>>();
+
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheUpdater<RandomnessResultCache>
+    for RelayedSignatureResultDBClient<RandomnessResultCache>
+{
+    async fn get_ready_to_commit_signatures(
+        &mut self,
+        current_block_height: usize,
+    ) -> DataAccessResult<Vec<RandomnessResultCache>> {
+        let ready_to_commit_signatures = self
+            .signature_results_cache
+            .get_ready_to_commit_signatures(current_block_height)
+            .await?;
+
+        if ready_to_commit_signatures.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let request_ids = ready_to_commit_signatures
+            .iter()
+            .map(|s| s.request_id())
+            .collect::<Vec<_>>();
+
+        let update_stmt = Query::update()
+            .table(RelayedRandomnessResultTable::Table)
+            .values([
+                (
+                    RelayedRandomnessResultTable::State,
+                    BLSResultCacheState::Committing.to_i32().into(),
+                ),
+                (
+                    RelayedRandomnessResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(RelayedRandomnessResultTable::ChainId).eq(self.chain_id as i64))
+            .and_where(Expr::col(RelayedRandomnessResultTable::RequestId).is_in(request_ids))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        Ok(ready_to_commit_signatures)
+    }
+
+    async fn update_commit_result(
+        &mut self,
+        task_request_id: &[u8],
+        status: BLSResultCacheState,
+    ) -> DataAccessResult<()> {
+        let update_stmt = Query::update()
+            .table(RelayedRandomnessResultTable::Table)
+            .values([
+                (RelayedRandomnessResultTable::State, status.to_i32().into()),
+                (
+                    RelayedRandomnessResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(RelayedRandomnessResultTable::ChainId).eq(self.chain_id as i64))
+            .and_where(Expr::col(RelayedRandomnessResultTable::RequestId).eq(task_request_id))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        self.signature_results_cache
+            .update_commit_result(task_request_id, status)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add(
+        &mut self,
+        group_index: usize,
+        task: RandomnessTask,
+        message: Vec<u8>,
+        threshold: usize,
+    ) -> DataAccessResult<bool> {
+        RelayedRandomnessResultMutation::add(
+            self.get_connection(),
+            self.chain_id as i64,
+            task.request_id.clone(),
+            group_index as i32,
+            message.clone(),
+            threshold as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.signature_results_cache
+            .add(group_index, task, message, threshold)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn add_partial_signature(
+        &mut self,
+        task_request_id: Vec<u8>,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> DataAccessResult<bool> {
+        let txn = self.get_connection().begin().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        let model = RelayedRandomnessResultQuery::select_by_request_id(
+            self.get_connection(),
+            self.chain_id,
+            &task_request_id,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?
+        .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        RelayedRandomnessResultMutation::add_partial_signature(
+            self.get_connection(),
+            model,
+            member_address,
+            partial_signature.clone(),
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        txn.commit().await.map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        self.signature_results_cache
+            .add_partial_signature(task_request_id, member_address, partial_signature)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn incr_committed_times(&mut self, task_request_id: &[u8]) -> DataAccessResult<()> {
+        let update_stmt = Query::update()
+            .table(RelayedRandomnessResultTable::Table)
+            .values([
+                (
+                    RelayedRandomnessResultTable::CommittedTimes,
+                    Expr::col(RelayedRandomnessResultTable::CommittedTimes).add(1),
+                ),
+                (
+                    RelayedRandomnessResultTable::UpdateAt,
+                    format_now_date().into(),
+                ),
+            ])
+            .and_where(Expr::col(RelayedRandomnessResultTable::ChainId).eq(self.chain_id as i64))
+            .and_where(Expr::col(RelayedRandomnessResultTable::RequestId).eq(task_request_id))
+            .to_owned();
+
+        self.db_client
+            .execute_update_statement(&update_stmt)
+            .await?;
+
+        self.signature_results_cache
+            .incr_committed_times(task_request_id)
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub struct RelayedRandomnessResultQuery;
+
+impl RelayedRandomnessResultQuery {
+    pub async fn select_by_request_id(
+        db: &DbConn,
+        chain_id: usize,
+        request_id: &[u8],
+    ) -> Result<Option<relayed_randomness_result::Model>, DbErr> {
+        RelayedRandomnessResult::find()
+            .filter(relayed_randomness_result::Column::ChainId.eq(chain_id as i64))
+            .filter(relayed_randomness_result::Column::RequestId.eq(request_id))
+            .one(db)
+            .await
+    }
+}
+
+pub struct RelayedRandomnessResultMutation;
+
+impl RelayedRandomnessResultMutation {
+    pub async fn add(
+        db: &DbConn,
+        chain_id: i64,
+        request_id: Vec<u8>,
+        group_index: i32,
+        message: Vec<u8>,
+        threshold: i32,
+    ) -> Result<relayed_randomness_result::ActiveModel, DbErr> {
+        relayed_randomness_result::ActiveModel {
+            chain_id: Set(chain_id),
+            request_id: Set(request_id),
+            group_index: Set(group_index),
+            message: Set(message),
+            threshold: Set(threshold),
+            partial_signatures: Set(
+                serde_json::to_string(&BTreeMap::<Address, Vec<u8>>::new()).unwrap()
+            ),
+            committed_times: Set(0),
+            create_at: Set(format_now_date()),
+            update_at: Set(format_now_date()),
+            state: Set(BLSResultCacheState::NotCommitted.to_i32()),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
+    pub async fn add_partial_signature(
+        db: &DbConn,
+        model: relayed_randomness_result::Model,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> Result<relayed_randomness_result::Model, DbErr> {
+        let mut partial_signatures: BTreeMap<Address, Vec<u8>> =
+            serde_json::from_str(&model.partial_signatures).unwrap();
+
+        partial_signatures.insert(member_address, partial_signature);
+
+        let mut randomness_result: relayed_randomness_result::ActiveModel = model.into();
+
+        randomness_result.partial_signatures =
+            Set(serde_json::to_string(&partial_signatures).unwrap());
+
+        randomness_result.update_at = Set(format_now_date());
+
+        randomness_result.update(db).await
+    }
+}
+
+pub(crate) fn build_randomness_record_query(
+    chain_id: usize,
+    and_where: Option<SimpleExpr>,
+) -> SelectStatement {
+    Query::select()
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::RequestId,
+        ))
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::GroupIndex,
+        ))
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::Message,
+        ))
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::Threshold,
+        ))
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::PartialSignatures,
+        ))
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::CommittedTimes,
+        ))
+        .column((
+            RelayedRandomnessResultTable::Table,
+            RelayedRandomnessResultTable::State,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::SubscriptionId,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::RequestType,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::Params,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::Requester,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::Seed,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::RequestConfirmations,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::CallbackGasLimit,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::CallbackMaxGasPrice,
+        ))
+        .column((
+            RelayedRandomnessTaskTable::Table,
+            RelayedRandomnessTaskTable::AssignmentBlockHeight,
+        ))
+        .from(RelayedRandomnessResultTable::Table)
+        .inner_join(
+            RelayedRandomnessTaskTable::Table,
+            Expr::col((
+                RelayedRandomnessResultTable::Table,
+                RelayedRandomnessResultTable::ChainId,
+            ))
+            .equals((
+                RelayedRandomnessTaskTable::Table,
+                RelayedRandomnessTaskTable::ChainId,
+            ))
+            .and(
+                Expr::col((
+                    RelayedRandomnessResultTable::Table,
+                    RelayedRandomnessResultTable::RequestId,
+                ))
+                .equals((
+                    RelayedRandomnessTaskTable::Table,
+                    RelayedRandomnessTaskTable::RequestId,
+                )),
+            ),
+        )
+        .and_where(
+            Expr::col((
+                RelayedRandomnessResultTable::Table,
+                RelayedRandomnessResultTable::ChainId,
+            ))
+            .eq(chain_id as i64),
+        )
+        .conditions(
+            and_where.is_some(),
+            |x| {
+                x.and_where(and_where.unwrap());
+            },
+            |_x| {},
+        )
+        .to_owned()
+}
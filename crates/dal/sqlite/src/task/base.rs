@@ -3,16 +3,16 @@ use crate::types::DBError;
 use crate::types::SqliteDB;
 use arpa_core::format_now_date;
 use arpa_core::u256_to_vec;
-use arpa_core::{address_to_string, RandomnessTask, Task};
+use arpa_core::{address_to_string, BLSTask, RandomnessTask, Task};
 use arpa_dal::error::DataAccessResult;
 use arpa_dal::error::RandomnessTaskError;
-use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater};
+use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater, BlockHeightRange, ListPage};
 use async_trait::async_trait;
 use entity::base_randomness_task;
 use entity::prelude::BaseRandomnessTask;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbBackend, DbConn, DbErr, EntityTrait, FromQueryResult,
-    QueryFilter, Set, Statement,
+    ActiveModelTrait, ColumnTrait, Condition, DbBackend, DbConn, DbErr, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, QuerySelect, Set, Statement,
 };
 use std::{marker::PhantomData, sync::Arc};
 
@@ -75,6 +75,30 @@ impl BLSTasksFetcher<RandomnessTask> for BaseBLSTasksDBClient<RandomnessTask> {
 
         Ok(task.is_some() && task.unwrap().state == 1)
     }
+
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<RandomnessTask>>> {
+        let conn = &self.db_client.connection;
+        let tasks =
+            BaseRandomnessTaskQuery::select_by_filter(conn, is_handled, block_height_range, page)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|model| BLSTask {
+                state: model.state == 1,
+                task: base_model_to_randomness_task(model),
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -148,6 +172,52 @@ impl BaseRandomnessTaskQuery {
             .one(db)
             .await
     }
+
+    pub async fn select_by_filter(
+        db: &DbConn,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> Result<Vec<base_randomness_task::Model>, DbErr> {
+        let mut select = BaseRandomnessTask::find();
+
+        if let Some(is_handled) = is_handled {
+            select = select.filter(base_randomness_task::Column::State.eq(is_handled as i32));
+        }
+        if let Some(from) = block_height_range.from {
+            select =
+                select.filter(base_randomness_task::Column::AssignmentBlockHeight.gte(from as i32));
+        }
+        if let Some(to) = block_height_range.to {
+            select =
+                select.filter(base_randomness_task::Column::AssignmentBlockHeight.lte(to as i32));
+        }
+
+        if let Some(after) = page.after {
+            select = select.filter(
+                Condition::any()
+                    .add(
+                        base_randomness_task::Column::AssignmentBlockHeight
+                            .gt(after.block_height as i32),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                base_randomness_task::Column::AssignmentBlockHeight
+                                    .eq(after.block_height as i32),
+                            )
+                            .add(base_randomness_task::Column::RequestId.gt(after.request_id)),
+                    ),
+            );
+        }
+
+        select
+            .order_by_asc(base_randomness_task::Column::AssignmentBlockHeight)
+            .order_by_asc(base_randomness_task::Column::RequestId)
+            .limit(page.limit.map(|limit| limit as u64))
+            .all(db)
+            .await
+    }
 }
 
 pub struct BaseRandomnessTaskMutation;
@@ -0,0 +1,246 @@
+use crate::types::model_to_blind_signature_task;
+use crate::types::DBError;
+use crate::types::SqliteDB;
+use arpa_core::format_now_date;
+use arpa_core::{address_to_string, BLSTask, BLSTaskError, BlindSignatureTask};
+use arpa_dal::error::DataAccessResult;
+use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater, BlockHeightRange, ListPage};
+use async_trait::async_trait;
+use entity::blind_signature_task;
+use entity::prelude::BlindSignatureTask as BlindSignatureTaskEntity;
+use sea_orm::{ActiveModelTrait, DbBackend, DbConn, DbErr, FromQueryResult, Set, Statement};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::sync::Arc;
+
+impl SqliteDB {
+    pub fn get_blind_signature_tasks_client(&self) -> BlindSignatureTasksDBClient {
+        BlindSignatureTasksDBClient {
+            db_client: Arc::new(self.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlindSignatureTasksDBClient {
+    db_client: Arc<SqliteDB>,
+}
+
+impl BlindSignatureTasksDBClient {
+    pub fn get_connection(&self) -> &DbConn {
+        &self.db_client.connection
+    }
+}
+
+#[async_trait]
+impl BLSTasksFetcher<BlindSignatureTask> for BlindSignatureTasksDBClient {
+    async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let conn = &self.db_client.connection;
+        let task = BlindSignatureTaskQuery::select_by_request_id(conn, task_request_id)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+        Ok(task.is_some())
+    }
+
+    async fn get(&self, task_request_id: &[u8]) -> DataAccessResult<BlindSignatureTask> {
+        let conn = &self.db_client.connection;
+        let task = BlindSignatureTaskQuery::select_by_request_id(conn, task_request_id)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+
+        task.map(model_to_blind_signature_task)
+            .ok_or_else(|| BLSTaskError::TaskNotFound.into())
+    }
+
+    async fn is_handled(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let conn = &self.db_client.connection;
+        let task = BlindSignatureTaskQuery::select_by_request_id(conn, task_request_id)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+
+        Ok(task.is_some() && task.unwrap().state == 1)
+    }
+
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<BlindSignatureTask>>> {
+        let conn = &self.db_client.connection;
+        let tasks =
+            BlindSignatureTaskQuery::select_by_filter(conn, is_handled, block_height_range, page)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|model| BLSTask {
+                state: model.state == 1,
+                task: model_to_blind_signature_task(model),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl BLSTasksUpdater<BlindSignatureTask> for BlindSignatureTasksDBClient {
+    async fn add(&mut self, task: BlindSignatureTask) -> DataAccessResult<()> {
+        BlindSignatureTaskMutation::add_task(
+            self.get_connection(),
+            task.request_id,
+            task.group_index as i32,
+            address_to_string(task.requester),
+            task.blinded_message,
+            task.request_confirmations as i32,
+            task.assignment_block_height as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(())
+    }
+
+    async fn check_and_get_available_tasks(
+        &mut self,
+        current_block_height: usize,
+        current_group_index: usize,
+        randomness_task_exclusive_window: usize,
+    ) -> DataAccessResult<Vec<BlindSignatureTask>> {
+        let before_assignment_block_height =
+            current_block_height.saturating_sub(randomness_task_exclusive_window);
+        BlindSignatureTaskMutation::fetch_available_tasks(
+            self.get_connection(),
+            current_group_index as i32,
+            before_assignment_block_height as i32,
+        )
+        .await
+        .map(|models| {
+            models
+                .into_iter()
+                .map(model_to_blind_signature_task)
+                .collect::<Vec<_>>()
+        })
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e.into()
+        })
+    }
+}
+
+pub struct BlindSignatureTaskQuery;
+
+impl BlindSignatureTaskQuery {
+    pub async fn select_by_request_id(
+        db: &DbConn,
+        request_id: &[u8],
+    ) -> Result<Option<blind_signature_task::Model>, DbErr> {
+        BlindSignatureTaskEntity::find()
+            .filter(blind_signature_task::Column::RequestId.eq(request_id))
+            .one(db)
+            .await
+    }
+
+    pub async fn select_by_filter(
+        db: &DbConn,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> Result<Vec<blind_signature_task::Model>, DbErr> {
+        let mut select = BlindSignatureTaskEntity::find();
+
+        if let Some(is_handled) = is_handled {
+            select = select.filter(blind_signature_task::Column::State.eq(is_handled as i32));
+        }
+        if let Some(from) = block_height_range.from {
+            select =
+                select.filter(blind_signature_task::Column::AssignmentBlockHeight.gte(from as i32));
+        }
+        if let Some(to) = block_height_range.to {
+            select =
+                select.filter(blind_signature_task::Column::AssignmentBlockHeight.lte(to as i32));
+        }
+
+        if let Some(after) = page.after {
+            select = select.filter(
+                Condition::any()
+                    .add(
+                        blind_signature_task::Column::AssignmentBlockHeight
+                            .gt(after.block_height as i32),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                blind_signature_task::Column::AssignmentBlockHeight
+                                    .eq(after.block_height as i32),
+                            )
+                            .add(blind_signature_task::Column::RequestId.gt(after.request_id)),
+                    ),
+            );
+        }
+
+        select
+            .order_by_asc(blind_signature_task::Column::AssignmentBlockHeight)
+            .order_by_asc(blind_signature_task::Column::RequestId)
+            .limit(page.limit.map(|limit| limit as u64))
+            .all(db)
+            .await
+    }
+}
+
+pub struct BlindSignatureTaskMutation;
+
+impl BlindSignatureTaskMutation {
+    pub async fn add_task(
+        db: &DbConn,
+        request_id: Vec<u8>,
+        group_index: i32,
+        requester: String,
+        blinded_message: Vec<u8>,
+        request_confirmations: i32,
+        assignment_block_height: i32,
+    ) -> Result<blind_signature_task::ActiveModel, DbErr> {
+        blind_signature_task::ActiveModel {
+            request_id: Set(request_id),
+            group_index: Set(group_index),
+            requester: Set(requester),
+            blinded_message: Set(blinded_message),
+            request_confirmations: Set(request_confirmations),
+            assignment_block_height: Set(assignment_block_height),
+            create_at: Set(format_now_date()),
+            update_at: Set(format_now_date()),
+            state: Set(0),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
+    pub async fn fetch_available_tasks(
+        db: &DbConn,
+        group_index: i32,
+        assignment_block_height: i32,
+    ) -> Result<Vec<blind_signature_task::Model>, DbErr> {
+        blind_signature_task::Model::find_by_statement(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"update blind_signature_task set state = 1, update_at = $1 where state = 0 and (group_index = $2 or assignment_block_height < $3) 
+                returning *"#,
+                vec![format_now_date().into(), group_index.into(), assignment_block_height.into()],
+            ))
+            .all(db).await
+    }
+}
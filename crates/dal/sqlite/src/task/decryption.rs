@@ -0,0 +1,244 @@
+use crate::types::model_to_decryption_task;
+use crate::types::DBError;
+use crate::types::SqliteDB;
+use arpa_core::format_now_date;
+use arpa_core::{address_to_string, BLSTask, BLSTaskError, DecryptionTask};
+use arpa_dal::error::DataAccessResult;
+use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater, BlockHeightRange, ListPage};
+use async_trait::async_trait;
+use entity::decryption_task;
+use entity::prelude::DecryptionTask as DecryptionTaskEntity;
+use sea_orm::{ActiveModelTrait, DbBackend, DbConn, DbErr, FromQueryResult, Set, Statement};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::sync::Arc;
+
+impl SqliteDB {
+    pub fn get_decryption_tasks_client(&self) -> DecryptionTasksDBClient {
+        DecryptionTasksDBClient {
+            db_client: Arc::new(self.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecryptionTasksDBClient {
+    db_client: Arc<SqliteDB>,
+}
+
+impl DecryptionTasksDBClient {
+    pub fn get_connection(&self) -> &DbConn {
+        &self.db_client.connection
+    }
+}
+
+#[async_trait]
+impl BLSTasksFetcher<DecryptionTask> for DecryptionTasksDBClient {
+    async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let conn = &self.db_client.connection;
+        let task = DecryptionTaskQuery::select_by_request_id(conn, task_request_id)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+        Ok(task.is_some())
+    }
+
+    async fn get(&self, task_request_id: &[u8]) -> DataAccessResult<DecryptionTask> {
+        let conn = &self.db_client.connection;
+        let task = DecryptionTaskQuery::select_by_request_id(conn, task_request_id)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+
+        task.map(model_to_decryption_task)
+            .ok_or_else(|| BLSTaskError::TaskNotFound.into())
+    }
+
+    async fn is_handled(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let conn = &self.db_client.connection;
+        let task = DecryptionTaskQuery::select_by_request_id(conn, task_request_id)
+            .await
+            .map_err(|e| {
+                let e: DBError = e.into();
+                e
+            })?;
+
+        Ok(task.is_some() && task.unwrap().state == 1)
+    }
+
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<DecryptionTask>>> {
+        let conn = &self.db_client.connection;
+        let tasks =
+            DecryptionTaskQuery::select_by_filter(conn, is_handled, block_height_range, page)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|model| BLSTask {
+                state: model.state == 1,
+                task: model_to_decryption_task(model),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl BLSTasksUpdater<DecryptionTask> for DecryptionTasksDBClient {
+    async fn add(&mut self, task: DecryptionTask) -> DataAccessResult<()> {
+        DecryptionTaskMutation::add_task(
+            self.get_connection(),
+            task.request_id,
+            task.group_index as i32,
+            address_to_string(task.requester),
+            task.ciphertext,
+            task.request_confirmations as i32,
+            task.assignment_block_height as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(())
+    }
+
+    async fn check_and_get_available_tasks(
+        &mut self,
+        current_block_height: usize,
+        current_group_index: usize,
+        randomness_task_exclusive_window: usize,
+    ) -> DataAccessResult<Vec<DecryptionTask>> {
+        let before_assignment_block_height =
+            current_block_height.saturating_sub(randomness_task_exclusive_window);
+        DecryptionTaskMutation::fetch_available_tasks(
+            self.get_connection(),
+            current_group_index as i32,
+            before_assignment_block_height as i32,
+        )
+        .await
+        .map(|models| {
+            models
+                .into_iter()
+                .map(model_to_decryption_task)
+                .collect::<Vec<_>>()
+        })
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e.into()
+        })
+    }
+}
+
+pub struct DecryptionTaskQuery;
+
+impl DecryptionTaskQuery {
+    pub async fn select_by_request_id(
+        db: &DbConn,
+        request_id: &[u8],
+    ) -> Result<Option<decryption_task::Model>, DbErr> {
+        DecryptionTaskEntity::find()
+            .filter(decryption_task::Column::RequestId.eq(request_id))
+            .one(db)
+            .await
+    }
+
+    pub async fn select_by_filter(
+        db: &DbConn,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> Result<Vec<decryption_task::Model>, DbErr> {
+        let mut select = DecryptionTaskEntity::find();
+
+        if let Some(is_handled) = is_handled {
+            select = select.filter(decryption_task::Column::State.eq(is_handled as i32));
+        }
+        if let Some(from) = block_height_range.from {
+            select = select.filter(decryption_task::Column::AssignmentBlockHeight.gte(from as i32));
+        }
+        if let Some(to) = block_height_range.to {
+            select = select.filter(decryption_task::Column::AssignmentBlockHeight.lte(to as i32));
+        }
+
+        if let Some(after) = page.after {
+            select = select.filter(
+                Condition::any()
+                    .add(
+                        decryption_task::Column::AssignmentBlockHeight
+                            .gt(after.block_height as i32),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                decryption_task::Column::AssignmentBlockHeight
+                                    .eq(after.block_height as i32),
+                            )
+                            .add(decryption_task::Column::RequestId.gt(after.request_id)),
+                    ),
+            );
+        }
+
+        select
+            .order_by_asc(decryption_task::Column::AssignmentBlockHeight)
+            .order_by_asc(decryption_task::Column::RequestId)
+            .limit(page.limit.map(|limit| limit as u64))
+            .all(db)
+            .await
+    }
+}
+
+pub struct DecryptionTaskMutation;
+
+impl DecryptionTaskMutation {
+    pub async fn add_task(
+        db: &DbConn,
+        request_id: Vec<u8>,
+        group_index: i32,
+        requester: String,
+        ciphertext: Vec<u8>,
+        request_confirmations: i32,
+        assignment_block_height: i32,
+    ) -> Result<decryption_task::ActiveModel, DbErr> {
+        decryption_task::ActiveModel {
+            request_id: Set(request_id),
+            group_index: Set(group_index),
+            requester: Set(requester),
+            ciphertext: Set(ciphertext),
+            request_confirmations: Set(request_confirmations),
+            assignment_block_height: Set(assignment_block_height),
+            create_at: Set(format_now_date()),
+            update_at: Set(format_now_date()),
+            state: Set(0),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
+    pub async fn fetch_available_tasks(
+        db: &DbConn,
+        group_index: i32,
+        assignment_block_height: i32,
+    ) -> Result<Vec<decryption_task::Model>, DbErr> {
+        decryption_task::Model::find_by_statement(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"update decryption_task set state = 1, update_at = $1 where state = 0 and (group_index = $2 or assignment_block_height < $3) 
+                returning *"#,
+                vec![format_now_date().into(), group_index.into(), assignment_block_height.into()],
+            ))
+            .all(db).await
+    }
+}
@@ -1,13 +1,19 @@
 mod base;
+mod blind;
+mod decryption;
 mod loot;
 mod main;
 mod op;
 mod redstone;
+mod relayed;
 mod taiko;
 
 pub use base::BaseBLSTasksDBClient;
+pub use blind::BlindSignatureTasksDBClient;
+pub use decryption::DecryptionTasksDBClient;
 pub use loot::LootBLSTasksDBClient;
 pub use main::BLSTasksDBClient;
 pub use op::OPBLSTasksDBClient;
 pub use redstone::RedstoneBLSTasksDBClient;
+pub use relayed::RelayedBLSTasksDBClient;
 pub use taiko::TaikoBLSTasksDBClient;
@@ -4,17 +4,17 @@ use crate::types::SqliteDB;
 use arpa_core::format_now_date;
 use arpa_core::u256_to_vec;
 use arpa_core::RandomnessRequestType;
-use arpa_core::{address_to_string, RandomnessTask, Task};
+use arpa_core::{address_to_string, BLSTask, RandomnessTask, Task};
 use arpa_dal::error::DataAccessResult;
 use arpa_dal::error::RandomnessTaskError;
-use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater};
+use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater, BlockHeightRange, ListPage};
 use async_trait::async_trait;
 use entity::op_randomness_task;
 use entity::prelude::OpRandomnessTask;
 use ethers_core::types::Address;
 use ethers_core::types::U256;
 use sea_orm::{ActiveModelTrait, DbBackend, DbConn, DbErr, FromQueryResult, Set, Statement};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 use std::{marker::PhantomData, sync::Arc};
 
 impl SqliteDB {
@@ -76,6 +76,30 @@ impl BLSTasksFetcher<RandomnessTask> for OPBLSTasksDBClient<RandomnessTask> {
 
         Ok(task.is_some() && task.unwrap().state == 1)
     }
+
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<RandomnessTask>>> {
+        let conn = &self.db_client.connection;
+        let tasks =
+            OPRandomnessTaskQuery::select_by_filter(conn, is_handled, block_height_range, page)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|model| BLSTask {
+                state: model.state == 1,
+                task: op_model_to_randomness_task(model),
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -161,6 +185,52 @@ impl OPRandomnessTaskQuery {
             .one(db)
             .await
     }
+
+    pub async fn select_by_filter(
+        db: &DbConn,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> Result<Vec<op_randomness_task::Model>, DbErr> {
+        let mut select = OpRandomnessTask::find();
+
+        if let Some(is_handled) = is_handled {
+            select = select.filter(op_randomness_task::Column::State.eq(is_handled as i32));
+        }
+        if let Some(from) = block_height_range.from {
+            select =
+                select.filter(op_randomness_task::Column::AssignmentBlockHeight.gte(from as i32));
+        }
+        if let Some(to) = block_height_range.to {
+            select =
+                select.filter(op_randomness_task::Column::AssignmentBlockHeight.lte(to as i32));
+        }
+
+        if let Some(after) = page.after {
+            select = select.filter(
+                Condition::any()
+                    .add(
+                        op_randomness_task::Column::AssignmentBlockHeight
+                            .gt(after.block_height as i32),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                op_randomness_task::Column::AssignmentBlockHeight
+                                    .eq(after.block_height as i32),
+                            )
+                            .add(op_randomness_task::Column::RequestId.gt(after.request_id)),
+                    ),
+            );
+        }
+
+        select
+            .order_by_asc(op_randomness_task::Column::AssignmentBlockHeight)
+            .order_by_asc(op_randomness_task::Column::RequestId)
+            .limit(page.limit.map(|limit| limit as u64))
+            .all(db)
+            .await
+    }
 }
 
 pub struct OPRandomnessTaskMutation;
@@ -0,0 +1,312 @@
+use crate::types::relayed_model_to_randomness_task;
+use crate::types::DBError;
+use crate::types::SqliteDB;
+use arpa_core::format_now_date;
+use arpa_core::u256_to_vec;
+use arpa_core::RandomnessRequestType;
+use arpa_core::{address_to_string, BLSTask, RandomnessTask, Task};
+use arpa_dal::error::DataAccessResult;
+use arpa_dal::error::RandomnessTaskError;
+use arpa_dal::{BLSTasksFetcher, BLSTasksUpdater, BlockHeightRange, ListPage};
+use async_trait::async_trait;
+use entity::prelude::RelayedRandomnessTask;
+use entity::relayed_randomness_task;
+use ethers_core::types::Address;
+use ethers_core::types::U256;
+use sea_orm::{ActiveModelTrait, DbBackend, DbConn, DbErr, FromQueryResult, Set, Statement};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::{marker::PhantomData, sync::Arc};
+
+impl SqliteDB {
+    /// Tasks of the chains in the relayed storage namespace share the same table,
+    /// the client only sees the ones of the given chain.
+    pub fn get_relayed_bls_tasks_client<T: Task>(
+        &self,
+        chain_id: usize,
+    ) -> RelayedBLSTasksDBClient<T> {
+        RelayedBLSTasksDBClient {
+            db_client: Arc::new(self.clone()),
+            chain_id,
+            bls_tasks: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RelayedBLSTasksDBClient<T: Task> {
+    db_client: Arc<SqliteDB>,
+    chain_id: usize,
+    bls_tasks: PhantomData<T>,
+}
+
+impl RelayedBLSTasksDBClient<RandomnessTask> {
+    pub fn get_connection(&self) -> &DbConn {
+        &self.db_client.connection
+    }
+}
+
+#[async_trait]
+impl BLSTasksFetcher<RandomnessTask> for RelayedBLSTasksDBClient<RandomnessTask> {
+    async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let conn = &self.db_client.connection;
+        let task =
+            RelayedRandomnessTaskQuery::select_by_request_id(conn, self.chain_id, task_request_id)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+        Ok(task.is_some())
+    }
+
+    async fn get(&self, task_request_id: &[u8]) -> DataAccessResult<RandomnessTask> {
+        let conn = &self.db_client.connection;
+        let task =
+            RelayedRandomnessTaskQuery::select_by_request_id(conn, self.chain_id, task_request_id)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        task.map(relayed_model_to_randomness_task).ok_or_else(|| {
+            RandomnessTaskError::NoRandomnessTask(format!("{:?}", task_request_id)).into()
+        })
+    }
+
+    async fn is_handled(&self, task_request_id: &[u8]) -> DataAccessResult<bool> {
+        let conn = &self.db_client.connection;
+        let task =
+            RelayedRandomnessTaskQuery::select_by_request_id(conn, self.chain_id, task_request_id)
+                .await
+                .map_err(|e| {
+                    let e: DBError = e.into();
+                    e
+                })?;
+
+        Ok(task.is_some() && task.unwrap().state == 1)
+    }
+
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<RandomnessTask>>> {
+        let conn = &self.db_client.connection;
+        let tasks = RelayedRandomnessTaskQuery::select_by_filter(
+            conn,
+            self.chain_id,
+            is_handled,
+            block_height_range,
+            page,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(tasks
+            .into_iter()
+            .map(|model| BLSTask {
+                state: model.state == 1,
+                task: relayed_model_to_randomness_task(model),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl BLSTasksUpdater<RandomnessTask> for RelayedBLSTasksDBClient<RandomnessTask> {
+    async fn add(&mut self, task: RandomnessTask) -> DataAccessResult<()> {
+        let seed_bytes = u256_to_vec(&task.seed);
+
+        RelayedRandomnessTaskMutation::add_task(
+            self.get_connection(),
+            self.chain_id as i64,
+            task.request_id,
+            task.subscription_id as i32,
+            task.group_index as i32,
+            task.request_type as i32,
+            task.params,
+            address_to_string(task.requester),
+            seed_bytes,
+            task.request_confirmations as i32,
+            task.callback_gas_limit as i32,
+            u256_to_vec(&task.callback_max_gas_price),
+            task.assignment_block_height as i32,
+        )
+        .await
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e
+        })?;
+
+        Ok(())
+    }
+
+    async fn check_and_get_available_tasks(
+        &mut self,
+        current_block_height: usize,
+        current_group_index: usize,
+        randomness_task_exclusive_window: usize,
+    ) -> DataAccessResult<Vec<RandomnessTask>> {
+        let before_assignment_block_height =
+            if current_block_height > randomness_task_exclusive_window {
+                current_block_height - randomness_task_exclusive_window
+            } else {
+                0
+            };
+        RelayedRandomnessTaskMutation::fetch_available_tasks(
+            self.get_connection(),
+            self.chain_id as i64,
+            current_group_index as i32,
+            before_assignment_block_height as i32,
+        )
+        .await
+        .map(|models| {
+            models
+                .into_iter()
+                .map(|model| RandomnessTask {
+                    request_id: model.request_id,
+                    subscription_id: model.subscription_id as u64,
+                    group_index: model.group_index as u32,
+                    request_type: RandomnessRequestType::from(model.request_type as u8),
+                    params: model.params,
+                    requester: model.requester.parse::<Address>().unwrap(),
+                    seed: U256::from_big_endian(&model.seed),
+                    request_confirmations: model.request_confirmations as u16,
+                    callback_gas_limit: model.callback_gas_limit as u32,
+                    callback_max_gas_price: U256::from_big_endian(&model.callback_max_gas_price),
+                    assignment_block_height: model.assignment_block_height as usize,
+                })
+                .collect::<Vec<_>>()
+        })
+        .map_err(|e| {
+            let e: DBError = e.into();
+            e.into()
+        })
+    }
+}
+
+pub struct RelayedRandomnessTaskQuery;
+
+impl RelayedRandomnessTaskQuery {
+    pub async fn select_by_request_id(
+        db: &DbConn,
+        chain_id: usize,
+        request_id: &[u8],
+    ) -> Result<Option<relayed_randomness_task::Model>, DbErr> {
+        RelayedRandomnessTask::find()
+            .filter(relayed_randomness_task::Column::ChainId.eq(chain_id as i64))
+            .filter(relayed_randomness_task::Column::RequestId.eq(request_id))
+            .one(db)
+            .await
+    }
+
+    pub async fn select_by_filter(
+        db: &DbConn,
+        chain_id: usize,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> Result<Vec<relayed_randomness_task::Model>, DbErr> {
+        let mut select = RelayedRandomnessTask::find()
+            .filter(relayed_randomness_task::Column::ChainId.eq(chain_id as i64));
+
+        if let Some(is_handled) = is_handled {
+            select = select.filter(relayed_randomness_task::Column::State.eq(is_handled as i32));
+        }
+        if let Some(from) = block_height_range.from {
+            select = select
+                .filter(relayed_randomness_task::Column::AssignmentBlockHeight.gte(from as i32));
+        }
+        if let Some(to) = block_height_range.to {
+            select = select
+                .filter(relayed_randomness_task::Column::AssignmentBlockHeight.lte(to as i32));
+        }
+
+        if let Some(after) = page.after {
+            select = select.filter(
+                Condition::any()
+                    .add(
+                        relayed_randomness_task::Column::AssignmentBlockHeight
+                            .gt(after.block_height as i32),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                relayed_randomness_task::Column::AssignmentBlockHeight
+                                    .eq(after.block_height as i32),
+                            )
+                            .add(relayed_randomness_task::Column::RequestId.gt(after.request_id)),
+                    ),
+            );
+        }
+
+        select
+            .order_by_asc(relayed_randomness_task::Column::AssignmentBlockHeight)
+            .order_by_asc(relayed_randomness_task::Column::RequestId)
+            .limit(page.limit.map(|limit| limit as u64))
+            .all(db)
+            .await
+    }
+}
+
+pub struct RelayedRandomnessTaskMutation;
+
+impl RelayedRandomnessTaskMutation {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_task(
+        db: &DbConn,
+        chain_id: i64,
+        request_id: Vec<u8>,
+        subscription_id: i32,
+        group_index: i32,
+        request_type: i32,
+        params: Vec<u8>,
+        requester: String,
+        seed: Vec<u8>,
+        request_confirmations: i32,
+        callback_gas_limit: i32,
+        callback_max_gas_price: Vec<u8>,
+        assignment_block_height: i32,
+    ) -> Result<relayed_randomness_task::ActiveModel, DbErr> {
+        relayed_randomness_task::ActiveModel {
+            chain_id: Set(chain_id),
+            request_id: Set(request_id),
+            subscription_id: Set(subscription_id),
+            group_index: Set(group_index),
+            request_type: Set(request_type),
+            params: Set(params),
+            requester: Set(requester),
+            seed: Set(seed),
+            request_confirmations: Set(request_confirmations),
+            callback_gas_limit: Set(callback_gas_limit),
+            callback_max_gas_price: Set(callback_max_gas_price),
+            assignment_block_height: Set(assignment_block_height),
+            create_at: Set(format_now_date()),
+            update_at: Set(format_now_date()),
+            state: Set(0),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+    }
+
+    pub async fn fetch_available_tasks(
+        db: &DbConn,
+        chain_id: i64,
+        group_index: i32,
+        assignment_block_height: i32,
+    ) -> Result<Vec<relayed_randomness_task::Model>, DbErr> {
+        relayed_randomness_task::Model::find_by_statement(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                r#"update relayed_randomness_task set state = 1, update_at = $1 where chain_id = $2 and state = 0 and (group_index = $3 or assignment_block_height < $4) 
+                returning *"#,
+                vec![format_now_date().into(), chain_id.into(), group_index.into(), assignment_block_height.into()],
+            ))
+            .all(db).await
+    }
+}
@@ -1,14 +1,21 @@
+use arpa_core::BlindSignatureTask;
+use arpa_core::DecryptionTask;
 use arpa_core::RandomnessRequestType;
 use arpa_core::RandomnessTask;
 use arpa_dal::cache::BLSResultCache;
+use arpa_dal::cache::BlindSignatureResultCache;
+use arpa_dal::cache::DecryptionResultCache;
 use arpa_dal::cache::RandomnessResultCache;
 use arpa_dal::error::DataAccessError;
 use arpa_dal::BLSResultCacheState;
 use entity::base_randomness_task;
+use entity::blind_signature_task;
+use entity::decryption_task;
 use entity::loot_randomness_task;
 use entity::op_randomness_task;
 use entity::randomness_task;
 use entity::redstone_randomness_task;
+use entity::relayed_randomness_task;
 use entity::taiko_randomness_task;
 use ethers_core::types::Address;
 use ethers_core::types::U256;
@@ -93,6 +100,120 @@ impl From<RandomnessRecord> for BLSResultCache<RandomnessResultCache> {
     }
 }
 
+#[derive(Debug, Clone, FromQueryResult)]
+pub(crate) struct BlindSignatureRecord {
+    // result
+    pub request_id: Vec<u8>,
+    pub group_index: i32,
+    pub message: Vec<u8>,
+    pub threshold: i32,
+    pub partial_signatures: String,
+    pub committed_times: i32,
+    pub state: i32,
+    // task
+    pub requester: String,
+    pub blinded_message: Vec<u8>,
+    pub request_confirmations: i32,
+    pub assignment_block_height: i32,
+}
+
+impl From<BlindSignatureRecord> for BLSResultCache<BlindSignatureResultCache> {
+    fn from(blind_signature_record: BlindSignatureRecord) -> Self {
+        let task = BlindSignatureTask {
+            request_id: blind_signature_record.request_id.clone(),
+            group_index: blind_signature_record.group_index as u32,
+            requester: blind_signature_record.requester.parse::<Address>().unwrap(),
+            blinded_message: blind_signature_record.blinded_message,
+            request_confirmations: blind_signature_record.request_confirmations as u16,
+            assignment_block_height: blind_signature_record.assignment_block_height as usize,
+        };
+
+        let partial_signatures: BTreeMap<Address, Vec<u8>> =
+            serde_json::from_str(&blind_signature_record.partial_signatures).unwrap();
+
+        BLSResultCache {
+            result_cache: BlindSignatureResultCache {
+                group_index: blind_signature_record.group_index as usize,
+                message: blind_signature_record.message,
+                blind_signature_task: task,
+                partial_signatures,
+                threshold: blind_signature_record.threshold as usize,
+                committed_times: blind_signature_record.committed_times as usize,
+            },
+            state: BLSResultCacheState::from(blind_signature_record.state),
+        }
+    }
+}
+
+pub(crate) fn model_to_blind_signature_task(
+    model: blind_signature_task::Model,
+) -> BlindSignatureTask {
+    BlindSignatureTask {
+        request_id: model.request_id,
+        group_index: model.group_index as u32,
+        requester: model.requester.parse::<Address>().unwrap(),
+        blinded_message: model.blinded_message,
+        request_confirmations: model.request_confirmations as u16,
+        assignment_block_height: model.assignment_block_height as usize,
+    }
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub(crate) struct DecryptionRecord {
+    // result
+    pub request_id: Vec<u8>,
+    pub group_index: i32,
+    pub message: Vec<u8>,
+    pub threshold: i32,
+    pub decryption_shares: String,
+    pub committed_times: i32,
+    pub state: i32,
+    // task
+    pub requester: String,
+    pub ciphertext: Vec<u8>,
+    pub request_confirmations: i32,
+    pub assignment_block_height: i32,
+}
+
+impl From<DecryptionRecord> for BLSResultCache<DecryptionResultCache> {
+    fn from(decryption_record: DecryptionRecord) -> Self {
+        let task = DecryptionTask {
+            request_id: decryption_record.request_id.clone(),
+            group_index: decryption_record.group_index as u32,
+            requester: decryption_record.requester.parse::<Address>().unwrap(),
+            ciphertext: decryption_record.ciphertext,
+            request_confirmations: decryption_record.request_confirmations as u16,
+            assignment_block_height: decryption_record.assignment_block_height as usize,
+        };
+
+        let decryption_shares: BTreeMap<Address, Vec<u8>> =
+            serde_json::from_str(&decryption_record.decryption_shares).unwrap();
+
+        BLSResultCache {
+            result_cache: DecryptionResultCache {
+                group_index: decryption_record.group_index as usize,
+                message: decryption_record.message,
+                decryption_task: task,
+                decryption_shares,
+                threshold: decryption_record.threshold as usize,
+                committed_times: decryption_record.committed_times as usize,
+            },
+            state: BLSResultCacheState::from(decryption_record.state),
+        }
+    }
+}
+
+pub(crate) fn model_to_decryption_task(model: decryption_task::Model) -> DecryptionTask {
+    DecryptionTask {
+        request_id: model.request_id,
+        group_index: model.group_index as u32,
+        requester: model.requester.parse::<Address>().unwrap(),
+        ciphertext: model.ciphertext,
+        request_confirmations: model.request_confirmations as u16,
+        assignment_block_height: model.assignment_block_height as usize,
+    }
+}
+
 pub(crate) fn model_to_randomness_task(model: randomness_task::Model) -> RandomnessTask {
     RandomnessTask {
         request_id: model.request_id,
@@ -125,6 +246,24 @@ pub(crate) fn op_model_to_randomness_task(model: op_randomness_task::Model) -> R
     }
 }
 
+pub(crate) fn relayed_model_to_randomness_task(
+    model: relayed_randomness_task::Model,
+) -> RandomnessTask {
+    RandomnessTask {
+        request_id: model.request_id,
+        subscription_id: model.subscription_id as u64,
+        group_index: model.group_index as u32,
+        request_type: RandomnessRequestType::from(model.request_type as u8),
+        params: model.params,
+        requester: model.requester.parse::<Address>().unwrap(),
+        seed: U256::from_big_endian(&model.seed),
+        request_confirmations: model.request_confirmations as u16,
+        callback_gas_limit: model.callback_gas_limit as u32,
+        callback_max_gas_price: U256::from_big_endian(&model.callback_max_gas_price),
+        assignment_block_height: model.assignment_block_height as usize,
+    }
+}
+
 pub(crate) fn base_model_to_randomness_task(model: base_randomness_task::Model) -> RandomnessTask {
     RandomnessTask {
         request_id: model.request_id,
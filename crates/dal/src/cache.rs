@@ -1,7 +1,7 @@
 use crate::error::{DataAccessResult, GroupError, NodeInfoError};
 use crate::{
-    BLSResultCacheState, BLSTasksHandler, BlockInfoHandler, ContextInfoUpdater, GroupInfoHandler,
-    NodeInfoHandler, SignatureResultCacheHandler,
+    BLSResultCacheState, BLSTasksHandler, BlockHeightRange, BlockInfoHandler, ContextInfoUpdater,
+    DKGCheckpoint, GroupInfoHandler, ListPage, NodeInfoHandler, SignatureResultCacheHandler,
 };
 
 use super::{
@@ -10,21 +10,26 @@ use super::{
     SignatureResultCacheUpdater,
 };
 use arpa_core::log::encoder;
-use arpa_core::{BLSTask, BLSTaskError, DKGStatus, DKGTask, Group, Member, RandomnessTask, Task};
+use arpa_core::{
+    BLSTask, BLSTaskError, BlindSignatureTask, DKGStatus, DKGTask, DecryptionTask, Group, Member,
+    RandomnessTask, Task,
+};
 use async_trait::async_trait;
 use dkg_core::primitives::DKGOutput;
 use ethers_core::types::Address;
 use log::info;
 use std::collections::{BTreeMap, HashMap};
+use std::time::SystemTime;
 use threshold_bls::group::{Curve, Element};
 use threshold_bls::serialize::point_to_hex;
 use threshold_bls::sig::Share;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct InMemoryBlockInfoCache {
     chain_id: usize,
     block_height: usize,
     block_time: usize,
+    block_updated_at: SystemTime,
 }
 
 impl InMemoryBlockInfoCache {
@@ -33,6 +38,18 @@ impl InMemoryBlockInfoCache {
             chain_id,
             block_height: 0,
             block_time,
+            block_updated_at: SystemTime::now(),
+        }
+    }
+}
+
+impl Default for InMemoryBlockInfoCache {
+    fn default() -> Self {
+        InMemoryBlockInfoCache {
+            chain_id: 0,
+            block_height: 0,
+            block_time: 0,
+            block_updated_at: SystemTime::now(),
         }
     }
 }
@@ -51,11 +68,16 @@ impl BlockInfoFetcher for InMemoryBlockInfoCache {
     fn get_block_time(&self) -> usize {
         self.block_time
     }
+
+    fn get_block_updated_at(&self) -> SystemTime {
+        self.block_updated_at
+    }
 }
 
 impl BlockInfoUpdater for InMemoryBlockInfoCache {
     fn set_block_height(&mut self, block_height: usize) {
         self.block_height = block_height;
+        self.block_updated_at = SystemTime::now();
     }
 }
 
@@ -164,6 +186,7 @@ pub struct InMemoryGroupInfoCache<C: Curve> {
     pub(crate) dkg_status: DKGStatus,
     pub(crate) self_index: usize,
     pub(crate) dkg_start_block_height: usize,
+    pub(crate) dkg_checkpoint: Option<DKGCheckpoint>,
 }
 
 impl<C: Curve> Default for InMemoryGroupInfoCache<C> {
@@ -180,6 +203,13 @@ impl<C: Curve> std::fmt::Debug for InMemoryGroupInfoCache<C> {
             .field("dkg_status", &self.dkg_status)
             .field("self_index", &self.self_index)
             .field("dkg_start_block_height", &self.dkg_start_block_height)
+            .field(
+                "dkg_checkpoint",
+                &self
+                    .dkg_checkpoint
+                    .as_ref()
+                    .map(|checkpoint| checkpoint.completed_phases),
+            )
             .finish()
     }
 }
@@ -194,6 +224,7 @@ impl<C: Curve> InMemoryGroupInfoCache<C> {
             dkg_status: DKGStatus::None,
             self_index: 0,
             dkg_start_block_height: 0,
+            dkg_checkpoint: None,
         }
     }
 
@@ -203,6 +234,7 @@ impl<C: Curve> InMemoryGroupInfoCache<C> {
         dkg_status: DKGStatus,
         self_index: usize,
         dkg_start_block_height: usize,
+        dkg_checkpoint: Option<DKGCheckpoint>,
     ) -> Self {
         InMemoryGroupInfoCache {
             share,
@@ -210,6 +242,7 @@ impl<C: Curve> InMemoryGroupInfoCache<C> {
             dkg_status,
             self_index,
             dkg_start_block_height,
+            dkg_checkpoint,
         }
     }
 
@@ -283,6 +316,8 @@ impl<C: Curve> GroupInfoUpdater<C> for InMemoryGroupInfoCache<C> {
 
         self.dkg_start_block_height = task.assignment_block_height;
 
+        self.dkg_checkpoint = None;
+
         task.members
             .iter()
             .enumerate()
@@ -339,6 +374,8 @@ impl<C: Curve> GroupInfoUpdater<C> for InMemoryGroupInfoCache<C> {
 
         let public_key = output.public.public_key().clone();
 
+        self.dkg_checkpoint = None;
+
         let mut partial_public_key = C::Point::new();
 
         let share = bincode::deserialize(&bincode::serialize(&output.share)?)?;
@@ -410,6 +447,8 @@ impl<C: Curve> GroupInfoUpdater<C> for InMemoryGroupInfoCache<C> {
 
         self.group.size = self.group.members.len();
 
+        self.dkg_checkpoint = None;
+
         self.refresh_context_entry();
 
         Ok(disqualified_nodes)
@@ -479,6 +518,33 @@ impl<C: Curve> GroupInfoUpdater<C> for InMemoryGroupInfoCache<C> {
 
         Ok(true)
     }
+
+    async fn save_dkg_checkpoint(
+        &mut self,
+        index: usize,
+        epoch: usize,
+        checkpoint: DKGCheckpoint,
+    ) -> DataAccessResult<()> {
+        self.only_has_group_task()?;
+
+        if self.group.index != index {
+            return Err(GroupError::GroupIndexObsolete(self.group.index).into());
+        }
+
+        if self.group.epoch != epoch {
+            return Err(GroupError::GroupEpochObsolete(self.group.epoch).into());
+        }
+
+        if self.group.state {
+            return Err(GroupError::GroupAlreadyReady.into());
+        }
+
+        self.dkg_checkpoint = Some(checkpoint);
+
+        self.refresh_context_entry();
+
+        Ok(())
+    }
 }
 
 impl<C: Curve> GroupInfoFetcher<C> for InMemoryGroupInfoCache<C> {
@@ -577,6 +643,12 @@ impl<C: Curve> GroupInfoFetcher<C> for InMemoryGroupInfoCache<C> {
         Ok(self.dkg_status)
     }
 
+    fn get_dkg_checkpoint(&self) -> DataAccessResult<Option<&DKGCheckpoint>> {
+        self.only_has_group_task()?;
+
+        Ok(self.dkg_checkpoint.as_ref())
+    }
+
     fn is_committer(&self, id_address: Address) -> DataAccessResult<bool> {
         self.only_has_group_task()?;
 
@@ -617,6 +689,28 @@ impl<T: Task + Sync + Clone> BLSTasksFetcher<T> for InMemoryBLSTasksQueue<T> {
             .map(|task| &task.state)
             .unwrap_or(&false))
     }
+
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<T>>> {
+        let mut tasks = self
+            .bls_tasks
+            .values()
+            .filter(|task| is_handled.map_or(true, |h| task.state == h))
+            .filter(|task| block_height_range.contains(task.task.assignment_block_height()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        tasks.sort_by(|a, b| {
+            (a.task.assignment_block_height(), a.task.request_id())
+                .cmp(&(b.task.assignment_block_height(), b.task.request_id()))
+        });
+
+        Ok(page.paginate(tasks, |task| &task.task))
+    }
 }
 
 #[async_trait]
@@ -653,6 +747,74 @@ impl BLSTasksUpdater<RandomnessTask> for InMemoryBLSTasksQueue<RandomnessTask> {
     }
 }
 
+#[async_trait]
+impl BLSTasksUpdater<DecryptionTask> for InMemoryBLSTasksQueue<DecryptionTask> {
+    async fn add(&mut self, task: DecryptionTask) -> DataAccessResult<()> {
+        self.bls_tasks
+            .insert(task.request_id().to_vec(), BLSTask { task, state: false });
+
+        Ok(())
+    }
+
+    async fn check_and_get_available_tasks(
+        &mut self,
+        current_block_height: usize,
+        current_group_index: usize,
+        randomness_task_exclusive_window: usize,
+    ) -> DataAccessResult<Vec<DecryptionTask>> {
+        let available_tasks = self
+            .bls_tasks
+            .iter_mut()
+            .filter(|(_, task)| !task.state)
+            .filter(|(_, task)| {
+                task.task.group_index == current_group_index as u32
+                    || current_block_height
+                        > task.task.assignment_block_height + randomness_task_exclusive_window
+            })
+            .map(|(_, task)| {
+                task.state = true;
+                task.task.clone()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(available_tasks)
+    }
+}
+
+#[async_trait]
+impl BLSTasksUpdater<BlindSignatureTask> for InMemoryBLSTasksQueue<BlindSignatureTask> {
+    async fn add(&mut self, task: BlindSignatureTask) -> DataAccessResult<()> {
+        self.bls_tasks
+            .insert(task.request_id().to_vec(), BLSTask { task, state: false });
+
+        Ok(())
+    }
+
+    async fn check_and_get_available_tasks(
+        &mut self,
+        current_block_height: usize,
+        current_group_index: usize,
+        randomness_task_exclusive_window: usize,
+    ) -> DataAccessResult<Vec<BlindSignatureTask>> {
+        let available_tasks = self
+            .bls_tasks
+            .iter_mut()
+            .filter(|(_, task)| !task.state)
+            .filter(|(_, task)| {
+                task.task.group_index == current_group_index as u32
+                    || current_block_height
+                        > task.task.assignment_block_height + randomness_task_exclusive_window
+            })
+            .map(|(_, task)| {
+                task.state = true;
+                task.task.clone()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(available_tasks)
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct InMemorySignatureResultCache<C: ResultCache> {
     signature_result_caches: BTreeMap<Vec<u8>, BLSResultCache<C>>,
@@ -682,6 +844,10 @@ impl Task for RandomnessResultCache {
     fn request_id(&self) -> &[u8] {
         &self.randomness_task.request_id
     }
+
+    fn assignment_block_height(&self) -> usize {
+        self.randomness_task.assignment_block_height
+    }
 }
 
 impl ResultCache for RandomnessResultCache {
@@ -689,6 +855,36 @@ impl ResultCache for RandomnessResultCache {
     type M = Vec<u8>;
 }
 
+impl Task for DecryptionResultCache {
+    fn request_id(&self) -> &[u8] {
+        &self.decryption_task.request_id
+    }
+
+    fn assignment_block_height(&self) -> usize {
+        self.decryption_task.assignment_block_height
+    }
+}
+
+impl ResultCache for DecryptionResultCache {
+    type Task = DecryptionTask;
+    type M = Vec<u8>;
+}
+
+impl Task for BlindSignatureResultCache {
+    fn request_id(&self) -> &[u8] {
+        &self.blind_signature_task.request_id
+    }
+
+    fn assignment_block_height(&self) -> usize {
+        self.blind_signature_task.assignment_block_height
+    }
+}
+
+impl ResultCache for BlindSignatureResultCache {
+    type Task = BlindSignatureTask;
+    type M = Vec<u8>;
+}
+
 #[derive(Debug, Clone)]
 pub struct BLSResultCache<C: ResultCache> {
     pub result_cache: C,
@@ -705,6 +901,30 @@ pub struct RandomnessResultCache {
     pub committed_times: usize,
 }
 
+/// Decryption shares of a ciphertext collected by the committer, which are committed
+/// as partial signatures with the ciphertext as the message.
+#[derive(Clone, Debug)]
+pub struct DecryptionResultCache {
+    pub group_index: usize,
+    pub decryption_task: DecryptionTask,
+    pub message: Vec<u8>,
+    pub threshold: usize,
+    pub decryption_shares: BTreeMap<Address, Vec<u8>>,
+    pub committed_times: usize,
+}
+
+/// Blind partial signatures collected by the committer, whose message is the blinded
+/// message of the requester.
+#[derive(Clone, Debug)]
+pub struct BlindSignatureResultCache {
+    pub group_index: usize,
+    pub blind_signature_task: BlindSignatureTask,
+    pub message: Vec<u8>,
+    pub threshold: usize,
+    pub partial_signatures: BTreeMap<Address, Vec<u8>>,
+    pub committed_times: usize,
+}
+
 #[async_trait]
 impl<C: ResultCache + Send + Sync> SignatureResultCacheFetcher<C>
     for InMemorySignatureResultCache<C>
@@ -719,6 +939,45 @@ impl<C: ResultCache + Send + Sync> SignatureResultCacheFetcher<C>
             .cloned()
             .ok_or_else(|| BLSTaskError::CommitterCacheNotExisted.into())
     }
+
+    async fn get_unfulfilled_count(&self) -> DataAccessResult<usize> {
+        Ok(self
+            .signature_result_caches
+            .values()
+            .filter(|v| {
+                v.state == BLSResultCacheState::NotCommitted
+                    || v.state == BLSResultCacheState::Committing
+            })
+            .count())
+    }
+
+    async fn list(
+        &self,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSResultCache<C>>> {
+        let mut results = self
+            .signature_result_caches
+            .values()
+            .filter(|v| state.map_or(true, |s| v.state == s))
+            .filter(|v| block_height_range.contains(v.result_cache.assignment_block_height()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        results.sort_by(|a, b| {
+            (
+                a.result_cache.assignment_block_height(),
+                a.result_cache.request_id(),
+            )
+                .cmp(&(
+                    b.result_cache.assignment_block_height(),
+                    b.result_cache.request_id(),
+                ))
+        });
+
+        Ok(page.paginate(results, |v| &v.result_cache))
+    }
 }
 
 #[async_trait]
@@ -836,10 +1095,240 @@ impl SignatureResultCacheUpdater<RandomnessResultCache>
     }
 }
 
+#[async_trait]
+impl SignatureResultCacheUpdater<DecryptionResultCache>
+    for InMemorySignatureResultCache<DecryptionResultCache>
+{
+    async fn add(
+        &mut self,
+        group_index: usize,
+        task: DecryptionTask,
+        message: Vec<u8>,
+        threshold: usize,
+    ) -> DataAccessResult<bool> {
+        if self.signature_result_caches.contains_key(&task.request_id) {
+            return Ok(false);
+        }
+
+        self.signature_result_caches.insert(
+            task.request_id.clone(),
+            BLSResultCache {
+                result_cache: DecryptionResultCache {
+                    group_index,
+                    decryption_task: task,
+                    message,
+                    threshold,
+                    decryption_shares: BTreeMap::new(),
+                    committed_times: 0,
+                },
+                state: BLSResultCacheState::NotCommitted,
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn add_partial_signature(
+        &mut self,
+        task_request_id: Vec<u8>,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> DataAccessResult<bool> {
+        let decryption_result_cache = self
+            .signature_result_caches
+            .get_mut(&task_request_id)
+            .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        if decryption_result_cache
+            .result_cache
+            .decryption_shares
+            .contains_key(&member_address)
+        {
+            return Ok(false);
+        }
+
+        decryption_result_cache
+            .result_cache
+            .decryption_shares
+            .insert(member_address, partial_signature);
+
+        Ok(true)
+    }
+
+    async fn get_ready_to_commit_signatures(
+        &mut self,
+        current_block_height: usize,
+    ) -> DataAccessResult<Vec<DecryptionResultCache>> {
+        let ready_to_commit_results = self
+            .signature_result_caches
+            .values_mut()
+            .filter(|v| {
+                ((current_block_height + 1)
+                    >= v.result_cache.decryption_task.assignment_block_height
+                        + v.result_cache.decryption_task.request_confirmations as usize)
+                    && v.state == BLSResultCacheState::NotCommitted
+                    && v.result_cache.decryption_shares.len() >= v.result_cache.threshold
+            })
+            .map(|v| {
+                v.state = BLSResultCacheState::Committing;
+                v.result_cache.clone()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ready_to_commit_results)
+    }
+
+    async fn update_commit_result(
+        &mut self,
+        task_request_id: &[u8],
+        status: BLSResultCacheState,
+    ) -> DataAccessResult<()> {
+        let decryption_result_cache = self
+            .signature_result_caches
+            .get_mut(task_request_id)
+            .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        decryption_result_cache.state = status;
+
+        Ok(())
+    }
+
+    async fn incr_committed_times(&mut self, task_request_id: &[u8]) -> DataAccessResult<()> {
+        let decryption_result_cache = self
+            .signature_result_caches
+            .get_mut(task_request_id)
+            .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        decryption_result_cache.result_cache.committed_times += 1;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SignatureResultCacheUpdater<BlindSignatureResultCache>
+    for InMemorySignatureResultCache<BlindSignatureResultCache>
+{
+    async fn add(
+        &mut self,
+        group_index: usize,
+        task: BlindSignatureTask,
+        message: Vec<u8>,
+        threshold: usize,
+    ) -> DataAccessResult<bool> {
+        if self.signature_result_caches.contains_key(&task.request_id) {
+            return Ok(false);
+        }
+
+        self.signature_result_caches.insert(
+            task.request_id.clone(),
+            BLSResultCache {
+                result_cache: BlindSignatureResultCache {
+                    group_index,
+                    blind_signature_task: task,
+                    message,
+                    threshold,
+                    partial_signatures: BTreeMap::new(),
+                    committed_times: 0,
+                },
+                state: BLSResultCacheState::NotCommitted,
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn add_partial_signature(
+        &mut self,
+        task_request_id: Vec<u8>,
+        member_address: Address,
+        partial_signature: Vec<u8>,
+    ) -> DataAccessResult<bool> {
+        let blind_signature_result_cache = self
+            .signature_result_caches
+            .get_mut(&task_request_id)
+            .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        if blind_signature_result_cache
+            .result_cache
+            .partial_signatures
+            .contains_key(&member_address)
+        {
+            return Ok(false);
+        }
+
+        blind_signature_result_cache
+            .result_cache
+            .partial_signatures
+            .insert(member_address, partial_signature);
+
+        Ok(true)
+    }
+
+    async fn get_ready_to_commit_signatures(
+        &mut self,
+        current_block_height: usize,
+    ) -> DataAccessResult<Vec<BlindSignatureResultCache>> {
+        let ready_to_commit_results = self
+            .signature_result_caches
+            .values_mut()
+            .filter(|v| {
+                ((current_block_height + 1)
+                    >= v.result_cache.blind_signature_task.assignment_block_height
+                        + v.result_cache.blind_signature_task.request_confirmations as usize)
+                    && v.state == BLSResultCacheState::NotCommitted
+                    && v.result_cache.partial_signatures.len() >= v.result_cache.threshold
+            })
+            .map(|v| {
+                v.state = BLSResultCacheState::Committing;
+                v.result_cache.clone()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ready_to_commit_results)
+    }
+
+    async fn update_commit_result(
+        &mut self,
+        task_request_id: &[u8],
+        status: BLSResultCacheState,
+    ) -> DataAccessResult<()> {
+        let blind_signature_result_cache = self
+            .signature_result_caches
+            .get_mut(task_request_id)
+            .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        blind_signature_result_cache.state = status;
+
+        Ok(())
+    }
+
+    async fn incr_committed_times(&mut self, task_request_id: &[u8]) -> DataAccessResult<()> {
+        let blind_signature_result_cache = self
+            .signature_result_caches
+            .get_mut(task_request_id)
+            .ok_or(BLSTaskError::CommitterCacheNotExisted)?;
+
+        blind_signature_result_cache.result_cache.committed_times += 1;
+
+        Ok(())
+    }
+}
+
 impl<PC: Curve + 'static> NodeInfoHandler<PC> for InMemoryNodeInfoCache<PC> {}
 impl<PC: Curve + 'static> GroupInfoHandler<PC> for InMemoryGroupInfoCache<PC> {}
 impl BLSTasksHandler<RandomnessTask> for InMemoryBLSTasksQueue<RandomnessTask> {}
+impl BLSTasksHandler<DecryptionTask> for InMemoryBLSTasksQueue<DecryptionTask> {}
+impl BLSTasksHandler<BlindSignatureTask> for InMemoryBLSTasksQueue<BlindSignatureTask> {}
 impl SignatureResultCacheHandler<RandomnessResultCache>
     for InMemorySignatureResultCache<RandomnessResultCache>
 {
 }
+impl SignatureResultCacheHandler<DecryptionResultCache>
+    for InMemorySignatureResultCache<DecryptionResultCache>
+{
+}
+impl SignatureResultCacheHandler<BlindSignatureResultCache>
+    for InMemorySignatureResultCache<BlindSignatureResultCache>
+{
+}
@@ -1,14 +1,16 @@
 pub mod cache;
 pub mod error;
 
-use arpa_core::{DKGStatus, DKGTask, Group, Member, Task};
+use arpa_core::{BLSTask, DKGStatus, DKGTask, Group, Member, Task};
 use async_trait::async_trait;
 use cache::BLSResultCache;
 pub use dkg_core::primitives::DKGOutput;
 use error::DataAccessResult;
 use ethers_core::types::Address;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::time::SystemTime;
 use threshold_bls::{group::Curve, sig::Share};
 
 pub trait BlockInfoHandler:
@@ -40,6 +42,9 @@ pub trait BlockInfoFetcher {
     fn get_block_height(&self) -> usize;
 
     fn get_block_time(&self) -> usize;
+
+    /// When the block height was last updated, or when the cache was created if it never was
+    fn get_block_updated_at(&self) -> SystemTime;
 }
 
 pub trait BlockInfoUpdater {
@@ -109,6 +114,26 @@ pub trait GroupInfoUpdater<C: Curve> {
         epoch: usize,
         members: BTreeMap<Address, Member<C>>,
     ) -> DataAccessResult<bool>;
+
+    async fn save_dkg_checkpoint(
+        &mut self,
+        index: usize,
+        epoch: usize,
+        checkpoint: DKGCheckpoint,
+    ) -> DataAccessResult<()>;
+}
+
+/// State of an ongoing DKG, saved whenever a phase is run, so that the DKG can be resumed after a restart.
+/// It is removed once the DKG output is saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DKGCheckpoint {
+    pub task: DKGTask,
+    /// Number of the phases which have been run
+    pub completed_phases: usize,
+    /// The serialized state machine to run the next phase with
+    pub state: Vec<u8>,
+    /// The serialized bundle of the last phase, which needs to be published if it is not on the board yet
+    pub bundle: Option<Vec<u8>>,
 }
 
 pub trait GroupInfoFetcher<C: Curve>: std::fmt::Debug {
@@ -140,6 +165,8 @@ pub trait GroupInfoFetcher<C: Curve>: std::fmt::Debug {
 
     fn get_dkg_status(&self) -> DataAccessResult<DKGStatus>;
 
+    fn get_dkg_checkpoint(&self) -> DataAccessResult<Option<&DKGCheckpoint>>;
+
     fn is_committer(&self, id_address: Address) -> DataAccessResult<bool>;
 }
 
@@ -150,6 +177,14 @@ pub trait BLSTasksFetcher<T: Task> {
     async fn get(&self, task_request_id: &[u8]) -> DataAccessResult<T>;
 
     async fn is_handled(&self, task_request_id: &[u8]) -> DataAccessResult<bool>;
+
+    /// Tasks in the order of their assignment block heights and then their request ids
+    async fn list(
+        &self,
+        is_handled: Option<bool>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSTask<T>>>;
 }
 
 #[async_trait]
@@ -169,6 +204,94 @@ pub trait SignatureResultCacheFetcher<T: ResultCache> {
     async fn contains(&self, task_request_id: &[u8]) -> DataAccessResult<bool>;
 
     async fn get(&self, task_request_id: &[u8]) -> DataAccessResult<BLSResultCache<T>>;
+
+    /// Number of results which are not committed yet, including the ones being committed
+    async fn get_unfulfilled_count(&self) -> DataAccessResult<usize>;
+
+    /// Results in the order of the assignment block heights and then the request ids of their tasks
+    async fn list(
+        &self,
+        state: Option<BLSResultCacheState>,
+        block_height_range: BlockHeightRange,
+        page: ListPage,
+    ) -> DataAccessResult<Vec<BLSResultCache<T>>>;
+}
+
+/// Range of the assignment block heights of tasks, both ends are inclusive
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeightRange {
+    pub from: Option<usize>,
+    pub to: Option<usize>,
+}
+
+impl BlockHeightRange {
+    pub fn contains(&self, block_height: usize) -> bool {
+        self.from.map_or(true, |from| block_height >= from)
+            && self.to.map_or(true, |to| block_height <= to)
+    }
+}
+
+/// A page of a list in the order of the assignment block heights and then the request ids
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListPage {
+    /// The maximum number of items to return, all of them if not set
+    pub limit: Option<usize>,
+    /// Starts right after this item, which is the last one of the previous page
+    pub after: Option<ListCursor>,
+}
+
+/// Position of an item in a list, by the assignment block height and the request id of its task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListCursor {
+    pub block_height: usize,
+    pub request_id: Vec<u8>,
+}
+
+impl ListCursor {
+    pub fn of(task: &dyn Task) -> Self {
+        ListCursor {
+            block_height: task.assignment_block_height(),
+            request_id: task.request_id().to_vec(),
+        }
+    }
+
+    /// Encodes the cursor as the block height in 8 big-endian bytes followed by the request id
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.block_height as u64).to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.request_id);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 8 {
+            return None;
+        }
+        let (block_height, request_id) = bytes.split_at(8);
+        Some(ListCursor {
+            block_height: u64::from_be_bytes(block_height.try_into().ok()?) as usize,
+            request_id: request_id.to_vec(),
+        })
+    }
+
+    pub fn is_before(&self, task: &dyn Task) -> bool {
+        (self.block_height, self.request_id.as_slice())
+            < (task.assignment_block_height(), task.request_id())
+    }
+}
+
+impl ListPage {
+    /// Takes the page out of the items which are already sorted by their positions
+    pub fn paginate<I>(&self, items: Vec<I>, task: impl Fn(&I) -> &dyn Task) -> Vec<I> {
+        items
+            .into_iter()
+            .filter(|item| {
+                self.after
+                    .as_ref()
+                    .map_or(true, |c| c.is_before(task(item)))
+            })
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -15,7 +15,7 @@ pub use board::BoardPublisher;
 pub mod node;
 pub use node::{DKGNodeError, DKGPhase, Phase2Result};
 use threshold_bls::group::Element;
-use threshold_bls::sig::Scheme;
+use threshold_bls::sig::{PossessionError, ProofOfPossession, Scheme, SignatureScheme};
 
 /// Low level primitives and datatypes for implementing DKGs
 pub mod primitives;
@@ -33,3 +33,124 @@ pub fn generate_keypair<S: Scheme>() -> (S::Private, S::Public) {
 
     (private, public)
 }
+
+/// Serializes the DKG public key of the private key to be registered, followed by the
+/// proof of possession of the private key.
+pub fn serialize_dkg_public_key<S: SignatureScheme>(
+    private: &S::Private,
+) -> Result<Vec<u8>, PossessionError<S>> {
+    let mut public = S::Public::one();
+    public.mul(private);
+
+    let proof = ProofOfPossession::<S>::prove(private)?;
+
+    Ok(bincode::serialize(&(public, proof))?)
+}
+
+/// Deserializes a registered DKG public key, which is only accepted along with a valid
+/// proof of possession of its private key.
+pub fn deserialize_dkg_public_key<S: SignatureScheme>(
+    bytes: &[u8],
+) -> Result<S::Public, PossessionError<S>> {
+    let (public, proof): (S::Public, ProofOfPossession<S>) = bincode::deserialize(bytes)?;
+
+    proof.verify(&public)?;
+
+    Ok(public)
+}
+
+/// Reads a DKG public key from the board. Until the NodeRegistry requires the proofs of
+/// possession, a bare key registered by an earlier version is accepted as well, so that the
+/// nodes registered before keep being grouped until they replace their keys. Every member
+/// reads the requirement as of the same block, so they agree on which keys are accepted.
+/// Returns the key along with whether it is such a legacy one.
+pub fn read_registered_dkg_public_key<S: SignatureScheme>(
+    bytes: &[u8],
+    is_proof_required: bool,
+) -> Result<(S::Public, bool), PossessionError<S>> {
+    match deserialize_dkg_public_key::<S>(bytes) {
+        Ok(public) => Ok((public, false)),
+        Err(e) if is_proof_required => Err(e),
+        Err(e) => {
+            // a key followed by an invalid proof is rejected, not taken as a legacy one
+            match bincode::deserialize::<S::Public>(bytes) {
+                Ok(public) if bincode::serialized_size(&public)? == bytes.len() as u64 => {
+                    Ok((public, true))
+                }
+                _ => Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        deserialize_dkg_public_key, generate_keypair, read_registered_dkg_public_key,
+        serialize_dkg_public_key,
+    };
+    use threshold_bls::{curve::bn254::PairingCurve, sig::G2Scheme};
+
+    type S = G2Scheme<PairingCurve>;
+
+    #[test]
+    fn registered_dkg_public_key_carries_proof_of_possession() {
+        let (private, public) = generate_keypair::<S>();
+
+        let bytes = serialize_dkg_public_key::<S>(&private).unwrap();
+
+        // the key itself comes first, as the node registry only reads the key
+        let key_bytes = bincode::serialize(&public).unwrap();
+        assert_eq!(key_bytes.len(), 128);
+        assert_eq!(&bytes[..key_bytes.len()], &key_bytes[..]);
+
+        assert_eq!(deserialize_dkg_public_key::<S>(&bytes).unwrap(), public);
+    }
+
+    #[test]
+    fn dkg_public_key_without_valid_proof_is_rejected() {
+        let (private, public) = generate_keypair::<S>();
+        let (other_private, _) = generate_keypair::<S>();
+
+        // a bare key
+        let key_bytes = bincode::serialize(&public).unwrap();
+        assert!(deserialize_dkg_public_key::<S>(&key_bytes).is_err());
+
+        // a key along with the proof of another key
+        let other_bytes = serialize_dkg_public_key::<S>(&other_private).unwrap();
+        let forged = [&key_bytes[..], &other_bytes[key_bytes.len()..]].concat();
+        assert!(deserialize_dkg_public_key::<S>(&forged).is_err());
+
+        let bytes = serialize_dkg_public_key::<S>(&private).unwrap();
+        assert!(deserialize_dkg_public_key::<S>(&bytes).is_ok());
+    }
+
+    #[test]
+    fn legacy_dkg_public_key_is_read_until_proofs_are_required() {
+        let (private, public) = generate_keypair::<S>();
+        let (other_private, _) = generate_keypair::<S>();
+
+        let bytes = serialize_dkg_public_key::<S>(&private).unwrap();
+        assert_eq!(
+            read_registered_dkg_public_key::<S>(&bytes, false).unwrap(),
+            (public, false)
+        );
+        assert_eq!(
+            read_registered_dkg_public_key::<S>(&bytes, true).unwrap(),
+            (public, false)
+        );
+
+        // the bare key registered by an earlier version
+        let key_bytes = bincode::serialize(&public).unwrap();
+        assert_eq!(
+            read_registered_dkg_public_key::<S>(&key_bytes, false).unwrap(),
+            (public, true)
+        );
+        assert!(read_registered_dkg_public_key::<S>(&key_bytes, true).is_err());
+
+        // but never a key along with an invalid proof
+        let other_bytes = serialize_dkg_public_key::<S>(&other_private).unwrap();
+        let forged = [&key_bytes[..], &other_bytes[key_bytes.len()..]].concat();
+        assert!(read_registered_dkg_public_key::<S>(&forged, false).is_err());
+    }
+}
@@ -56,7 +56,7 @@ where
     C: Curve,
     B: BoardPublisher<C> + Send,
     P: Phase0<C> + Send,
-    P::Next: Send,
+    P::Next: Phase1<C, Shares = BundledShares<C>> + Send,
     R: RngCore,
     F: Fn() -> R + Send,
 {
@@ -85,7 +85,7 @@ impl<C, B, P> DKGPhase<C, B, &[BundledShares<C>]> for P
 where
     C: Curve,
     B: BoardPublisher<C> + Send,
-    P: Phase1<C> + Send,
+    P: Phase1<C, Shares = BundledShares<C>> + Send,
     P::Next: Send,
 {
     type Next = P::Next;
@@ -0,0 +1,632 @@
+//! Public verification of the transcript of a DKG.
+//!
+//! Everything a member posts on the board is in plaintext except the shares, which are encrypted
+//! to their holders. The audit replays the steps of the protocol which need no secret key over the
+//! whole transcript: the shape of every bundle, the complaints carried by the responses, and the
+//! justifications with `share_correct`. This tells which dealers qualified and why, the same way
+//! as the members who committed the result did.
+use super::common::share_correct;
+use crate::primitives::{
+    status::{Status, StatusMatrix},
+    types::*,
+    DKGResult,
+};
+use crate::read_registered_dkg_public_key;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use threshold_bls::{
+    group::Curve,
+    poly::{Idx, PublicPoly},
+    sig::SignatureScheme,
+};
+
+/// The transcript of a DKG as read from the board. Every bundle is indexed by the participant
+/// who posted it, and is empty if the participant posted nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DKGTranscript {
+    /// The threshold of the DKG
+    pub threshold: usize,
+    /// The serialized DKG public keys, empty for the participants who didn't register one
+    pub dkg_keys: Vec<Vec<u8>>,
+    /// The serialized `BundledShares` posted in Phase 0
+    pub shares: Vec<Vec<u8>>,
+    /// The serialized `BundledResponses` posted in Phase 1
+    pub responses: Vec<Vec<u8>>,
+    /// The serialized `BundledJustification`s posted in Phase 2
+    pub justifications: Vec<Vec<u8>>,
+    /// Whether the NodeRegistry required the proofs of possession when the DKG was assigned,
+    /// before which the bare keys registered by an earlier version are accepted
+    #[serde(default)]
+    pub is_dkg_key_proof_required: bool,
+}
+
+/// Whether a bundle posted by a node follows the protocol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum BundleStatus {
+    /// Nothing was posted
+    Missing,
+    /// The bundle follows the protocol
+    Valid,
+    /// The bundle doesn't follow the protocol for the given reason
+    Malformed(String),
+}
+
+/// How the members ended the DKG with the transcript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DKGAuditOutcome {
+    /// At least a threshold of dealers qualified. The members commit the distributed public key
+    /// along with the disqualified nodes.
+    Succeeded,
+    /// Less than a threshold of dealers qualified. The members commit the disqualified nodes only.
+    Failed,
+    /// A bundle read in the given phase could not be deserialized, so the members gave up the
+    /// DKG in that phase without committing anything.
+    Aborted(usize),
+}
+
+/// The audit of a node of the DKG as a dealer, along with the bundles it posted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DealerReport {
+    /// The index of the participant on the board
+    pub participant_index: usize,
+    /// The index of the node in the DKG
+    pub node_index: Idx,
+    pub shares: BundleStatus,
+    pub responses: BundleStatus,
+    pub justifications: BundleStatus,
+    /// Share holders who complained about the dealer, and didn't get a correct justification
+    pub justified_complaints: Vec<Idx>,
+    /// Share holders who complained about the dealer, but got a correct justification
+    pub frivolous_complaints: Vec<Idx>,
+    /// Share holders whose share in the justifications of the dealer failed `share_correct`
+    pub failed_justifications: Vec<Idx>,
+    pub qualified: bool,
+}
+
+/// The result of auditing a DKG transcript
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DKGAuditReport {
+    pub threshold: usize,
+    pub outcome: DKGAuditOutcome,
+    pub dealers: Vec<DealerReport>,
+    pub qualified_node_indices: Vec<Idx>,
+    /// The node indices committed as disqualified by the members, empty if the DKG was aborted
+    pub disqualified_node_indices: Vec<Idx>,
+}
+
+/// Audits the transcript of a DKG without any secret key.
+///
+/// Bundles are taken into account in the order of the board, with the same checks the members
+/// run, so the qualified nodes are the ones every honest member ends up with. The only thing which
+/// can't be told from the board is whether an encrypted share is correct: this is up to the
+/// complaints of its holder, and a holder who had not enough valid shares leaves the DKG without
+/// posting its responses.
+pub fn audit_transcript<C, S>(transcript: &DKGTranscript) -> DKGResult<DKGAuditReport>
+where
+    C: Curve,
+    S: SignatureScheme<Public = C::Point>,
+{
+    let threshold = transcript.threshold;
+
+    // the nodes are the participants who registered a DKG key, indexed in order
+    let participants = transcript
+        .dkg_keys
+        .iter()
+        .enumerate()
+        .filter(|(_, key)| !key.is_empty())
+        .map(|(participant_index, _)| participant_index)
+        .collect::<Vec<_>>();
+
+    // the members who can't prove the possession of their keys are disqualified before
+    // any share is dealt
+    let invalid_key_indices = participants
+        .iter()
+        .enumerate()
+        .filter(|(_, participant_index)| {
+            read_registered_dkg_public_key::<S>(
+                &transcript.dkg_keys[**participant_index],
+                transcript.is_dkg_key_proof_required,
+            )
+            .is_err()
+        })
+        .map(|(node_index, _)| node_index as Idx)
+        .collect::<Vec<_>>();
+
+    let n = participants.len();
+
+    let node_index_of = |participant_index: usize| {
+        participants
+            .iter()
+            .position(|p| *p == participant_index)
+            .map(|i| i as Idx)
+    };
+
+    let contains_index = |idx: Idx| (idx as usize) < n;
+
+    let mut dealers = participants
+        .iter()
+        .enumerate()
+        .map(|(node_index, participant_index)| DealerReport {
+            participant_index: *participant_index,
+            node_index: node_index as Idx,
+            shares: BundleStatus::Missing,
+            responses: BundleStatus::Missing,
+            justifications: BundleStatus::Missing,
+            justified_complaints: vec![],
+            frivolous_complaints: vec![],
+            failed_justifications: vec![],
+            qualified: false,
+        })
+        .collect::<Vec<_>>();
+
+    if !invalid_key_indices.is_empty() {
+        return Ok(DKGAuditReport {
+            threshold,
+            outcome: DKGAuditOutcome::Failed,
+            dealers,
+            qualified_node_indices: vec![],
+            disqualified_node_indices: invalid_key_indices,
+        });
+    }
+
+    let mut aborted = None;
+
+    // Phase 0: the public polynomials of the dealers
+    let mut publics = HashMap::<Idx, PublicPoly<C>>::new();
+
+    for (participant_index, bytes) in posted(&transcript.shares) {
+        let poster = node_index_of(participant_index);
+
+        let status = match bincode::deserialize::<BundledShares<C>>(bytes) {
+            Ok(bundle) => {
+                if !contains_index(bundle.dealer_idx) {
+                    BundleStatus::Malformed(format!("invalid dealer index {}", bundle.dealer_idx))
+                } else if bundle.public.degree() != threshold.saturating_sub(1) {
+                    BundleStatus::Malformed(format!(
+                        "public polynomial of degree {}, expected {}",
+                        bundle.public.degree(),
+                        threshold.saturating_sub(1)
+                    ))
+                } else {
+                    publics.insert(bundle.dealer_idx, bundle.public);
+
+                    let holders = bundle
+                        .shares
+                        .iter()
+                        .map(|share| share.share_idx)
+                        .collect::<BTreeSet<_>>();
+
+                    let missing_holders = (0..n as Idx)
+                        .filter(|idx| !holders.contains(idx))
+                        .collect::<Vec<_>>();
+
+                    if Some(bundle.dealer_idx) != poster {
+                        BundleStatus::Malformed(format!("posted as dealer {}", bundle.dealer_idx))
+                    } else if !missing_holders.is_empty() {
+                        BundleStatus::Malformed(format!(
+                            "no share for holders {:?}",
+                            missing_holders
+                        ))
+                    } else if bundle.shares.len() != n {
+                        BundleStatus::Malformed("duplicated or unknown share holders".to_string())
+                    } else {
+                        BundleStatus::Valid
+                    }
+                }
+            }
+            Err(e) => {
+                aborted.get_or_insert(1);
+                BundleStatus::Malformed(format!("could not deserialize: {}", e))
+            }
+        };
+
+        if let Some(poster) = poster {
+            dealers[poster as usize].shares = status;
+        }
+    }
+
+    // Phase 1: the complaints of the share holders
+    let mut statuses = StatusMatrix::new(n, n, Status::Success);
+
+    for (participant_index, bytes) in posted(&transcript.responses) {
+        let poster = node_index_of(participant_index);
+
+        let status = match bincode::deserialize::<BundledResponses>(bytes) {
+            Ok(bundle) => {
+                if !contains_index(bundle.share_idx)
+                    || bundle
+                        .responses
+                        .iter()
+                        .any(|response| !contains_index(response.dealer_idx))
+                {
+                    BundleStatus::Malformed("invalid share holder or dealer index".to_string())
+                } else {
+                    for response in bundle.responses.iter() {
+                        statuses.set(response.dealer_idx, bundle.share_idx, response.status);
+                    }
+
+                    if Some(bundle.share_idx) != poster {
+                        BundleStatus::Malformed(format!(
+                            "posted as share holder {}",
+                            bundle.share_idx
+                        ))
+                    } else {
+                        BundleStatus::Valid
+                    }
+                }
+            }
+            Err(e) => {
+                aborted.get_or_insert(2);
+                BundleStatus::Malformed(format!("could not deserialize: {}", e))
+            }
+        };
+
+        if let Some(poster) = poster {
+            dealers[poster as usize].responses = status;
+        }
+    }
+
+    let complaints = (0..n as Idx)
+        .map(|dealer| {
+            (0..n as Idx)
+                .filter(|&holder| statuses.get(dealer, holder) == Status::Complaint)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Phase 2: the justifications of the dealers, only read if anyone complained
+    let justifications_required = complaints.iter().any(|holders| !holders.is_empty());
+
+    let mut failed_justifications = vec![BTreeSet::new(); n];
+
+    if justifications_required {
+        for (participant_index, bytes) in posted(&transcript.justifications) {
+            let poster = node_index_of(participant_index);
+
+            let status = match bincode::deserialize::<BundledJustification<C>>(bytes) {
+                Ok(bundle) => {
+                    if !contains_index(bundle.dealer_idx) {
+                        BundleStatus::Malformed(format!(
+                            "invalid dealer index {}",
+                            bundle.dealer_idx
+                        ))
+                    } else if let Some(public) = publics.get(&bundle.dealer_idx) {
+                        let mut incorrect_holders = vec![];
+
+                        for justification in bundle.justifications.iter() {
+                            if !share_correct::<C>(
+                                justification.share_idx,
+                                &justification.share,
+                                public,
+                            ) {
+                                failed_justifications[bundle.dealer_idx as usize]
+                                    .insert(justification.share_idx);
+                                incorrect_holders.push(justification.share_idx);
+                            } else if contains_index(justification.share_idx) {
+                                statuses.set(
+                                    bundle.dealer_idx,
+                                    justification.share_idx,
+                                    Status::Success,
+                                );
+                            } else {
+                                incorrect_holders.push(justification.share_idx);
+                            }
+                        }
+
+                        if Some(bundle.dealer_idx) != poster {
+                            BundleStatus::Malformed(format!(
+                                "posted as dealer {}",
+                                bundle.dealer_idx
+                            ))
+                        } else if !incorrect_holders.is_empty() {
+                            BundleStatus::Malformed(format!(
+                                "incorrect shares for holders {:?}",
+                                incorrect_holders
+                            ))
+                        } else {
+                            BundleStatus::Valid
+                        }
+                    } else {
+                        BundleStatus::Malformed(format!(
+                            "dealer {} has no valid public polynomial",
+                            bundle.dealer_idx
+                        ))
+                    }
+                }
+                Err(e) => {
+                    aborted.get_or_insert(3);
+                    BundleStatus::Malformed(format!("could not deserialize: {}", e))
+                }
+            };
+
+            if let Some(poster) = poster {
+                dealers[poster as usize].justifications = status;
+            }
+        }
+    }
+
+    // QUAL is the set of dealers whose row of the matrix is all set
+    let qualified_node_indices = (0..n as Idx)
+        .filter(|&dealer| statuses.all_true(dealer))
+        .collect::<Vec<_>>();
+
+    for (dealer, report) in dealers.iter_mut().enumerate() {
+        let (frivolous, justified): (Vec<Idx>, Vec<Idx>) = complaints[dealer]
+            .iter()
+            .copied()
+            .partition(|holder| statuses.get(dealer as Idx, *holder) == Status::Success);
+
+        report.justified_complaints = justified;
+        report.frivolous_complaints = frivolous;
+        report.failed_justifications = failed_justifications[dealer].iter().copied().collect();
+        report.qualified = qualified_node_indices.contains(&(dealer as Idx));
+    }
+
+    let outcome = match aborted {
+        Some(phase) => DKGAuditOutcome::Aborted(phase),
+        None if qualified_node_indices.len() >= threshold => DKGAuditOutcome::Succeeded,
+        None => DKGAuditOutcome::Failed,
+    };
+
+    let disqualified_node_indices = match outcome {
+        DKGAuditOutcome::Aborted(_) => vec![],
+        _ => (0..n as Idx)
+            .filter(|idx| !qualified_node_indices.contains(idx))
+            .collect(),
+    };
+
+    Ok(DKGAuditReport {
+        threshold,
+        outcome,
+        dealers,
+        qualified_node_indices,
+        disqualified_node_indices,
+    })
+}
+
+fn posted(bundles: &[Vec<u8>]) -> impl Iterator<Item = (usize, &Vec<u8>)> {
+    bundles
+        .iter()
+        .enumerate()
+        .filter(|(_, bundle)| !bundle.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{
+        common::tests::setup_group,
+        joint_feldman::DKG,
+        phases::{Phase0, Phase1, Phase2, Phase3},
+    };
+    use crate::{deserialize_dkg_public_key, serialize_dkg_public_key};
+    use threshold_bls::{
+        curve::bn254::{G1Curve, PairingCurve},
+        group::Element,
+        sig::G1Scheme,
+    };
+
+    /// Misbehaviours of the members while running the DKG
+    #[derive(Default)]
+    struct Faults {
+        /// Dealers who don't post their shares
+        absent_dealers: Vec<usize>,
+        /// A holder who complains about a dealer with a valid share
+        false_complaint: Option<(usize, usize)>,
+        /// A dealer who justifies with an incorrect share
+        tampered_justification: Option<usize>,
+    }
+
+    /// Runs the DKG and returns its transcript with the qualified nodes seen by the given member
+    fn run_dkg<C: Curve, S: SignatureScheme<Public = C::Point, Private = C::Scalar>>(
+        n: usize,
+        t: usize,
+        faults: &Faults,
+        observer: usize,
+    ) -> (DKGTranscript, Vec<Idx>) {
+        let (privs, group) = setup_group::<C>(n, t);
+
+        let mut transcript = DKGTranscript {
+            threshold: t,
+            dkg_keys: privs
+                .iter()
+                .map(|private| serialize_dkg_public_key::<S>(private).unwrap())
+                .collect(),
+            shares: vec![vec![]; n],
+            responses: vec![vec![]; n],
+            justifications: vec![vec![]; n],
+            is_dkg_key_proof_required: true,
+        };
+
+        let phase1s = privs
+            .into_iter()
+            .enumerate()
+            .map(|(i, private)| {
+                let mut phase0 = DKG::new(private, String::new(), group.clone()).unwrap();
+                phase0.set_rpc_endpoint();
+                let (phase1, shares) = phase0.encrypt_shares(rand::thread_rng).unwrap();
+                if !faults.absent_dealers.contains(&i) {
+                    transcript.shares[i] = bincode::serialize(&shares.unwrap()).unwrap();
+                }
+                phase1
+            })
+            .collect::<Vec<_>>();
+
+        let shares = parse(&transcript.shares);
+
+        let phase2s = phase1s
+            .into_iter()
+            .enumerate()
+            .map(|(i, phase1)| {
+                let (phase2, responses) = phase1.process_shares(&shares, true).unwrap();
+                let mut responses = responses.unwrap();
+                if let Some((holder, dealer)) = faults.false_complaint {
+                    if holder == i {
+                        responses.responses[dealer].status = Status::Complaint;
+                    }
+                }
+                transcript.responses[i] = bincode::serialize(&responses).unwrap();
+                phase2
+            })
+            .collect::<Vec<_>>();
+
+        let responses = parse(&transcript.responses);
+
+        let mut phase3s = vec![];
+        for (i, phase2) in phase2s.into_iter().enumerate() {
+            match phase2.process_responses(&responses) {
+                Ok(output) => {
+                    if i == observer {
+                        let qual = output.qual.nodes.iter().map(|node| node.id()).collect();
+                        return (transcript, qual);
+                    }
+                }
+                Err(next) => {
+                    let (phase3, justifications) = next.unwrap();
+                    if let Some(mut justifications) = justifications {
+                        if faults.tampered_justification == Some(i) {
+                            justifications.justifications[0]
+                                .share
+                                .add(&C::Scalar::one());
+                        }
+                        transcript.justifications[i] = bincode::serialize(&justifications).unwrap();
+                    }
+                    phase3s.push(phase3);
+                }
+            }
+        }
+
+        let justifications = parse(&transcript.justifications);
+
+        let output = phase3s
+            .remove(observer)
+            .process_justifications(&justifications)
+            .unwrap();
+
+        let qual = output.qual.nodes.iter().map(|node| node.id()).collect();
+
+        (transcript, qual)
+    }
+
+    fn parse<D: serde::de::DeserializeOwned>(bundles: &[Vec<u8>]) -> Vec<D> {
+        posted(bundles)
+            .map(|(_, bundle)| bincode::deserialize(bundle).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_audit_honest_transcript() {
+        let (transcript, qual) =
+            run_dkg::<G1Curve, G1Scheme<PairingCurve>>(5, 3, &Faults::default(), 0);
+
+        let report = audit_transcript::<G1Curve, G1Scheme<PairingCurve>>(&transcript).unwrap();
+
+        assert_eq!(DKGAuditOutcome::Succeeded, report.outcome);
+        assert_eq!(qual, report.qualified_node_indices);
+        assert!(report.disqualified_node_indices.is_empty());
+
+        for dealer in report.dealers.iter() {
+            assert_eq!(BundleStatus::Valid, dealer.shares);
+            assert_eq!(BundleStatus::Valid, dealer.responses);
+            assert_eq!(BundleStatus::Missing, dealer.justifications);
+            assert!(dealer.justified_complaints.is_empty());
+            assert!(dealer.qualified);
+        }
+    }
+
+    #[test]
+    fn test_audit_absent_dealer_and_false_complaint() {
+        let faults = Faults {
+            absent_dealers: vec![0],
+            false_complaint: Some((4, 1)),
+            ..Default::default()
+        };
+
+        let (transcript, qual) = run_dkg::<G1Curve, G1Scheme<PairingCurve>>(5, 3, &faults, 2);
+
+        let report = audit_transcript::<G1Curve, G1Scheme<PairingCurve>>(&transcript).unwrap();
+
+        assert_eq!(DKGAuditOutcome::Succeeded, report.outcome);
+        assert_eq!(qual, report.qualified_node_indices);
+        assert_eq!(vec![0], report.disqualified_node_indices);
+
+        assert_eq!(BundleStatus::Missing, report.dealers[0].shares);
+        assert_eq!(vec![1, 2, 3, 4], report.dealers[0].justified_complaints);
+        assert!(!report.dealers[0].qualified);
+
+        assert_eq!(BundleStatus::Valid, report.dealers[1].justifications);
+        assert_eq!(vec![4], report.dealers[1].frivolous_complaints);
+        assert!(report.dealers[1].justified_complaints.is_empty());
+        assert!(report.dealers[1].qualified);
+    }
+
+    #[test]
+    fn test_audit_failed_justification() {
+        let faults = Faults {
+            false_complaint: Some((4, 1)),
+            tampered_justification: Some(1),
+            ..Default::default()
+        };
+
+        let (transcript, qual) = run_dkg::<G1Curve, G1Scheme<PairingCurve>>(5, 3, &faults, 2);
+
+        let report = audit_transcript::<G1Curve, G1Scheme<PairingCurve>>(&transcript).unwrap();
+
+        assert_eq!(DKGAuditOutcome::Succeeded, report.outcome);
+        assert_eq!(qual, report.qualified_node_indices);
+        assert_eq!(vec![1], report.disqualified_node_indices);
+
+        assert!(matches!(
+            report.dealers[1].justifications,
+            BundleStatus::Malformed(_)
+        ));
+        assert_eq!(vec![4], report.dealers[1].failed_justifications);
+        assert_eq!(vec![4], report.dealers[1].justified_complaints);
+        assert!(!report.dealers[1].qualified);
+    }
+
+    #[test]
+    fn test_audit_undeserializable_bundle() {
+        let (mut transcript, _) =
+            run_dkg::<G1Curve, G1Scheme<PairingCurve>>(5, 3, &Faults::default(), 0);
+
+        transcript.shares[2] = vec![1, 2, 3];
+
+        let report = audit_transcript::<G1Curve, G1Scheme<PairingCurve>>(&transcript).unwrap();
+
+        assert_eq!(DKGAuditOutcome::Aborted(1), report.outcome);
+        assert!(matches!(
+            report.dealers[2].shares,
+            BundleStatus::Malformed(_)
+        ));
+        assert!(report.disqualified_node_indices.is_empty());
+    }
+
+    #[test]
+    fn test_audit_invalid_dkg_key() {
+        let (mut transcript, _) =
+            run_dkg::<G1Curve, G1Scheme<PairingCurve>>(5, 3, &Faults::default(), 0);
+
+        // a broken key, and a bare key without its proof of possession
+        transcript.dkg_keys[2].truncate(10);
+        let bare_key =
+            deserialize_dkg_public_key::<G1Scheme<PairingCurve>>(&transcript.dkg_keys[3]).unwrap();
+        transcript.dkg_keys[3] = bincode::serialize(&bare_key).unwrap();
+
+        let report = audit_transcript::<G1Curve, G1Scheme<PairingCurve>>(&transcript).unwrap();
+
+        assert_eq!(DKGAuditOutcome::Failed, report.outcome);
+        assert!(report.qualified_node_indices.is_empty());
+        assert_eq!(report.disqualified_node_indices, vec![2, 3]);
+
+        // the bare key is accepted as a legacy one before the proofs are required
+        transcript.is_dkg_key_proof_required = false;
+
+        let report = audit_transcript::<G1Curve, G1Scheme<PairingCurve>>(&transcript).unwrap();
+
+        assert_eq!(DKGAuditOutcome::Failed, report.outcome);
+        assert_eq!(report.disqualified_node_indices, vec![2]);
+    }
+}
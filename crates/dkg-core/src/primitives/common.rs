@@ -320,7 +320,7 @@ pub mod tests {
     use rand::thread_rng;
     use threshold_bls::poly::{Eval, Poly, PolyError};
 
-    fn reconstruct<C: Curve>(
+    pub fn reconstruct<C: Curve>(
         thr: usize,
         shares: &[DKGOutput<C>],
     ) -> Result<PrivatePoly<C>, PolyError> {
@@ -385,6 +385,7 @@ pub mod tests {
     where
         C: Curve,
         P: Phase0<C>,
+        P::Next: Phase1<C, Shares = BundledShares<C>>,
     {
         let n = dkgs.len();
         let mut all_shares = Vec::with_capacity(n);
@@ -75,4 +75,12 @@ pub enum ShareError {
     /// second is the degree it should be,i.e. `threshold - 1`.
     #[error("[dealer: {0}] polynomial does not have the correct degree, got: {1}, expected {2}")]
     InvalidPublicPolynomial(Idx, usize, usize),
+    /// InvalidShareIndex is raised when a PVSS dealing does not contain exactly
+    /// one encrypted share for every participant of the group.
+    #[error("[dealer: {0}] Encrypted share of participant {1} is missing or duplicated")]
+    InvalidShareIndex(Idx, Idx),
+    /// InvalidProof is raised when the proofs of a PVSS encrypted share do not
+    /// verify against the public polynomial of the dealer.
+    #[error("[dealer: {0}] Invalid proof of the encrypted share of participant {1}")]
+    InvalidProof(Idx, Idx),
 }
@@ -161,6 +161,8 @@ pub struct DKGWaitingShare<C: Curve> {
 
 impl<C: Curve> Phase1<C> for DKGWaitingShare<C> {
     type Next = DKGWaitingResponse<C>;
+    type Shares = BundledShares<C>;
+
     #[allow(unused_assignments)]
     /// Tries to decrypt the provided shares and calculate the secret key and the
     /// threshold public key. If `publish_all` is set to true then the returned
@@ -181,13 +183,22 @@ impl<C: Curve> Phase1<C> for DKGWaitingShare<C> {
         // publish_all = false;
         let thr = self.info.thr();
         let my_idx = self.info.index;
-        let (shares, publics, statuses) = process_shares_get_all(
+        let (shares, mut publics, statuses) = process_shares_get_all(
             &self.info.group,
             &self.info.group,
             my_idx,
             &self.info.private_key,
             bundles,
         )?;
+        // our own justifications are checked against the public polynomial we published, like
+        // everybody else does, otherwise we would leave ourselves out of QUAL after a complaint
+        if let Some(bundle) = bundles
+            .iter()
+            .rev()
+            .find(|b| b.dealer_idx == my_idx && b.public.degree() == thr - 1)
+        {
+            publics.insert(my_idx, bundle.public.clone());
+        }
 
         // we check with `thr - 1` because we already have our shares
         if shares.len() < thr - 1 {
@@ -428,7 +439,7 @@ where
 pub mod tests {
     use super::*;
     use crate::primitives::{
-        common::tests::{check2, full_dkg, id_out, id_resp, invalid2, invalid_shares, setup_group},
+        common::tests::{check2, full_dkg, id_resp, invalid2, invalid_shares, setup_group},
         default_threshold,
     };
     use static_assertions::assert_impl_all;
@@ -479,7 +490,14 @@ pub mod tests {
             invalid2,
             id_resp,
             check2,
-            id_out,
+            |outputs| {
+                // the dealers complained about are justified, so they are in QUAL for everyone,
+                // including themselves
+                for output in outputs.iter() {
+                    assert_eq!(output.qual.len(), n);
+                }
+                outputs
+            },
         )
         .unwrap();
     }
@@ -15,6 +15,11 @@ pub mod joint_feldman;
 
 pub mod resharing;
 
+pub mod pvss;
+
+/// Public verification of a DKG transcript, which needs no secret key
+pub mod audit;
+
 mod common;
 
 mod errors;
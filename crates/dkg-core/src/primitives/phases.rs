@@ -1,5 +1,5 @@
 use crate::primitives::{
-    types::{BundledJustification, BundledResponses, BundledShares, DKGOutput},
+    types::{BundledJustification, BundledResponses, DKGOutput},
     DKGError, DKGResult,
 };
 use rand::RngCore;
@@ -17,7 +17,11 @@ pub trait Phase0<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a>
 
     fn set_rpc_endpoint(&mut self);
 
-    fn encrypt_shares<R, F>(self, rng: F) -> DKGResult<(Self::Next, Option<BundledShares<C>>)>
+    #[allow(clippy::type_complexity)]
+    fn encrypt_shares<R, F>(
+        self,
+        rng: F,
+    ) -> DKGResult<(Self::Next, Option<<Self::Next as Phase1<C>>::Shares>)>
     where
         R: RngCore,
         F: Fn() -> R;
@@ -29,9 +33,12 @@ pub trait Phase0<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a>
 pub trait Phase1<C: Curve>: Clone + Debug + Serialize + for<'a> Deserialize<'a> {
     type Next: Phase2<C>;
 
+    /// The bundle each dealer publishes in the first phase, e.g. `BundledShares`
+    type Shares: Clone + Debug + Serialize + for<'a> Deserialize<'a>;
+
     fn process_shares(
         self,
-        bundles: &[BundledShares<C>],
+        bundles: &[Self::Shares],
         publish_all: bool,
     ) -> DKGResult<(Self::Next, Option<BundledResponses>)>;
 }
@@ -0,0 +1,1007 @@
+//! Implements a Distributed Key Generation protocol based on publicly verifiable
+//! secret sharing (PVSS), in the spirit of the non-interactive DKG from
+//! [Groth](https://eprint.iacr.org/2021/339).
+//!
+//! Every share is encrypted bit by bit with ElGamal to the public key of its
+//! holder, along with non-interactive zero-knowledge proofs that each ciphertext
+//! encrypts a bit and that the bits add up to the evaluation of the dealer's
+//! public polynomial. Anyone can check a dealing from the board, so the dealers
+//! with an invalid dealing are disqualified by every participant in the same
+//! way right after the shares are processed. There is no complaint and no
+//! justification, hence no share is ever revealed.
+//!
+//! The protocol runs in two phases: no response is published after processing
+//! the shares, and the output is returned as soon as the responses are processed.
+use crate::primitives::{
+    group::Group,
+    phases::{Phase0, Phase1, Phase2, Phase3},
+    types::*,
+    DKGError, DKGResult, ShareError,
+};
+
+use threshold_bls::{
+    ecies::{self, EciesCipher},
+    group::{Curve, Element, Scalar},
+    hash::hasher::{Hasher, Keccak256Hasher},
+    poly::{Idx, Poly, PrivatePoly, PublicPoly},
+    sig::Share,
+};
+
+use log::warn;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use rand_core::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Debug;
+
+const BIT_PROOF_DOMAIN: &[u8] = b"ARPA-DKG-PVSS-BIT";
+const SHARE_PROOF_DOMAIN: &[u8] = b"ARPA-DKG-PVSS-SHARE";
+
+/// Chaum-Pedersen proof that the discrete logarithms of two points, w.r.t. the
+/// generator and the public key of the share holder, are equal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct DLEQProof<C: Curve> {
+    pub challenge: C::Scalar,
+    pub response: C::Scalar,
+}
+
+/// Disjunctive Chaum-Pedersen proof that an `EncryptedBit` encrypts either 0 or 1.
+/// The challenges and responses are indexed by the value of the bit.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct BitProof<C: Curve> {
+    pub challenges: [C::Scalar; 2],
+    pub responses: [C::Scalar; 2],
+}
+
+/// EncryptedBit holds the ElGamal encryption `(r * G, r * pk + b * G)` of the
+/// bit `b` of a share to the public key `pk` of its holder.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct EncryptedBit<C: Curve> {
+    pub ephemeral: C::Point,
+    pub cipher: C::Point,
+    pub proof: BitProof<C>,
+}
+
+/// PVSSEncryptedShare holds the publicly verifiable encryption of a share
+/// destined to the `share_idx`-th participant.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PVSSEncryptedShare<C: Curve> {
+    /// The index of the participant this share belongs to
+    pub share_idx: Idx,
+    /// The encrypted bits of the share, least significant first
+    pub bits: Vec<EncryptedBit<C>>,
+    /// Proof that the bits add up to the evaluation of the public polynomial
+    /// at `share_idx`
+    pub proof: DLEQProof<C>,
+    /// The ECIES encrypted rpc endpoint of the dealer, which is not verifiable
+    pub rpc_endpoint_secret: EciesCipher<C>,
+}
+
+/// PVSSBundledShares holds the dealing a dealer publishes during the first phase
+/// of the protocol.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PVSSBundledShares<C: Curve> {
+    /// The dealer's index
+    pub dealer_idx: Idx,
+    /// The encrypted shares in the order of the group
+    pub shares: Vec<PVSSEncryptedShare<C>>,
+    /// The commitment of the secret polynomial created by the dealer
+    pub public: PublicPoly<C>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+struct PVSSInfo<C: Curve> {
+    private_key: C::Scalar,
+    public_key: C::Point,
+    index: Idx,
+    group: Group<C>,
+    secret: Poly<C::Scalar>,
+    public: Poly<C::Point>,
+    rpc_endpoint: String,
+}
+
+/// PVSSDKG is the first stage of the PVSS based DKG, which deals the encrypted
+/// shares of a fresh secret polynomial along with their proofs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PVSSDKG<C: Curve> {
+    info: PVSSInfo<C>,
+}
+
+impl<C: Curve> PVSSDKG<C> {
+    /// Creates a new PVSS DKG instance from the provided private key and group.
+    ///
+    /// The private key must be part of the group, otherwise this will return an error.
+    pub fn new(
+        private_key: C::Scalar,
+        rpc_endpoint: String,
+        group: Group<C>,
+    ) -> Result<PVSSDKG<C>, DKGError> {
+        use rand::prelude::*;
+        Self::new_rand(private_key, rpc_endpoint, group, &mut thread_rng())
+    }
+
+    /// Creates a new PVSS DKG instance from the provided private key, group and RNG.
+    ///
+    /// The private key must be part of the group, otherwise this will return an error.
+    pub fn new_rand<R: RngCore>(
+        private_key: C::Scalar,
+        rpc_endpoint: String,
+        group: Group<C>,
+        rng: &mut R,
+    ) -> Result<PVSSDKG<C>, DKGError> {
+        let mut public_key = C::Point::one();
+        public_key.mul(&private_key);
+
+        let index = group
+            .index(&public_key)
+            .ok_or(DKGError::PublicKeyNotFound)?;
+
+        let secret = PrivatePoly::<C>::new_from(group.threshold - 1, rng);
+        let public = secret.commit::<C::Point>();
+
+        let info = PVSSInfo {
+            private_key,
+            public_key,
+            index,
+            group,
+            secret,
+            public,
+            rpc_endpoint,
+        };
+
+        Ok(PVSSDKG { info })
+    }
+}
+
+impl<C: Curve> Phase0<C> for PVSSDKG<C> {
+    type Next = PVSSWaitingShare<C>;
+
+    fn set_rpc_endpoint(&mut self) {
+        let my_idx = self.info.index;
+
+        let my_node = self
+            .info
+            .group
+            .nodes
+            .iter_mut()
+            .find(|node| node.id() == my_idx)
+            .unwrap();
+
+        my_node.set_rpc_endpoint(self.info.rpc_endpoint.clone());
+    }
+
+    /// Evaluates the secret polynomial at the index of each DKG participant and
+    /// encrypts the result bit by bit with the corresponding public key, along
+    /// with the proofs which make the dealing publicly verifiable.
+    fn encrypt_shares<R, F>(
+        self,
+        rng: F,
+    ) -> DKGResult<(PVSSWaitingShare<C>, Option<PVSSBundledShares<C>>)>
+    where
+        R: RngCore,
+        F: Fn() -> R,
+    {
+        let bundle = create_pvss_bundle(
+            self.info.index,
+            &self.info.secret,
+            &self.info.public,
+            &self.info.rpc_endpoint,
+            &self.info.group,
+            &mut rng(),
+        )?;
+        let dw = PVSSWaitingShare { info: self.info };
+        Ok((dw, Some(bundle)))
+    }
+}
+
+/// DKG Stage which waits to receive the dealings of the first phase. Every
+/// dealing is verified publicly, and the dealers with an invalid one are
+/// disqualified without any complaint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PVSSWaitingShare<C: Curve> {
+    info: PVSSInfo<C>,
+}
+
+impl<C: Curve> Phase1<C> for PVSSWaitingShare<C> {
+    type Next = PVSSWaitingResponse<C>;
+    type Shares = PVSSBundledShares<C>;
+
+    /// Verifies the dealings and decrypts the shares destined to us. The set of
+    /// qualified dealers only depends on the board, so it is the same for every
+    /// participant as long as the board hands the same dealings to everyone. A board
+    /// which can't provide a published dealing must either fail or have every participant
+    /// leave it out.
+    /// No response is returned whatever `publish_all` is set to.
+    ///
+    /// A dealer is disqualified in the following cases:
+    /// - absentee dealing
+    /// - invalid dealer index
+    /// - invalid length of public polynomial
+    /// - missing or duplicated encrypted share for any participant
+    /// - invalid proof of any encrypted share
+    ///
+    /// Only the first dealing of a dealer is considered, so an invalid dealing can't be
+    /// replaced by a later one.
+    fn process_shares(
+        mut self,
+        bundles: &[PVSSBundledShares<C>],
+        _publish_all: bool,
+    ) -> DKGResult<(PVSSWaitingResponse<C>, Option<BundledResponses>)> {
+        let thr = self.info.group.threshold;
+        let my_idx = self.info.index;
+
+        let mut dealer_indices = Vec::new();
+        let mut qual_indices = Vec::new();
+        let mut dist_share = C::Scalar::zero();
+        let mut dist_pub = PublicPoly::<C>::zero();
+
+        for bundle in bundles {
+            // only the first dealing of a dealer counts, whether it is valid or not
+            if dealer_indices.contains(&bundle.dealer_idx) {
+                continue;
+            }
+            dealer_indices.push(bundle.dealer_idx);
+
+            if let Err(e) = verify_pvss_bundle(&self.info.group, bundle) {
+                warn!("{}", e);
+                continue;
+            }
+
+            let share = if bundle.dealer_idx == my_idx {
+                self.info.secret.eval(my_idx).value
+            } else {
+                decrypt_pvss_share(
+                    &self.info.private_key,
+                    my_idx,
+                    bundle.dealer_idx,
+                    &bundle.public,
+                    // the dealing is verified to have our share
+                    find_share(bundle, my_idx).unwrap(),
+                )?
+            };
+
+            // the rpc endpoint can't be verified, which is not a reason to disqualify
+            let rpc_endpoint = decrypt_rpc_endpoint(
+                &self.info.private_key,
+                &find_share(bundle, my_idx).unwrap().rpc_endpoint_secret,
+            )
+            .unwrap_or_default();
+
+            if let Some(node) = self
+                .info
+                .group
+                .nodes
+                .iter_mut()
+                .find(|node| node.id() == bundle.dealer_idx)
+            {
+                node.set_rpc_endpoint(rpc_endpoint);
+            }
+
+            dist_share.add(&share);
+            dist_pub.add(&bundle.public);
+            qual_indices.push(bundle.dealer_idx);
+        }
+
+        if qual_indices.len() < thr {
+            let disqualified_node_indices = self
+                .info
+                .group
+                .nodes
+                .iter()
+                .map(|node| node.id())
+                .filter(|idx| !qual_indices.contains(idx))
+                .collect::<Vec<_>>();
+
+            return Err(DKGError::NotEnoughValidShares(
+                qual_indices.len(),
+                thr,
+                disqualified_node_indices,
+            ));
+        }
+
+        // everybody else disqualified our dealing as well
+        if !qual_indices.contains(&my_idx) {
+            return Err(DKGError::Rejected);
+        }
+
+        let qual_nodes = self
+            .info
+            .group
+            .nodes
+            .iter()
+            .filter(|node| qual_indices.contains(&node.id()))
+            .cloned()
+            .collect();
+        let qual = Group::<C>::new(qual_nodes, thr)?;
+
+        let dkg = PVSSWaitingResponse {
+            index: my_idx,
+            qual,
+            dist_share,
+            dist_pub,
+        };
+
+        Ok((dkg, None))
+    }
+}
+
+/// DKG Stage which holds the output of the protocol, since the qualified dealers
+/// are already determined after the first phase.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "C::Scalar: DeserializeOwned")]
+pub struct PVSSWaitingResponse<C: Curve> {
+    index: Idx,
+    qual: Group<C>,
+    dist_share: C::Scalar,
+    dist_pub: PublicPoly<C>,
+}
+
+impl<C: Curve> PVSSWaitingResponse<C> {
+    fn output(self) -> DKGOutput<C> {
+        DKGOutput {
+            qual: self.qual,
+            public: self.dist_pub,
+            share: Share {
+                index: self.index,
+                private: self.dist_share,
+            },
+            disqualified_node_indices: vec![],
+        }
+    }
+}
+
+impl<C: Curve> Phase2<C> for PVSSWaitingResponse<C> {
+    type Next = PVSSWaitingResponse<C>;
+
+    /// Returns the output right away, the responses are ignored since nothing
+    /// can be complained about.
+    fn process_responses(
+        self,
+        _responses: &[BundledResponses],
+    ) -> Result<DKGOutput<C>, DKGResult<(Self::Next, Option<BundledJustification<C>>)>> {
+        Ok(self.output())
+    }
+}
+
+impl<C: Curve> Phase3<C> for PVSSWaitingResponse<C> {
+    /// Never reached since no justification is required, returns the same
+    /// output as the second phase.
+    fn process_justifications(
+        self,
+        _justifs: &[BundledJustification<C>],
+    ) -> Result<DKGOutput<C>, DKGError> {
+        Ok(self.output())
+    }
+}
+
+/// Creates the publicly verifiable encrypted shares with the given secret
+/// polynomial to the given group.
+pub fn create_pvss_bundle<C: Curve, R: RngCore>(
+    dealer_idx: Idx,
+    secret: &PrivatePoly<C>,
+    public: &PublicPoly<C>,
+    node_rpc_endpoint: &str,
+    group: &Group<C>,
+    rng: &mut R,
+) -> DKGResult<PVSSBundledShares<C>> {
+    let shares = group
+        .nodes
+        .iter()
+        .map(|n| {
+            let share = secret.eval(n.id()).value;
+
+            // encrypt every bit with a fresh randomness
+            let randomness = share
+                .to_bits_le()
+                .iter()
+                .map(|_| C::Scalar::rand(rng))
+                .collect::<Vec<_>>();
+            let bits = share
+                .to_bits_le()
+                .into_iter()
+                .zip(randomness.iter())
+                .enumerate()
+                .map(|(position, (bit, r))| {
+                    encrypt_bit(dealer_idx, n.id(), position, n.key(), bit, r, rng)
+                })
+                .collect::<Vec<_>>();
+
+            // the bits add up to the share, encrypted with the sum of the randomness
+            let (ephemeral, cipher) = aggregate_bits(&bits);
+            let proof = prove_dleq(
+                &[dealer_idx, n.id()],
+                n.key(),
+                &ephemeral,
+                &sub::<C>(&cipher, &public.eval(n.id()).value),
+                &weighted_sum::<C::Scalar>(&randomness),
+                rng,
+            );
+
+            let rpc_endpoint_buff = bincode::serialize(node_rpc_endpoint)?;
+            let rpc_endpoint_secret = ecies::encrypt::<C, _>(n.key(), &rpc_endpoint_buff, rng);
+
+            Ok(PVSSEncryptedShare {
+                share_idx: n.id(),
+                bits,
+                proof,
+                rpc_endpoint_secret,
+            })
+        })
+        .collect::<Result<Vec<_>, DKGError>>()?;
+
+    Ok(PVSSBundledShares {
+        dealer_idx,
+        shares,
+        public: public.clone(),
+    })
+}
+
+/// Verifies a dealing against the group with nothing but public information.
+pub fn verify_pvss_bundle<C: Curve>(
+    group: &Group<C>,
+    bundle: &PVSSBundledShares<C>,
+) -> Result<(), ShareError> {
+    let dealer_idx = bundle.dealer_idx;
+    if !group.contains_index(dealer_idx) {
+        return Err(ShareError::InvalidShareIndex(dealer_idx, dealer_idx));
+    }
+
+    if bundle.public.degree() != group.threshold - 1 {
+        return Err(ShareError::InvalidPublicPolynomial(
+            dealer_idx,
+            bundle.public.degree(),
+            group.threshold - 1,
+        ));
+    }
+
+    let bits_len = C::Scalar::zero().to_bits_le().len();
+
+    for node in group.nodes.iter() {
+        let share_idx = node.id();
+        let share = match bundle
+            .shares
+            .iter()
+            .filter(|s| s.share_idx == share_idx)
+            .count()
+        {
+            1 => find_share(bundle, share_idx).unwrap(),
+            _ => return Err(ShareError::InvalidShareIndex(dealer_idx, share_idx)),
+        };
+
+        if share.bits.len() != bits_len
+            || !share
+                .bits
+                .iter()
+                .enumerate()
+                .all(|(position, bit)| verify_bit(dealer_idx, share_idx, position, node.key(), bit))
+        {
+            return Err(ShareError::InvalidProof(dealer_idx, share_idx));
+        }
+
+        let (ephemeral, cipher) = aggregate_bits(&share.bits);
+        if !verify_dleq(
+            &[dealer_idx, share_idx],
+            node.key(),
+            &ephemeral,
+            &sub::<C>(&cipher, &bundle.public.eval(share_idx).value),
+            &share.proof,
+        ) {
+            return Err(ShareError::InvalidProof(dealer_idx, share_idx));
+        }
+    }
+
+    if bundle.shares.len() != group.len() {
+        return Err(ShareError::InvalidShareIndex(
+            dealer_idx,
+            group.len() as Idx,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Decrypts the bits of a verified encrypted share and recomposes the share.
+pub fn decrypt_pvss_share<C: Curve>(
+    private_key: &C::Scalar,
+    own_idx: Idx,
+    dealer_idx: Idx,
+    public: &PublicPoly<C>,
+    share: &PVSSEncryptedShare<C>,
+) -> Result<C::Scalar, ShareError> {
+    let mut minus_private_key = private_key.clone();
+    minus_private_key.negate();
+
+    let bits = share
+        .bits
+        .iter()
+        .map(|bit| {
+            // b * G = (r * pk + b * G) - sk * (r * G)
+            let mut plain = bit.ephemeral.clone();
+            plain.mul(&minus_private_key);
+            plain.add(&bit.cipher);
+            if plain == C::Point::zero() {
+                Ok(C::Scalar::zero())
+            } else if plain == C::Point::one() {
+                Ok(C::Scalar::one())
+            } else {
+                Err(ShareError::InvalidShare(dealer_idx))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let clear_share = weighted_sum::<C::Scalar>(&bits);
+    if !super::common::share_correct::<C>(own_idx, &clear_share, public) {
+        return Err(ShareError::InvalidShare(dealer_idx));
+    }
+
+    Ok(clear_share)
+}
+
+fn decrypt_rpc_endpoint<C: Curve>(
+    private_key: &C::Scalar,
+    rpc_endpoint_secret: &EciesCipher<C>,
+) -> DKGResult<String> {
+    let rpc_endpoint_buff = ecies::decrypt::<C>(private_key, rpc_endpoint_secret)
+        .map_err(|err| DKGError::ShareError(ShareError::InvalidCiphertext(0, err)))?;
+
+    Ok(bincode::deserialize(&rpc_endpoint_buff)?)
+}
+
+fn find_share<C: Curve>(
+    bundle: &PVSSBundledShares<C>,
+    share_idx: Idx,
+) -> Option<&PVSSEncryptedShare<C>> {
+    bundle.shares.iter().find(|s| s.share_idx == share_idx)
+}
+
+/// Encrypts the bit and proves that either `(R, E)` or `(R, E - G)` is a
+/// Diffie-Hellman tuple w.r.t. `pk`, simulating the proof of the false branch.
+fn encrypt_bit<C: Curve, R: RngCore>(
+    dealer_idx: Idx,
+    share_idx: Idx,
+    position: usize,
+    key: &C::Point,
+    bit: bool,
+    r: &C::Scalar,
+    rng: &mut R,
+) -> EncryptedBit<C> {
+    let ephemeral = mul::<C>(&C::Point::one(), r);
+    let mut cipher = mul::<C>(key, r);
+    if bit {
+        cipher.add(&C::Point::one());
+    }
+
+    let (real, simulated) = if bit { (1, 0) } else { (0, 1) };
+
+    let mut challenges = [C::Scalar::zero(), C::Scalar::zero()];
+    let mut responses = [C::Scalar::zero(), C::Scalar::zero()];
+    let mut commitments = [
+        (C::Point::zero(), C::Point::zero()),
+        (C::Point::zero(), C::Point::zero()),
+    ];
+
+    challenges[simulated] = C::Scalar::rand(rng);
+    responses[simulated] = C::Scalar::rand(rng);
+    commitments[simulated] = bit_commitments::<C>(
+        key,
+        &ephemeral,
+        &cipher,
+        simulated,
+        &challenges[simulated],
+        &responses[simulated],
+    );
+
+    let w = C::Scalar::rand(rng);
+    commitments[real] = (mul::<C>(&C::Point::one(), &w), mul::<C>(key, &w));
+
+    let mut challenge = bit_challenge::<C>(
+        dealer_idx,
+        share_idx,
+        position,
+        key,
+        &ephemeral,
+        &cipher,
+        &commitments,
+    );
+    challenge.sub(&challenges[simulated]);
+    challenges[real] = challenge;
+
+    let mut response = challenges[real].clone();
+    response.mul(r);
+    response.add(&w);
+    responses[real] = response;
+
+    EncryptedBit {
+        ephemeral,
+        cipher,
+        proof: BitProof {
+            challenges,
+            responses,
+        },
+    }
+}
+
+fn verify_bit<C: Curve>(
+    dealer_idx: Idx,
+    share_idx: Idx,
+    position: usize,
+    key: &C::Point,
+    bit: &EncryptedBit<C>,
+) -> bool {
+    let proof = &bit.proof;
+    let commitments = [0, 1].map(|value| {
+        bit_commitments::<C>(
+            key,
+            &bit.ephemeral,
+            &bit.cipher,
+            value,
+            &proof.challenges[value],
+            &proof.responses[value],
+        )
+    });
+
+    let mut challenge = proof.challenges[0].clone();
+    challenge.add(&proof.challenges[1]);
+
+    challenge
+        == bit_challenge::<C>(
+            dealer_idx,
+            share_idx,
+            position,
+            key,
+            &bit.ephemeral,
+            &bit.cipher,
+            &commitments,
+        )
+}
+
+/// Recomputes the commitments `(z * G - c * R, z * pk - c * (E - value * G))`
+/// of the branch where the bit equals `value`.
+fn bit_commitments<C: Curve>(
+    key: &C::Point,
+    ephemeral: &C::Point,
+    cipher: &C::Point,
+    value: usize,
+    challenge: &C::Scalar,
+    response: &C::Scalar,
+) -> (C::Point, C::Point) {
+    let mut target_commitment = mul_sub::<C>(key, response, cipher, challenge);
+    if value == 1 {
+        target_commitment.add(&mul::<C>(&C::Point::one(), challenge));
+    }
+
+    (
+        mul_sub::<C>(&C::Point::one(), response, ephemeral, challenge),
+        target_commitment,
+    )
+}
+
+fn bit_challenge<C: Curve>(
+    dealer_idx: Idx,
+    share_idx: Idx,
+    position: usize,
+    key: &C::Point,
+    ephemeral: &C::Point,
+    cipher: &C::Point,
+    commitments: &[(C::Point, C::Point); 2],
+) -> C::Scalar {
+    hash_to_scalar::<C>(
+        BIT_PROOF_DOMAIN,
+        &[dealer_idx, share_idx, position as Idx],
+        &[
+            key,
+            ephemeral,
+            cipher,
+            &commitments[0].0,
+            &commitments[0].1,
+            &commitments[1].0,
+            &commitments[1].1,
+        ],
+    )
+}
+
+/// Proves the knowledge of `x` such that `a = x * G` and `b = x * key`.
+fn prove_dleq<C: Curve, R: RngCore>(
+    indices: &[Idx],
+    key: &C::Point,
+    a: &C::Point,
+    b: &C::Point,
+    x: &C::Scalar,
+    rng: &mut R,
+) -> DLEQProof<C> {
+    let w = C::Scalar::rand(rng);
+    let t1 = mul::<C>(&C::Point::one(), &w);
+    let t2 = mul::<C>(key, &w);
+
+    let challenge = hash_to_scalar::<C>(SHARE_PROOF_DOMAIN, indices, &[key, a, b, &t1, &t2]);
+
+    let mut response = challenge.clone();
+    response.mul(x);
+    response.add(&w);
+
+    DLEQProof {
+        challenge,
+        response,
+    }
+}
+
+fn verify_dleq<C: Curve>(
+    indices: &[Idx],
+    key: &C::Point,
+    a: &C::Point,
+    b: &C::Point,
+    proof: &DLEQProof<C>,
+) -> bool {
+    let t1 = mul_sub::<C>(&C::Point::one(), &proof.response, a, &proof.challenge);
+    let t2 = mul_sub::<C>(key, &proof.response, b, &proof.challenge);
+
+    proof.challenge == hash_to_scalar::<C>(SHARE_PROOF_DOMAIN, indices, &[key, a, b, &t1, &t2])
+}
+
+/// Derives the Fiat-Shamir challenge of a proof from its transcript
+fn hash_to_scalar<C: Curve>(domain: &[u8], indices: &[Idx], points: &[&C::Point]) -> C::Scalar {
+    let mut message = domain.to_vec();
+    for idx in indices {
+        message.extend_from_slice(&idx.to_be_bytes());
+    }
+    for point in points {
+        message.extend(bincode::serialize(point).expect("a point is always serializable"));
+    }
+
+    let digest = Keccak256Hasher.hash(&[], &message).unwrap();
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&digest);
+
+    C::Scalar::rand(&mut ChaCha20Rng::from_seed(seed))
+}
+
+/// Returns `(sum(2^i * R_i), sum(2^i * E_i))` of the encrypted bits
+fn aggregate_bits<C: Curve>(bits: &[EncryptedBit<C>]) -> (C::Point, C::Point) {
+    bits.iter().rev().fold(
+        (C::Point::zero(), C::Point::zero()),
+        |(mut e, mut c), bit| {
+            e.add(&e.clone());
+            e.add(&bit.ephemeral);
+            c.add(&c.clone());
+            c.add(&bit.cipher);
+            (e, c)
+        },
+    )
+}
+
+/// Returns `sum(2^i * values[i])`
+fn weighted_sum<S: Scalar<RHS = S>>(values: &[S]) -> S {
+    values.iter().rev().fold(S::zero(), |mut acc, value| {
+        acc.add(&acc.clone());
+        acc.add(value);
+        acc
+    })
+}
+
+fn mul<C: Curve>(point: &C::Point, scalar: &C::Scalar) -> C::Point {
+    let mut point = point.clone();
+    point.mul(scalar);
+    point
+}
+
+/// Returns `x * a - y * b`, negating the scalar rather than the point
+fn mul_sub<C: Curve>(a: &C::Point, x: &C::Scalar, b: &C::Point, y: &C::Scalar) -> C::Point {
+    let mut minus_y = y.clone();
+    minus_y.negate();
+
+    let mut res = mul::<C>(b, &minus_y);
+    res.add(&mul::<C>(a, x));
+    res
+}
+
+fn sub<C: Curve>(a: &C::Point, b: &C::Point) -> C::Point {
+    let mut minus_one = C::Scalar::one();
+    minus_one.negate();
+
+    let mut res = b.clone();
+    res.mul(&minus_one);
+    res.add(a);
+    res
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::primitives::{
+        common::tests::{full_dkg, reconstruct, setup_group},
+        default_threshold,
+    };
+    use rand::prelude::*;
+    use static_assertions::assert_impl_all;
+    use threshold_bls::curve::bn254::{G1Curve as BCurve, G1};
+
+    // Every share is encrypted and proved bit by bit, so the groups are kept
+    // small to run the tests without optimizations in a reasonable time.
+
+    assert_impl_all!(PVSSDKG<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(PVSSWaitingShare<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(PVSSWaitingResponse<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+    assert_impl_all!(PVSSBundledShares<BCurve>: Serialize, DeserializeOwned, Clone, Debug);
+
+    fn setup_dkg<C: Curve>(n: usize, thr: usize) -> Vec<PVSSDKG<C>> {
+        let (privs, group) = setup_group::<C>(n, thr);
+        privs
+            .into_iter()
+            .map(|p| PVSSDKG::new(p, String::from("127.0.0.1:50061"), group.clone()).unwrap())
+            .collect::<Vec<_>>()
+    }
+
+    fn deal<C: Curve>(
+        dkgs: Vec<PVSSDKG<C>>,
+    ) -> (Vec<PVSSWaitingShare<C>>, Vec<PVSSBundledShares<C>>) {
+        dkgs.into_iter()
+            .map(|dkg| {
+                let (ndkg, bundle) = dkg.encrypt_shares(thread_rng).unwrap();
+                (ndkg, bundle.unwrap())
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn bit_proofs() {
+        let rng = &mut thread_rng();
+        let private_key = <BCurve as Curve>::Scalar::rand(rng);
+        let mut key = G1::one();
+        key.mul(&private_key);
+
+        for bit in [false, true] {
+            let r = <BCurve as Curve>::Scalar::rand(rng);
+            let encrypted = encrypt_bit::<BCurve, _>(0, 1, 2, &key, bit, &r, rng);
+            assert!(verify_bit(0, 1, 2, &key, &encrypted));
+
+            // the proof is bound to the position of the bit
+            assert!(!verify_bit(0, 1, 3, &key, &encrypted));
+            assert!(!verify_bit(0, 2, 2, &key, &encrypted));
+
+            // the proof doesn't hold for the other bit
+            let mut flipped = encrypted.clone();
+            if bit {
+                flipped.cipher = sub::<BCurve>(&flipped.cipher, &G1::one());
+            } else {
+                flipped.cipher.add(&G1::one());
+            }
+            assert!(!verify_bit(0, 1, 2, &key, &flipped));
+        }
+
+        // a value other than 0 or 1 can't be proved
+        let r = <BCurve as Curve>::Scalar::rand(rng);
+        let mut encrypted = encrypt_bit::<BCurve, _>(0, 1, 2, &key, true, &r, rng);
+        encrypted.cipher.add(&G1::one());
+        assert!(!verify_bit(0, 1, 2, &key, &encrypted));
+    }
+
+    #[test]
+    fn test_full_dkg() {
+        let n = 2;
+        let thr = default_threshold(n);
+        let (outputs, _) = full_dkg(thr, setup_dkg::<BCurve>(n, thr));
+        for out in outputs.iter() {
+            assert_eq!(out.qual.len(), n);
+            assert!(out.disqualified_node_indices.is_empty());
+            assert!(out
+                .qual
+                .nodes
+                .iter()
+                .all(|node| node.get_rpc_endpoint() == Some(&String::from("127.0.0.1:50061"))));
+        }
+    }
+
+    #[test]
+    fn invalid_dealing_is_disqualified() {
+        let (n, thr) = (2, 1);
+        let (mut dkgs, mut bundles) = deal(setup_dkg::<BCurve>(n, thr));
+
+        // the bit stays a valid ciphertext but does not match its proof anymore
+        bundles[1].shares[0].bits[0].cipher.add(&G1::one());
+
+        // the bad dealer learns that it has been rejected
+        let bad = dkgs.pop().unwrap();
+        assert!(matches!(
+            bad.process_shares(&bundles, false),
+            Err(DKGError::Rejected)
+        ));
+
+        let (dkg, responses) = dkgs.pop().unwrap().process_shares(&bundles, false).unwrap();
+        assert!(responses.is_none());
+        let output = dkg.process_responses(&[]).unwrap();
+
+        assert_eq!(output.qual.len(), n - 1);
+        assert!(!output.qual.contains_index(1));
+
+        let recovered_public = reconstruct(thr, std::slice::from_ref(&output))
+            .unwrap()
+            .commit::<G1>();
+        assert_eq!(output.public.public_key(), recovered_public.public_key());
+    }
+
+    #[test]
+    fn only_first_dealing_counts() {
+        let (n, thr) = (2, 1);
+        let (mut dkgs, mut bundles) = deal(setup_dkg::<BCurve>(n, thr));
+
+        // the valid dealing published after the invalid one is ignored
+        let valid = bundles[1].clone();
+        bundles[1].shares[0].bits[0].cipher.add(&G1::one());
+        bundles.push(valid);
+
+        dkgs.pop();
+        let (dkg, _) = dkgs.pop().unwrap().process_shares(&bundles, false).unwrap();
+        let output = dkg.process_responses(&[]).unwrap();
+
+        assert_eq!(output.qual.len(), n - 1);
+        assert!(!output.qual.contains_index(1));
+    }
+
+    #[test]
+    fn not_enough_valid_dealings() {
+        let n = 2;
+        let thr = default_threshold(n);
+        let (dkgs, bundles) = deal(setup_dkg::<BCurve>(n, thr));
+
+        let res = dkgs
+            .into_iter()
+            .next()
+            .unwrap()
+            .process_shares(&bundles[..1], false);
+
+        match res {
+            Err(DKGError::NotEnoughValidShares(got, required, disqualified)) => {
+                assert_eq!(got, 1);
+                assert_eq!(required, thr);
+                assert_eq!(disqualified, vec![1]);
+            }
+            _ => panic!("should not have enough valid dealings"),
+        }
+    }
+
+    #[test]
+    fn verify_pvss_bundle_rejects_invalid_dealings() {
+        let (_, group) = setup_group::<BCurve>(2, 1);
+        let secret = PrivatePoly::<BCurve>::new(0);
+        let public = secret.commit();
+        let bundle =
+            create_pvss_bundle(1, &secret, &public, "", &group, &mut thread_rng()).unwrap();
+
+        assert!(verify_pvss_bundle(&group, &bundle).is_ok());
+
+        let mut missing_share = bundle.clone();
+        missing_share.shares.pop();
+        assert!(matches!(
+            verify_pvss_bundle(&group, &missing_share),
+            Err(ShareError::InvalidShareIndex(1, 1))
+        ));
+
+        let mut wrong_public = bundle.clone();
+        wrong_public.public = PrivatePoly::<BCurve>::new(0).commit();
+        assert!(matches!(
+            verify_pvss_bundle(&group, &wrong_public),
+            Err(ShareError::InvalidProof(1, 0))
+        ));
+
+        let mut swapped_bits = bundle.clone();
+        swapped_bits.shares[0].bits.swap(0, 1);
+        assert!(matches!(
+            verify_pvss_bundle(&group, &swapped_bits),
+            Err(ShareError::InvalidProof(1, 0))
+        ));
+
+        let mut wrong_degree = bundle;
+        wrong_degree.public = PrivatePoly::<BCurve>::new(1).commit();
+        assert!(matches!(
+            verify_pvss_bundle(&group, &wrong_degree),
+            Err(ShareError::InvalidPublicPolynomial(1, 1, 0))
+        ));
+    }
+}
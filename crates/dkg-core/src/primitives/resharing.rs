@@ -179,6 +179,8 @@ pub struct RDKGWaitingShare<C: Curve> {
 
 impl<C: Curve> Phase1<C> for RDKGWaitingShare<C> {
     type Next = RDKGWaitingResponse<C>;
+    type Shares = BundledShares<C>;
+
     #[allow(unused_assignments)]
     fn process_shares(
         self,
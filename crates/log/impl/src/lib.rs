@@ -17,6 +17,8 @@ struct FunctionLogVisitor {
     show_input: bool,
     ignore_input_args: Vec<String>,
     show_return: bool,
+    /// enter a tracing span named after the function during its execution
+    span: bool,
     /// support async function by `#[async_trait]`
     async_trait: bool,
     /// we don't want add log before returning in sub block or sub closure/async block
@@ -60,6 +62,7 @@ pub fn log_function(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     let mut show_input = false;
     let mut show_return = false;
+    let mut span = false;
     let mut ignore_input_args = vec![];
 
     for arg in args {
@@ -70,6 +73,9 @@ pub fn log_function(attr: TokenStream, input: TokenStream) -> TokenStream {
             NestedMeta::Lit(Lit::Str(x)) if x.token().to_string() == "\"show-return\"" => {
                 show_return = true;
             }
+            NestedMeta::Lit(Lit::Str(x)) if x.token().to_string() == "\"span\"" => {
+                span = true;
+            }
             NestedMeta::Lit(Lit::Str(x)) if x.token().to_string().starts_with("\"except") => {
                 ignore_input_args = x
                     .token()
@@ -86,11 +92,21 @@ pub fn log_function(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     }
 
+    // the guard of an entered span can't be held across await points,
+    // so we only support async functions whose body is boxed by `#[async_trait]`
+    if span && fn_sig.asyncness.is_some() && !fn_async_trait {
+        return macro_error!(
+            "span option is only supported on sync functions or async functions with #[async_trait]",
+            fn_sig.span()
+        );
+    }
+
     let mut visitor = FunctionLogVisitor {
         name: fn_ident.clone(),
         show_input,
         ignore_input_args,
         show_return,
+        span,
         async_trait: fn_async_trait,
         current_block_count: 0,
         current_closure_or_async_block_count: 0,
@@ -116,9 +132,19 @@ pub fn log_function(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
+    let span_code = if span && !fn_async_trait {
+        quote! {
+            let __span = tracing::info_span!(stringify!(#fn_ident));
+            let __span_guard = __span.enter();
+        }
+    } else {
+        TokenStream2::new()
+    };
+
     quote! {
         #fn_sig {
             log_mdc::insert("fn_name", stringify!(#fn_ident));
+            #span_code
             #args_text
             #stmts
             #post_code
@@ -203,6 +229,22 @@ impl FunctionLogVisitor {
         )
     }
 
+    /// Instruments the future boxed by `#[async_trait]` with a span named after the function.
+    fn instrument_boxed_future(&self, stmt: Stmt) -> Stmt {
+        let fn_ident = &self.name;
+        match stmt {
+            Stmt::Expr(Expr::Call(mut c)) if c.args.len() == 1 => {
+                let fut = c.args.pop().unwrap().into_value();
+                c.args.push(parse_quote!(tracing::Instrument::instrument(
+                    #fut,
+                    tracing::info_span!(stringify!(#fn_ident))
+                )));
+                Stmt::Expr(Expr::Call(c))
+            }
+            _ => stmt,
+        }
+    }
+
     fn insert_log_and_fold_expr_stmt(&mut self, e: Expr) -> Stmt {
         if !self.async_trait && self.current_block_count == 0 {
             self.has_return_stmt = true;
@@ -305,7 +347,12 @@ impl Fold for FunctionLogVisitor {
                         let last = p.path.segments.last();
                         match (self.async_trait, first, last) {
                             (true, Some(f), Some(l)) if f.ident == "Box" && l.ident == "pin" => {
-                                fold::fold_stmt(self, Stmt::Expr(Expr::Call(c)))
+                                let stmt = fold::fold_stmt(self, Stmt::Expr(Expr::Call(c)));
+                                if self.span {
+                                    self.instrument_boxed_future(stmt)
+                                } else {
+                                    stmt
+                                }
                             }
                             _ => self.insert_log_and_fold_expr_stmt(Expr::Call(c)),
                         }
@@ -25,10 +25,15 @@
 //!     with test logger.
 //!
 //! Note: Logging result can be different with different logger implementation.
+//!
+//! 4. With the "span" option, a tracing span named after the function is entered during its execution,
+//! so that it can be exported along with the spans of its callers. This works on sync functions
+//! and async functions with `#[async_trait]`.
 
 pub use arpa_log_impl::*;
 pub use log::debug;
 pub use log_mdc;
+pub use tracing;
 
 #[derive(Debug)]
 pub struct LogModel<'a> {
@@ -3,7 +3,7 @@ mod logger;
 #[cfg(test)]
 mod tests {
     use crate::logger::{self, SimpleLogger, SL};
-    use arpa_log::LogModel;
+    use arpa_log::{tracing, LogModel};
     use arpa_log_impl::log_function;
     use async_trait::async_trait;
     use log::debug;
@@ -85,6 +85,29 @@ mod tests {
         }
     }
 
+    #[log_function("span")]
+    fn enter_span() -> Option<String> {
+        tracing::Span::current()
+            .metadata()
+            .map(|m| m.name().to_string())
+    }
+
+    #[async_trait]
+    trait AsyncSpanTest {
+        async fn test_async_span(&self) -> Option<String>;
+    }
+
+    #[async_trait]
+    impl AsyncSpanTest for Dummy {
+        #[log_function("span")]
+        async fn test_async_span(&self) -> Option<String> {
+            tokio::task::yield_now().await;
+            tracing::Span::current()
+                .metadata()
+                .map(|m| m.name().to_string())
+        }
+    }
+
     static START: Once = Once::new();
     // Sure to run this once
     fn setup_tests() {
@@ -191,6 +214,27 @@ mod tests {
         assert_eq!(expected, logger().last_message().unwrap());
     }
 
+    #[test]
+    fn test_enter_span() {
+        setup_tests();
+        tracing::subscriber::with_default(tracing_subscriber::registry(), || {
+            assert_eq!(Some("enter_span".to_string()), enter_span());
+        });
+        let expected = build_expected_log("enter_span", &[], "\"ignored\"");
+        assert_eq!(expected, logger().last_message().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_span_by_async_trait() {
+        setup_tests();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let dummy = Dummy {};
+        assert_eq!(
+            Some("test_async_span".to_string()),
+            dummy.test_async_span().await
+        );
+    }
+
     fn build_expected_log(fn_name: &str, fn_args: &[&str], fn_return: &str) -> String {
         let log = LogModel {
             fn_name,
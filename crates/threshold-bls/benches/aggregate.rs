@@ -0,0 +1,132 @@
+//! Compares the recovery of the group signature from the partial signatures of groups of
+//! 3 to 100 members, with a point multiplication per share, with a multi-scalar
+//! multiplication, and with the Lagrange coefficients taken from a `LagrangeCache`.
+//!
+//! Run it with `cargo bench -p threshold-bls --bench aggregate`.
+use std::time::{Duration, Instant};
+use threshold_bls::{
+    group::{Element, Scalar},
+    poly::{Eval, Idx, LagrangeCache, Poly},
+    schemes::bn254::G2Scheme as S,
+    sig::{Partial, Scheme, Share, ThresholdScheme},
+};
+
+type Private = <S as Scheme>::Private;
+type Signature = <S as Scheme>::Signature;
+
+const GROUP_SIZES: [usize; 5] = [3, 10, 25, 50, 100];
+
+const ITERATIONS: u32 = 20;
+
+fn main() {
+    println!(
+        "{:>5} {:>5} {:>14} {:>14} {:>14} {:>14}",
+        "n", "t", "deserialize", "naive", "recover", "with cache"
+    );
+
+    for n in GROUP_SIZES {
+        let threshold = n / 2 + 1;
+        let partials = partials(n, threshold);
+        let evals = deserialize(&partials);
+
+        let expected = naive_recover(threshold, &evals);
+        assert_eq!(
+            bincode::serialize(&expected).unwrap(),
+            S::aggregate_with_cache(threshold, &partials, &mut LagrangeCache::default()).unwrap()
+        );
+
+        let deserialization = measure(|| deserialize(&partials));
+        let naive = measure(|| naive_recover(threshold, &evals));
+        let recover = measure(|| Poly::<Signature>::recover(threshold, evals.clone()).unwrap());
+
+        // the cache is warmed up by the first iteration, as it is by the first
+        // task of a group in the node
+        let mut cache = LagrangeCache::default();
+        let with_cache = measure(|| {
+            Poly::<Signature>::recover_with_cache(threshold, evals.clone(), &mut cache).unwrap()
+        });
+
+        println!(
+            "{:>5} {:>5} {:>14?} {:>14?} {:>14?} {:>14?}",
+            n, threshold, deserialization, naive, recover, with_cache
+        );
+    }
+}
+
+fn partials(n: usize, threshold: usize) -> Vec<Partial> {
+    let private = Poly::<Private>::new(threshold - 1);
+    let msg = b"randomness";
+
+    (0..n as Idx)
+        .map(|i| {
+            let eval = private.eval(i);
+            let share = Share {
+                index: eval.index,
+                private: eval.value,
+            };
+            S::partial_sign(&share, msg).unwrap()
+        })
+        .collect()
+}
+
+/// Deserializes the partials as `ThresholdScheme::aggregate` does before recovering
+fn deserialize(partials: &[Partial]) -> Vec<Eval<Signature>> {
+    partials
+        .iter()
+        .map(|partial| {
+            let eval: Eval<Vec<u8>> = bincode::deserialize(partial).unwrap();
+            Eval {
+                index: eval.index,
+                value: bincode::deserialize(&eval.value).unwrap(),
+            }
+        })
+        .collect()
+}
+
+/// Recovers the signature with a point multiplication per share, as `Poly::recover`
+/// did before using multi-scalar multiplication.
+fn naive_recover(threshold: usize, evals: &[Eval<Signature>]) -> Signature {
+    let xs = evals[..threshold]
+        .iter()
+        .map(|eval| {
+            let mut x = Private::new();
+            x.set_int((eval.index + 1).into());
+            x
+        })
+        .collect::<Vec<_>>();
+
+    let mut acc = Signature::new();
+    for (i, xi) in xs.iter().enumerate() {
+        let mut num = Private::one();
+        let mut den = Private::one();
+
+        for (j, xj) in xs.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+
+            num.mul(xj);
+
+            let mut tmp = *xj;
+            tmp.sub(xi);
+            den.mul(&tmp);
+        }
+
+        num.mul(&den.inverse().unwrap());
+
+        let mut yi = evals[i].value;
+        yi.mul(&num);
+        acc.add(&yi);
+    }
+
+    acc
+}
+
+/// Returns the mean duration of the closure over `ITERATIONS` runs
+fn measure<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
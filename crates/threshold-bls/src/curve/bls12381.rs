@@ -3,8 +3,8 @@ use crate::hash::hasher::Keccak256Hasher;
 use crate::hash::try_and_increment::TryAndIncrement;
 use crate::hash::HashToCurve;
 use ark_bls12_381 as bls12_381;
-use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
-use ark_ff::PrimeField;
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{BigInteger, PrimeField};
 use ark_ff::{Field, One, UniformRand, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand_core::RngCore;
@@ -44,7 +44,7 @@ type ZG1 = <bls12_381::Bls12_381 as PairingEngine>::G1Projective;
 pub struct G1(
     #[serde(deserialize_with = "deserialize_group")]
     #[serde(serialize_with = "serialize_group")]
-    ZG1,
+    pub(crate) ZG1,
 );
 
 type ZG2 = <bls12_381::Bls12_381 as PairingEngine>::G2Projective;
@@ -53,7 +53,7 @@ type ZG2 = <bls12_381::Bls12_381 as PairingEngine>::G2Projective;
 pub struct G2(
     #[serde(deserialize_with = "deserialize_group")]
     #[serde(serialize_with = "serialize_group")]
-    ZG2,
+    pub(crate) ZG2,
 );
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -103,6 +103,10 @@ impl Sc for Scalar {
     fn sub(&mut self, other: &Self) {
         self.0.sub_assign(other.0);
     }
+
+    fn to_bits_le(&self) -> Vec<bool> {
+        self.0.into_repr().to_bits_le()
+    }
 }
 
 impl fmt::Display for Scalar {
@@ -134,16 +138,24 @@ impl Element for G1 {
     fn mul(&mut self, mul: &Scalar) {
         self.0.mul_assign(mul.0);
     }
+
+    fn multi_scalar_mul(elements: &[Self], scalars: &[Scalar]) -> Self {
+        let bases =
+            ZG1::batch_normalization_into_affine(&elements.iter().map(|e| e.0).collect::<Vec<_>>());
+        let scalars = scalars.iter().map(|s| s.0.into_repr()).collect::<Vec<_>>();
+
+        Self(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
 }
 
 /// Implementation of Point using G1 from BLS12_381
 impl Point for G1 {
     type Error = BLS12Error;
 
-    fn map(&mut self, data: &[u8]) -> Result<(), BLS12Error> {
+    fn map_with_domain(&mut self, domain: &[u8], data: &[u8]) -> Result<(), BLS12Error> {
         let hasher = TryAndIncrement::new(&Keccak256Hasher);
 
-        let hash = hasher.hash(&[], data)?;
+        let hash = hasher.hash(domain, data)?;
 
         *self = Self(hash);
 
@@ -180,16 +192,24 @@ impl Element for G2 {
     fn mul(&mut self, mul: &Scalar) {
         self.0.mul_assign(mul.0)
     }
+
+    fn multi_scalar_mul(elements: &[Self], scalars: &[Scalar]) -> Self {
+        let bases =
+            ZG2::batch_normalization_into_affine(&elements.iter().map(|e| e.0).collect::<Vec<_>>());
+        let scalars = scalars.iter().map(|s| s.0.into_repr()).collect::<Vec<_>>();
+
+        Self(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
 }
 
 /// Implementation of Point using G2 from BLS12_381
 impl Point for G2 {
     type Error = BLS12Error;
 
-    fn map(&mut self, data: &[u8]) -> Result<(), BLS12Error> {
+    fn map_with_domain(&mut self, domain: &[u8], data: &[u8]) -> Result<(), BLS12Error> {
         let hasher = TryAndIncrement::new(&Keccak256Hasher);
 
-        let hash = hasher.hash(&[], data)?;
+        let hash = hasher.hash(domain, data)?;
 
         *self = Self(hash);
 
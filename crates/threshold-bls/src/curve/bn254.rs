@@ -4,8 +4,8 @@ use crate::hash::try_and_increment::TryAndIncrement;
 use crate::hash::HashToCurve;
 use crate::serialize::ContractSerialize;
 use ark_bn254 as bn254;
-use ark_ec::{PairingEngine, ProjectiveCurve};
-use ark_ff::PrimeField;
+use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
+use ark_ff::{BigInteger, PrimeField};
 use ark_ff::{Field, One, UniformRand, Zero};
 use rand_core::RngCore;
 use serde::{
@@ -197,6 +197,10 @@ impl Sc for Scalar {
     fn sub(&mut self, other: &Self) {
         self.0.sub_assign(other.0);
     }
+
+    fn to_bits_le(&self) -> Vec<bool> {
+        self.0.into_repr().to_bits_le()
+    }
 }
 
 impl fmt::Display for Scalar {
@@ -228,16 +232,24 @@ impl Element for G1 {
     fn mul(&mut self, mul: &Scalar) {
         self.0.mul_assign(mul.0);
     }
+
+    fn multi_scalar_mul(elements: &[Self], scalars: &[Scalar]) -> Self {
+        let bases =
+            ZG1::batch_normalization_into_affine(&elements.iter().map(|e| e.0).collect::<Vec<_>>());
+        let scalars = scalars.iter().map(|s| s.0.into_repr()).collect::<Vec<_>>();
+
+        Self(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
 }
 
 /// Implementation of Point using G1 from BN254
 impl Point for G1 {
     type Error = BNError;
 
-    fn map(&mut self, data: &[u8]) -> Result<(), BNError> {
+    fn map_with_domain(&mut self, domain: &[u8], data: &[u8]) -> Result<(), BNError> {
         let hasher = TryAndIncrement::new(&Keccak256Hasher);
 
-        let hash = hasher.hash(&[], data)?;
+        let hash = hasher.hash(domain, data)?;
 
         *self = Self(hash);
 
@@ -274,16 +286,24 @@ impl Element for G2 {
     fn mul(&mut self, mul: &Scalar) {
         self.0.mul_assign(mul.0)
     }
+
+    fn multi_scalar_mul(elements: &[Self], scalars: &[Scalar]) -> Self {
+        let bases =
+            ZG2::batch_normalization_into_affine(&elements.iter().map(|e| e.0).collect::<Vec<_>>());
+        let scalars = scalars.iter().map(|s| s.0.into_repr()).collect::<Vec<_>>();
+
+        Self(VariableBaseMSM::multi_scalar_mul(&bases, &scalars))
+    }
 }
 
 /// Implementation of Point using G2 from BN254
 impl Point for G2 {
     type Error = BNError;
 
-    fn map(&mut self, data: &[u8]) -> Result<(), BNError> {
+    fn map_with_domain(&mut self, domain: &[u8], data: &[u8]) -> Result<(), BNError> {
         let hasher = TryAndIncrement::new(&Keccak256Hasher);
 
-        let hash = hasher.hash(&[], data)?;
+        let hash = hasher.hash(domain, data)?;
 
         *self = Self(hash);
 
@@ -483,4 +503,32 @@ mod tests {
 
         assert_eq!(exp, res);
     }
+
+    #[test]
+    fn scalar_to_bits_le() {
+        let mut sc = Scalar::new();
+        sc.set_int(6);
+        let bits = sc.to_bits_le();
+        assert_eq!(bits.len(), 256);
+        assert_eq!(&bits[..4], &[false, true, true, false]);
+        assert!(bits[4..].iter().all(|bit| !bit));
+
+        // the bits recompose to the scalar
+        let rng = &mut rand::thread_rng();
+        let sc = Scalar::rand(rng);
+        let mut two = Scalar::new();
+        two.set_int(2);
+        let recomposed = sc
+            .to_bits_le()
+            .iter()
+            .rev()
+            .fold(Scalar::new(), |mut acc, bit| {
+                acc.mul(&two);
+                if *bit {
+                    acc.add(&Scalar::one());
+                }
+                acc
+            });
+        assert_eq!(recomposed, sc);
+    }
 }
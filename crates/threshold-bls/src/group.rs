@@ -34,6 +34,20 @@ pub trait Element:
     fn zero() -> Self {
         Self::new()
     }
+
+    /// Returns the sum of the elements each multiplied by the scalar at the same position.
+    /// Points override it with a variable-base multi-scalar multiplication.
+    fn multi_scalar_mul(elements: &[Self], scalars: &[Self::RHS]) -> Self {
+        elements
+            .iter()
+            .zip(scalars)
+            .fold(Self::zero(), |mut acc, (element, scalar)| {
+                let mut product = element.clone();
+                product.mul(scalar);
+                acc.add(&product);
+                acc
+            })
+    }
 }
 
 /// Scalar can be multiplied by only a Scalar, no other elements.
@@ -42,6 +56,8 @@ pub trait Scalar: Element {
     fn inverse(&self) -> Option<Self>;
     fn negate(&mut self);
     fn sub(&mut self, other: &Self);
+    /// Returns the bits of the integer representation, least significant first
+    fn to_bits_le(&self) -> Vec<bool>;
     // TODO
 }
 
@@ -51,7 +67,14 @@ pub trait Point: Element {
     type Error: Debug;
 
     /// Maps the provided data to a group element
-    fn map(&mut self, data: &[u8]) -> Result<(), <Self as Point>::Error>;
+    fn map(&mut self, data: &[u8]) -> Result<(), <Self as Point>::Error> {
+        self.map_with_domain(&[], data)
+    }
+
+    /// Maps the provided data to a group element under the domain separation tag, an empty
+    /// tag maps the same as `map`
+    fn map_with_domain(&mut self, domain: &[u8], data: &[u8])
+        -> Result<(), <Self as Point>::Error>;
 }
 
 /// A group holds functionalities to create scalar and points related; it is
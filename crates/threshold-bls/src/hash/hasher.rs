@@ -12,8 +12,16 @@ pub struct Keccak256Hasher;
 impl Hasher for Keccak256Hasher {
     type Error = BLSError;
 
-    fn hash(&self, _domain: &[u8], message: &[u8]) -> Result<Vec<u8>, Self::Error> {
-        Ok(ethers_core::utils::keccak256(message).into())
+    /// Hashes the message without a domain as the contracts do. Under a domain, the digest
+    /// is masked with the digest of the domain, so that it is not the digest of any known
+    /// message, and neither is any point hashed from it.
+    fn hash(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        let mut digest = ethers_core::utils::keccak256(message);
+        if !domain.is_empty() {
+            let mask = ethers_core::utils::keccak256(domain);
+            digest.iter_mut().zip(mask).for_each(|(d, m)| *d ^= m);
+        }
+        Ok(digest.into())
     }
 }
 
@@ -29,8 +37,12 @@ pub mod tests {
         let message = b"hello";
         let res = hasher.hash(&[], message).unwrap();
         assert_eq!(
-            hex::encode(res),
+            hex::encode(&res),
             "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"
         );
+
+        let res_with_domain = hasher.hash(b"domain", message).unwrap();
+        assert_ne!(res_with_domain, res);
+        assert_ne!(res_with_domain, hasher.hash(&[], b"domainhello").unwrap());
     }
 }
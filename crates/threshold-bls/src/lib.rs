@@ -173,6 +173,9 @@ pub mod curve;
 /// Elliptic Curve Integrated Encryption Scheme using SHA256 as the Key Derivation
 pub mod ecies;
 
+/// Threshold encryption to the public key of a group, decrypted by `t` holders of its shares
+pub mod tenc;
+
 /// Definitions of generic traits with scalars of prime fields and points on elliptic curves.
 pub mod group;
 
@@ -1,7 +1,10 @@
 use crate::group::{Curve, Element, Point, Scalar};
 use rand_core::RngCore;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
 use thiserror::Error;
 
 pub type PrivatePoly<C> = Poly<<C as Curve>::Scalar>;
@@ -9,6 +12,9 @@ pub type PublicPoly<C> = Poly<<C as Curve>::Point>;
 
 pub type Idx = u32;
 
+/// The number of index sets a `LagrangeCache` keeps by default
+pub const DEFAULT_LAGRANGE_CACHE_CAPACITY: usize = 128;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Eval<A> {
     pub value: A,
@@ -131,72 +137,59 @@ where
     /// Given at least `t` polynomial evaluations, it will recover the polynomial's
     /// constant term
     pub fn recover(t: usize, shares: Vec<Eval<C>>) -> Result<C, PolyError> {
-        let xs = Self::share_map(t, shares)?;
-
-        // iterate over all indices and for each multiply the lagrange basis
-        // with the value of the share
-        let mut acc = C::zero();
-        for (i, xi) in &xs {
-            let mut yi = xi.1.clone();
-            let mut num = C::RHS::one();
-            let mut den = C::RHS::one();
+        let (indices, values) = Self::share_vecs(t, shares)?;
 
-            for (j, xj) in &xs {
-                if i == j {
-                    continue;
-                }
+        let coefficients = lagrange_coefficients::<C::RHS>(&indices)?;
 
-                // xj - 0
-                num.mul(&xj.0);
+        Ok(C::multi_scalar_mul(&values, &coefficients))
+    }
 
-                // 1 / (xj - xi)
-                let mut tmp = xj.0.clone();
-                tmp.sub(&xi.0);
-                den.mul(&tmp);
-            }
+    /// Recovers the polynomial's constant term as `recover` does, taking the Lagrange
+    /// coefficients from the cache if the same indices were recovered from before
+    pub fn recover_with_cache(
+        t: usize,
+        shares: Vec<Eval<C>>,
+        cache: &mut LagrangeCache<C::RHS>,
+    ) -> Result<C, PolyError> {
+        let (indices, values) = Self::share_vecs(t, shares)?;
 
-            let inv = den.inverse().ok_or(PolyError::NoInverse)?;
-            num.mul(&inv);
-            yi.mul(&num);
-            acc.add(&yi);
-        }
+        let coefficients = cache.coefficients(&indices)?;
 
-        Ok(acc)
+        Ok(C::multi_scalar_mul(&values, coefficients))
     }
 
     /// Given at least `t` polynomial evaluations, it will recover the entire polynomial
     pub fn full_recover(t: usize, shares: Vec<Eval<C>>) -> Result<Self, PolyError> {
         let xs = Self::share_map(t, shares)?;
 
-        // iterate over all indices and for each multiply the lagrange basis
-        // with the value of the share
-        let res = xs
-            .iter()
-            // get the share and the lagrange basis
-            .map(|(i, share)| (share, Poly::<C::RHS>::lagrange_basis(*i, &xs)))
-            // get the linear combination poly
-            .map(|(share, basis)| {
-                // calculate the linear combination coefficients
-                let linear_coeffs = basis
-                    .0
-                    .into_iter()
-                    .map(move |c| {
-                        // y_j * L_y
-                        // TODO: Can we avoid allocating here?
-                        let mut s = share.1.clone();
-                        s.mul(&c);
-                        s
-                    })
-                    .collect::<Vec<_>>();
+        if xs.is_empty() {
+            return Ok(Self::zero());
+        }
 
-                Self::from(linear_coeffs)
+        // the lagrange basis of each share
+        let bases = xs
+            .keys()
+            .map(|i| Poly::<C::RHS>::lagrange_basis(*i, &xs))
+            .collect::<Vec<_>>();
+
+        let values = xs
+            .values()
+            .map(|(_, value)| value.clone())
+            .collect::<Vec<_>>();
+
+        // each coefficient is the linear combination of the shares with the
+        // coefficients of the same degree of their lagrange bases
+        let coeffs = (0..t)
+            .map(|k| {
+                let scalars = bases
+                    .iter()
+                    .map(|basis| basis.0[k].clone())
+                    .collect::<Vec<_>>();
+                C::multi_scalar_mul(&values, &scalars)
             })
-            .fold(Self::zero(), |mut acc, poly| {
-                acc.add(&poly);
-                acc
-            });
+            .collect::<Vec<_>>();
 
-        Ok(res)
+        Ok(Self::from(coeffs))
     }
 
     fn share_map(
@@ -227,6 +220,15 @@ where
         Ok(xs)
     }
 
+    /// Returns the indices of the `t` shares to recover from in ascending order,
+    /// along with their values
+    fn share_vecs(t: usize, shares: Vec<Eval<C>>) -> Result<(Vec<Idx>, Vec<C>), PolyError> {
+        Ok(Self::share_map(t, shares)?
+            .into_iter()
+            .map(|(i, (_, value))| (i, value))
+            .unzip())
+    }
+
     /// Returns the constant term of the polynomial which can be interpreted as
     /// the threshold public key
     pub fn public_key(&self) -> &C {
@@ -234,6 +236,103 @@ where
     }
 }
 
+/// Returns the Lagrange coefficients at zero of the shares of the given indices, which are
+/// evaluated at `index + 1` as in `Poly::eval`.
+fn lagrange_coefficients<X: Scalar<RHS = X>>(indices: &[Idx]) -> Result<Vec<X>, PolyError> {
+    let xs = indices
+        .iter()
+        .map(|i| {
+            let mut x = X::new();
+            x.set_int((i + 1).into());
+            x
+        })
+        .collect::<Vec<_>>();
+
+    xs.iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            let mut num = X::one();
+            let mut den = X::one();
+
+            for (j, xj) in xs.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                // xj - 0
+                num.mul(xj);
+
+                // 1 / (xj - xi)
+                let mut tmp = xj.clone();
+                tmp.sub(xi);
+                den.mul(&tmp);
+            }
+
+            let inv = den.inverse().ok_or(PolyError::NoInverse)?;
+            num.mul(&inv);
+            Ok(num)
+        })
+        .collect()
+}
+
+/// Caches the Lagrange coefficients at zero by the set of indices recovered from.
+///
+/// The committee of a group is mostly the same from one aggregation to the next, so
+/// reusing the coefficients saves the O(t²) scalar work of each recovery. The least
+/// recently inserted set is evicted once the capacity is reached.
+#[derive(Debug, Clone)]
+pub struct LagrangeCache<X> {
+    capacity: usize,
+    coefficients: HashMap<Vec<Idx>, Vec<X>>,
+    insertion_order: Vec<Vec<Idx>>,
+}
+
+impl<X> Default for LagrangeCache<X> {
+    fn default() -> Self {
+        Self::new(DEFAULT_LAGRANGE_CACHE_CAPACITY)
+    }
+}
+
+impl<X> LagrangeCache<X> {
+    /// Returns an empty cache keeping at most `capacity` index sets
+    pub fn new(capacity: usize) -> Self {
+        LagrangeCache {
+            capacity: capacity.max(1),
+            coefficients: HashMap::new(),
+            insertion_order: vec![],
+        }
+    }
+
+    /// Returns the number of cached index sets
+    pub fn len(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+}
+
+impl<X: Scalar<RHS = X>> LagrangeCache<X> {
+    /// Returns the Lagrange coefficients at zero of the given indices in ascending order,
+    /// computing and caching them if the set of indices is new
+    pub fn coefficients(&mut self, indices: &[Idx]) -> Result<&[X], PolyError> {
+        if !self.coefficients.contains_key(indices) {
+            let coefficients = lagrange_coefficients(indices)?;
+
+            if self.coefficients.len() >= self.capacity {
+                let evicted = self.insertion_order.remove(0);
+                self.coefficients.remove(&evicted);
+            }
+
+            self.insertion_order.push(indices.to_vec());
+            self.coefficients.insert(indices.to_vec(), coefficients);
+        }
+
+        Ok(&self.coefficients[indices])
+    }
+}
+
 impl<C: Element> From<Vec<C>> for Poly<C> {
     fn from(c: Vec<C>) -> Self {
         Self(c)
@@ -507,6 +606,71 @@ pub mod tests {
         Poly::<Sc>::full_recover(threshold, shares).unwrap_err();
     }
 
+    #[test]
+    fn recover_with_cache() {
+        let degree = 4;
+        let threshold = degree + 1;
+        let poly = Poly::<G1>::new_from(degree, &mut thread_rng());
+        let mut cache = LagrangeCache::<Sc>::new(1);
+
+        // the same index set in another order hits the cache
+        let shares = (0..threshold as Idx)
+            .rev()
+            .map(|i| poly.eval(i))
+            .collect::<Vec<_>>();
+        let recovered = Poly::<G1>::recover_with_cache(threshold, shares, &mut cache).unwrap();
+        assert_eq!(&recovered, poly.public_key());
+        assert_eq!(cache.len(), 1);
+
+        let shares = (0..threshold as Idx)
+            .map(|i| poly.eval(i))
+            .collect::<Vec<_>>();
+        let recovered = Poly::<G1>::recover_with_cache(threshold, shares, &mut cache).unwrap();
+        assert_eq!(&recovered, poly.public_key());
+        assert_eq!(cache.len(), 1);
+
+        // another index set evicts the previous one
+        let shares = (2..threshold as Idx + 2)
+            .map(|i| poly.eval(i))
+            .collect::<Vec<_>>();
+        let recovered =
+            Poly::<G1>::recover_with_cache(threshold, shares.clone(), &mut cache).unwrap();
+        assert_eq!(&recovered, poly.public_key());
+        assert_eq!(recovered, Poly::<G1>::recover(threshold, shares).unwrap());
+        assert_eq!(cache.len(), 1);
+
+        // insufficient shares are rejected before touching the cache
+        let shares = (0..degree as Idx).map(|i| poly.eval(i)).collect::<Vec<_>>();
+        Poly::<G1>::recover_with_cache(threshold, shares, &mut cache).unwrap_err();
+    }
+
+    #[test]
+    fn lagrange_cache_capacity() {
+        let mut cache = LagrangeCache::<Sc>::new(2);
+        assert!(cache.is_empty());
+
+        cache.coefficients(&[0, 1]).unwrap();
+        cache.coefficients(&[1, 2]).unwrap();
+        cache.coefficients(&[0, 1]).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // the first inserted set is evicted
+        cache.coefficients(&[2, 3]).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.coefficients.contains_key(&vec![0, 1]));
+
+        // the coefficients of a set sum to one, as they interpolate the constant 1
+        let sum = cache
+            .coefficients(&[2, 3])
+            .unwrap()
+            .iter()
+            .fold(Sc::zero(), |mut acc, c| {
+                acc.add(c);
+                acc
+            });
+        assert_eq!(sum, Sc::one());
+    }
+
     #[test]
     fn benchy() {
         use std::time::SystemTime;
@@ -1,8 +1,12 @@
+#[cfg(feature = "bls12_381")]
+use crate::curve::bls12381;
 use crate::curve::{bn254, BLSError};
 use crate::group::{Point, Scalar};
 use ark_ec::{AffineCurve, ModelParameters, ProjectiveCurve};
 use ark_ff::Field;
 use ark_ff::PrimeField;
+#[cfg(feature = "bls12_381")]
+use ark_ff::{BigInteger, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ethers_core::utils::hex;
 
@@ -95,6 +99,122 @@ impl ContractSerialize for bn254::G2 {
     }
 }
 
+/// Length of a padded base field element in the EIP-2537 encoding.
+#[cfg(feature = "bls12_381")]
+const EIP2537_FP_LEN: usize = 64;
+
+/// Length of a BLS12-381 base field element without the EIP-2537 padding.
+#[cfg(feature = "bls12_381")]
+const BLS12381_FP_LEN: usize = 48;
+
+#[cfg(feature = "bls12_381")]
+fn serialize_bls12381_fp(f: &ark_bls12_381::Fq) -> Vec<u8> {
+    let mut bytes = vec![0u8; EIP2537_FP_LEN - BLS12381_FP_LEN];
+    bytes.extend_from_slice(&f.into_repr().to_bytes_be());
+    bytes
+}
+
+#[cfg(feature = "bls12_381")]
+fn deserialize_bls12381_fp(bytes: &[u8]) -> Result<ark_bls12_381::Fq, BLSError> {
+    let (padding, be) = bytes.split_at(EIP2537_FP_LEN - BLS12381_FP_LEN);
+    if padding.iter().any(|b| *b != 0) {
+        return Err(BLSError::ContractSerializationError);
+    }
+    let f = ark_bls12_381::Fq::from_be_bytes_mod_order(be);
+    // reject non-canonical encodings, i.e. values not smaller than the modulus
+    if f.into_repr().to_bytes_be() != be {
+        return Err(BLSError::ContractSerializationError);
+    }
+    Ok(f)
+}
+
+/// Encodes a G1 point as in EIP-2537: `x || y`, each coordinate a 64 bytes
+/// big-endian field element, with the point at infinity encoded as all zeros.
+#[cfg(feature = "bls12_381")]
+impl ContractSerialize for bls12381::G1 {
+    fn serialize_to_contract_form(&self) -> Result<Vec<u8>, BLSError> {
+        let affine = self.0.into_affine();
+        if affine.infinity {
+            return Ok(vec![0u8; 2 * EIP2537_FP_LEN]);
+        }
+
+        Ok([
+            serialize_bls12381_fp(&affine.x),
+            serialize_bls12381_fp(&affine.y),
+        ]
+        .concat())
+    }
+
+    fn deserialize_from_contract_form(bytes: &[u8]) -> Result<Self, BLSError> {
+        if bytes.len() != 2 * EIP2537_FP_LEN {
+            return Err(BLSError::ContractSerializationError);
+        }
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(bls12381::G1(ark_bls12_381::G1Projective::zero()));
+        }
+
+        let x = deserialize_bls12381_fp(&bytes[..EIP2537_FP_LEN])?;
+        let y = deserialize_bls12381_fp(&bytes[EIP2537_FP_LEN..])?;
+
+        let affine = ark_bls12_381::G1Affine::new(x, y, false);
+        if !affine.is_on_curve() || !affine.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(BLSError::NotValidPoint);
+        }
+
+        Ok(bls12381::G1(affine.into_projective()))
+    }
+}
+
+/// Encodes a G2 point as in EIP-2537: `x.c0 || x.c1 || y.c0 || y.c1`, each
+/// a 64 bytes big-endian field element, with the point at infinity encoded as all zeros.
+#[cfg(feature = "bls12_381")]
+impl ContractSerialize for bls12381::G2 {
+    fn serialize_to_contract_form(&self) -> Result<Vec<u8>, BLSError> {
+        let affine = self.0.into_affine();
+        if affine.infinity {
+            return Ok(vec![0u8; 4 * EIP2537_FP_LEN]);
+        }
+
+        Ok([
+            serialize_bls12381_fp(&affine.x.c0),
+            serialize_bls12381_fp(&affine.x.c1),
+            serialize_bls12381_fp(&affine.y.c0),
+            serialize_bls12381_fp(&affine.y.c1),
+        ]
+        .concat())
+    }
+
+    fn deserialize_from_contract_form(bytes: &[u8]) -> Result<Self, BLSError> {
+        if bytes.len() != 4 * EIP2537_FP_LEN {
+            return Err(BLSError::ContractSerializationError);
+        }
+        if bytes.iter().all(|b| *b == 0) {
+            return Ok(bls12381::G2(ark_bls12_381::G2Projective::zero()));
+        }
+
+        let mut coordinates = bytes
+            .chunks(EIP2537_FP_LEN)
+            .map(deserialize_bls12381_fp)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+        let mut next = || {
+            coordinates
+                .next()
+                .ok_or(BLSError::ContractSerializationError)
+        };
+
+        let x = ark_bls12_381::Fq2::new(next()?, next()?);
+        let y = ark_bls12_381::Fq2::new(next()?, next()?);
+
+        let affine = ark_bls12_381::G2Affine::new(x, y, false);
+        if !affine.is_on_curve() || !affine.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(BLSError::NotValidPoint);
+        }
+
+        Ok(bls12381::G2(affine.into_projective()))
+    }
+}
+
 pub fn scalar_to_hex<S: Scalar>(s: &S) -> String {
     let bytes = bincode::serialize(s).unwrap();
     format!("0x{}", hex::encode(bytes))
@@ -139,4 +259,100 @@ pub mod tests {
             assert_eq!(g2, g2_deserialized);
         }
     }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_serialize_bls12381_g1_element() {
+        use crate::curve::bls12381::G1;
+
+        for _ in 0..10 {
+            let g1 = G1::rand(&mut thread_rng());
+
+            let g1_bytes = g1.serialize_to_contract_form().unwrap();
+            assert_eq!(g1_bytes.len(), 128);
+
+            let g1_deserialized = G1::deserialize_from_contract_form(&g1_bytes).unwrap();
+
+            assert_eq!(g1, g1_deserialized);
+        }
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_serialize_bls12381_g2_element() {
+        use crate::curve::bls12381::G2;
+
+        for _ in 0..10 {
+            let g2 = G2::rand(&mut thread_rng());
+
+            let g2_bytes = g2.serialize_to_contract_form().unwrap();
+            assert_eq!(g2_bytes.len(), 256);
+
+            let g2_deserialized = G2::deserialize_from_contract_form(&g2_bytes).unwrap();
+
+            assert_eq!(g2, g2_deserialized);
+        }
+    }
+
+    // generator encodings as given by the EIP-2537 precompile specification
+    #[cfg(feature = "bls12_381")]
+    const EIP2537_G1_GENERATOR: &str = "0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+    #[cfg(feature = "bls12_381")]
+    const EIP2537_G2_GENERATOR: &str = "00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80000000000000000000000000000000013e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be";
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_bls12381_eip2537_parity() {
+        use crate::curve::bls12381::{G1, G2};
+        use ethers_core::utils::hex;
+
+        let g1_bytes = G1::one().serialize_to_contract_form().unwrap();
+        assert_eq!(hex::encode(g1_bytes), EIP2537_G1_GENERATOR);
+        assert_eq!(
+            G1::deserialize_from_contract_form(&hex::decode(EIP2537_G1_GENERATOR).unwrap())
+                .unwrap(),
+            G1::one()
+        );
+
+        let g2_bytes = G2::one().serialize_to_contract_form().unwrap();
+        assert_eq!(hex::encode(g2_bytes), EIP2537_G2_GENERATOR);
+        assert_eq!(
+            G2::deserialize_from_contract_form(&hex::decode(EIP2537_G2_GENERATOR).unwrap())
+                .unwrap(),
+            G2::one()
+        );
+
+        // the point at infinity is encoded as all zeros
+        assert_eq!(
+            G1::new().serialize_to_contract_form().unwrap(),
+            vec![0u8; 128]
+        );
+        assert_eq!(
+            G2::deserialize_from_contract_form(&[0u8; 256]).unwrap(),
+            G2::new()
+        );
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_bls12381_eip2537_rejects_invalid_encodings() {
+        use crate::curve::bls12381::G1;
+        use ethers_core::utils::hex;
+
+        let valid = hex::decode(EIP2537_G1_GENERATOR).unwrap();
+
+        // wrong length
+        assert!(G1::deserialize_from_contract_form(&valid[..127]).is_err());
+
+        // non-zero padding
+        let mut bytes = valid.clone();
+        bytes[0] = 1;
+        assert!(G1::deserialize_from_contract_form(&bytes).is_err());
+
+        // not on the curve
+        let mut bytes = valid;
+        bytes[127] ^= 1;
+        assert!(G1::deserialize_from_contract_form(&bytes).is_err());
+    }
 }
@@ -130,6 +130,29 @@ pub mod common {
             T::internal_verify(public, msg_bytes, sig_bytes, true)
         }
 
+        fn sign_with_domain(
+            private: &Self::Private,
+            domain: &[u8],
+            msg: &[u8],
+        ) -> Result<Vec<u8>, Self::Error> {
+            let mut h = Self::Signature::new();
+            h.map_with_domain(domain, msg)
+                .map_err(|_| BLSError::HashingError)?;
+            T::internal_sign(private, &bincode::serialize(&h)?, false)
+        }
+
+        fn verify_with_domain(
+            public: &Self::Public,
+            domain: &[u8],
+            msg: &[u8],
+            sig: &[u8],
+        ) -> Result<(), Self::Error> {
+            let mut h = Self::Signature::new();
+            h.map_with_domain(domain, msg)
+                .map_err(|_| BLSError::HashingError)?;
+            T::internal_verify(public, &bincode::serialize(&h)?, sig, false)
+        }
+
         fn aggregation_verify_on_the_same_msg(
             partial_publics: &[Self::Public],
             msg_bytes: &[u8],
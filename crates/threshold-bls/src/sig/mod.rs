@@ -4,6 +4,9 @@ pub use blind::{BlindError, Token};
 mod bls;
 pub use bls::{BLSError, G1Scheme, G2Scheme};
 
+mod pop;
+pub use pop::{PossessionError, ProofOfPossession, POP_DST};
+
 mod tblind;
 pub use tblind::BlindThresholdError;
 
@@ -0,0 +1,133 @@
+//! Proofs of possession for the public keys of any type which implements
+//! [`SignatureScheme`](../trait.SignatureScheme.html), which rule out rogue-key attacks
+//! wherever public keys of different owners are aggregated.
+use crate::group::Element;
+use crate::sig::SignatureScheme;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The domain separation tag under which the public key is hashed to the curve, so that
+/// a proof is never a valid signature on any ordinary message.
+pub const POP_DST: &[u8] = b"BLS_POP_ARPA_KECCAK256_TAI_";
+
+/// Errors associated with proving and verifying the possession of a private key.
+#[derive(Debug, Error)]
+pub enum PossessionError<I: SignatureScheme> {
+    /// BincodeError is raised when there is an error in (de)serialization
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// SignatureError is raised when the proof could not be produced or verified
+    #[error("signing error {0}")]
+    SignatureError(I::Error),
+
+    /// IdentityPublicKey is raised when the public key is the identity element, which
+    /// any signature of the identity would verify against
+    #[error("the public key is the identity element")]
+    IdentityPublicKey,
+}
+
+/// A signature on the public key itself hashed under [`POP_DST`], which proves that its owner
+/// knows the matching private key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ProofOfPossession<S: SignatureScheme> {
+    signature: S::Signature,
+}
+
+impl<S: SignatureScheme> ProofOfPossession<S> {
+    /// Proves the possession of the private key of the public key it derives.
+    pub fn prove(private: &S::Private) -> Result<Self, PossessionError<S>> {
+        let mut public = S::Public::one();
+        public.mul(private);
+
+        let signature = S::sign_with_domain(private, POP_DST, &bincode::serialize(&public)?)
+            .map_err(PossessionError::SignatureError)?;
+
+        Ok(ProofOfPossession {
+            signature: bincode::deserialize(&signature)?,
+        })
+    }
+
+    /// Verifies that the proof was produced by the owner of the public key.
+    pub fn verify(&self, public: &S::Public) -> Result<(), PossessionError<S>> {
+        if *public == S::Public::zero() {
+            return Err(PossessionError::IdentityPublicKey);
+        }
+
+        S::verify_with_domain(
+            public,
+            POP_DST,
+            &bincode::serialize(public)?,
+            &bincode::serialize(&self.signature)?,
+        )
+        .map_err(PossessionError::SignatureError)
+    }
+}
+
+#[cfg(feature = "bn254")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::bn254::PairingCurve as PCurve;
+    use crate::sig::{G1Scheme, G2Scheme, Scheme};
+    use rand::prelude::*;
+
+    fn prove_and_verify<S: SignatureScheme>() {
+        let (private, public) = S::keypair(&mut thread_rng());
+        let proof = ProofOfPossession::<S>::prove(&private).unwrap();
+        proof.verify(&public).unwrap();
+
+        // the proof is bound to the key it was produced for
+        let (_, other_public) = S::keypair(&mut thread_rng());
+        assert!(matches!(
+            proof.verify(&other_public),
+            Err(PossessionError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn pop_g2() {
+        prove_and_verify::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn pop_g1() {
+        prove_and_verify::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn pop_is_not_a_signature_on_the_key() {
+        type S = G2Scheme<PCurve>;
+        let (private, public) = S::keypair(&mut thread_rng());
+
+        // a plain signature on the serialized key, with or without the tag, e.g. obtained by
+        // asking the owner to sign it as an ordinary message, is not accepted as a proof
+        let key = bincode::serialize(&public).unwrap();
+        for message in [key.clone(), [POP_DST, &key].concat()] {
+            let signature = S::sign(&private, &message).unwrap();
+            let proof = ProofOfPossession::<S> {
+                signature: bincode::deserialize(&signature).unwrap(),
+            };
+            assert!(proof.verify(&public).is_err());
+        }
+
+        // nor is a proof a valid signature on them
+        let proof = ProofOfPossession::<S>::prove(&private).unwrap();
+        let signature = bincode::serialize(&proof.signature).unwrap();
+        assert!(S::verify(&public, &key, &signature).is_err());
+        assert!(S::verify(&public, &[POP_DST, &key].concat(), &signature).is_err());
+    }
+
+    #[test]
+    fn pop_rejects_identity_public_key() {
+        type S = G2Scheme<PCurve>;
+        let proof = ProofOfPossession::<S> {
+            signature: <S as Scheme>::Signature::zero(),
+        };
+        assert!(matches!(
+            proof.verify(&<S as Scheme>::Public::zero()),
+            Err(PossessionError::IdentityPublicKey)
+        ));
+    }
+}
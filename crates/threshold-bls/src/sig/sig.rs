@@ -1,7 +1,7 @@
 pub use super::tbls::Share; // import and re-export it for easier access
 use crate::{
     group::{Element, Point, Scalar},
-    poly::Poly,
+    poly::{LagrangeCache, Poly},
 };
 use rand_core::RngCore;
 use serde::{de::DeserializeOwned, Serialize};
@@ -64,6 +64,22 @@ pub trait SignatureScheme: Scheme {
     /// Verifies that the signature on the provided message was produced by the public key
     fn verify(public: &Self::Public, msg: &[u8], sig: &[u8]) -> Result<(), Self::Error>;
 
+    /// Signs the message hashed under the domain separation tag, which is not a valid
+    /// signature on any message signed by `sign`
+    fn sign_with_domain(
+        private: &Self::Private,
+        domain: &[u8],
+        msg: &[u8],
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Verifies the signature produced by `sign_with_domain` under the same tag
+    fn verify_with_domain(
+        public: &Self::Public,
+        domain: &[u8],
+        msg: &[u8],
+        sig: &[u8],
+    ) -> Result<(), Self::Error>;
+
     fn aggregation_verify_on_the_same_msg(
         partial_publics: &[Self::Public],
         msg: &[u8],
@@ -164,6 +180,14 @@ pub trait ThresholdScheme: Scheme {
     /// not verify if the partial signatures are correct or not; it only
     /// aggregates them.
     fn aggregate(threshold: usize, partials: &[Partial]) -> Result<Vec<u8>, Self::Error>;
+
+    /// Aggregates the partial signatures as `aggregate` does, reusing the Lagrange
+    /// coefficients of the cache when the same signers were aggregated before.
+    fn aggregate_with_cache(
+        threshold: usize,
+        partials: &[Partial],
+        cache: &mut LagrangeCache<Self::Private>,
+    ) -> Result<Vec<u8>, Self::Error>;
 }
 
 /// BlindThreshold is ThresholdScheme that allows to verify a partially blinded
@@ -0,0 +1,559 @@
+//! # Threshold Encryption
+//!
+//! Implements a threshold variant of the ECIES in [`ecies`](../ecies/index.html), in the spirit
+//! of TDH1 from [Shoup and Gennaro](https://www.shoup.net/papers/thresh1.pdf). A message is
+//! encrypted to the public key of a group, and can only be decrypted once `t` holders of the
+//! shares of its private key publish their decryption shares.
+//!
+//! The ciphertext carries a label, e.g. the condition under which it may be revealed, along with
+//! a proof of knowledge of its ephemeral secret which is bound to the label and the encrypted
+//! payload. Holders of shares only decrypt ciphertexts with a valid proof, so that no ciphertext
+//! can be mauled into another one, or relabeled, to have it decrypted at a different time.
+//!
+//! Each decryption share comes with a Chaum-Pedersen proof that it was computed with the same
+//! share as the partial public key of its holder, hence invalid shares can be told apart before
+//! they are combined.
+//!
+//! The holders multiply the ephemeral public key of any well formed ciphertext by their share, so
+//! their shares must not be used to multiply points chosen by others in the same group for any
+//! other purpose, e.g. to sign blinded messages, as such a point could be the ephemeral public key
+//! of a ciphertext. With a group key in G2 which also signs blinded messages in G2, ciphertexts
+//! are encrypted in G1 to the public key of the group in G1.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use threshold_bls::{
+//!     curve::bn254::G1Curve,
+//!     group::Curve,
+//!     poly::{Idx, Poly},
+//!     sig::Share,
+//!     tenc::{combine, encrypt, partial_decrypt, verify_share},
+//! };
+//!
+//! let (n, t) = (5, 3);
+//! let rng = &mut rand::thread_rng();
+//!
+//! // the shares of the private key of the group, e.g. as the output of a DKG
+//! let private_poly = Poly::<<G1Curve as Curve>::Scalar>::new(t - 1);
+//! let public_poly = private_poly.commit::<<G1Curve as Curve>::Point>();
+//! let shares = (0..n)
+//!     .map(|i| private_poly.eval(i as Idx))
+//!     .map(|eval| Share { index: eval.index, private: eval.value })
+//!     .collect::<Vec<_>>();
+//!
+//! // anyone can encrypt a message to the public key of the group
+//! let cipher = encrypt::<G1Curve, _>(public_poly.public_key(), b"block:100", b"bid", rng);
+//!
+//! // each holder publishes a decryption share, which can be verified by anyone
+//! let decryption_shares = shares
+//!     .iter()
+//!     .take(t)
+//!     .map(|share| partial_decrypt(share, &cipher, rng).unwrap())
+//!     .collect::<Vec<_>>();
+//! for decryption_share in decryption_shares.iter() {
+//!     let partial_public_key = public_poly.eval(decryption_share.index).value;
+//!     verify_share(&partial_public_key, &cipher, decryption_share).unwrap();
+//! }
+//!
+//! // any `t` of them recover the message
+//! let message = combine(t, &cipher, decryption_shares).unwrap();
+//! assert_eq!(&message[..], b"bid");
+//! ```
+
+use crate::group::{Curve, Element, Scalar};
+use crate::hash::hasher::{Hasher, Keccak256Hasher};
+use crate::poly::{Eval, Idx, Poly, PolyError};
+use crate::sig::Share;
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    ChaCha20Poly1305, Key,
+};
+use hkdf::Hkdf;
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+
+/// The nonce length
+const NONCE_LEN: usize = 12;
+
+/// The symmetric key length
+const KEY_LEN: usize = 32;
+
+/// The domain separator of the key derivation
+const KDF_DOMAIN: &[u8] = b"ARPA-TENC-KDF";
+
+/// The domain separator of the proofs of the ciphertexts
+const CIPHER_PROOF_DOMAIN: &[u8] = b"ARPA-TENC-CIPHER";
+
+/// The domain separator of the proofs of the decryption shares
+const SHARE_PROOF_DOMAIN: &[u8] = b"ARPA-TENC-SHARE";
+
+/// Errors associated with threshold decryption.
+#[derive(Debug, Error)]
+pub enum ThresholdDecryptionError {
+    /// InvalidCipher is raised when the proof of the ciphertext doesn't verify
+    #[error("the ciphertext is not well formed")]
+    InvalidCipher,
+
+    /// InvalidShare is raised when the proof of a decryption share doesn't verify
+    #[error("invalid decryption share of index {0}")]
+    InvalidShare(Idx),
+
+    /// NotEnoughShares is raised if the decryption shares provided for combination
+    /// were fewer than the threshold
+    #[error("not enough decryption shares: {0}/{1}")]
+    NotEnoughShares(usize, usize),
+
+    /// PolyError is raised when the shared key could not be recovered
+    #[error("could not recover the shared key: {0}")]
+    PolyError(#[from] PolyError),
+
+    /// DecryptionFailed is raised when the recovered key doesn't open the payload
+    #[error("could not decrypt the payload")]
+    DecryptionFailed,
+}
+
+/// Schnorr proof of the knowledge of the ephemeral secret of a ciphertext.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherProof<C: Curve> {
+    challenge: C::Scalar,
+    response: C::Scalar,
+}
+
+/// A ciphertext encrypted to the public key of a group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ThresholdCipher<C: Curve> {
+    /// The label which the ciphertext is bound to
+    label: Vec<u8>,
+    /// The ephemeral public key, whose multiple by the group private key derives the
+    /// symmetric key
+    ephemeral: C::Point,
+    /// The nonce used to encrypt the payload
+    nonce: [u8; NONCE_LEN],
+    /// The encrypted payload, authenticated along with the label
+    aead: Vec<u8>,
+    /// The proof of the knowledge of the ephemeral secret
+    proof: CipherProof<C>,
+}
+
+impl<C: Curve> ThresholdCipher<C> {
+    /// Returns the label which the ciphertext is bound to
+    pub fn label(&self) -> &[u8] {
+        &self.label
+    }
+
+    /// Verifies that the ciphertext is well formed, i.e. that its creator knows the
+    /// ephemeral secret.
+    pub fn verify(&self) -> Result<(), ThresholdDecryptionError> {
+        let commitment = mul_sub::<C>(
+            &C::Point::one(),
+            &self.proof.response,
+            &self.ephemeral,
+            &self.proof.challenge,
+        );
+
+        if self.proof.challenge != self.challenge(&commitment) {
+            return Err(ThresholdDecryptionError::InvalidCipher);
+        }
+
+        Ok(())
+    }
+
+    fn challenge(&self, commitment: &C::Point) -> C::Scalar {
+        hash_to_scalar::<C>(
+            CIPHER_PROOF_DOMAIN,
+            &[&self.label, &self.nonce, &self.aead],
+            &[&self.ephemeral, commitment],
+        )
+    }
+}
+
+/// Chaum-Pedersen proof that a decryption share and the partial public key of its holder
+/// have the same discrete logarithm w.r.t. the ephemeral public key and the generator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecryptionShareProof<C: Curve> {
+    challenge: C::Scalar,
+    response: C::Scalar,
+}
+
+/// The share of the symmetric key of a ciphertext, which is computed by a holder of a share
+/// of the group private key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DecryptionShare<C: Curve> {
+    /// The index of the share of the group private key
+    pub index: Idx,
+    /// The ephemeral public key multiplied by the share of the group private key
+    pub value: C::Point,
+    /// Proof that the share was computed with the share of the group private key
+    pub proof: DecryptionShareProof<C>,
+}
+
+/// Encrypts the message to the public key of a group, bound to the provided label
+pub fn encrypt<C: Curve, R: RngCore>(
+    to: &C::Point,
+    label: &[u8],
+    msg: &[u8],
+    rng: &mut R,
+) -> ThresholdCipher<C> {
+    let eph_secret = C::Scalar::rand(rng);
+    let ephemeral = mul::<C>(&C::Point::one(), &eph_secret);
+
+    // the symmetric key is derived from eph * public, which is recovered from the
+    // decryption shares as share_i * (eph * G)
+    let key = derive::<C>(&mul::<C>(to, &eph_secret));
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+
+    let aead = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(&nonce.into(), Payload { msg, aad: label })
+        .expect("aead should not fail");
+
+    let mut cipher = ThresholdCipher {
+        label: label.to_vec(),
+        ephemeral,
+        nonce,
+        aead,
+        proof: CipherProof {
+            challenge: C::Scalar::zero(),
+            response: C::Scalar::zero(),
+        },
+    };
+
+    let w = C::Scalar::rand(rng);
+    let challenge: C::Scalar = cipher.challenge(&mul::<C>(&C::Point::one(), &w));
+
+    let mut response = challenge.clone();
+    response.mul(&eph_secret);
+    response.add(&w);
+
+    cipher.proof = CipherProof {
+        challenge,
+        response,
+    };
+
+    cipher
+}
+
+/// Computes the decryption share of a well formed ciphertext with a share of the group
+/// private key
+pub fn partial_decrypt<C: Curve, R: RngCore>(
+    share: &Share<C::Scalar>,
+    cipher: &ThresholdCipher<C>,
+    rng: &mut R,
+) -> Result<DecryptionShare<C>, ThresholdDecryptionError> {
+    cipher.verify()?;
+
+    let value = mul::<C>(&cipher.ephemeral, &share.private);
+    let partial_public_key = mul::<C>(&C::Point::one(), &share.private);
+
+    let w = C::Scalar::rand(rng);
+    let t1 = mul::<C>(&C::Point::one(), &w);
+    let t2 = mul::<C>(&cipher.ephemeral, &w);
+
+    let challenge =
+        share_challenge::<C>(share.index, cipher, &partial_public_key, &value, &t1, &t2);
+
+    let mut response = challenge.clone();
+    response.mul(&share.private);
+    response.add(&w);
+
+    Ok(DecryptionShare {
+        index: share.index,
+        value,
+        proof: DecryptionShareProof {
+            challenge,
+            response,
+        },
+    })
+}
+
+/// Verifies the decryption share of a ciphertext against the partial public key of its holder
+pub fn verify_share<C: Curve>(
+    partial_public_key: &C::Point,
+    cipher: &ThresholdCipher<C>,
+    share: &DecryptionShare<C>,
+) -> Result<(), ThresholdDecryptionError> {
+    let proof = &share.proof;
+
+    let t1 = mul_sub::<C>(
+        &C::Point::one(),
+        &proof.response,
+        partial_public_key,
+        &proof.challenge,
+    );
+    let t2 = mul_sub::<C>(
+        &cipher.ephemeral,
+        &proof.response,
+        &share.value,
+        &proof.challenge,
+    );
+
+    if proof.challenge
+        != share_challenge::<C>(
+            share.index,
+            cipher,
+            partial_public_key,
+            &share.value,
+            &t1,
+            &t2,
+        )
+    {
+        return Err(ThresholdDecryptionError::InvalidShare(share.index));
+    }
+
+    Ok(())
+}
+
+/// Recovers the message from at least `threshold` decryption shares. Note that this method
+/// does not verify if the decryption shares are correct or not, in which case the payload
+/// fails to be decrypted.
+pub fn combine<C: Curve>(
+    threshold: usize,
+    cipher: &ThresholdCipher<C>,
+    shares: Vec<DecryptionShare<C>>,
+) -> Result<Vec<u8>, ThresholdDecryptionError> {
+    let mut evals: Vec<Eval<C::Point>> = Vec::with_capacity(shares.len());
+    for share in shares {
+        if evals.iter().all(|eval| eval.index != share.index) {
+            evals.push(Eval {
+                index: share.index,
+                value: share.value,
+            });
+        }
+    }
+
+    if threshold > evals.len() {
+        return Err(ThresholdDecryptionError::NotEnoughShares(
+            evals.len(),
+            threshold,
+        ));
+    }
+
+    let shared = Poly::<C::Point>::recover(threshold, evals)?;
+    let key = derive::<C>(&shared);
+
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(
+            &cipher.nonce.into(),
+            Payload {
+                msg: &cipher.aead,
+                aad: &cipher.label,
+            },
+        )
+        .map_err(|_| ThresholdDecryptionError::DecryptionFailed)
+}
+
+fn share_challenge<C: Curve>(
+    index: Idx,
+    cipher: &ThresholdCipher<C>,
+    partial_public_key: &C::Point,
+    value: &C::Point,
+    t1: &C::Point,
+    t2: &C::Point,
+) -> C::Scalar {
+    hash_to_scalar::<C>(
+        SHARE_PROOF_DOMAIN,
+        &[&index.to_be_bytes(), &cipher.label],
+        &[&cipher.ephemeral, partial_public_key, value, t1, t2],
+    )
+}
+
+/// Derives the symmetric key from the shared point
+fn derive<C: Curve>(shared: &C::Point) -> [u8; KEY_LEN] {
+    let serialized = bincode::serialize(shared).expect("could not serialize element");
+
+    let h = Hkdf::<Sha256>::new(None, &serialized);
+    let mut key = [0u8; KEY_LEN];
+    h.expand(KDF_DOMAIN, &mut key)
+        .expect("hkdf should not fail");
+
+    key
+}
+
+/// Derives the Fiat-Shamir challenge of a proof from its transcript, by reducing the keccak256
+/// digest of the transcript modulo the order of the scalar field
+fn hash_to_scalar<C: Curve>(domain: &[u8], data: &[&[u8]], points: &[&C::Point]) -> C::Scalar {
+    let mut message = domain.to_vec();
+    for bytes in data {
+        // length prefixed, so that the boundaries of the items are unambiguous
+        message.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+        message.extend_from_slice(bytes);
+    }
+    for point in points {
+        message.extend(bincode::serialize(point).expect("a point is always serializable"));
+    }
+
+    let digest = Keccak256Hasher.hash(&[], &message).unwrap();
+
+    let mut base = C::Scalar::new();
+    base.set_int(256);
+
+    digest.iter().fold(C::Scalar::zero(), |mut acc, byte| {
+        let mut byte_scalar = C::Scalar::new();
+        byte_scalar.set_int(*byte as u64);
+        acc.mul(&base);
+        acc.add(&byte_scalar);
+        acc
+    })
+}
+
+fn mul<C: Curve>(point: &C::Point, scalar: &C::Scalar) -> C::Point {
+    let mut point = point.clone();
+    point.mul(scalar);
+    point
+}
+
+/// Returns `a * x - c * y`
+fn mul_sub<C: Curve>(x: &C::Point, a: &C::Scalar, y: &C::Point, c: &C::Scalar) -> C::Point {
+    let mut neg_c = c.clone();
+    neg_c.negate();
+
+    let mut res = mul::<C>(x, a);
+    res.add(&mul::<C>(y, &neg_c));
+    res
+}
+
+#[cfg(feature = "bn254")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::bn254::G1Curve;
+    use rand::thread_rng;
+
+    type Point = <G1Curve as Curve>::Point;
+    type Scalar = <G1Curve as Curve>::Scalar;
+
+    const LABEL: &[u8] = b"reveal after block 100";
+
+    fn shares(n: usize, t: usize) -> (Vec<Share<Scalar>>, Poly<Point>) {
+        let private = Poly::<Scalar>::new(t - 1);
+        let shares = (0..n)
+            .map(|i| private.eval(i as Idx))
+            .map(|e| Share {
+                index: e.index,
+                private: e.value,
+            })
+            .collect();
+        (shares, private.commit())
+    }
+
+    #[test]
+    fn threshold_decryption() {
+        let (n, t) = (5, 3);
+        let (shares, public) = shares(n, t);
+        let msg = b"sealed bid".to_vec();
+
+        let cipher = encrypt::<G1Curve, _>(public.public_key(), LABEL, &msg, &mut thread_rng());
+        cipher.verify().unwrap();
+        assert_eq!(cipher.label(), LABEL);
+
+        let decryption_shares = shares
+            .iter()
+            .map(|share| partial_decrypt(share, &cipher, &mut thread_rng()).unwrap())
+            .collect::<Vec<_>>();
+
+        for share in decryption_shares.iter() {
+            verify_share(&public.eval(share.index).value, &cipher, share).unwrap();
+        }
+
+        // any t of the shares recover the message
+        assert_eq!(
+            combine(t, &cipher, decryption_shares[..t].to_vec()).unwrap(),
+            msg
+        );
+        assert_eq!(
+            combine(t, &cipher, decryption_shares[n - t..].to_vec()).unwrap(),
+            msg
+        );
+    }
+
+    #[test]
+    fn not_enough_decryption_shares() {
+        let (n, t) = (5, 3);
+        let (shares, public) = shares(n, t);
+
+        let cipher = encrypt::<G1Curve, _>(public.public_key(), LABEL, b"bid", &mut thread_rng());
+
+        let share = partial_decrypt(&shares[0], &cipher, &mut thread_rng()).unwrap();
+
+        // the same share submitted twice doesn't count twice
+        assert!(matches!(
+            combine(t, &cipher, vec![share.clone(), share.clone(), share]),
+            Err(ThresholdDecryptionError::NotEnoughShares(1, 3))
+        ));
+    }
+
+    #[test]
+    fn invalid_decryption_share_is_detected() {
+        let (n, t) = (5, 3);
+        let (shares, public) = shares(n, t);
+
+        let cipher = encrypt::<G1Curve, _>(public.public_key(), LABEL, b"bid", &mut thread_rng());
+
+        // a share computed with another share of the private key
+        let mut forged = partial_decrypt(&shares[1], &cipher, &mut thread_rng()).unwrap();
+        forged.index = 0;
+        assert!(matches!(
+            verify_share(&public.eval(0).value, &cipher, &forged),
+            Err(ThresholdDecryptionError::InvalidShare(0))
+        ));
+
+        // a share of the right holder with a tampered value
+        let mut tampered = partial_decrypt(&shares[2], &cipher, &mut thread_rng()).unwrap();
+        tampered.value.add(&Point::one());
+        assert!(verify_share(&public.eval(2).value, &cipher, &tampered).is_err());
+
+        // which makes the decryption fail once combined
+        let decryption_shares = vec![
+            partial_decrypt(&shares[0], &cipher, &mut thread_rng()).unwrap(),
+            partial_decrypt(&shares[1], &cipher, &mut thread_rng()).unwrap(),
+            tampered,
+        ];
+        assert!(matches!(
+            combine(t, &cipher, decryption_shares),
+            Err(ThresholdDecryptionError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn relabeled_cipher_is_not_decrypted() {
+        let (n, t) = (5, 3);
+        let (shares, public) = shares(n, t);
+
+        let cipher = encrypt::<G1Curve, _>(public.public_key(), LABEL, b"bid", &mut thread_rng());
+
+        let mut relabeled = cipher.clone();
+        relabeled.label = b"reveal now".to_vec();
+        assert!(matches!(
+            partial_decrypt(&shares[0], &relabeled, &mut thread_rng()),
+            Err(ThresholdDecryptionError::InvalidCipher)
+        ));
+
+        // a fresh proof on the reused ephemeral key can't be made without its secret
+        let mut mauled = encrypt::<G1Curve, _>(
+            public.public_key(),
+            b"reveal now",
+            b"bid",
+            &mut thread_rng(),
+        );
+        mauled.ephemeral = cipher.ephemeral;
+        assert!(mauled.verify().is_err());
+    }
+
+    #[test]
+    fn cipher_serialization() {
+        let (_, public) = shares(3, 2);
+
+        let cipher = encrypt::<G1Curve, _>(public.public_key(), LABEL, b"bid", &mut thread_rng());
+
+        let bytes = bincode::serialize(&cipher).unwrap();
+        let deserialized: ThresholdCipher<G1Curve> = bincode::deserialize(&bytes).unwrap();
+        deserialized.verify().unwrap();
+        assert_eq!(deserialized.aead, cipher.aead);
+    }
+}
@@ -68,15 +68,11 @@ pub struct RelayedChain {
 }
 
 impl Config {
-    pub fn load(config_path: PathBuf) -> Config {
-        let config_str = &read_to_string(config_path).unwrap_or_else(|e| {
-            panic!(
-                "Error loading configuration file: {:?}, please check the configuration!",
-                e
-            )
-        });
-
-        serde_yaml::from_str(config_str).expect("Error loading configuration file")
+    pub fn load(config_path: PathBuf) -> Result<Config, ConfigError> {
+        let config_str = read_to_string(&config_path)
+            .map_err(|e| ConfigError::ReadingFileError(format!("{:?}: {}", config_path, e)))?;
+
+        serde_yaml::from_str(&config_str).map_err(|e| ConfigError::ParsingError(e.to_string()))
     }
 
     pub fn main_chain_id(&self) -> u32 {
@@ -250,4 +246,8 @@ pub enum ConfigError {
     BuildingAccountError(#[from] WalletError),
     #[error("the chain id: {0} is not supported")]
     InvalidChainId(u32),
+    #[error("failed to read the configuration file {0}")]
+    ReadingFileError(String),
+    #[error("failed to parse the configuration file: {0}")]
+    ParsingError(String),
 }
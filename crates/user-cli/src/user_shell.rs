@@ -1,13 +1,18 @@
 use arpa_contract_client::contract_stub::adapter::Adapter as AdapterContract;
+use arpa_contract_client::contract_stub::adapter::FulfillRandomnessCall;
+use arpa_contract_client::contract_stub::controller::Controller as ControllerContract;
 use arpa_contract_client::contract_stub::ierc20::IERC20 as ArpaContract;
 use arpa_contract_client::contract_stub::staking::Staking as StakingContract;
 use arpa_contract_client::ethers::adapter::AdapterClient;
 use arpa_contract_client::{TransactionCaller, ViewCaller};
+use arpa_core::randcast_verify::{
+    derive_randomness_output, verify_randomness_signature, RandomnessOutput,
+};
 use arpa_core::RandomnessRequestType;
 use arpa_core::{address_to_string, pad_to_bytes32};
 use arpa_core::{u256_to_vec, HttpWalletSigner};
 use arpa_user_cli::config::{Config, ConfigError};
-use ethers::abi::AbiEncode;
+use ethers::abi::{AbiDecode, AbiEncode};
 use ethers::prelude::{NonceManagerMiddleware, SignerMiddleware};
 use ethers::providers::{Http, Middleware, Provider};
 use ethers::signers::Signer;
@@ -23,6 +28,8 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
+use threshold_bls::schemes::bn254::G2Scheme;
+use threshold_bls::sig::Scheme;
 
 pub const SIMPLE_ADAPTER_CODE: &str = "0x6080604052348015600f57600080fd5b506004361060325760003560e01c806376a911bc146037578063a39402d7146066575b600080fd5b60486042366004607e565b50600090565b60405167ffffffffffffffff90911681526020015b60405180910390f35b6071604236600460b9565b604051908152602001605d565b600060208284031215608f57600080fd5b813573ffffffffffffffffffffffffffffffffffffffff8116811460b257600080fd5b9392505050565b60006020828403121560ca57600080fd5b813567ffffffffffffffff81111560e057600080fd5b820160e0818503121560b257600080fdfea264697066735822122060db0656f5a3a02d609b3fb8d9ae455165807d775077e751b503136af39395c464736f6c63430008120033";
 pub const RANDOMNESS_REWARD_GAS: u32 = 9000;
@@ -167,6 +174,21 @@ pub struct RandomnessRequestResult {
     pub success: bool,
 }
 
+#[derive(Debug)]
+pub struct RandomnessVerification {
+    pub request_id: String,
+    pub group_index: u32,
+    pub group_epoch: ethers::core::types::U256,
+    pub request_type: RandomnessRequestType,
+    pub seed: ethers::core::types::U256,
+    pub assignment_block_height: u64,
+    pub fulfillment_block_height: u64,
+    pub fulfillment_transaction_hash: H256,
+    pub signature: String,
+    pub randomness: ethers::core::types::U256,
+    pub output: RandomnessOutput,
+}
+
 #[derive(Debug)]
 pub struct Consumer {
     pub address: Address,
@@ -1409,6 +1431,114 @@ async fn randcast(args: ArgMatches, context: &mut Context) -> anyhow::Result<Opt
                 hex::encode(pending_request_commitment)
             )))
         }
+        Some(("verify-request", sub_matches)) => {
+            let chain_id = sub_matches.get_one::<u32>("chain-id").unwrap();
+            let adapter_contract = AdapterContract::new(
+                context.config.adapter_address(*chain_id)?,
+                context.signer(*chain_id)?,
+            );
+
+            let r_id = sub_matches.get_one::<String>("request-id").unwrap();
+
+            let fulfillment_filter = adapter_contract
+                .randomness_request_result_filter()
+                .topic1(H256::from(
+                    pad_to_bytes32(&hex::decode(r_id.trim_start_matches("0x"))?).unwrap(),
+                ))
+                .from_block(context.config.adapter_deployed_block_height(*chain_id)?)
+                .to_block(BlockNumber::Latest);
+
+            let (fulfillment, meta) = match fulfillment_filter.query_with_meta().await?.pop() {
+                Some(fulfillment) => fulfillment,
+                None => return Ok(Some(format!("request {} is not fulfilled yet", r_id))),
+            };
+
+            let transaction = context
+                .provider(*chain_id)?
+                .get_transaction(meta.transaction_hash)
+                .await?
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "fulfillment transaction {:?} not found",
+                        meta.transaction_hash
+                    )
+                })?;
+
+            // the signature is not in the event, so it's decoded from the calldata of the fulfillment
+            let fulfillment_call =
+                FulfillRandomnessCall::decode(&transaction.input).map_err(|_| {
+                    anyhow::anyhow!(
+                        "fulfillment transaction {:?} doesn't call fulfillRandomness of the adapter directly",
+                        meta.transaction_hash
+                    )
+                })?;
+            let request_detail = fulfillment_call.request_detail;
+
+            let controller_address = AdapterClient::call_contract_view(
+                *chain_id as usize,
+                "get_controller",
+                adapter_contract.get_controller(),
+                context.config.contract_view_retry_descriptor(*chain_id)?,
+            )
+            .await?;
+
+            // the controller oracle on relayed chains shares the same getGroup interface
+            let controller_contract =
+                ControllerContract::new(controller_address, context.provider(*chain_id)?);
+
+            // the group may have been regrouped since, so its public key is read at the fulfillment block
+            let group = controller_contract
+                .get_group(U256::from(fulfillment.group_index))
+                .block(meta.block_number)
+                .call()
+                .await?;
+
+            let group_public_key: <G2Scheme as Scheme>::Public = bincode::deserialize(
+                &group
+                    .public_key
+                    .iter()
+                    .flat_map(u256_to_vec)
+                    .collect::<Vec<_>>(),
+            )?;
+
+            let signature = u256_to_vec(&fulfillment_call.signature);
+
+            let randomness = verify_randomness_signature::<G2Scheme>(
+                &group_public_key,
+                &request_detail.seed,
+                request_detail.block_num.as_usize(),
+                &signature,
+            )?;
+
+            if randomness != fulfillment.randommness {
+                return Err(anyhow::anyhow!(
+                    "derived randomness {} doesn't match the fulfilled randomness {}",
+                    randomness,
+                    fulfillment.randommness
+                ));
+            }
+
+            let request_type = RandomnessRequestType::from(request_detail.request_type);
+
+            let output =
+                derive_randomness_output(request_type, &request_detail.params, randomness)?;
+
+            let verification = RandomnessVerification {
+                request_id: format!("0x{}", hex::encode(fulfillment.request_id)),
+                group_index: fulfillment.group_index,
+                group_epoch: group.epoch,
+                request_type,
+                seed: request_detail.seed,
+                assignment_block_height: request_detail.block_num.as_u64(),
+                fulfillment_block_height: meta.block_number.as_u64(),
+                fulfillment_transaction_hash: meta.transaction_hash,
+                signature: format!("0x{}", hex::encode(&signature)),
+                randomness,
+                output,
+            };
+
+            Ok(Some(format!("verified: {:#?}", verification)))
+        }
 
         _ => panic!("Unknown subcommand {:?}", args.subcommand_name()),
     }
@@ -1824,7 +1954,7 @@ fn read_file_line_by_line(filepath: PathBuf) -> anyhow::Result<String> {
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
 
-    let config = Config::load(opt.config_path);
+    let config = Config::load(opt.config_path)?;
 
     let mut providers = BTreeMap::new();
     providers.insert(
@@ -1954,6 +2084,11 @@ async fn main() -> anyhow::Result<()> {
                         .about("Get pending commitment by request id")
                         .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(u32)).help("chain id in decimal format"))
                         .arg(Arg::new("request-id").required(true).help("request id in hex format"))
+                ).subcommand(
+                    Command::new("verify-request").visible_alias("vr")
+                        .about("Verify the group signature of a fulfilled request and derive its randomness, random words or shuffled array offline")
+                        .arg(Arg::new("chain-id").required(true).value_parser(value_parser!(u32)).help("chain id in decimal format"))
+                        .arg(Arg::new("request-id").required(true).help("request id in hex format"))
                 ).subcommand(
                     Command::new("adapter-config").visible_alias("ac")
                         .about("Get adapter config")